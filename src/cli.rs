@@ -16,6 +16,16 @@ pub enum AssetAction {
         #[arg(long, short = 'c', value_enum, default_value = "images")]
         category: AssetCategory,
     },
+
+    /// Delete cached URL downloads (assets/downloads/) no longer referenced by any scene or config
+    Prune {
+        /// Path to the project directory
+        path: PathBuf,
+
+        /// List what would be removed without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -79,6 +89,10 @@ pub struct Cli {
     /// Directory to save intermediate files when --debug is enabled (default: ./output/debug/)
     #[arg(global = true, long)]
     pub debug_dir: Option<PathBuf>,
+
+    /// Replace TTS with silent audio sized from each script (150 wpm) for fast visual iteration
+    #[arg(global = true, long)]
+    pub dry_tts: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -91,6 +105,10 @@ pub enum Command {
         /// Project preset: short (9:16 vertical), recap (16:9 landscape), educational (long-form)
         #[arg(long)]
         preset: Option<String>,
+
+        /// Also create an empty .vidgenignore for scene files that shouldn't be rendered
+        #[arg(long)]
+        ignore_file: bool,
     },
 
     /// Render a video project to MP4
@@ -107,7 +125,7 @@ pub enum Command {
         #[arg(long)]
         fps: Option<u32>,
 
-        /// Output quality: draft, standard, high
+        /// Output quality: draft, standard, high, or a [quality.presets] name
         #[arg(long, value_name = "LEVEL")]
         quality: Option<String>,
 
@@ -120,6 +138,23 @@ pub enum Command {
         #[arg(long, value_delimiter = ',')]
         scenes: Option<Vec<usize>>,
 
+        /// Only render scenes whose `tags` include this name (combines with --scenes)
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Render the scenes in a shuffled order (scene files are not modified).
+        /// Output goes to <output>/shuffle-<seed>/
+        #[arg(long)]
+        shuffle: bool,
+
+        /// Seed for --shuffle; the same seed always produces the same order
+        #[arg(long, requires = "shuffle")]
+        seed: Option<u64>,
+
+        /// With --shuffle, keep the first and last scenes in place
+        #[arg(long, requires = "shuffle")]
+        pin_ends: bool,
+
         /// Generate SRT subtitle files alongside the video output
         #[arg(long)]
         subtitles: bool,
@@ -151,6 +186,47 @@ pub enum Command {
         /// Post-process crop to aspect ratio (e.g., "9:16", "1:1")
         #[arg(long)]
         crop: Option<String>,
+
+        /// Print newline-delimited JSON progress events ({progress,total,message}) to stdout
+        #[arg(long)]
+        progress_json: bool,
+
+        /// Render N times and print median/min/max phase timings (TTS, capture, encode) as JSON
+        #[arg(long, value_name = "N")]
+        benchmark: Option<usize>,
+
+        /// Render only the scenes picked by --scenes/--tag and append them to the existing output
+        #[arg(long, conflicts_with_all = ["shuffle", "crop", "benchmark", "subtitles", "burn_in", "progress_json"])]
+        append: bool,
+
+        /// Skip formats whose scenes and settings are unchanged since the last render, reusing their output
+        #[arg(long, conflicts_with_all = ["no_cache", "benchmark", "append"])]
+        only_changed_formats: bool,
+
+        /// Print how each scene's duration and voice and each transition are decided, as JSON, without rendering
+        #[arg(long, conflicts_with_all = ["benchmark", "append", "progress_json", "only_changed_formats", "crop"])]
+        explain: bool,
+
+        /// Container and codecs of the final file: mp4 (H.264/AAC), mov (ProRes/PCM),
+        /// webm (VP9/Opus) or gif (no audio). Overrides output.container
+        #[arg(long, visible_alias = "container", value_name = "KIND", conflicts_with = "append")]
+        output_format: Option<crate::config::OutputContainer>,
+
+        /// Seconds one frame capture may take before it is retried (overrides render.capture_timeout)
+        #[arg(long, value_name = "SECS")]
+        capture_timeout: Option<f64>,
+
+        /// Times a failed frame capture is retried after reloading the page (overrides render.capture_retries)
+        #[arg(long, value_name = "N")]
+        capture_retries: Option<u32>,
+
+        /// Seconds of black and silence between scenes joined by a cut (overrides video.scene_gap_secs)
+        #[arg(long, value_name = "SECS")]
+        scene_gap: Option<f64>,
+
+        /// Override a project.toml setting for this render only, e.g. --set video.fps=60 (repeatable, not saved)
+        #[arg(long = "set", visible_alias = "config-override", value_name = "KEY=VALUE")]
+        set: Vec<String>,
     },
 
     /// Preview a single frame of a scene as a PNG image
@@ -177,6 +253,36 @@ pub enum Command {
         /// Generate an animated GIF preview of the scene
         #[arg(long)]
         gif: bool,
+
+        /// Render the scene in every configured format as one labeled contact sheet
+        #[arg(long)]
+        all_formats: bool,
+
+        /// Interactive mode: type frame numbers to re-capture them with one warm browser
+        #[arg(long, short = 'i')]
+        interactive: bool,
+
+        /// Shade the areas covered by a platform's UI (tiktok, reels, shorts) on the preview
+        #[arg(long, value_name = "PLATFORM")]
+        platform_overlay: Option<String>,
+
+        /// Capture evenly spaced frames of this scene into one timeline strip PNG
+        #[arg(long, value_name = "SCENE", conflicts_with_all = ["all", "gif", "all_formats", "interactive"])]
+        strip: Option<usize>,
+
+        /// Number of frames in the --strip timeline
+        #[arg(long, default_value_t = 8, requires = "strip")]
+        count: u32,
+    },
+
+    /// Render the theme's color swatches and font samples to a PNG
+    ThemePreview {
+        /// Path to the project directory
+        path: PathBuf,
+
+        /// Output PNG file path (default: theme-preview.png)
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
     },
 
     /// Watch project files for changes and auto-preview or re-render
@@ -259,10 +365,91 @@ pub enum Command {
         path: PathBuf,
     },
 
+    /// Synthesize each scene's narration to audio files, without capturing or encoding video
+    Tts {
+        /// Path to the project directory
+        path: PathBuf,
+
+        /// Directory for the audio files (default: <output>/audio)
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+
+        /// Comma-separated scene indices to synthesize (0-based)
+        #[arg(long, value_delimiter = ',')]
+        scenes: Option<Vec<usize>>,
+
+        /// Regenerate narration, ignoring cached audio
+        #[arg(long)]
+        force_tts: bool,
+    },
+
+    /// List rendered videos in the output directory with durations and sizes
+    Status {
+        /// Path to the project directory
+        path: PathBuf,
+    },
+
+    /// Generate one scene per row of a CSV or JSON data file, mapping columns to template props
+    Generate {
+        /// Path to the project directory
+        path: PathBuf,
+
+        /// Template used for every generated scene
+        #[arg(long)]
+        template: String,
+
+        /// Data file: .csv with a header row, or .json array of objects
+        #[arg(long)]
+        data: PathBuf,
+
+        /// Column used as the scene's voiceover script (default: "script")
+        #[arg(long, value_name = "COLUMN")]
+        script_column: Option<String>,
+    },
+
+    /// Split scenes whose `items` prop is too long into consecutive scenes of the same template
+    Paginate {
+        /// Path to the project directory
+        path: PathBuf,
+
+        /// Maximum number of items per scene
+        #[arg(long)]
+        page_size: usize,
+
+        /// Comma-separated scene indices to split (0-based, default: all)
+        #[arg(long, value_delimiter = ',')]
+        scenes: Option<Vec<usize>>,
+    },
+
+    /// List quality presets (built-in and project-defined) usable with --quality
+    Qualities {
+        /// Path to the project directory
+        path: PathBuf,
+
+        /// Print the presets as JSON to stdout
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check external tools (FFmpeg, Chromium, TTS engines) and report their versions
+    Doctor {
+        /// Print the report as JSON to stdout
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Validate project for common issues (missing templates, fonts, assets, timing, contrast)
     Validate {
         /// Path to the project directory
         path: PathBuf,
+
+        /// Renumber scene files without gaps and rewrite frontmatter in canonical order first
+        #[arg(long)]
+        fix: bool,
+
+        /// Print the errors and warnings as JSON to stdout
+        #[arg(long)]
+        json: bool,
     },
 
     /// Show what changed since last render (text changes, duration differences)
@@ -280,12 +467,26 @@ pub enum Command {
         update: bool,
     },
 
-    /// Start an MCP server over stdio for AI agent integration
+    /// Start an MCP server over stdio (or HTTP with --http) for AI agent integration
     #[command(long_about = "Start a Model Context Protocol (MCP) server on stdin/stdout.\n\
         AI agents (like Claude) connect via this transport to create and render videos\n\
         programmatically. The server exposes tools for project management, scene editing,\n\
-        and rendering.")]
-    Mcp,
+        and rendering.\n\n\
+        With --http the server listens for streamable HTTP (POST + SSE) at\n\
+        http://<host>:<port>/mcp instead, for agents running on another machine.")]
+    Mcp {
+        /// Serve over HTTP/SSE instead of stdio
+        #[arg(long)]
+        http: bool,
+
+        /// Address to listen on with --http
+        #[arg(long, default_value = "127.0.0.1", requires = "http")]
+        host: String,
+
+        /// Port to listen on with --http
+        #[arg(long, default_value_t = 8080, requires = "http")]
+        port: u16,
+    },
 
     /// Capture video clips from websites or YouTube
     #[cfg(any(feature = "clipper", feature = "youtube"))]
@@ -1,8 +1,10 @@
 use crate::cli::AssetCategory;
 use crate::error::{VidgenError, VidgenResult};
+use crate::config;
 use crate::scene;
 use colored::*;
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
 /// Add an asset to the project: download a URL or copy a local file into assets/.
 pub fn add(source: &str, project_path: &Path, category: &AssetCategory) -> VidgenResult<()> {
@@ -77,6 +79,100 @@ pub fn add(source: &str, project_path: &Path, category: &AssetCategory) -> Vidge
     Ok(())
 }
 
+/// Collect every http(s) URL string in a JSON value (recursing into arrays and objects).
+fn collect_urls(value: &serde_json::Value, urls: &mut HashSet<String>) {
+    match value {
+        serde_json::Value::String(s) if scene::is_url(s) => {
+            urls.insert(s.clone());
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_urls(item, urls);
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            for v in obj.values() {
+                collect_urls(v, urls);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Cache keys (see `scene::url_cache_key`) of all URLs referenced by the project
+/// config and scene frontmatter, including scenes excluded by `.vidgenignore`.
+fn referenced_cache_keys(project_path: &Path) -> VidgenResult<HashSet<String>> {
+    let mut urls = HashSet::new();
+    let config = config::load_config(project_path)?;
+    let value = serde_json::to_value(&config)
+        .map_err(|e| VidgenError::Other(format!("Failed to inspect config: {e}")))?;
+    collect_urls(&value, &mut urls);
+    // A scene that fails to parse aborts the prune rather than orphaning its downloads
+    for path in scene::all_scene_file_paths(project_path)? {
+        let s = scene::parse_scene(&std::fs::read_to_string(&path)?, &path)?;
+        let value = serde_json::to_value(&s.frontmatter)
+            .map_err(|e| VidgenError::Other(format!("Failed to inspect scene: {e}")))?;
+        collect_urls(&value, &mut urls);
+    }
+    Ok(urls.iter().map(|u| scene::url_cache_key(u)).collect())
+}
+
+/// Remove files in `download_dir` whose hash stem isn't in `keep`.
+/// Returns the removed (or, with `dry_run`, removable) files and their total size.
+fn prune_downloads(
+    download_dir: &Path,
+    keep: &HashSet<String>,
+    dry_run: bool,
+) -> VidgenResult<(Vec<PathBuf>, u64)> {
+    let mut removed = Vec::new();
+    let mut reclaimed = 0u64;
+    if !download_dir.is_dir() {
+        return Ok((removed, reclaimed));
+    }
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(download_dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_file())
+        .collect();
+    entries.sort();
+    for path in entries {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        if keep.contains(stem) {
+            continue;
+        }
+        reclaimed += std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        if !dry_run {
+            std::fs::remove_file(&path)?;
+        }
+        removed.push(path);
+    }
+    Ok((removed, reclaimed))
+}
+
+/// Delete cached URL downloads in `assets/downloads/` that no scene or config
+/// references any more, reporting the reclaimed space.
+pub fn prune(project_path: &Path, dry_run: bool) -> VidgenResult<()> {
+    let keep = referenced_cache_keys(project_path)?;
+    let download_dir = project_path.join("assets/downloads");
+    let (removed, reclaimed) = prune_downloads(&download_dir, &keep, dry_run)?;
+
+    let verb = if dry_run { "Would remove" } else { "Removed" };
+    for path in &removed {
+        eprintln!("  {} {}", "-".dimmed(), path.display());
+    }
+    let size_str = if reclaimed > 1_048_576 {
+        format!("{:.1}MB", reclaimed as f64 / 1_048_576.0)
+    } else {
+        format!("{:.0}KB", reclaimed as f64 / 1024.0)
+    };
+    eprintln!(
+        "{} {verb} {} orphaned download(s), {size_str} reclaimed ({} referenced)",
+        "done:".green().bold(),
+        removed.len(),
+        keep.len()
+    );
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,4 +205,100 @@ mod tests {
         let result = add("/nonexistent/file.png", dir.path(), &AssetCategory::Images);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_prune_keeps_referenced_downloads() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        std::fs::create_dir_all(project.join("scenes")).unwrap();
+        std::fs::create_dir_all(project.join("assets/downloads")).unwrap();
+        std::fs::write(project.join("project.toml"), "[project]\nname = \"Prune\"\n").unwrap();
+
+        let url = "https://example.com/photo.jpg";
+        std::fs::write(
+            project.join("scenes/01-intro.md"),
+            format!("---\ntemplate: title-card\nbackground:\n  image: \"{url}\"\n---\nHi."),
+        )
+        .unwrap();
+
+        let downloads = project.join("assets/downloads");
+        let kept = downloads.join(format!("{}.jpg", scene::url_cache_key(url)));
+        let orphan = downloads.join(format!(
+            "{}.png",
+            scene::url_cache_key("https://example.com/old.png")
+        ));
+        std::fs::write(&kept, b"kept").unwrap();
+        std::fs::write(&orphan, b"orphaned bytes").unwrap();
+
+        // Dry run reports but doesn't delete
+        let keep = referenced_cache_keys(project).unwrap();
+        let (removed, reclaimed) = prune_downloads(&downloads, &keep, true).unwrap();
+        assert_eq!(removed, vec![orphan.clone()]);
+        assert_eq!(reclaimed, 14);
+        assert!(orphan.exists());
+
+        prune(project, false).unwrap();
+        assert!(kept.exists());
+        assert!(!orphan.exists());
+    }
+
+    #[test]
+    fn test_prune_keeps_downloads_of_ignored_scenes() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        std::fs::create_dir_all(project.join("scenes/_drafts")).unwrap();
+        std::fs::create_dir_all(project.join("assets/downloads")).unwrap();
+        std::fs::write(
+            project.join("project.toml"),
+            "[project]\nname = \"Prune\"\n",
+        )
+        .unwrap();
+        std::fs::write(project.join(".vidgenignore"), "_drafts/\n_wip*.md\n").unwrap();
+
+        let urls = [
+            "https://example.com/draft.jpg",
+            "https://example.com/wip.jpg",
+        ];
+        std::fs::write(
+            project.join("scenes/_drafts/01-draft.md"),
+            format!(
+                "---\ntemplate: title-card\nbackground:\n  image: \"{}\"\n---\n",
+                urls[0]
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            project.join("scenes/_wip-02.md"),
+            format!(
+                "---\ntemplate: title-card\nbackground:\n  image: \"{}\"\n---\n",
+                urls[1]
+            ),
+        )
+        .unwrap();
+        let cached: Vec<PathBuf> = urls
+            .iter()
+            .map(|u| project.join(format!("assets/downloads/{}.jpg", scene::url_cache_key(u))))
+            .collect();
+        for path in &cached {
+            std::fs::write(path, b"x").unwrap();
+        }
+
+        prune(project, false).unwrap();
+        assert!(cached.iter().all(|p| p.exists()));
+    }
+
+    #[test]
+    fn test_prune_aborts_on_invalid_scene() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        std::fs::create_dir_all(project.join("scenes")).unwrap();
+        std::fs::create_dir_all(project.join("assets/downloads")).unwrap();
+        std::fs::write(project.join("project.toml"), "[project]\nname = \"Prune\"\n").unwrap();
+        std::fs::write(project.join("scenes/01.md"), "no frontmatter").unwrap();
+        let cached = project.join("assets/downloads/abc.jpg");
+        std::fs::write(&cached, b"x").unwrap();
+
+        assert!(prune(project, false).is_err());
+        assert!(cached.exists());
+    }
 }
@@ -63,7 +63,7 @@ async fn capture_web(
     fps: u32,
 ) -> VidgenResult<()> {
     use crate::render::browser;
-    use crate::render::encoder::SceneEncoder;
+    use crate::render::encoder::{MusicFade, SceneEncoder};
     use chromiumoxide::cdp::browser_protocol::emulation::SetDeviceMetricsOverrideParams;
     use chromiumoxide::page::ScreenshotParams;
 
@@ -124,8 +124,8 @@ async fn capture_web(
     let pixels_per_frame = scroll_speed as f64 / fps as f64;
 
     let mut encoder = SceneEncoder::new(
-        &output_path, fps, width, height, &platform,
-        None, None, 0.0, 0.0, None, false,
+        &output_path, fps, fps, width, height, &platform,
+        None, None, 0.0, &MusicFade::default(), 0.0, None, None, false,
     )?;
 
     eprintln!(
@@ -8,7 +8,7 @@ use std::path::Path;
 /// Run the diff command: compare current scene text with cached TTS audio.
 pub async fn run(project_path: &Path) -> VidgenResult<()> {
     let cfg = config::load_config(project_path)?;
-    let scenes = scene::load_scenes(project_path)?;
+    let scenes = scene::load_scenes_with_variables(project_path, &cfg.variables)?;
 
     let cache_dir = project_path.join("assets/voiceover");
 
@@ -53,7 +53,8 @@ pub async fn run(project_path: &Path) -> VidgenResult<()> {
             .unwrap_or(&cfg.voice.engine);
         let voice = scene_voice_cfg
             .and_then(|v| v.voice_name())
-            .or(cfg.voice.default_voice.as_deref());
+            .or(cfg.voice.default_voice.as_deref())
+            .or(tts::default_voice_for(engine_name));
         let speed = scene_voice_cfg
             .and_then(|v| v.speed)
             .unwrap_or(cfg.voice.speed);
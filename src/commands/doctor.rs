@@ -0,0 +1,289 @@
+use crate::error::{VidgenError, VidgenResult};
+use colored::*;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Status of one external tool vidgen shells out to.
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolStatus {
+    pub name: String,
+    /// What vidgen uses the tool for.
+    pub purpose: String,
+    /// Rendering cannot work without this tool.
+    pub required: bool,
+    pub available: bool,
+    /// First line of the tool's version output, if it reported one.
+    pub version: Option<String>,
+    pub path: Option<String>,
+}
+
+/// Health report for the external tools and credentials vidgen depends on.
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    pub tools: Vec<ToolStatus>,
+    /// Whether `ELEVEN_API_KEY` is set (the value itself is never reported).
+    pub eleven_api_key: bool,
+}
+
+impl DoctorReport {
+    /// True when every required tool is available.
+    pub fn is_healthy(&self) -> bool {
+        self.tools.iter().all(|t| t.available || !t.required)
+    }
+}
+
+/// Result of probing a single executable.
+#[derive(Debug, Clone, Default)]
+pub struct Probe {
+    pub path: Option<PathBuf>,
+    pub version: Option<String>,
+}
+
+/// Executable, version arguments, purpose, required.
+const TOOLS: &[(&str, &[&str], &str, bool)] = &[
+    ("ffmpeg", &["-version"], "video encoding", true),
+    ("ffprobe", &["-version"], "audio/video duration probing", true),
+    ("say", &[], "native TTS (macOS)", false),
+    ("espeak-ng", &["--version"], "native TTS (Linux)", false),
+    ("edge-tts", &["--version"], "edge TTS engine", false),
+    ("piper", &["--version"], "piper TTS engine", false),
+];
+
+/// Build the report from probe functions, so tests can substitute fake tools.
+///
+/// `probe_tool` returns `None` when the executable is missing; `chromium` is the
+/// detected browser probe (if any); `env` looks up environment variables.
+pub fn build_report(
+    probe_tool: impl Fn(&str, &[&str]) -> Option<Probe>,
+    chromium: Option<Probe>,
+    env: impl Fn(&str) -> Option<String>,
+) -> DoctorReport {
+    let mut tools: Vec<ToolStatus> = Vec::new();
+
+    let status = |name: &str, purpose: &str, required: bool, probe: Option<Probe>| {
+        let probe = probe.unwrap_or_default();
+        ToolStatus {
+            name: name.to_string(),
+            purpose: purpose.to_string(),
+            required,
+            available: probe.path.is_some(),
+            version: probe.version,
+            path: probe.path.map(|p| p.display().to_string()),
+        }
+    };
+
+    let (ffmpeg, rest) = TOOLS.split_at(2);
+    for (name, args, purpose, required) in ffmpeg {
+        tools.push(status(name, purpose, *required, probe_tool(name, args)));
+    }
+    tools.push(status("chromium", "scene rendering", true, chromium));
+    for (name, args, purpose, required) in rest {
+        tools.push(status(name, purpose, *required, probe_tool(name, args)));
+    }
+
+    DoctorReport {
+        tools,
+        eleven_api_key: env("ELEVEN_API_KEY").is_some_and(|v| !v.trim().is_empty()),
+    }
+}
+
+/// Locate `name` on PATH and capture the first line of its version output.
+fn probe_executable(name: &str, version_args: &[&str]) -> Option<Probe> {
+    let which = Command::new("which").arg(name).output().ok()?;
+    if !which.status.success() {
+        return None;
+    }
+    let path = PathBuf::from(String::from_utf8_lossy(&which.stdout).trim());
+    let version = if version_args.is_empty() {
+        None
+    } else {
+        run_version(&path, version_args)
+    };
+    Some(Probe {
+        path: Some(path),
+        version,
+    })
+}
+
+/// Run `<path> <args>` and return the first non-empty output line.
+fn run_version(path: &std::path::Path, args: &[&str]) -> Option<String> {
+    let output = Command::new(path).args(args).output().ok()?;
+    // Some tools (e.g. espeak-ng on older builds) print their version to stderr
+    first_line(&String::from_utf8_lossy(&output.stdout))
+        .or_else(|| first_line(&String::from_utf8_lossy(&output.stderr)))
+}
+
+fn first_line(text: &str) -> Option<String> {
+    text.lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .map(str::to_string)
+}
+
+/// Detect the Chromium/Chrome executable chromiumoxide would launch
+/// (`VIDGEN_CHROME_PATH` when set).
+fn probe_chromium() -> Option<Probe> {
+    use chromiumoxide::detection::{default_executable, DetectionOptions};
+    let path = match crate::render::browser::chrome_path_override() {
+        Some(path) => path.is_file().then_some(path)?,
+        None => default_executable(DetectionOptions::default()).ok()?,
+    };
+    let version = run_version(&path, &["--version"]);
+    Some(Probe {
+        path: Some(path),
+        version,
+    })
+}
+
+pub fn run(json: bool) -> VidgenResult<()> {
+    let report = build_report(probe_executable, probe_chromium(), |k| std::env::var(k).ok());
+
+    if json {
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| VidgenError::Other(format!("Failed to serialize report: {e}")))?;
+        println!("{json}");
+        return health_result(&report);
+    }
+
+    eprintln!("Checking external tools...");
+    for tool in &report.tools {
+        if tool.available {
+            eprintln!(
+                "  {} {:<10} {}",
+                "\u{2713}".green(),
+                tool.name,
+                tool.version.as_deref().unwrap_or("(version unknown)").dimmed(),
+            );
+        } else if tool.required {
+            eprintln!(
+                "  {} {:<10} not found (required for {})",
+                "\u{2717}".red(),
+                tool.name,
+                tool.purpose,
+            );
+        } else {
+            eprintln!(
+                "  {} {:<10} not found ({})",
+                "-".dimmed(),
+                tool.name,
+                tool.purpose,
+            );
+        }
+    }
+    if report.eleven_api_key {
+        eprintln!("  {} ELEVEN_API_KEY set", "\u{2713}".green());
+    } else {
+        eprintln!("  {} ELEVEN_API_KEY not set (elevenlabs TTS engine)", "-".dimmed());
+    }
+
+    if report.is_healthy() {
+        eprintln!("\n{}", "All required tools found.".green());
+    }
+    health_result(&report)
+}
+
+/// Fail (non-zero exit) when a required tool is missing, so `doctor` works as a CI check.
+fn health_result(report: &DoctorReport) -> VidgenResult<()> {
+    let missing: Vec<&str> = report
+        .tools
+        .iter()
+        .filter(|t| t.required && !t.available)
+        .map(|t| t.name.as_str())
+        .collect();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(VidgenError::Other(format!(
+            "Required tools missing: {}",
+            missing.join(", ")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_probe(installed: &'static [&'static str]) -> impl Fn(&str, &[&str]) -> Option<Probe> {
+        move |name, _| {
+            installed.contains(&name).then(|| Probe {
+                path: Some(PathBuf::from(format!("/usr/bin/{name}"))),
+                version: Some(format!("{name} version 1.0")),
+            })
+        }
+    }
+
+    fn tool<'a>(report: &'a DoctorReport, name: &str) -> &'a ToolStatus {
+        report.tools.iter().find(|t| t.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_report_all_tools_available() {
+        let chromium = Probe {
+            path: Some(PathBuf::from("/usr/bin/chromium")),
+            version: Some("Chromium 120.0".into()),
+        };
+        let report = build_report(
+            fake_probe(&["ffmpeg", "ffprobe", "say", "espeak-ng", "edge-tts", "piper"]),
+            Some(chromium),
+            |_| Some("secret".into()),
+        );
+
+        assert_eq!(report.tools.len(), 7);
+        assert!(report.tools.iter().all(|t| t.available));
+        assert!(report.is_healthy());
+        assert!(report.eleven_api_key);
+        assert_eq!(tool(&report, "ffmpeg").version.as_deref(), Some("ffmpeg version 1.0"));
+        assert_eq!(tool(&report, "chromium").path.as_deref(), Some("/usr/bin/chromium"));
+    }
+
+    #[test]
+    fn test_report_missing_optional_tools() {
+        let report = build_report(
+            fake_probe(&["ffmpeg", "ffprobe", "edge-tts"]),
+            Some(Probe {
+                path: Some(PathBuf::from("/opt/chrome")),
+                version: None,
+            }),
+            |_| None,
+        );
+
+        assert!(report.is_healthy());
+        assert!(!report.eleven_api_key);
+        let piper = tool(&report, "piper");
+        assert!(!piper.available);
+        assert!(!piper.required);
+        assert!(piper.version.is_none() && piper.path.is_none());
+        assert!(tool(&report, "edge-tts").available);
+    }
+
+    #[test]
+    fn test_report_missing_required_tools() {
+        let report = build_report(fake_probe(&["ffprobe"]), None, |_| None);
+
+        assert!(!report.is_healthy());
+        assert!(!tool(&report, "ffmpeg").available);
+        assert!(!tool(&report, "chromium").available);
+        assert!(tool(&report, "chromium").required);
+        assert_eq!(
+            health_result(&report).unwrap_err().to_string(),
+            "Required tools missing: ffmpeg, chromium"
+        );
+    }
+
+    #[test]
+    fn test_blank_api_key_not_counted() {
+        let report = build_report(fake_probe(&[]), None, |_| Some("  ".into()));
+        assert!(!report.eleven_api_key);
+    }
+
+    #[test]
+    fn test_first_line() {
+        assert_eq!(
+            first_line("\n  ffmpeg version 6.1 Copyright\nbuilt with gcc\n").as_deref(),
+            Some("ffmpeg version 6.1 Copyright")
+        );
+        assert_eq!(first_line("   \n"), None);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::config;
 use crate::error::{VidgenError, VidgenResult};
 use crate::render::browser::capture_single_frame;
+use crate::render::capture_size;
 use crate::scene;
 use crate::template::TemplateRegistry;
 use colored::*;
@@ -25,7 +26,7 @@ impl ExportFormat {
 }
 
 /// Open a file with the platform's default viewer.
-fn open_file(path: &Path) {
+pub(crate) fn open_file(path: &Path) {
     #[cfg(target_os = "macos")]
     let _ = std::process::Command::new("open").arg(path).spawn();
     #[cfg(target_os = "linux")]
@@ -89,7 +90,7 @@ async fn render_combined_gif(
                 total_frames,
                 Some(project_path),
             )?;
-            let png = capture_single_frame(&html, width, height, f, total_frames).await?;
+            let png = capture_single_frame(&html, width, height, f, total_frames, s.frontmatter.wait_for.as_deref()).await?;
             let frame_path = temp_dir
                 .path()
                 .join(format!("frame-{global_frame_idx:04}.png"));
@@ -180,14 +181,14 @@ pub async fn run(
     smart: bool,
 ) -> VidgenResult<()> {
     let cfg = config::load_config(project_path)?;
-    let scenes = scene::load_scenes(project_path)?;
+    let scenes = scene::load_scenes_with_variables(project_path, &cfg.variables)?;
     let count = scenes.len();
 
     let mut registry = TemplateRegistry::new()?;
     registry.register_project_templates(project_path)?;
+    registry.apply_emoji_fallback(&cfg.render, project_path);
 
-    let width = cfg.video.width;
-    let height = cfg.video.height;
+    let (width, height) = capture_size(&cfg, cfg.video.width, cfg.video.height);
     let fps = cfg.video.fps;
 
     // Default output directory: project_path/output/
@@ -284,7 +285,7 @@ pub async fn run(
                                 Some(project_path),
                             )?;
                             let candidate =
-                                capture_single_frame(&html, width, height, cf, total).await?;
+                                capture_single_frame(&html, width, height, cf, total, s.frontmatter.wait_for.as_deref()).await?;
                             let w = image_weight(&candidate);
                             if w > best_weight {
                                 best_weight = w;
@@ -302,7 +303,7 @@ pub async fn run(
                             total,
                             Some(project_path),
                         )?;
-                        capture_single_frame(&html, width, height, f, total).await?
+                        capture_single_frame(&html, width, height, f, total, s.frontmatter.wait_for.as_deref()).await?
                     };
                     let filename = format!("export-{:02}.png", i + 1);
                     let path = output_dir.join(&filename);
@@ -375,7 +376,7 @@ pub async fn run(
                         Some(project_path),
                     )?;
                     let candidate =
-                        capture_single_frame(&html, width, height, cf, total_frames).await?;
+                        capture_single_frame(&html, width, height, cf, total_frames, s.frontmatter.wait_for.as_deref()).await?;
                     let w = image_weight(&candidate);
                     if w > best_weight {
                         best_weight = w;
@@ -414,7 +415,7 @@ pub async fn run(
                     total_frames,
                     Some(project_path),
                 )?;
-                let data = capture_single_frame(&html, width, height, f, total_frames).await?;
+                let data = capture_single_frame(&html, width, height, f, total_frames, s.frontmatter.wait_for.as_deref()).await?;
                 (data, f)
             };
 
@@ -500,7 +501,7 @@ pub async fn run_audio(
     output: Option<PathBuf>,
 ) -> VidgenResult<()> {
     let cfg = config::load_config(project_path)?;
-    let scenes = scene::load_scenes(project_path)?;
+    let scenes = scene::load_scenes_with_variables(project_path, &cfg.variables)?;
     let count = scenes.len();
 
     // Load .env from project directory (if present) so keys like ELEVEN_API_KEY are available
@@ -544,7 +545,8 @@ pub async fn run_audio(
         let scene_voice_cfg = scene.frontmatter.voice.as_ref();
         let voice = scene_voice_cfg
             .and_then(|v| v.voice_name())
-            .or(cfg.voice.default_voice.as_deref());
+            .or(cfg.voice.default_voice.as_deref())
+            .or(tts_engine.default_voice());
         let speed = scene_voice_cfg
             .and_then(|v| v.speed)
             .unwrap_or(cfg.voice.speed);
@@ -565,24 +567,12 @@ pub async fn run_audio(
                 let dest = if indices.len() == 1 {
                     // Single scene: use output path directly if given
                     output.clone().unwrap_or_else(|| {
-                        let fallback_name = format!("scene-{:02}", i + 1);
-                        let scene_name = scene
-                            .source_path
-                            .file_stem()
-                            .and_then(|s| s.to_str())
-                            .unwrap_or(&fallback_name);
-                        audio_output_dir.join(format!("{scene_name}.wav"))
+                        audio_output_dir.join(scene::audio_file_name(i, &scene.source_path, "wav"))
                     })
                 } else {
                     // Multiple scenes: save to output dir
                     let out_dir = output.as_deref().unwrap_or(&audio_output_dir);
-                    let fallback_name = format!("scene-{:02}", i + 1);
-                    let scene_name = scene
-                        .source_path
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or(&fallback_name);
-                    out_dir.join(format!("{scene_name}.wav"))
+                    out_dir.join(scene::audio_file_name(i, &scene.source_path, "wav"))
                 };
 
                 if let Some(parent) = dest.parent() {
@@ -616,7 +606,7 @@ pub async fn run_audio(
 /// Export subtitles as SRT file, with one entry per scene based on TTS durations.
 pub async fn run_subtitles(project_path: &Path, output: Option<PathBuf>) -> VidgenResult<()> {
     let cfg = config::load_config(project_path)?;
-    let scenes = scene::load_scenes(project_path)?;
+    let scenes = scene::load_scenes_with_variables(project_path, &cfg.variables)?;
 
     // Load .env from project directory (if present) so keys like ELEVEN_API_KEY are available
     let _ = dotenvy::from_path(project_path.join(".env"));
@@ -649,7 +639,8 @@ pub async fn run_subtitles(project_path: &Path, output: Option<PathBuf>) -> Vidg
         let scene_voice_cfg = s.frontmatter.voice.as_ref();
         let voice = scene_voice_cfg
             .and_then(|v| v.voice_name())
-            .or(cfg.voice.default_voice.as_deref());
+            .or(cfg.voice.default_voice.as_deref())
+            .or(tts_engine.default_voice());
         let speed = scene_voice_cfg
             .and_then(|v| v.speed)
             .unwrap_or(cfg.voice.speed);
@@ -709,6 +700,7 @@ pub async fn run_subtitles(project_path: &Path, output: Option<PathBuf>) -> Vidg
 }
 
 /// Render an animated GIF or WebP from a scene.
+#[allow(clippy::too_many_arguments)]
 async fn render_animated(
     registry: &TemplateRegistry<'_>,
     scene: &scene::Scene,
@@ -744,7 +736,7 @@ async fn render_animated(
             total_frames,
             Some(project_path),
         )?;
-        let png = capture_single_frame(&html, width, height, f, total_frames).await?;
+        let png = capture_single_frame(&html, width, height, f, total_frames, scene.frontmatter.wait_for.as_deref()).await?;
         let frame_path = temp_dir.path().join(format!("frame-{frame_idx:04}.png"));
         std::fs::write(&frame_path, &png)?;
         frame_idx += 1;
@@ -0,0 +1,246 @@
+use crate::commands::scenes::{add_scenes, SceneInput};
+use crate::error::{VidgenError, VidgenResult};
+use colored::*;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// One data row: column name → value.
+pub type DataRow = serde_json::Map<String, serde_json::Value>;
+
+/// Column used as the voiceover script when `--script-column` isn't given.
+pub const DEFAULT_SCRIPT_COLUMN: &str = "script";
+
+/// Generate one scene per row of a CSV or JSON data file, using `template` for
+/// every scene. Columns become props; the script column becomes the voiceover.
+pub fn run(
+    project_path: &Path,
+    template: &str,
+    data: &Path,
+    script_column: Option<&str>,
+) -> VidgenResult<()> {
+    if !project_path.exists() {
+        return Err(VidgenError::ProjectNotFound(project_path.to_path_buf()));
+    }
+    let rows = load_rows(data)?;
+    if rows.is_empty() {
+        return Err(VidgenError::Other(format!(
+            "{} has no data rows",
+            data.display()
+        )));
+    }
+
+    let inputs = scenes_from_rows(
+        template,
+        &rows,
+        script_column.unwrap_or(DEFAULT_SCRIPT_COLUMN),
+    );
+    let result = add_scenes(project_path, None, inputs)?;
+    eprintln!(
+        "{} Generated {} scene(s) from {} ({} total)",
+        "generate:".green().bold(),
+        result.scenes_added,
+        data.display(),
+        result.total_scenes
+    );
+    for file in result
+        .files
+        .iter()
+        .skip(result.total_scenes - result.scenes_added)
+    {
+        eprintln!("  {file}");
+    }
+    Ok(())
+}
+
+/// Read rows from a `.csv` file (header row + records) or a `.json` array of objects.
+pub fn load_rows(data: &Path) -> VidgenResult<Vec<DataRow>> {
+    let text = std::fs::read_to_string(data)?;
+    match data
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .as_deref()
+    {
+        Some("csv") => parse_csv_rows(&text),
+        Some("json") => parse_json_rows(&text),
+        _ => Err(VidgenError::Other(format!(
+            "Unsupported data file: {}. Use a .csv or .json file.",
+            data.display()
+        ))),
+    }
+}
+
+/// Map each row to a scene: the script column is the voiceover, every other
+/// non-empty column is a prop.
+pub fn scenes_from_rows(template: &str, rows: &[DataRow], script_column: &str) -> Vec<SceneInput> {
+    rows.iter()
+        .map(|row| {
+            let script = row
+                .get(script_column)
+                .map(|v| match v {
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .unwrap_or_default();
+            let props: HashMap<String, serde_json::Value> = row
+                .iter()
+                .filter(|(k, v)| {
+                    k.as_str() != script_column && !v.is_null() && v.as_str() != Some("")
+                })
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+            SceneInput {
+                template: Some(template.to_string()),
+                script,
+                duration: None,
+                props: if props.is_empty() { None } else { Some(props) },
+                transition: None,
+                voice: None,
+                background: None,
+            }
+        })
+        .collect()
+}
+
+/// Parse a JSON array of objects.
+fn parse_json_rows(text: &str) -> VidgenResult<Vec<DataRow>> {
+    let value: serde_json::Value = serde_json::from_str(text)
+        .map_err(|e| VidgenError::Other(format!("Invalid JSON data: {e}")))?;
+    let items = value
+        .as_array()
+        .ok_or_else(|| VidgenError::Other("JSON data must be an array of objects".into()))?;
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            item.as_object().cloned().ok_or_else(|| {
+                VidgenError::Other(format!("JSON data item {} is not an object", i + 1))
+            })
+        })
+        .collect()
+}
+
+/// Parse CSV with a header row. Supports quoted fields (with `""` escapes,
+/// commas and line breaks inside quotes); blank lines are skipped.
+fn parse_csv_rows(text: &str) -> VidgenResult<Vec<DataRow>> {
+    let mut records = parse_csv_records(text).into_iter();
+    let Some(header) = records.next() else {
+        return Ok(Vec::new());
+    };
+    let header: Vec<String> = header.into_iter().map(|h| h.trim().to_string()).collect();
+
+    records
+        .enumerate()
+        .map(|(i, fields)| {
+            if fields.len() != header.len() {
+                return Err(VidgenError::Other(format!(
+                    "CSV row {} has {} field(s), expected {} (one per header column)",
+                    i + 1,
+                    fields.len(),
+                    header.len()
+                )));
+            }
+            Ok(header
+                .iter()
+                .cloned()
+                .zip(fields.into_iter().map(serde_json::Value::String))
+                .collect())
+        })
+        .collect()
+}
+
+fn parse_csv_records(text: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                if !(record.len() == 1 && record[0].is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                }
+                record.clear();
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene;
+
+    #[test]
+    fn test_csv_rows_become_scenes_with_props() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("team");
+        crate::commands::init::run(&project, None, false).unwrap();
+        let before = scene::load_scenes(&project).unwrap().len();
+
+        let data = dir.path().join("people.csv");
+        std::fs::write(
+            &data,
+            "name,title,script\n\
+             Ada,Engineer,Meet Ada.\n\
+             \"Grace, Jr.\",\"Rear \"\"Admiral\"\"\",Meet Grace.\n\
+             Linus,Maintainer,\n",
+        )
+        .unwrap();
+        run(&project, "lower-third", &data, None).unwrap();
+
+        let scenes = scene::load_scenes(&project).unwrap();
+        assert_eq!(scenes.len(), before + 3);
+        let generated = &scenes[before..];
+        assert!(generated
+            .iter()
+            .all(|s| s.frontmatter.template == "lower-third"));
+        assert_eq!(generated[0].frontmatter.props["name"], "Ada");
+        assert_eq!(generated[0].frontmatter.props["title"], "Engineer");
+        assert_eq!(generated[0].script.trim(), "Meet Ada.");
+        assert_eq!(generated[1].frontmatter.props["name"], "Grace, Jr.");
+        assert_eq!(generated[1].frontmatter.props["title"], "Rear \"Admiral\"");
+        assert!(!generated[1].frontmatter.props.contains_key("script"));
+        assert_eq!(generated[2].frontmatter.props["name"], "Linus");
+        assert!(generated[2].script.trim().is_empty());
+    }
+
+    #[test]
+    fn test_json_rows_keep_value_types() {
+        let rows = parse_json_rows(r#"[{"name": "Ada", "years": 12}, {"name": "Grace"}]"#).unwrap();
+        let scenes = scenes_from_rows("lower-third", &rows, "script");
+        assert_eq!(scenes.len(), 2);
+        let props = scenes[0].props.as_ref().unwrap();
+        assert_eq!(props["years"], 12);
+        assert!(parse_json_rows(r#"{"name": "Ada"}"#).is_err());
+    }
+
+    #[test]
+    fn test_csv_row_length_mismatch() {
+        let err = parse_csv_rows("name,title\nAda\n").unwrap_err();
+        assert!(err.to_string().contains("row 1 has 1 field(s), expected 2"));
+    }
+}
@@ -1,7 +1,7 @@
 use crate::config;
 use crate::error::VidgenResult;
+use crate::render::synthesize_narration;
 use crate::scene;
-use crate::tts;
 use colored::*;
 use std::path::Path;
 
@@ -9,63 +9,12 @@ pub async fn run(project_path: &Path) -> VidgenResult<()> {
     let config = config::load_config(project_path)?;
     config.validate()?;
 
-    let scenes = scene::load_scenes(project_path)?;
+    let scenes = scene::load_scenes_with_variables(project_path, &config.variables)?;
 
     // Load .env from project directory (if present) so TTS API keys are available
     let _ = dotenvy::from_path(project_path.join(".env"));
 
-    // Create TTS engine (optional — if it fails, we show "?" for auto durations)
-    let tts_engine = tts::create_engine(&config.voice).ok();
-
-    // Synthesize TTS for each scene to get accurate durations
-    let temp_dir = tempfile::tempdir()?;
-    let mut tts_durations: Vec<Option<f64>> = Vec::new();
-
-    for (i, s) in scenes.iter().enumerate() {
-        let script = s.script.trim();
-        if script.is_empty() || tts_engine.is_none() {
-            tts_durations.push(None);
-            continue;
-        }
-
-        let wav_path = temp_dir.path().join(format!("scene-{i:03}.wav"));
-
-        // Determine per-scene voice/speed overrides (same logic as render/mod.rs)
-        let scene_voice_cfg = s.frontmatter.voice.as_ref();
-        let scene_engine_override = scene_voice_cfg.and_then(|v| v.engine.as_deref());
-        let voice = scene_voice_cfg
-            .and_then(|v| v.voice_name())
-            .or(config.voice.default_voice.as_deref());
-        let speed = scene_voice_cfg
-            .and_then(|v| v.speed)
-            .unwrap_or(config.voice.speed);
-
-        // Use a per-scene engine if the scene overrides the engine
-        let scene_engine: Option<Box<dyn tts::TtsEngine>> =
-            if let Some(engine_name) = scene_engine_override {
-                let mut voice_cfg = config.voice.clone();
-                voice_cfg.engine = engine_name.to_string();
-                tts::create_engine(&voice_cfg).ok()
-            } else {
-                None
-            };
-        let effective_engine: &dyn tts::TtsEngine = scene_engine
-            .as_deref()
-            .unwrap_or_else(|| tts_engine.as_ref().unwrap().as_ref());
-
-        match tts::cache::synthesize_cached_with_options(
-            effective_engine,
-            script,
-            voice,
-            speed,
-            &wav_path,
-            project_path,
-            false,
-        ) {
-            Ok(result) => tts_durations.push(Some(result.duration_secs)),
-            Err(_) => tts_durations.push(None),
-        }
-    }
+    let tts_durations = scene_tts_durations(&config, &scenes, project_path, "info:")?;
 
     // Print project header
     println!("\n{} {}", "Project:".bold(), config.project.name);
@@ -86,6 +35,14 @@ pub async fn run(project_path: &Path) -> VidgenResult<()> {
     if let Some(ref bg) = config.audio.background {
         println!("{} {} ({}dB)", "Music:  ".bold(), bg.file, bg.volume);
     }
+    if let Some(ref music) = config.audio.music {
+        println!(
+            "{} {} ({}dB, whole video)",
+            "Music:  ".bold(),
+            music,
+            config.audio.music_volume
+        );
+    }
 
     // Print scene list
     println!("\n{} ({}):", "Scenes".bold(), scenes.len());
@@ -100,6 +57,7 @@ pub async fn run(project_path: &Path) -> VidgenResult<()> {
             config.voice.padding_before,
             config.voice.padding_after,
             config.voice.auto_fallback_duration,
+            s.frontmatter.min_duration,
         );
 
         // Determine if we could resolve the duration
@@ -165,3 +123,27 @@ pub async fn run(project_path: &Path) -> VidgenResult<()> {
 
     Ok(())
 }
+
+/// Narration length per scene, used to resolve `duration: auto` without rendering.
+/// Runs the render's (cached) TTS pass, voice overrides, `voice.fallback` and
+/// `--dry-tts` included; `None` when a scene has no script or its audio could not
+/// be produced.
+pub(crate) fn scene_tts_durations(
+    config: &config::ProjectConfig,
+    scenes: &[scene::Scene],
+    project_path: &Path,
+    label: &str,
+) -> VidgenResult<Vec<Option<f64>>> {
+    let temp_dir = tempfile::tempdir()?;
+    let dry_tts = std::env::var("VIDGEN_DRY_TTS").is_ok();
+    let narration = synthesize_narration(
+        scenes,
+        config,
+        project_path,
+        temp_dir.path(),
+        dry_tts,
+        false,
+        label,
+    );
+    Ok(narration.durations)
+}
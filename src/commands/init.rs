@@ -54,6 +54,8 @@ pub struct CreateProjectResult {
 fn format_duration_yaml(duration: &SceneDuration) -> String {
     match duration {
         SceneDuration::Auto => "auto".to_string(),
+        SceneDuration::MatchMusic => "match-music".to_string(),
+        SceneDuration::Percent(p) => format!("\"{p}%\""),
         SceneDuration::Fixed(d) => {
             if *d == d.floor() {
                 format!("{}", *d as i64)
@@ -585,8 +587,16 @@ fn apply_preset(preset: &str, path: &Path) -> VidgenResult<CreateProjectOptions>
     }
 }
 
+/// Scaffold a `.vidgenignore` with only explanatory comments.
+fn write_ignore_file(path: &Path) -> VidgenResult<()> {
+    let content = "# Scene files vidgen should skip (glob patterns matched against names in scenes/)\n\
+                   # _draft*.md\n";
+    std::fs::write(path.join(crate::scene::IGNORE_FILE), content)?;
+    Ok(())
+}
+
 /// CLI entry point — delegates to `create_project()`.
-pub fn run(path: &Path, preset: Option<&str>) -> VidgenResult<()> {
+pub fn run(path: &Path, preset: Option<&str>, ignore_file: bool) -> VidgenResult<()> {
     let opts = if let Some(preset_name) = preset {
         apply_preset(preset_name, path)?
     } else {
@@ -603,7 +613,11 @@ pub fn run(path: &Path, preset: Option<&str>) -> VidgenResult<()> {
             scenes: None,
         }
     };
-    let result = create_project(&opts)?;
+    let mut result = create_project(&opts)?;
+    if ignore_file {
+        write_ignore_file(path)?;
+        result.files.push(crate::scene::IGNORE_FILE.to_string());
+    }
 
     eprintln!(
         "{} Created project at {}",
@@ -624,6 +638,21 @@ pub fn run(path: &Path, preset: Option<&str>) -> VidgenResult<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_init_with_ignore_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_path = dir.path().join("ignored");
+        run(&project_path, None, true).unwrap();
+        let ignore = std::fs::read_to_string(project_path.join(".vidgenignore")).unwrap();
+        // Only comments, so nothing is ignored until the user adds a pattern
+        assert!(ignore.lines().all(|l| l.starts_with('#')));
+        assert!(crate::scene::load_ignore_patterns(&project_path).is_empty());
+
+        let plain = dir.path().join("plain");
+        run(&plain, None, false).unwrap();
+        assert!(!plain.join(".vidgenignore").exists());
+    }
+
     #[test]
     fn test_create_project_default() {
         let dir = tempfile::tempdir().unwrap();
@@ -1,17 +1,47 @@
 use crate::error::{VidgenError, VidgenResult};
 use crate::mcp::McServer;
+use rmcp::transport::streamable_http_server::{
+    session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
+};
 use rmcp::ServiceExt;
+use std::sync::Arc;
 
-pub async fn run() -> VidgenResult<()> {
-    // Send tracing output to stderr so it doesn't corrupt the MCP stdio JSON-RPC channel.
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing_subscriber::filter::LevelFilter::INFO.into()),
-        )
-        .with_writer(std::io::stderr)
-        .init();
+/// URL path the HTTP transport serves MCP requests on.
+pub const MCP_HTTP_PATH: &str = "/mcp";
+
+/// How the MCP server talks to its client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transport {
+    /// JSON-RPC over stdin/stdout, for agents that spawn vidgen themselves (default)
+    Stdio,
+    /// Streamable HTTP (POST requests, SSE responses) at `http://<host>:<port>/mcp`,
+    /// for agents running on another machine or in a container
+    Http { host: String, port: u16 },
+}
+
+impl Transport {
+    /// Pick the transport from the `vidgen mcp` flags.
+    pub fn from_args(http: bool, host: String, port: u16) -> Self {
+        if http {
+            Transport::Http { host, port }
+        } else {
+            Transport::Stdio
+        }
+    }
+}
+
+/// Router serving the MCP server over streamable HTTP at [`MCP_HTTP_PATH`].
+/// Each client session gets its own server instance.
+fn http_router() -> axum::Router {
+    let service = StreamableHttpService::new(
+        || Ok(McServer::new()),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+    axum::Router::new().nest_service(MCP_HTTP_PATH, service)
+}
 
+async fn serve_stdio() -> VidgenResult<()> {
     let server = McServer::new();
     let service = server
         .serve(rmcp::transport::stdio())
@@ -23,3 +53,110 @@ pub async fn run() -> VidgenResult<()> {
         .map_err(|e| VidgenError::Other(format!("MCP server error: {e}")))?;
     Ok(())
 }
+
+async fn serve_http(host: &str, port: u16) -> VidgenResult<()> {
+    let listener = tokio::net::TcpListener::bind((host, port))
+        .await
+        .map_err(|e| {
+            VidgenError::Other(format!(
+                "Failed to bind MCP HTTP server to {host}:{port}: {e}"
+            ))
+        })?;
+    let addr = listener.local_addr()?;
+    tracing::info!("MCP server listening on http://{addr}{MCP_HTTP_PATH}");
+    axum::serve(listener, http_router())
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+        .map_err(|e| VidgenError::Other(format!("MCP server error: {e}")))
+}
+
+pub async fn run(transport: Transport) -> VidgenResult<()> {
+    // Send tracing output to stderr so it doesn't corrupt the MCP stdio JSON-RPC channel.
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::from_default_env()
+                .add_directive(tracing_subscriber::filter::LevelFilter::INFO.into()),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+
+    match transport {
+        Transport::Stdio => serve_stdio().await,
+        Transport::Http { host, port } => serve_http(&host, port).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Cli, Command};
+    use clap::Parser;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn parse(args: &[&str]) -> Transport {
+        match Cli::try_parse_from(args).unwrap().command {
+            Command::Mcp { http, host, port } => Transport::from_args(http, host, port),
+            other => panic!("unexpected command: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_transport_selection() {
+        assert_eq!(parse(&["vidgen", "mcp"]), Transport::Stdio);
+        assert_eq!(
+            parse(&["vidgen", "mcp", "--http"]),
+            Transport::Http {
+                host: "127.0.0.1".into(),
+                port: 8080
+            }
+        );
+        assert_eq!(
+            parse(&["vidgen", "mcp", "--http", "--port", "9000", "--host", "0.0.0.0"]),
+            Transport::Http {
+                host: "0.0.0.0".into(),
+                port: 9000
+            }
+        );
+        // --port only makes sense with --http
+        assert!(Cli::try_parse_from(["vidgen", "mcp", "--port", "9000"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_http_transport_answers_initialize() {
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move { axum::serve(listener, http_router()).await });
+
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-03-26","capabilities":{},"clientInfo":{"name":"test","version":"0"}}}"#;
+        let request = format!(
+            "POST {MCP_HTTP_PATH} HTTP/1.1\r\nHost: {addr}\r\nContent-Type: application/json\r\n\
+             Accept: application/json, text/event-stream\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = Vec::new();
+        let mut buf = [0u8; 4096];
+        // The SSE stream stays open; read until the initialize result arrives
+        let read = async {
+            loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                response.extend_from_slice(&buf[..n]);
+                if n == 0 || String::from_utf8_lossy(&response).contains("serverInfo") {
+                    break;
+                }
+            }
+        };
+        tokio::time::timeout(std::time::Duration::from_secs(10), read)
+            .await
+            .unwrap();
+        let text = String::from_utf8_lossy(&response);
+        assert!(text.starts_with("HTTP/1.1 200"), "{text}");
+        assert!(text.contains("serverInfo"), "{text}");
+        server.abort();
+    }
+}
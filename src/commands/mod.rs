@@ -2,15 +2,22 @@ pub mod asset;
 #[cfg(any(feature = "clipper", feature = "youtube"))]
 pub mod clip;
 pub mod diff;
+pub mod doctor;
 pub mod export;
+pub mod generate;
 pub mod info;
 pub mod init;
 pub mod mcp;
+pub mod paginate;
 pub mod preview;
+pub mod qualities;
 pub mod quickrender;
 pub mod render;
 pub mod scenes;
+pub mod status;
 pub mod templates;
 pub mod test;
+pub mod theme_preview;
+pub mod tts;
 pub mod validate;
 pub mod watch;
@@ -0,0 +1,31 @@
+use crate::commands::scenes::{paginate_scenes, ITEMS_PROP};
+use crate::error::{VidgenError, VidgenResult};
+use colored::*;
+use std::path::Path;
+
+/// Split scenes whose `items` prop is longer than `page_size` into consecutive
+/// scenes of the same template. `scenes` holds 0-based indices (default: all).
+pub fn run(project_path: &Path, page_size: usize, scenes: Option<Vec<usize>>) -> VidgenResult<()> {
+    if !project_path.exists() {
+        return Err(VidgenError::ProjectNotFound(project_path.to_path_buf()));
+    }
+    let result = paginate_scenes(project_path, page_size, scenes.as_deref())?;
+    if result.scenes_split == 0 {
+        eprintln!(
+            "{} No scene has more than {page_size} {ITEMS_PROP}",
+            "paginate:".green().bold()
+        );
+        return Ok(());
+    }
+    eprintln!(
+        "{} Split {} scene(s) into {} more ({} total)",
+        "paginate:".green().bold(),
+        result.scenes_split,
+        result.scenes_added,
+        result.total_scenes
+    );
+    for file in &result.files {
+        eprintln!("  {file}");
+    }
+    Ok(())
+}
@@ -1,12 +1,425 @@
 use crate::config;
 use crate::error::{VidgenError, VidgenResult};
-use crate::render::browser::capture_single_frame;
+use crate::render::browser::{capture_single_frame, BrowserSession};
+use crate::render::capture_size;
 use crate::scene;
 use crate::template::TemplateRegistry;
+use base64::Engine;
 use colored::*;
+use std::io::{BufRead, Write};
 use std::path::{Path, PathBuf};
 
-/// Run the preview command: render a single frame (or all scenes / animated GIF).
+/// Height (px) every format is scaled to in the `--all-formats` contact sheet.
+const MONTAGE_CELL_HEIGHT: u32 = 540;
+/// Spacing around and between contact sheet cells.
+const MONTAGE_GAP: u32 = 24;
+/// Height reserved above each cell for its label.
+const MONTAGE_LABEL_HEIGHT: u32 = 32;
+
+/// One format's position in the contact sheet.
+#[derive(Debug, Clone, PartialEq)]
+struct MontageCell {
+    label: String,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+/// Lay out one cell per format in a single row, all scaled to `cell_height` so
+/// each keeps its aspect ratio. Returns the cells and the sheet's total size.
+fn montage_layout(formats: &[(String, u32, u32)], cell_height: u32) -> (Vec<MontageCell>, u32, u32) {
+    let mut cells = Vec::new();
+    let mut x = MONTAGE_GAP;
+    for (name, w, h) in formats {
+        let width = ((*w as f64 * cell_height as f64) / *h as f64).round() as u32;
+        cells.push(MontageCell {
+            label: format!("{name} \u{b7} {w}\u{d7}{h}"),
+            x,
+            y: MONTAGE_GAP + MONTAGE_LABEL_HEIGHT,
+            width,
+            height: cell_height,
+        });
+        x += width + MONTAGE_GAP;
+    }
+    let total_height = MONTAGE_GAP * 2 + MONTAGE_LABEL_HEIGHT + cell_height;
+    (cells, x, total_height)
+}
+
+/// Build the contact sheet page: each frame as an `<img>` with its label above.
+fn montage_html(cells: &[MontageCell], pngs: &[Vec<u8>], width: u32, height: u32) -> String {
+    let mut body = String::new();
+    for (cell, png) in cells.iter().zip(pngs) {
+        let data = base64::engine::general_purpose::STANDARD.encode(png);
+        let label = cell.label.replace('&', "&amp;").replace('<', "&lt;");
+        body.push_str(&format!(
+            "<div class=\"label\" style=\"left:{}px;top:{}px\">{label}</div>\
+             <img src=\"data:image/png;base64,{data}\" style=\"left:{}px;top:{}px;width:{}px;height:{}px\">",
+            cell.x, MONTAGE_GAP, cell.x, cell.y, cell.width, cell.height,
+        ));
+    }
+    format!(
+        "<!DOCTYPE html><html><head><style>\
+         html,body{{margin:0;width:{width}px;height:{height}px;background:#18181b;overflow:hidden}}\
+         .label{{position:absolute;color:#e4e4e7;font:600 18px system-ui,sans-serif}}\
+         img{{position:absolute;outline:1px solid #3f3f46}}\
+         </style></head><body>{body}</body></html>"
+    )
+}
+
+/// Render frame 0 of one scene in every configured format and save a labeled montage.
+async fn preview_all_formats(
+    project_path: &Path,
+    cfg: &config::ProjectConfig,
+    registry: &TemplateRegistry<'_>,
+    s: &scene::Scene,
+    scene_index: usize,
+    output: Option<PathBuf>,
+) -> VidgenResult<()> {
+    let formats: Vec<(String, u32, u32)> = crate::render::resolve_formats(cfg, None)
+        .into_iter()
+        .map(|(name, w, h, _)| (name, w, h))
+        .collect();
+
+    eprintln!(
+        "{} Previewing scene {} in {} format(s)...",
+        "preview:".cyan().bold(),
+        scene_index,
+        formats.len()
+    );
+
+    let mut pngs = Vec::new();
+    for (name, w, h) in &formats {
+        let fmt_scene = crate::render::apply_format_overrides(s, name);
+        let total = fmt_scene.total_frames(cfg.video.fps);
+        let html = registry.render_scene_html(&fmt_scene, &cfg.theme, *w, *h, 0, total, Some(project_path))?;
+        pngs.push(capture_single_frame(&html, *w, *h, 0, total, fmt_scene.frontmatter.wait_for.as_deref()).await?);
+        eprintln!("  {}: {}x{}", name, w, h);
+    }
+
+    let (cells, sheet_w, sheet_h) = montage_layout(&formats, MONTAGE_CELL_HEIGHT);
+    let html = montage_html(&cells, &pngs, sheet_w, sheet_h);
+    let montage = capture_single_frame(&html, sheet_w, sheet_h, 0, 1, None).await?;
+
+    let output_path = output.unwrap_or_else(|| PathBuf::from("preview-formats.png"));
+    std::fs::write(&output_path, &montage)?;
+    eprintln!(
+        "{} Saved format contact sheet to {} ({}x{})",
+        "done:".green().bold(),
+        output_path.display(),
+        sheet_w,
+        sheet_h
+    );
+    Ok(())
+}
+
+/// A screen area covered by platform UI, as fractions of the frame size.
+#[derive(Debug, Clone, Copy)]
+struct UiRegion {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+const fn region(x: f64, y: f64, width: f64, height: f64) -> UiRegion {
+    UiRegion { x, y, width, height }
+}
+
+/// Approximate UI areas of vertical video apps: top bar, right action bar
+/// (likes, comments, share) and the bottom caption/account area.
+const PLATFORM_UI_REGIONS: &[(&str, &[UiRegion])] = &[
+    (
+        "tiktok",
+        &[
+            region(0.0, 0.0, 1.0, 0.08),
+            region(0.86, 0.38, 0.14, 0.48),
+            region(0.0, 0.80, 0.84, 0.20),
+        ],
+    ),
+    (
+        "reels",
+        &[
+            region(0.0, 0.0, 1.0, 0.07),
+            region(0.86, 0.50, 0.14, 0.38),
+            region(0.0, 0.82, 0.86, 0.18),
+        ],
+    ),
+    (
+        "shorts",
+        &[
+            region(0.0, 0.0, 1.0, 0.06),
+            region(0.85, 0.45, 0.15, 0.40),
+            region(0.0, 0.80, 0.85, 0.20),
+        ],
+    ),
+];
+
+/// Guide color and opacity for the shaded UI regions.
+const OVERLAY_COLOR: [u8; 3] = [255, 40, 40];
+const OVERLAY_ALPHA: f64 = 0.35;
+
+/// Look up the UI regions for a `--platform-overlay` name.
+fn platform_ui_regions(platform: &str) -> VidgenResult<&'static [UiRegion]> {
+    PLATFORM_UI_REGIONS
+        .iter()
+        .find(|(name, _)| *name == platform)
+        .map(|(_, regions)| *regions)
+        .ok_or_else(|| {
+            let names: Vec<&str> = PLATFORM_UI_REGIONS.iter().map(|(n, _)| *n).collect();
+            VidgenError::Other(format!(
+                "Unknown platform overlay \"{platform}\". Valid: {}",
+                names.join(", ")
+            ))
+        })
+}
+
+/// Shade UI regions on a PNG so content the platform UI would cover is easy to spot.
+fn apply_platform_overlay(png: &[u8], regions: &[UiRegion]) -> VidgenResult<Vec<u8>> {
+    let mut img = image::load_from_memory(png)
+        .map_err(|e| VidgenError::Other(format!("Failed to decode preview PNG: {e}")))?
+        .to_rgba8();
+    let (w, h) = img.dimensions();
+    for r in regions {
+        let x0 = (r.x * w as f64).round() as u32;
+        let y0 = (r.y * h as f64).round() as u32;
+        let x1 = (((r.x + r.width) * w as f64).round() as u32).min(w);
+        let y1 = (((r.y + r.height) * h as f64).round() as u32).min(h);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let px = img.get_pixel_mut(x, y);
+                for c in 0..3 {
+                    let blended = px[c] as f64 * (1.0 - OVERLAY_ALPHA)
+                        + OVERLAY_COLOR[c] as f64 * OVERLAY_ALPHA;
+                    px[c] = blended.round() as u8;
+                }
+            }
+        }
+    }
+    let mut out = std::io::Cursor::new(Vec::new());
+    img.write_to(&mut out, image::ImageFormat::Png)
+        .map_err(|e| VidgenError::Other(format!("Failed to encode preview PNG: {e}")))?;
+    Ok(out.into_inner())
+}
+
+/// Height (px) each frame is scaled to in a `--strip` timeline.
+const STRIP_CELL_HEIGHT: u32 = 270;
+/// Spacing around and between timeline strip cells.
+const STRIP_GAP: u32 = 8;
+
+/// Evenly spaced frame indices for a `count`-cell strip, from the first frame
+/// to the last. `count` is capped at the scene's frame count.
+fn strip_frame_indices(total_frames: u32, count: u32) -> Vec<u32> {
+    let count = count.min(total_frames);
+    if count <= 1 {
+        return vec![0; count as usize];
+    }
+    let last = (total_frames - 1) as u64;
+    (0..count as u64)
+        .map(|i| ((i * last + (count as u64 - 1) / 2) / (count as u64 - 1)) as u32)
+        .collect()
+}
+
+/// Scale each frame to `cell_height` and place them left to right on a dark
+/// background, in order. Returns the encoded PNG.
+fn montage_strip(pngs: &[Vec<u8>], cell_height: u32) -> VidgenResult<Vec<u8>> {
+    let mut cells = Vec::new();
+    for png in pngs {
+        let img = image::load_from_memory(png)
+            .map_err(|e| VidgenError::Other(format!("Failed to decode preview PNG: {e}")))?;
+        let width =
+            ((img.width() as f64 * cell_height as f64) / img.height() as f64).round() as u32;
+        cells.push(
+            img.resize_exact(
+                width.max(1),
+                cell_height,
+                image::imageops::FilterType::Triangle,
+            )
+            .to_rgba8(),
+        );
+    }
+
+    let width = STRIP_GAP + cells.iter().map(|c| c.width() + STRIP_GAP).sum::<u32>();
+    let height = cell_height + STRIP_GAP * 2;
+    let mut strip = image::RgbaImage::from_pixel(width, height, image::Rgba([24, 24, 27, 255]));
+    let mut x = STRIP_GAP;
+    for cell in &cells {
+        image::imageops::overlay(&mut strip, cell, x as i64, STRIP_GAP as i64);
+        x += cell.width() + STRIP_GAP;
+    }
+
+    let mut out = std::io::Cursor::new(Vec::new());
+    strip
+        .write_to(&mut out, image::ImageFormat::Png)
+        .map_err(|e| VidgenError::Other(format!("Failed to encode strip PNG: {e}")))?;
+    Ok(out.into_inner())
+}
+
+/// `--strip`: capture `count` evenly spaced frames of one scene and save them
+/// side by side as a timeline strip.
+#[allow(clippy::too_many_arguments)]
+async fn preview_strip(
+    project_path: &Path,
+    cfg: &config::ProjectConfig,
+    registry: &TemplateRegistry<'_>,
+    s: &scene::Scene,
+    scene_index: usize,
+    count: u32,
+    overlay_regions: Option<&[UiRegion]>,
+    output: Option<PathBuf>,
+) -> VidgenResult<()> {
+    let (width, height) = capture_size(cfg, cfg.video.width, cfg.video.height);
+    let total_frames = s.total_frames(cfg.video.fps);
+    let frames = strip_frame_indices(total_frames, count);
+    if frames.is_empty() {
+        return Err(VidgenError::Other("--count must be at least 1".into()));
+    }
+
+    eprintln!(
+        "{} Capturing {} frames of scene {} ({} frames) for a timeline strip...",
+        "preview:".cyan().bold(),
+        frames.len(),
+        scene_index,
+        total_frames
+    );
+
+    let mut pngs = Vec::new();
+    for &frame in &frames {
+        let html = registry.render_scene_html(
+            s,
+            &cfg.theme,
+            width,
+            height,
+            frame,
+            total_frames,
+            Some(project_path),
+        )?;
+        let mut png = capture_single_frame(
+            &html,
+            width,
+            height,
+            frame,
+            total_frames,
+            s.frontmatter.wait_for.as_deref(),
+        )
+        .await?;
+        if let Some(regions) = overlay_regions {
+            png = apply_platform_overlay(&png, regions)?;
+        }
+        pngs.push(png);
+        eprintln!("  frame {}/{}", frame, total_frames);
+    }
+
+    let strip = montage_strip(&pngs, STRIP_CELL_HEIGHT)?;
+    let output_path = output.unwrap_or_else(|| PathBuf::from("preview-strip.png"));
+    std::fs::write(&output_path, &strip)?;
+    eprintln!(
+        "{} Saved timeline strip to {} (scene {}, {} frames)",
+        "done:".green().bold(),
+        output_path.display(),
+        scene_index,
+        frames.len()
+    );
+    Ok(())
+}
+
+/// One line of input to the `--interactive` REPL.
+#[derive(Debug, Clone, PartialEq)]
+enum ReplInput {
+    Frame(u32),
+    Quit,
+    Empty,
+}
+
+/// Parse a REPL line: a frame number, `n`/`p` for the next/previous frame
+/// (relative to `current`), `q` to quit.
+fn parse_repl_input(line: &str, current: u32, total_frames: u32) -> Result<ReplInput, String> {
+    let frame = match line.trim() {
+        "" => return Ok(ReplInput::Empty),
+        "q" | "quit" | "exit" => return Ok(ReplInput::Quit),
+        "n" | "next" => current.saturating_add(1),
+        "p" | "prev" => current.saturating_sub(1),
+        other => other
+            .parse::<u32>()
+            .map_err(|_| format!("Not a frame number: \"{other}\" (use a number, n, p or q)"))?,
+    };
+    if frame >= total_frames {
+        return Err(format!(
+            "Frame {frame} out of range (scene has {total_frames} frames, 0-indexed)"
+        ));
+    }
+    Ok(ReplInput::Frame(frame))
+}
+
+/// `--interactive`: read frame numbers from stdin and re-capture each one with
+/// a single warm browser, overwriting the output PNG.
+#[allow(clippy::too_many_arguments)]
+async fn preview_interactive(
+    project_path: &Path,
+    cfg: &config::ProjectConfig,
+    registry: &TemplateRegistry<'_>,
+    s: &scene::Scene,
+    scene_index: usize,
+    start_frame: u32,
+    total_frames: u32,
+    output: Option<PathBuf>,
+) -> VidgenResult<()> {
+    let (width, height) = capture_size(cfg, cfg.video.width, cfg.video.height);
+    let output_path = output.unwrap_or_else(|| PathBuf::from("preview.png"));
+
+    eprintln!(
+        "{} Interactive preview of scene {} ({} frames). Enter a frame number, n/p for next/previous, q to quit.",
+        "preview:".cyan().bold(),
+        scene_index,
+        total_frames
+    );
+    let mut session = BrowserSession::launch(width, height).await?;
+
+    let stdin = std::io::stdin();
+    let mut current = start_frame.min(total_frames.saturating_sub(1));
+    let mut pending = Some(current);
+    loop {
+        if let Some(frame) = pending.take() {
+            let html = registry.render_scene_html(s, &cfg.theme, width, height, frame, total_frames, Some(project_path))?;
+            match session.capture_frame(&html, frame, total_frames, s.frontmatter.wait_for.as_deref()).await {
+                Ok(png) => {
+                    std::fs::write(&output_path, &png)?;
+                    eprintln!("  frame {}/{} \u{2192} {}", frame, total_frames, output_path.display());
+                    if session.captures() == 1 {
+                        crate::commands::export::open_file(&output_path);
+                    }
+                    current = frame;
+                }
+                Err(e) => eprintln!("  {} {}", "error:".red().bold(), e),
+            }
+        }
+
+        eprint!("frame> ");
+        let _ = std::io::stderr().flush();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            break; // EOF
+        }
+        match parse_repl_input(&line, current, total_frames) {
+            Ok(ReplInput::Frame(f)) => pending = Some(f),
+            Ok(ReplInput::Quit) => break,
+            Ok(ReplInput::Empty) => {}
+            Err(msg) => eprintln!("  {msg}"),
+        }
+    }
+
+    eprintln!(
+        "{} Captured {} frame(s) with one browser session",
+        "done:".green().bold(),
+        session.captures()
+    );
+    session.close();
+    Ok(())
+}
+
+/// Run the preview command: render a single frame (or all scenes / animated GIF
+/// / one scene across all formats / a timeline strip / interactive frame scrubbing).
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     project_path: &Path,
     scene_index: usize,
@@ -14,16 +427,22 @@ pub async fn run(
     output: Option<PathBuf>,
     all: bool,
     gif: bool,
+    all_formats: bool,
+    interactive: bool,
+    platform_overlay: Option<&str>,
+    strip: Option<usize>,
+    strip_count: u32,
 ) -> VidgenResult<()> {
     let cfg = config::load_config(project_path)?;
-    let scenes = scene::load_scenes(project_path)?;
+    let scenes = scene::load_scenes_with_variables(project_path, &cfg.variables)?;
     let count = scenes.len();
+    let overlay_regions = platform_overlay.map(platform_ui_regions).transpose()?;
 
     let mut registry = TemplateRegistry::new()?;
     registry.register_project_templates(project_path)?;
+    registry.apply_emoji_fallback(&cfg.render, project_path);
 
-    let width = cfg.video.width;
-    let height = cfg.video.height;
+    let (width, height) = capture_size(&cfg, cfg.video.width, cfg.video.height);
     let fps = cfg.video.fps;
 
     if all {
@@ -40,7 +459,10 @@ pub async fn run(
         for (i, s) in scenes.iter().enumerate() {
             let total = s.total_frames(fps);
             let html = registry.render_scene_html(s, &cfg.theme, width, height, 0, total, Some(project_path))?;
-            let png = capture_single_frame(&html, width, height, 0, total).await?;
+            let mut png = capture_single_frame(&html, width, height, 0, total, s.frontmatter.wait_for.as_deref()).await?;
+            if let Some(regions) = overlay_regions {
+                png = apply_platform_overlay(&png, regions)?;
+            }
             let filename = format!("preview-{:02}.png", i + 1);
             let path = output_dir.join(&filename);
             std::fs::write(&path, &png)?;
@@ -59,6 +481,7 @@ pub async fn run(
         return Ok(());
     }
 
+    let scene_index = strip.unwrap_or(scene_index);
     if scene_index >= count {
         return Err(VidgenError::SceneIndexOutOfRange {
             index: scene_index,
@@ -69,6 +492,14 @@ pub async fn run(
     let s = &scenes[scene_index];
     let total_frames = s.total_frames(fps);
 
+    if strip.is_some() {
+        return preview_strip(project_path, &cfg, &registry, s, scene_index, strip_count, overlay_regions, output).await;
+    }
+
+    if all_formats {
+        return preview_all_formats(project_path, &cfg, &registry, s, scene_index, output).await;
+    }
+
     if s.frontmatter.duration.is_auto() {
         eprintln!(
             "{} Scene {} has auto duration — using {:.1}s fallback for preview (TTS not run in preview mode)",
@@ -78,6 +509,10 @@ pub async fn run(
         );
     }
 
+    if interactive {
+        return preview_interactive(project_path, &cfg, &registry, s, scene_index, frame, total_frames, output).await;
+    }
+
     if gif {
         // --gif: render multiple frames and assemble via FFmpeg into a GIF
         let gif_frames = total_frames.min(fps * 3); // cap at 3 seconds
@@ -101,7 +536,7 @@ pub async fn run(
         let mut f = 0u32;
         while f < total_frames && frame_idx < gif_frames {
             let html = registry.render_scene_html(s, &cfg.theme, width, height, f, total_frames, Some(project_path))?;
-            let png = capture_single_frame(&html, width, height, f, total_frames).await?;
+            let png = capture_single_frame(&html, width, height, f, total_frames, s.frontmatter.wait_for.as_deref()).await?;
             let frame_path = temp_dir.path().join(format!("frame-{frame_idx:04}.png"));
             std::fs::write(&frame_path, &png)?;
             frame_idx += 1;
@@ -155,7 +590,10 @@ pub async fn run(
     );
 
     let html = registry.render_scene_html(s, &cfg.theme, width, height, frame, total_frames, Some(project_path))?;
-    let png_data = capture_single_frame(&html, width, height, frame, total_frames).await?;
+    let mut png_data = capture_single_frame(&html, width, height, frame, total_frames, s.frontmatter.wait_for.as_deref()).await?;
+    if let Some(regions) = overlay_regions {
+        png_data = apply_platform_overlay(&png_data, regions)?;
+    }
 
     let output_path = output.unwrap_or_else(|| PathBuf::from("preview.png"));
     std::fs::write(&output_path, &png_data)?;
@@ -172,3 +610,127 @@ pub async fn run(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid_png(width: u32, height: u32) -> Vec<u8> {
+        let img = image::RgbaImage::from_pixel(width, height, image::Rgba([255, 255, 255, 255]));
+        let mut out = std::io::Cursor::new(Vec::new());
+        img.write_to(&mut out, image::ImageFormat::Png).unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn test_platform_overlay_shades_ui_regions() {
+        let regions = platform_ui_regions("tiktok").unwrap();
+        let png = apply_platform_overlay(&solid_png(100, 200), regions).unwrap();
+        let img = image::load_from_memory(&png).unwrap().to_rgba8();
+        let white = image::Rgba([255, 255, 255, 255]);
+
+        // Right action bar and bottom caption area are tinted
+        assert_ne!(*img.get_pixel(95, 120), white);
+        assert_ne!(*img.get_pixel(10, 190), white);
+        assert_eq!(img.get_pixel(95, 120)[0], 255);
+        assert!(img.get_pixel(95, 120)[1] < 255);
+        // The center of the frame is left untouched
+        assert_eq!(*img.get_pixel(50, 100), white);
+        assert_eq!(img.dimensions(), (100, 200));
+    }
+
+    #[test]
+    fn test_unknown_platform_overlay() {
+        let err = platform_ui_regions("myspace").unwrap_err().to_string();
+        assert!(err.contains("tiktok, reels, shorts"));
+    }
+
+    fn formats() -> Vec<(String, u32, u32)> {
+        vec![
+            ("landscape".into(), 1920, 1080),
+            ("portrait".into(), 1080, 1920),
+            ("square".into(), 1080, 1080),
+        ]
+    }
+
+    #[test]
+    fn test_montage_one_cell_per_format() {
+        let (cells, width, height) = montage_layout(&formats(), 540);
+        assert_eq!(cells.len(), 3);
+        assert!(cells[0].label.starts_with("landscape"));
+        assert!(cells[1].label.starts_with("portrait"));
+        assert!(cells[2].label.starts_with("square"));
+
+        // Each cell keeps its format's aspect ratio
+        assert_eq!((cells[0].width, cells[0].height), (960, 540));
+        assert_eq!((cells[1].width, cells[1].height), (304, 540));
+        assert_eq!((cells[2].width, cells[2].height), (540, 540));
+
+        // Cells sit side by side without overlapping and fit in the sheet
+        for pair in cells.windows(2) {
+            assert!(pair[0].x + pair[0].width < pair[1].x);
+        }
+        let last = cells.last().unwrap();
+        assert!(last.x + last.width <= width);
+        assert!(cells.iter().all(|c| c.y + c.height <= height));
+    }
+
+    #[test]
+    fn test_montage_html_has_image_and_label_per_cell() {
+        let (cells, width, height) = montage_layout(&formats(), 540);
+        let pngs = vec![vec![0u8; 4]; cells.len()];
+        let html = montage_html(&cells, &pngs, width, height);
+        assert_eq!(html.matches("<img ").count(), 3);
+        assert_eq!(html.matches("class=\"label\"").count(), 3);
+        assert!(html.contains("portrait \u{b7} 1080\u{d7}1920"));
+        assert!(html.contains("width:304px;height:540px"));
+    }
+
+    #[test]
+    fn test_strip_frame_indices_span_scene() {
+        // 8 cells over a 240-frame scene: first and last frame included, evenly spaced
+        assert_eq!(
+            strip_frame_indices(240, 8),
+            vec![0, 34, 68, 102, 137, 171, 205, 239]
+        );
+        assert_eq!(strip_frame_indices(90, 3), vec![0, 45, 89]);
+        assert_eq!(strip_frame_indices(90, 1), vec![0]);
+        // Never more cells than frames, and no duplicates
+        assert_eq!(strip_frame_indices(4, 8), vec![0, 1, 2, 3]);
+        assert!(strip_frame_indices(90, 0).is_empty());
+    }
+
+    #[test]
+    fn test_montage_strip_has_one_cell_per_frame() {
+        let frames = strip_frame_indices(240, 8);
+        let pngs: Vec<Vec<u8>> = frames.iter().map(|_| solid_png(192, 108)).collect();
+        let strip = montage_strip(&pngs, 54).unwrap();
+        let img = image::load_from_memory(&strip).unwrap().to_rgba8();
+        // 8 cells of 96x54 with gaps around and between them
+        assert_eq!(
+            img.dimensions(),
+            (8 * 96 + 9 * STRIP_GAP, 54 + 2 * STRIP_GAP)
+        );
+
+        let white = image::Rgba([255, 255, 255, 255]);
+        let y = STRIP_GAP + 27;
+        let cells = (0..img.width())
+            .filter(|&x| {
+                img.get_pixel(x, y) == &white && (x == 0 || img.get_pixel(x - 1, y) != &white)
+            })
+            .count();
+        assert_eq!(cells, frames.len());
+    }
+
+    #[test]
+    fn test_parse_repl_input() {
+        assert_eq!(parse_repl_input("42\n", 0, 100), Ok(ReplInput::Frame(42)));
+        assert_eq!(parse_repl_input("n", 41, 100), Ok(ReplInput::Frame(42)));
+        assert_eq!(parse_repl_input("p", 0, 100), Ok(ReplInput::Frame(0)));
+        assert_eq!(parse_repl_input(" q ", 5, 100), Ok(ReplInput::Quit));
+        assert_eq!(parse_repl_input("\n", 5, 100), Ok(ReplInput::Empty));
+        assert!(parse_repl_input("100", 0, 100).unwrap_err().contains("out of range"));
+        assert!(parse_repl_input("n", 99, 100).is_err());
+        assert!(parse_repl_input("abc", 0, 100).unwrap_err().contains("Not a frame number"));
+    }
+}
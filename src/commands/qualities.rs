@@ -0,0 +1,40 @@
+use crate::config::{self, list_qualities};
+use crate::error::{VidgenError, VidgenResult};
+use colored::*;
+use std::path::Path;
+
+/// List the quality presets available to a project (built-in and `[quality.presets]`).
+pub fn run(project_path: &Path, json: bool) -> VidgenResult<()> {
+    let config = config::load_config(project_path)?;
+    config.validate()?;
+    let qualities = list_qualities(&config.quality);
+
+    if json {
+        let json = serde_json::to_string_pretty(&qualities)
+            .map_err(|e| VidgenError::Other(format!("Failed to serialize qualities: {e}")))?;
+        println!("{json}");
+        return Ok(());
+    }
+
+    eprintln!(
+        "{} {} quality preset(s) available\n",
+        "qualities:".cyan().bold(),
+        qualities.len()
+    );
+    for q in &qualities {
+        let label = if q.custom { "project" } else { "built-in" };
+        let marker = if q.name == config.output.quality {
+            "*"
+        } else {
+            " "
+        };
+        println!(
+            "{marker} {:<12} crf={:<3} preset={:<10} {}",
+            q.name,
+            q.crf,
+            q.preset,
+            label.dimmed()
+        );
+    }
+    Ok(())
+}
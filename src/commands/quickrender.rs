@@ -85,24 +85,24 @@ pub async fn run(
             padding_after: None,
             auto_fallback_duration: None,
             formats: None,
+            container: None,
+            capture_timeout: None,
+            capture_retries: None,
+            scene_gap_secs: None,
         };
         crate::config::update_config(&project_path, &update)?;
     }
 
     // Render the project (single default format, no multi-format for quickrender)
+    let options = commands::render::RenderOptions {
+        quality: quality.map(|q| q.to_string()),
+        no_cache: true, // ephemeral project
+        ..Default::default()
+    };
     let results = commands::render::render_project(
         &project_path,
-        None,
-        quality.map(|q| q.to_string()),
-        None,
-        None,
-        None,
-        None,
-        None,
-        false,
-        true,  // no_cache for quickrender (ephemeral project)
-        false, // no GPU by default
-        None,  // no speed override
+        &options,
+        crate::render::RenderProgress::noop(),
     )
     .await?;
 
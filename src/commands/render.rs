@@ -3,7 +3,7 @@ use crate::error::{VidgenError, VidgenResult};
 use crate::scene;
 use colored::*;
 use serde::Serialize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command as ProcessCommand;
 
 /// Structured result from rendering a single format.
@@ -15,65 +15,299 @@ pub struct RenderResult {
     pub duration_secs: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subtitle_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub captioned_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loop_path: Option<String>,
+    /// SHA-256 (lowercase hex) of the file at `output_path`
+    pub sha256: String,
+    pub stats: crate::render::RenderStats,
+}
+
+/// Select the scenes to render: keep those whose 0-based index is in `indices`
+/// (if given) and that carry `tag` (if given). Transitions are resolved later
+/// between the remaining neighbours.
+pub fn select_scenes(
+    scenes: Vec<scene::Scene>,
+    indices: Option<&[usize]>,
+    tag: Option<&str>,
+) -> Vec<scene::Scene> {
+    scenes
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| indices.map_or(true, |idx| idx.contains(i)))
+        .filter(|(_, s)| tag.map_or(true, |t| s.has_tag(t)))
+        .map(|(_, s)| s)
+        .collect()
+}
+
+/// Fail when the scene filters leave nothing to render; an empty scene list
+/// would otherwise produce a broken zero-scene concat.
+fn ensure_scenes_selected(
+    selected: &[scene::Scene],
+    total: usize,
+    indices: Option<&[usize]>,
+    tag: Option<&str>,
+) -> VidgenResult<()> {
+    if !selected.is_empty() {
+        return Ok(());
+    }
+    let mut parts = Vec::new();
+    if let Some(idx) = indices {
+        let list: Vec<String> = idx.iter().map(|i| i.to_string()).collect();
+        parts.push(format!("--scenes {}", list.join(",")));
+    }
+    if let Some(t) = tag {
+        parts.push(format!("--tag \"{t}\""));
+    }
+    let filter = if parts.is_empty() {
+        "the selection".to_string()
+    } else {
+        parts.join(" and ")
+    };
+    Err(VidgenError::NoScenesMatch { filter, total })
+}
+
+/// In-memory scene shuffle for rendering A/B ordering variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShuffleOptions {
+    pub seed: u64,
+    /// Keep the first and last scenes (intro/outro) in place.
+    pub pin_ends: bool,
+}
+
+impl ShuffleOptions {
+    /// Use `seed` if given, otherwise derive one from the clock (printed so the
+    /// variant can be reproduced).
+    pub fn new(seed: Option<u64>, pin_ends: bool) -> Self {
+        let seed = seed.unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or(0)
+        });
+        Self { seed, pin_ends }
+    }
+}
+
+/// Deterministic permutation of `0..len` (Fisher-Yates) for the given options.
+pub fn shuffle_order(len: usize, options: ShuffleOptions) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..len).collect();
+    let (lo, hi) = if options.pin_ends && len > 2 {
+        (1, len - 1)
+    } else {
+        (0, len)
+    };
+    let mut state = options.seed;
+    for i in (lo + 1..hi).rev() {
+        let j = lo + (scene::splitmix64(&mut state) % (i - lo + 1) as u64) as usize;
+        order.swap(i, j);
+    }
+    order
+}
+
+/// Reorder scenes in memory according to `shuffle_order`. Files on disk are untouched.
+pub fn shuffle_scenes(scenes: Vec<scene::Scene>, options: ShuffleOptions) -> Vec<scene::Scene> {
+    let order = shuffle_order(scenes.len(), options);
+    let mut slots: Vec<Option<scene::Scene>> = scenes.into_iter().map(Some).collect();
+    order.into_iter().filter_map(|i| slots[i].take()).collect()
+}
+
+/// Median/min/max of one phase across benchmark runs, in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PhaseSummary {
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl PhaseSummary {
+    /// Summarize samples; the median of an even count is the mean of the middle two.
+    pub fn from_samples(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Self {
+                median: 0.0,
+                min: 0.0,
+                max: 0.0,
+            };
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let mid = sorted.len() / 2;
+        let median = if sorted.len() % 2 == 0 {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        } else {
+            sorted[mid]
+        };
+        Self {
+            median,
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+/// `render --benchmark` report, printed as JSON for CI tracking.
+#[derive(Debug, Serialize)]
+pub struct BenchmarkReport {
+    pub runs: usize,
+    pub tts: PhaseSummary,
+    pub capture: PhaseSummary,
+    pub encode: PhaseSummary,
+    pub total: PhaseSummary,
+}
+
+/// Collapse one render's per-format results into a single run sample:
+/// TTS is shared across formats, capture and encode add up.
+fn run_stats(results: &[RenderResult]) -> crate::render::RenderStats {
+    crate::render::RenderStats {
+        tts_secs: results.first().map_or(0.0, |r| r.stats.tts_secs),
+        capture_secs: results.iter().map(|r| r.stats.capture_secs).sum(),
+        encode_secs: results.iter().map(|r| r.stats.encode_secs).sum(),
+    }
+}
+
+/// Aggregate per-run phase timings (`total` is the sum of the three phases).
+pub fn aggregate_benchmark(runs: &[crate::render::RenderStats]) -> BenchmarkReport {
+    let phase = |f: fn(&crate::render::RenderStats) -> f64| {
+        PhaseSummary::from_samples(&runs.iter().map(f).collect::<Vec<_>>())
+    };
+    BenchmarkReport {
+        runs: runs.len(),
+        tts: phase(|s| s.tts_secs),
+        capture: phase(|s| s.capture_secs),
+        encode: phase(|s| s.encode_secs),
+        total: phase(|s| s.tts_secs + s.capture_secs + s.encode_secs),
+    }
+}
+
+/// Load the project config with `--set` overrides applied in memory (never
+/// saved to project.toml), then validate the result.
+fn load_render_config(
+    path: &Path,
+    overrides: &config::ConfigUpdate,
+) -> VidgenResult<config::ProjectConfig> {
+    let mut config = config::load_config(path)?;
+    config::apply_config_update(&mut config, overrides);
+    config.validate()?;
+    Ok(config)
+}
+
+/// Reject render options that only work on MP4 output.
+fn check_container_options(
+    container: config::OutputContainer,
+    append: bool,
+    crop: Option<&str>,
+) -> VidgenResult<()> {
+    if container == config::OutputContainer::Mp4 {
+        return Ok(());
+    }
+    if append {
+        return Err(VidgenError::Other(format!(
+            "--append joins MP4 outputs by stream copy and can't be used with {container} output"
+        )));
+    }
+    if crop.is_some() {
+        return Err(VidgenError::Other(format!(
+            "--crop re-encodes the final video as H.264 and can't be used with {container} output"
+        )));
+    }
+    Ok(())
+}
+
+/// Move a shuffled variant's outputs into a `shuffle-<seed>` subdirectory of
+/// wherever they would normally go, including formats with their own
+/// `output_dir`, so the variant never overwrites the main render. Returns the
+/// new default output directory.
+fn shuffle_output_dirs(video: &mut config::VideoConfig, output_dir: &Path, seed: u64) -> PathBuf {
+    let variant = format!("shuffle-{seed}");
+    for fc in video.formats.iter_mut().flat_map(|f| f.values_mut()) {
+        if let Some(dir) = fc.output_dir.as_mut() {
+            *dir = format!("{}/{variant}", dir.trim_end_matches('/'));
+        }
+    }
+    output_dir.join(variant)
+}
+
+/// Options for a render, one field per `vidgen render` flag. `Default` renders
+/// the project as configured.
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    pub fps: Option<u32>,
+    pub quality: Option<String>,
+    pub formats: Option<Vec<String>>,
+    /// Scene indices to render (0-based)
+    pub scenes: Option<Vec<usize>>,
+    pub tag: Option<String>,
+    pub shuffle: Option<ShuffleOptions>,
+    /// Force `output.subtitles.enabled` on or off
+    pub subtitles: Option<bool>,
+    /// Force `output.subtitles.burn_in` on or off
+    pub burn_in: Option<bool>,
+    pub parallel: Option<usize>,
+    pub force_tts: bool,
+    pub no_cache: bool,
+    pub gpu: bool,
+    pub only_changed_formats: bool,
+    pub speed: Option<f32>,
+    /// Aspect ratio to crop the finished video to (e.g. "9:16")
+    pub crop: Option<String>,
+    pub progress_json: bool,
+    /// Render this many times and print phase timings instead of a report
+    pub benchmark: Option<usize>,
+    /// Join the selected scenes onto the existing outputs
+    pub append: bool,
+    /// `--set` overrides, applied in memory
+    pub config_overrides: config::ConfigUpdate,
 }
 
 /// Programmatic render entry point. Returns structured results (one per format).
-#[allow(clippy::too_many_arguments)]
+/// The CLI-only fields of `options` (`crop`, `progress_json`, `benchmark`,
+/// `append`) are handled by [`run`] and ignored here.
 pub async fn render_project(
     path: &Path,
-    fps: Option<u32>,
-    quality: Option<String>,
-    formats: Option<Vec<String>>,
-    scenes_filter: Option<Vec<usize>>,
-    subtitles_override: Option<bool>,
-    burn_in_override: Option<bool>,
-    parallel_override: Option<usize>,
-    force_tts: bool,
-    no_cache: bool,
-    gpu: bool,
-    speed: Option<f32>,
+    options: &RenderOptions,
+    progress: crate::render::RenderProgress,
 ) -> VidgenResult<Vec<RenderResult>> {
     if !path.exists() {
         return Err(VidgenError::ProjectNotFound(path.to_path_buf()));
     }
 
-    // Load config and validate
-    let mut config = config::load_config(path)?;
-    config.validate()?;
+    // Load config, apply --set overrides and validate
+    let mut config = load_render_config(path, &options.config_overrides)?;
 
     // Apply overrides
-    if let Some(s) = speed {
+    if let Some(s) = options.speed {
         config.voice.speed = s;
     }
-    let fps = fps.unwrap_or(config.video.fps);
-    let quality_name = quality.as_deref().unwrap_or(&config.output.quality);
+    let fps = options.fps.unwrap_or(config.video.fps);
+    let quality_name = options.quality.as_deref().unwrap_or(&config.output.quality);
 
-    if let Some(subs) = subtitles_override {
+    if let Some(subs) = options.subtitles {
         config.output.subtitles.enabled = subs;
+        if !subs {
+            config.output.subtitles.karaoke_vtt = false;
+            config.output.subtitles.html_overlay = false;
+            config.output.subtitles.per_scene = false;
+            config.output.subtitles.embed = false;
+        }
     }
-    if let Some(burn) = burn_in_override {
+    if let Some(burn) = options.burn_in {
         config.output.subtitles.burn_in = burn;
         if burn {
             // burn-in implies subtitles enabled
             config.output.subtitles.enabled = true;
         }
     }
-    if let Some(par) = parallel_override {
+    if let Some(par) = options.parallel {
         config.video.parallel_scenes = Some(par);
     }
 
-    // Load scenes, optionally filtering by index
-    let all_scenes = scene::load_scenes(path)?;
-    let scenes = if let Some(ref indices) = scenes_filter {
-        all_scenes
-            .into_iter()
-            .enumerate()
-            .filter(|(i, _)| indices.contains(i))
-            .map(|(_, s)| s)
-            .collect()
-    } else {
-        all_scenes
-    };
+    // Load scenes, optionally filtering by index and tag
+    let all_scenes = scene::load_scenes_with_variables(path, &config.variables)?;
+    let total = all_scenes.len();
+    let mut scenes = select_scenes(all_scenes, options.scenes.as_deref(), options.tag.as_deref());
+    ensure_scenes_selected(&scenes, total, options.scenes.as_deref(), options.tag.as_deref())?;
     let scenes_rendered = scenes.len();
 
     // Resolve output directory (strip ./ prefix if present)
@@ -82,9 +316,21 @@ pub async fn render_project(
         .directory
         .strip_prefix("./")
         .unwrap_or(&config.output.directory);
-    let output_dir = path.join(output_rel);
+    let mut output_dir = path.join(output_rel);
+
+    // Shuffled variants go to their own directory so they don't overwrite the main output
+    if let Some(shuffle) = options.shuffle {
+        scenes = shuffle_scenes(scenes, shuffle);
+        output_dir = shuffle_output_dirs(&mut config.video, &output_dir, shuffle.seed);
+        eprintln!(
+            "{} Shuffled scene order with seed {}{}",
+            "render:".cyan().bold(),
+            shuffle.seed,
+            if shuffle.pin_ends { " (first/last pinned)" } else { "" },
+        );
+    }
 
-    let format_filter = formats.as_deref();
+    let format_filter = options.formats.as_deref();
 
     let format_outputs = crate::render::render_project(
         &config,
@@ -93,11 +339,12 @@ pub async fn render_project(
         quality_name,
         &output_dir,
         path,
-        crate::render::RenderProgress::noop(),
+        progress,
         format_filter,
-        force_tts,
-        no_cache,
-        gpu,
+        options.force_tts,
+        options.no_cache,
+        options.gpu,
+        options.only_changed_formats,
     )
     .await?;
 
@@ -111,119 +358,108 @@ pub async fn render_project(
                 scenes_rendered,
                 duration_secs,
                 subtitle_path: fo.subtitle_path.map(|p| p.display().to_string()),
+                captioned_path: fo.captioned_path.map(|p| p.display().to_string()),
+                loop_path: fo.loop_path.map(|p| p.display().to_string()),
+                sha256: fo.sha256,
+                stats: fo.stats,
             }
         })
         .collect())
 }
 
-/// Programmatic render entry point with MCP progress reporting.
-pub async fn render_project_with_progress(
+/// Print, as JSON, how each selected scene's duration and voice and each
+/// boundary's transition would be decided, without capturing or encoding.
+/// Runs (cached) TTS like `vidgen info` to measure narration.
+pub fn explain(
     path: &Path,
-    fps: Option<u32>,
-    quality: Option<String>,
-    formats: Option<Vec<String>>,
     scenes_filter: Option<Vec<usize>>,
-    progress: crate::render::RenderProgress,
-) -> VidgenResult<Vec<RenderResult>> {
+    tag: Option<String>,
+    shuffle: Option<ShuffleOptions>,
+    speed: Option<f32>,
+    config_overrides: &config::ConfigUpdate,
+) -> VidgenResult<()> {
     if !path.exists() {
         return Err(VidgenError::ProjectNotFound(path.to_path_buf()));
     }
+    let mut config = load_render_config(path, config_overrides)?;
+    if let Some(s) = speed {
+        config.voice.speed = s;
+    }
 
-    let config = config::load_config(path)?;
-    config.validate()?;
-    let fps = fps.unwrap_or(config.video.fps);
-    let quality_name = quality.as_deref().unwrap_or(&config.output.quality);
-    let all_scenes = scene::load_scenes(path)?;
-    let scenes = if let Some(ref indices) = scenes_filter {
-        all_scenes
-            .into_iter()
-            .enumerate()
-            .filter(|(i, _)| indices.contains(i))
-            .map(|(_, s)| s)
-            .collect()
-    } else {
-        all_scenes
-    };
-    let scenes_rendered = scenes.len();
-
-    let output_rel = config
-        .output
-        .directory
-        .strip_prefix("./")
-        .unwrap_or(&config.output.directory);
-    let output_dir = path.join(output_rel);
-
-    let format_filter = formats.as_deref();
+    let all_scenes = scene::load_scenes_with_variables(path, &config.variables)?;
+    let total = all_scenes.len();
+    let mut scenes = select_scenes(all_scenes, scenes_filter.as_deref(), tag.as_deref());
+    ensure_scenes_selected(&scenes, total, scenes_filter.as_deref(), tag.as_deref())?;
+    if let Some(options) = shuffle {
+        scenes = shuffle_scenes(scenes, options);
+    }
 
-    let format_outputs = crate::render::render_project(
+    let _ = dotenvy::from_path(path.join(".env"));
+    let tts_durations = super::info::scene_tts_durations(&config, &scenes, path, "explain:")?;
+    let explanation = crate::render::explain::explain(
         &config,
         &scenes,
-        fps,
-        quality_name,
-        &output_dir,
+        &tts_durations,
         path,
-        progress,
-        format_filter,
-        false, // MCP doesn't support force_tts yet
-        false, // MCP doesn't support no_cache yet
-        false, // MCP doesn't support gpu yet
-    )
-    .await?;
-
-    Ok(format_outputs
-        .into_iter()
-        .map(|fo| {
-            let duration_secs: f64 = fo.effective_durations.iter().sum();
-            RenderResult {
-                output_path: fo.output_path.display().to_string(),
-                format_name: fo.format_name,
-                scenes_rendered,
-                duration_secs,
-                subtitle_path: fo.subtitle_path.map(|p| p.display().to_string()),
-            }
-        })
-        .collect())
+        crate::render::encoder::probe_video_duration,
+    );
+    let json = serde_json::to_string_pretty(&explanation)
+        .map_err(|e| VidgenError::Other(format!("Failed to serialize explanation: {e}")))?;
+    println!("{json}");
+    Ok(())
 }
 
 /// CLI entry point — delegates to `render_project()`.
-#[allow(clippy::too_many_arguments)]
-pub async fn run(
-    path: &Path,
-    fps: Option<u32>,
-    quality: Option<String>,
-    formats: Option<Vec<String>>,
-    scenes: Option<Vec<usize>>,
-    subtitles: bool,
-    burn_in: bool,
-    parallel: Option<usize>,
-    force_tts: bool,
-    no_cache: bool,
-    gpu: bool,
-    speed: Option<f32>,
-    crop: Option<&str>,
-) -> VidgenResult<()> {
-    let subtitles_override = if subtitles { Some(true) } else { None };
-    let burn_in_override = if burn_in { Some(true) } else { None };
-    let results = render_project(
-        path,
-        fps,
-        quality,
-        formats,
-        scenes,
-        subtitles_override,
-        burn_in_override,
-        parallel,
-        force_tts,
-        no_cache,
-        gpu,
-        speed,
-    )
-    .await?;
-    for r in &results {
+pub async fn run(path: &Path, options: &RenderOptions) -> VidgenResult<()> {
+    let crop = options.crop.as_deref();
+    if path.exists() && (options.append || crop.is_some()) {
+        let container = load_render_config(path, &options.config_overrides)?
+            .output
+            .container;
+        check_container_options(container, options.append, crop)?;
+    }
+
+    if options.append {
+        return run_append(path, options).await;
+    }
+
+    if let Some(runs) = options.benchmark {
+        if runs == 0 {
+            return Err(VidgenError::Other(
+                "--benchmark needs at least 1 run".into(),
+            ));
+        }
+        let mut samples = Vec::with_capacity(runs);
+        for run in 1..=runs {
+            eprintln!("{} Benchmark run {}/{}", "render:".cyan().bold(), run, runs);
+            let results =
+                render_project(path, options, crate::render::RenderProgress::noop()).await?;
+            samples.push(run_stats(&results));
+        }
+        let report = aggregate_benchmark(&samples);
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| VidgenError::Other(format!("Failed to serialize benchmark: {e}")))?;
+        println!("{json}");
+        return Ok(());
+    }
+
+    let progress = if options.progress_json {
+        crate::render::RenderProgress::json()
+    } else {
+        crate::render::RenderProgress::noop()
+    };
+    let mut results = render_project(path, options, progress).await?;
+    for r in &mut results {
         eprintln!(
             "  Format \"{}\": {} scenes, {:.1}s total → {}",
             r.format_name, r.scenes_rendered, r.duration_secs, r.output_path
         );
+        if let Some(ref captioned) = r.captioned_path {
+            eprintln!("  Captioned copy \u{2192} {}", captioned);
+        }
+        if let Some(ref looped) = r.loop_path {
+            eprintln!("  Loop \u{2192} {}", looped);
+        }
 
         let video_path = std::path::Path::new(&r.output_path);
 
@@ -231,12 +467,17 @@ pub async fn run(
         if let Some(aspect) = crop {
             if video_path.exists() {
                 match crate::render::encoder::apply_crop(video_path, aspect) {
-                    Ok(()) => eprintln!("  Cropped to {}", aspect),
+                    Ok(()) => {
+                        eprintln!("  Cropped to {}", aspect);
+                        r.sha256 = crate::render::file_sha256(video_path)?;
+                    }
                     Err(e) => eprintln!("  {} Crop failed: {}", "warning:".yellow().bold(), e),
                 }
             }
         }
 
+        eprintln!("  SHA-256 {}", r.sha256);
+
         // Print quality report for the output file
         if video_path.exists() {
             if let Err(e) = print_quality_report(video_path) {
@@ -251,6 +492,91 @@ pub async fn run(
     Ok(())
 }
 
+/// Render only the selected (new) scenes and join them onto the existing outputs
+/// by stream copy. The existing files are set aside first and restored on failure.
+/// Subtitles are not regenerated, since they would only cover the new scenes.
+async fn run_append(path: &Path, options: &RenderOptions) -> VidgenResult<()> {
+    if options.scenes.is_none() && options.tag.is_none() {
+        return Err(VidgenError::Other(
+            "--append needs --scenes or --tag to select the new scenes".into(),
+        ));
+    }
+    let bases = stash_append_bases(path, options.formats.as_deref())?;
+
+    let append_options = RenderOptions {
+        shuffle: None,
+        subtitles: Some(false),
+        burn_in: Some(false),
+        only_changed_formats: false,
+        ..options.clone()
+    };
+    let rendered =
+        render_project(path, &append_options, crate::render::RenderProgress::noop()).await;
+    if let Err(e) = rendered {
+        restore_append_bases(&bases);
+        return Err(e);
+    }
+
+    for (output, base) in &bases {
+        if let Err(e) = crate::render::encoder::append_videos(base, output, output) {
+            restore_append_bases(&bases);
+            return Err(e);
+        }
+        let _ = std::fs::remove_file(base);
+        eprintln!("{} Appended → {}", "done:".green().bold(), output.display());
+    }
+    Ok(())
+}
+
+/// Where `render --append` keeps an existing output while the new scenes render.
+fn append_base_path(output: &Path) -> PathBuf {
+    output.with_extension("append-base.mp4")
+}
+
+/// Move each format's existing output aside, returning `(output, stashed)` pairs.
+/// Fails without moving anything if an output doesn't exist yet.
+fn stash_append_bases(
+    path: &Path,
+    formats: Option<&[String]>,
+) -> VidgenResult<Vec<(PathBuf, PathBuf)>> {
+    let config = config::load_config(path)?;
+    let output_rel = config
+        .output
+        .directory
+        .strip_prefix("./")
+        .unwrap_or(&config.output.directory);
+    let output_dir = path.join(output_rel);
+    let resolved = crate::render::resolve_formats(&config, formats);
+    let outputs: Vec<PathBuf> = resolved
+        .iter()
+        .map(|(name, ..)| {
+            crate::render::format_output_path(&config, name, resolved.len(), &output_dir, path)
+        })
+        .collect();
+    if let Some(missing) = outputs.iter().find(|o| !o.exists()) {
+        return Err(VidgenError::Other(format!(
+            "Nothing to append to: {} not found. Render the full video first.",
+            missing.display()
+        )));
+    }
+    let mut bases = Vec::new();
+    for output in outputs {
+        let base = append_base_path(&output);
+        std::fs::rename(&output, &base)?;
+        bases.push((output, base));
+    }
+    Ok(bases)
+}
+
+/// Put stashed outputs back after a failed append.
+fn restore_append_bases(bases: &[(PathBuf, PathBuf)]) {
+    for (output, base) in bases {
+        if base.exists() {
+            let _ = std::fs::rename(base, output);
+        }
+    }
+}
+
 /// Probe the rendered video file and print a quality report with key metrics.
 fn print_quality_report(video_path: &Path) -> VidgenResult<()> {
     // Run ffprobe to get format and stream info as JSON
@@ -377,7 +703,7 @@ fn format_bitrate(bps: u64) -> String {
 }
 
 /// Format a file size in bytes to a human-readable string.
-fn format_file_size(bytes: u64) -> String {
+pub(crate) fn format_file_size(bytes: u64) -> String {
     if bytes >= 1_073_741_824 {
         format!("{:.1} GB", bytes as f64 / 1_073_741_824.0)
     } else if bytes >= 1_048_576 {
@@ -418,3 +744,299 @@ fn detect_audio_peak(video_path: &Path) -> Option<String> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::parse_scene;
+
+    fn tagged_scenes() -> Vec<scene::Scene> {
+        [
+            "---\ntemplate: title-card\ntags: [intro]\n---\nA",
+            "---\ntemplate: content-text\ntags: [section-2, draft]\n---\nB",
+            "---\ntemplate: content-text\n---\nC",
+            "---\ntemplate: content-text\ntags: [section-2]\n---\nD",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, c)| parse_scene(c, Path::new(&format!("{i:02}.md"))).unwrap())
+        .collect()
+    }
+
+    fn scripts(scenes: &[scene::Scene]) -> Vec<&str> {
+        scenes.iter().map(|s| s.script.as_str()).collect()
+    }
+
+    #[test]
+    fn test_select_scenes_by_tag() {
+        let selected = select_scenes(tagged_scenes(), None, Some("section-2"));
+        assert_eq!(scripts(&selected), ["B", "D"]);
+    }
+
+    #[test]
+    fn test_select_scenes_unknown_tag_excludes_all() {
+        assert!(select_scenes(tagged_scenes(), None, Some("outro")).is_empty());
+    }
+
+    #[test]
+    fn test_select_scenes_tag_and_indices_combine() {
+        let selected = select_scenes(tagged_scenes(), Some(&[0, 1, 2]), Some("section-2"));
+        assert_eq!(scripts(&selected), ["B"]);
+    }
+
+    #[test]
+    fn test_select_scenes_no_filters() {
+        assert_eq!(select_scenes(tagged_scenes(), None, None).len(), 4);
+        let selected = select_scenes(tagged_scenes(), Some(&[3, 0]), None);
+        assert_eq!(scripts(&selected), ["A", "D"]);
+    }
+
+    #[test]
+    fn test_empty_selection_errors_clearly() {
+        let selected = select_scenes(tagged_scenes(), Some(&[99]), None);
+        let err = ensure_scenes_selected(&selected, 4, Some(&[99]), None).unwrap_err();
+        assert!(matches!(err, VidgenError::NoScenesMatch { total: 4, .. }));
+        assert_eq!(
+            err.to_string(),
+            "No scenes match --scenes 99 (project has 4 scenes)"
+        );
+        assert!(err.hint().is_some());
+
+        let err = ensure_scenes_selected(&[], 4, Some(&[0, 1]), Some("outro")).unwrap_err();
+        assert!(err.to_string().contains("--scenes 0,1 and --tag \"outro\""));
+
+        let selected = select_scenes(tagged_scenes(), Some(&[1]), None);
+        assert!(ensure_scenes_selected(&selected, 4, Some(&[1]), None).is_ok());
+    }
+
+    fn opts(seed: u64, pin_ends: bool) -> ShuffleOptions {
+        ShuffleOptions { seed, pin_ends }
+    }
+
+    #[test]
+    fn test_shuffle_same_seed_same_order() {
+        let a = shuffle_order(10, opts(42, false));
+        let b = shuffle_order(10, opts(42, false));
+        assert_eq!(a, b);
+
+        let mut sorted = a.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_shuffle_different_seeds_differ() {
+        assert_ne!(shuffle_order(10, opts(1, false)), shuffle_order(10, opts(2, false)));
+    }
+
+    #[test]
+    fn test_shuffle_pin_ends() {
+        for seed in 0..20 {
+            let order = shuffle_order(6, opts(seed, true));
+            assert_eq!(order[0], 0);
+            assert_eq!(order[5], 5);
+        }
+        assert_eq!(shuffle_order(2, opts(7, true)), [0, 1]);
+    }
+
+    #[test]
+    fn test_shuffle_scenes_is_permutation() {
+        let shuffled = shuffle_scenes(tagged_scenes(), opts(3, false));
+        let mut got = scripts(&shuffled);
+        assert_eq!(got.len(), 4);
+        got.sort_unstable();
+        assert_eq!(got, ["A", "B", "C", "D"]);
+    }
+
+    #[test]
+    fn test_phase_summary_median_min_max() {
+        let odd = PhaseSummary::from_samples(&[3.0, 1.0, 2.0]);
+        assert_eq!((odd.median, odd.min, odd.max), (2.0, 1.0, 3.0));
+        let even = PhaseSummary::from_samples(&[4.0, 1.0, 3.0, 2.0]);
+        assert_eq!((even.median, even.min, even.max), (2.5, 1.0, 4.0));
+        assert_eq!(PhaseSummary::from_samples(&[]).max, 0.0);
+    }
+
+    #[test]
+    fn test_aggregate_benchmark_per_phase() {
+        use crate::render::RenderStats;
+        let stat = |tts, capture, encode| RenderStats {
+            tts_secs: tts,
+            capture_secs: capture,
+            encode_secs: encode,
+        };
+        let report = aggregate_benchmark(&[
+            stat(1.0, 10.0, 2.0),
+            stat(3.0, 8.0, 1.0),
+            stat(2.0, 12.0, 3.0),
+        ]);
+        assert_eq!(report.runs, 3);
+        assert_eq!(
+            (report.tts.median, report.tts.min, report.tts.max),
+            (2.0, 1.0, 3.0)
+        );
+        assert_eq!(report.capture.median, 10.0);
+        assert_eq!((report.encode.min, report.encode.max), (1.0, 3.0));
+        assert_eq!((report.total.min, report.total.max), (12.0, 17.0));
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["capture"]["max"], 12.0);
+    }
+
+    #[test]
+    fn test_shuffle_output_dirs_cover_format_output_dir() {
+        let project = Path::new("/projects/demo");
+        let mut cfg: config::ProjectConfig = toml::from_str(
+            "[project]\nname = \"Demo\"\n\n[video.formats.landscape]\nwidth = 1920\nheight = 1080\n\n[video.formats.portrait]\nwidth = 1080\nheight = 1920\noutput_dir = \"./uploads/reels/\"\n",
+        )
+        .unwrap();
+        let main_dir = project.join("output");
+        let paths = |cfg: &config::ProjectConfig, dir: &Path| {
+            ["landscape", "portrait"]
+                .map(|name| crate::render::format_output_path(cfg, name, 2, dir, project))
+        };
+        let main = paths(&cfg, &main_dir);
+
+        let shuffle_dir = shuffle_output_dirs(&mut cfg.video, &main_dir, 7);
+        let shuffled = paths(&cfg, &shuffle_dir);
+        assert_eq!(shuffled[0], project.join("output/shuffle-7/demo-landscape.mp4"));
+        assert_eq!(shuffled[1], project.join("uploads/reels/shuffle-7/demo-portrait.mp4"));
+        assert!(shuffled.iter().all(|p| !main.contains(p)));
+    }
+
+    #[test]
+    fn test_stash_append_bases_requires_existing_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("demo");
+        crate::commands::init::run(&project, None, false).unwrap();
+
+        let err = stash_append_bases(&project, None).unwrap_err();
+        assert!(err.to_string().contains("Nothing to append to"));
+
+        let cfg = config::load_config(&project).unwrap();
+        let output = crate::render::format_output_path(
+            &cfg,
+            "default",
+            1,
+            &project.join("output"),
+            &project,
+        );
+        std::fs::create_dir_all(output.parent().unwrap()).unwrap();
+        std::fs::write(&output, b"mp4").unwrap();
+        let bases = stash_append_bases(&project, None).unwrap();
+        assert_eq!(bases, [(output.clone(), append_base_path(&output))]);
+        assert!(!output.exists());
+
+        restore_append_bases(&bases);
+        assert_eq!(std::fs::read(&output).unwrap(), b"mp4");
+    }
+
+    #[test]
+    fn test_output_format_combinations() {
+        use crate::cli::{Cli, Command};
+        use clap::Parser;
+        use config::OutputContainer;
+
+        let parse = |args: &[&str]| match Cli::try_parse_from(args).map(|c| c.command) {
+            Ok(Command::Render { output_format, .. }) => Ok(output_format),
+            Ok(other) => panic!("unexpected command: {other:?}"),
+            Err(e) => Err(e),
+        };
+        assert_eq!(parse(&["vidgen", "render", "p"]).unwrap(), None);
+        assert_eq!(
+            parse(&["vidgen", "render", "p", "--output-format", "webm"]).unwrap(),
+            Some(OutputContainer::Webm)
+        );
+        assert_eq!(
+            parse(&["vidgen", "render", "p", "--container", "gif"]).unwrap(),
+            Some(OutputContainer::Gif)
+        );
+        assert!(parse(&["vidgen", "render", "p", "--output-format", "avi"]).is_err());
+        assert!(parse(&["vidgen", "render", "p", "--output-format", "mov", "--append"]).is_err());
+
+        for container in OutputContainer::ALL {
+            assert!(check_container_options(container, false, None).is_ok());
+        }
+        assert!(check_container_options(OutputContainer::Mp4, true, Some("9:16")).is_ok());
+        let err = check_container_options(OutputContainer::Webm, true, None).unwrap_err();
+        assert!(err.to_string().contains("--append"));
+        let err = check_container_options(OutputContainer::Gif, false, Some("1:1")).unwrap_err();
+        assert!(err.to_string().contains("--crop"));
+    }
+
+    #[test]
+    fn test_config_overrides_apply_without_saving() {
+        let dir = tempfile::tempdir().unwrap();
+        let toml = "[project]\nname = \"Overrides\"\n\n[video]\nfps = 30\n\n[output]\nquality = \"standard\"\n";
+        std::fs::write(dir.path().join("project.toml"), toml).unwrap();
+
+        let overrides = config::ConfigUpdate::from_overrides(&[
+            "video.fps=60".into(),
+            "output.quality=high".into(),
+        ])
+        .unwrap();
+        let effective = load_render_config(dir.path(), &overrides).unwrap();
+        assert_eq!(effective.video.fps, 60);
+        assert_eq!(effective.output.quality, "high");
+
+        // project.toml is untouched
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("project.toml")).unwrap(),
+            toml
+        );
+        let on_disk = config::load_config(dir.path()).unwrap();
+        assert_eq!(on_disk.video.fps, 30);
+        assert_eq!(on_disk.output.quality, "standard");
+
+        // Overridden values are validated like the file's own
+        let zero_speed = config::ConfigUpdate::from_overrides(&["voice.speed=0".into()]).unwrap();
+        assert!(load_render_config(dir.path(), &zero_speed).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_render_empty_project_reports_no_scenes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("project.toml"),
+            "[project]\nname = \"Empty\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir(dir.path().join("scenes")).unwrap();
+
+        let err = render_project(
+            dir.path(),
+            &RenderOptions::default(),
+            crate::render::RenderProgress::noop(),
+        )
+        .await
+        .err()
+        .expect("an empty project must not render");
+        assert!(matches!(err, VidgenError::NoScenes(_)));
+        assert!(
+            err.to_string().starts_with("Project has no scenes"),
+            "{err}"
+        );
+        assert!(err.hint().unwrap().contains("vidgen generate"));
+
+        // A scene list emptied by other means is refused before any rendering starts
+        let config: crate::config::ProjectConfig =
+            toml::from_str("[project]\nname = \"Empty\"\n").unwrap();
+        let err = crate::render::render_project(
+            &config,
+            &[],
+            30,
+            "standard",
+            &dir.path().join("output"),
+            dir.path(),
+            crate::render::RenderProgress::noop(),
+            None,
+            false,
+            false,
+            false,
+            false,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, VidgenError::NoScenes(_)));
+        assert!(!dir.path().join("output").exists());
+    }
+}
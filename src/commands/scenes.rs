@@ -1,7 +1,8 @@
 use crate::config;
 use crate::error::{VidgenError, VidgenResult};
 use crate::render::browser::capture_single_frame;
-use crate::scene::{self, SceneDuration};
+use crate::render::capture_size;
+use crate::scene::{self, extract_scene_slug, scene_file_paths, SceneDuration};
 use crate::template::TemplateRegistry;
 use base64::Engine;
 use serde::Serialize;
@@ -12,64 +13,149 @@ use std::path::{Path, PathBuf};
 // Shared utilities
 // ---------------------------------------------------------------------------
 
-/// Return sorted `.md` file paths from the `scenes/` directory.
-pub fn scene_file_paths(project_path: &Path) -> VidgenResult<Vec<PathBuf>> {
-    let scenes_dir = project_path.join("scenes");
-    if !scenes_dir.exists() {
-        return Ok(vec![]);
-    }
-    let mut entries: Vec<PathBuf> = std::fs::read_dir(&scenes_dir)?
-        .filter_map(|e| e.ok())
-        .map(|e| e.path())
-        .filter(|p| p.extension().is_some_and(|ext| ext == "md"))
-        .collect();
-    entries.sort();
-    Ok(entries)
-}
-
-/// Extract the template slug from a scene filename: `01-title-card.md` → `title-card`.
-fn extract_scene_slug(path: &Path) -> String {
-    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("scene");
-    // Strip the leading numeric prefix (e.g., "01-")
-    if let Some(pos) = stem.find('-') {
-        let after = &stem[pos + 1..];
-        if !after.is_empty() {
-            return after.to_string();
+/// Final sequential names for `files_with_slugs` (slug, original_path) in the
+/// desired order, without renaming anything.
+///
+/// Numbering is per folder: each file stays in its section folder and is
+/// numbered among the listed files in that folder, so `scenes/02-body/` gets its
+/// own `01-`, `02-`, ... A section folder takes one number in its parent where
+/// its first scene appears, so `01-intro.md`, `02-body/`, `03-outro.md` keep
+/// their numbers. Fails if the order would move a scene across sections, since
+/// folder names decide the order of their scenes.
+fn renumbered_paths(
+    scenes_dir: &Path,
+    files_with_slugs: &[(String, PathBuf)],
+) -> VidgenResult<Vec<PathBuf>> {
+    let mut per_dir: HashMap<PathBuf, usize> = HashMap::new();
+    let mut counted_sections: Vec<PathBuf> = Vec::new();
+    let mut final_paths: Vec<PathBuf> = Vec::new();
+    for (slug, original) in files_with_slugs {
+        let dir = original.parent().unwrap_or(scenes_dir).to_path_buf();
+        // Sections between `scenes/` and the file, outermost first
+        let mut sections: Vec<&Path> = dir
+            .ancestors()
+            .take_while(|a| a.starts_with(scenes_dir) && *a != scenes_dir)
+            .collect();
+        sections.reverse();
+        for section in sections {
+            if !counted_sections.iter().any(|c| c == section) {
+                counted_sections.push(section.to_path_buf());
+                let parent = section.parent().unwrap_or(scenes_dir).to_path_buf();
+                *per_dir.entry(parent).or_insert(0) += 1;
+            }
         }
+        let n = per_dir.entry(dir.clone()).or_insert(0);
+        *n += 1;
+        final_paths.push(dir.join(format!("{:02}-{slug}.md", *n)));
+    }
+    // Within a folder the numbering is already in order; across folders the
+    // folder names decide, so every section boundary must still sort forward
+    if let Some(pair) = final_paths
+        .windows(2)
+        .find(|w| w[0].parent() != w[1].parent() && w[0] >= w[1])
+    {
+        return Err(VidgenError::InvalidSceneOrder(format!(
+            "{} would have to play before {}, which is in another section folder; \
+             move the file between folders instead",
+            pair[1].display(),
+            pair[0].display()
+        )));
     }
-    stem.to_string()
+    Ok(final_paths)
 }
 
-/// Two-phase rename of scene files to sequential numbering.
+/// Two-phase rename of scene files to sequential numbering (see [`renumbered_paths`]).
 /// `files_with_slugs` is (slug, original_path) in desired order.
+///
+/// Slugs may repeat (five `title-card` scenes are fine): the numeric prefix keeps
+/// final names unique, and every file is moved to a staging name first so no
+/// rename can land on a file that hasn't been moved yet. Fails before touching
+/// anything if a final name is taken by a file outside the list (e.g. one
+/// skipped via `.vidgenignore`).
 fn renumber_scene_files(
     scenes_dir: &Path,
     files_with_slugs: &[(String, PathBuf)],
 ) -> VidgenResult<Vec<PathBuf>> {
-    // Phase 1: rename all to temp names to avoid collisions
+    let final_paths = renumbered_paths(scenes_dir, files_with_slugs)?;
+    for path in &final_paths {
+        if path.exists()
+            && !files_with_slugs
+                .iter()
+                .any(|(_, original)| original == path)
+        {
+            return Err(VidgenError::Other(format!(
+                "Cannot renumber scenes: {} already exists and is not a listed scene \
+                 (is it ignored via {}?)",
+                path.display(),
+                scene::IGNORE_FILE
+            )));
+        }
+    }
+
+    // Phase 1: rename all to staging names that don't exist yet
     let mut temp_paths = Vec::new();
-    for (i, (_slug, original)) in files_with_slugs.iter().enumerate() {
-        let tmp = scenes_dir.join(format!("__tmp_{i:04}.md"));
+    let mut counter = 0;
+    for (_slug, original) in files_with_slugs {
+        let dir = original.parent().unwrap_or(Path::new("."));
+        let tmp = unused_staging_path(dir, "__tmp", &mut counter);
         std::fs::rename(original, &tmp)?;
         temp_paths.push(tmp);
     }
 
     // Phase 2: rename to final sequential names
-    let mut final_paths = Vec::new();
-    for (i, (slug, _)) in files_with_slugs.iter().enumerate() {
-        let final_name = format!("{:02}-{slug}.md", i + 1);
-        let final_path = scenes_dir.join(&final_name);
-        std::fs::rename(&temp_paths[i], &final_path)?;
-        final_paths.push(final_path);
+    for (tmp, final_path) in temp_paths.iter().zip(&final_paths) {
+        std::fs::rename(tmp, final_path)?;
     }
 
     Ok(final_paths)
 }
 
+/// Renumber scene files contiguously in their current order (see [`renumbered_paths`]),
+/// closing gaps such as `01-`, `03-`. Returns the (old, new) path of each renamed file.
+pub(crate) fn normalize_scene_numbering(project_path: &Path) -> VidgenResult<Vec<(PathBuf, PathBuf)>> {
+    let paths = scene_file_paths(project_path)?;
+    let files_with_slugs: Vec<(String, PathBuf)> = paths
+        .iter()
+        .map(|p| (extract_scene_slug(p), p.clone()))
+        .collect();
+    let scenes_dir = project_path.join("scenes");
+    if renumbered_paths(&scenes_dir, &files_with_slugs)? == paths {
+        return Ok(Vec::new());
+    }
+    let final_paths = renumber_scene_files(&scenes_dir, &files_with_slugs)?;
+    Ok(paths
+        .into_iter()
+        .zip(final_paths)
+        .filter(|(old, new)| old != new)
+        .collect())
+}
+
+/// Scene file path relative to `scenes/` for results (`02-body/01-chart.md`).
+fn scene_file_label(project_path: &Path, path: &Path) -> String {
+    path.strip_prefix(project_path.join("scenes"))
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Next `{prefix}_NNNN.md` in `dir` that doesn't exist, so staging never
+/// overwrites a file left behind by an interrupted run.
+fn unused_staging_path(dir: &Path, prefix: &str, counter: &mut usize) -> PathBuf {
+    loop {
+        let candidate = dir.join(format!("{prefix}_{:04}.md", *counter));
+        *counter += 1;
+        if !candidate.exists() {
+            return candidate;
+        }
+    }
+}
+
 /// Format a SceneDuration for YAML frontmatter output.
 fn format_duration_yaml(duration: &SceneDuration) -> String {
     match duration {
         SceneDuration::Auto => "auto".to_string(),
+        SceneDuration::MatchMusic => "match-music".to_string(),
+        SceneDuration::Percent(p) => format!("\"{p}%\""),
         SceneDuration::Fixed(d) => {
             if *d == d.floor() {
                 format!("{}", *d as i64)
@@ -165,11 +251,19 @@ pub fn add_scenes(
         });
     }
 
+    // New scenes join the section of the scene they follow (or precede, at the start)
+    let target_dir = insert_pos
+        .checked_sub(1)
+        .or((count > 0).then_some(0))
+        .and_then(|i| existing[i].parent())
+        .map_or_else(|| scenes_dir.clone(), Path::to_path_buf);
+
     // Write new scene files to temp names first
     let mut new_paths = Vec::new();
-    for (i, input) in scenes.iter().enumerate() {
+    let mut counter = 0;
+    for input in &scenes {
         let template = input.template.as_deref().unwrap_or("title-card");
-        let tmp_path = scenes_dir.join(format!("__new_{i:04}.md"));
+        let tmp_path = unused_staging_path(&target_dir, "__new", &mut counter);
         write_scene_input_to_file(
             template,
             &input.script,
@@ -195,16 +289,19 @@ pub fn add_scenes(
         combined.push((extract_scene_slug(path), path.clone()));
     }
 
-    let final_paths = renumber_scene_files(&scenes_dir, &combined)?;
+    let final_paths = match renumber_scene_files(&scenes_dir, &combined) {
+        Ok(paths) => paths,
+        Err(e) => {
+            for (_, path) in &new_paths {
+                let _ = std::fs::remove_file(path);
+            }
+            return Err(e);
+        }
+    };
 
     let files: Vec<String> = final_paths
         .iter()
-        .map(|p| {
-            p.file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("unknown")
-                .to_string()
-        })
+        .map(|p| scene_file_label(project_path, p))
         .collect();
 
     Ok(AddScenesResult {
@@ -309,11 +406,7 @@ pub fn update_scene(
         final_path = final_paths[scene_index].clone();
     }
 
-    let file = final_path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("unknown")
-        .to_string();
+    let file = scene_file_label(project_path, &final_path);
 
     Ok(UpdateSceneResult {
         scene_index,
@@ -344,17 +437,10 @@ pub fn remove_scenes(project_path: &Path, indices: &[usize]) -> VidgenResult<Rem
         }
     }
 
-    let scenes_dir = project_path.join("scenes");
-
-    // Delete the files at the given indices
     let mut to_remove: Vec<usize> = indices.to_vec();
     to_remove.sort_unstable();
     to_remove.dedup();
 
-    for &idx in to_remove.iter().rev() {
-        std::fs::remove_file(&paths[idx])?;
-    }
-
     // Collect remaining files with their slugs
     let remaining: Vec<(String, PathBuf)> = paths
         .iter()
@@ -362,6 +448,14 @@ pub fn remove_scenes(project_path: &Path, indices: &[usize]) -> VidgenResult<Rem
         .filter(|(i, _)| !to_remove.contains(i))
         .map(|(_, p)| (extract_scene_slug(p), p.clone()))
         .collect();
+    // Refuse before deleting anything if the survivors can't be renumbered in order
+    let scenes_dir = project_path.join("scenes");
+    renumbered_paths(&scenes_dir, &remaining)?;
+
+    // Delete the files at the given indices
+    for &idx in to_remove.iter().rev() {
+        std::fs::remove_file(&paths[idx])?;
+    }
 
     let final_paths = if remaining.is_empty() {
         vec![]
@@ -371,12 +465,7 @@ pub fn remove_scenes(project_path: &Path, indices: &[usize]) -> VidgenResult<Rem
 
     let files: Vec<String> = final_paths
         .iter()
-        .map(|p| {
-            p.file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("unknown")
-                .to_string()
-        })
+        .map(|p| scene_file_label(project_path, p))
         .collect();
 
     Ok(RemoveScenesResult {
@@ -396,11 +485,8 @@ pub struct ReorderScenesResult {
     pub files: Vec<String>,
 }
 
-pub fn reorder_scenes(project_path: &Path, order: &[usize]) -> VidgenResult<ReorderScenesResult> {
-    let paths = scene_file_paths(project_path)?;
-    let count = paths.len();
-
-    // Validate: order must be a permutation of 0..count
+/// Check that `order` is a permutation of `0..count`.
+fn check_permutation(order: &[usize], count: usize) -> VidgenResult<()> {
     if order.len() != count {
         return Err(VidgenError::InvalidSceneOrder(format!(
             "expected {} indices, got {}",
@@ -420,6 +506,14 @@ pub fn reorder_scenes(project_path: &Path, order: &[usize]) -> VidgenResult<Reor
         }
         seen[idx] = true;
     }
+    Ok(())
+}
+
+pub fn reorder_scenes(project_path: &Path, order: &[usize]) -> VidgenResult<ReorderScenesResult> {
+    let paths = scene_file_paths(project_path)?;
+    let count = paths.len();
+
+    check_permutation(order, count)?;
 
     let scenes_dir = project_path.join("scenes");
 
@@ -433,12 +527,7 @@ pub fn reorder_scenes(project_path: &Path, order: &[usize]) -> VidgenResult<Reor
 
     let files: Vec<String> = final_paths
         .iter()
-        .map(|p| {
-            p.file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("unknown")
-                .to_string()
-        })
+        .map(|p| scene_file_label(project_path, p))
         .collect();
 
     Ok(ReorderScenesResult {
@@ -447,6 +536,281 @@ pub fn reorder_scenes(project_path: &Path, order: &[usize]) -> VidgenResult<Reor
     })
 }
 
+// ---------------------------------------------------------------------------
+// batch_edit
+// ---------------------------------------------------------------------------
+
+/// One step of a [`batch_edit`]. Indices refer to the scene order left by the
+/// steps before it.
+pub enum SceneEdit {
+    Add {
+        insert_at: Option<usize>,
+        scenes: Vec<SceneInput>,
+    },
+    Update {
+        scene_index: usize,
+        update: SceneUpdate,
+    },
+    Remove {
+        indices: Vec<usize>,
+    },
+    Reorder {
+        order: Vec<usize>,
+    },
+    /// Move one scene to position `to`, shifting the scenes in between
+    Move {
+        from: usize,
+        to: usize,
+    },
+}
+
+impl SceneEdit {
+    fn name(&self) -> &'static str {
+        match self {
+            SceneEdit::Add { .. } => "add",
+            SceneEdit::Update { .. } => "update",
+            SceneEdit::Remove { .. } => "remove",
+            SceneEdit::Reorder { .. } => "reorder",
+            SceneEdit::Move { .. } => "move",
+        }
+    }
+
+    /// Check this edit against a project of `count` scenes; returns the count after it.
+    fn check(&self, count: usize) -> VidgenResult<usize> {
+        let in_range = |index: usize, limit: usize| {
+            if index < limit {
+                Ok(())
+            } else {
+                Err(VidgenError::SceneIndexOutOfRange { index, count })
+            }
+        };
+        match self {
+            SceneEdit::Add { insert_at, scenes } => {
+                in_range(insert_at.unwrap_or(count), count + 1)?;
+                Ok(count + scenes.len())
+            }
+            SceneEdit::Update { scene_index, .. } => {
+                in_range(*scene_index, count)?;
+                Ok(count)
+            }
+            SceneEdit::Remove { indices } => {
+                for &idx in indices {
+                    in_range(idx, count)?;
+                }
+                let mut unique = indices.clone();
+                unique.sort_unstable();
+                unique.dedup();
+                Ok(count - unique.len())
+            }
+            SceneEdit::Reorder { order } => {
+                check_permutation(order, count)?;
+                Ok(count)
+            }
+            SceneEdit::Move { from, to } => {
+                in_range(*from, count)?;
+                in_range(*to, count)?;
+                Ok(count)
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct BatchEditResult {
+    pub operations_applied: usize,
+    pub total_scenes: usize,
+    pub files: Vec<String>,
+}
+
+/// Apply `edits` in order as one transaction. Every edit is checked against the
+/// scene count it will see (indices, permutations) before any file changes, and
+/// if applying still fails part-way (e.g. a reorder across section folders) the
+/// scene files are restored to their state before the batch.
+pub fn batch_edit(project_path: &Path, edits: Vec<SceneEdit>) -> VidgenResult<BatchEditResult> {
+    let batch_error = |i: usize, name: &str, e: VidgenError| {
+        VidgenError::Other(format!(
+            "Batch edit operation {i} ({name}) failed, no changes were made: {e}"
+        ))
+    };
+
+    let original = scene_file_paths(project_path)?;
+    let mut count = original.len();
+    for (i, edit) in edits.iter().enumerate() {
+        count = edit.check(count).map_err(|e| batch_error(i, edit.name(), e))?;
+    }
+
+    let snapshot: Vec<(PathBuf, String)> = original
+        .iter()
+        .map(|p| Ok((p.clone(), std::fs::read_to_string(p)?)))
+        .collect::<VidgenResult<_>>()?;
+
+    let operations_applied = edits.len();
+    for (i, edit) in edits.into_iter().enumerate() {
+        let name = edit.name();
+        if let Err(e) = apply_edit(project_path, edit) {
+            restore_scene_files(project_path, &snapshot)?;
+            return Err(batch_error(i, name, e));
+        }
+    }
+
+    let files: Vec<String> = scene_file_paths(project_path)?
+        .iter()
+        .map(|p| scene_file_label(project_path, p))
+        .collect();
+    Ok(BatchEditResult {
+        operations_applied,
+        total_scenes: files.len(),
+        files,
+    })
+}
+
+fn apply_edit(project_path: &Path, edit: SceneEdit) -> VidgenResult<()> {
+    match edit {
+        SceneEdit::Add { insert_at, scenes } => add_scenes(project_path, insert_at, scenes).map(drop),
+        SceneEdit::Update {
+            scene_index,
+            update,
+        } => update_scene(project_path, scene_index, update).map(drop),
+        SceneEdit::Remove { indices } => remove_scenes(project_path, &indices).map(drop),
+        SceneEdit::Reorder { order } => reorder_scenes(project_path, &order).map(drop),
+        SceneEdit::Move { from, to } => {
+            let mut order: Vec<usize> = (0..scene_file_paths(project_path)?.len()).collect();
+            let moved = order.remove(from);
+            order.insert(to, moved);
+            reorder_scenes(project_path, &order).map(drop)
+        }
+    }
+}
+
+/// Put the scene files back exactly as `snapshot` recorded them.
+fn restore_scene_files(project_path: &Path, snapshot: &[(PathBuf, String)]) -> VidgenResult<()> {
+    for path in scene_file_paths(project_path)? {
+        std::fs::remove_file(path)?;
+    }
+    for (path, content) in snapshot {
+        std::fs::write(path, content)?;
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// paginate_scenes
+// ---------------------------------------------------------------------------
+
+/// Prop holding the list that [`paginate_scenes`] splits.
+pub const ITEMS_PROP: &str = "items";
+
+/// Split `items` into consecutive pages of at most `page_size` entries.
+pub fn paginate_items(
+    items: &[serde_json::Value],
+    page_size: usize,
+) -> Vec<Vec<serde_json::Value>> {
+    items
+        .chunks(page_size.max(1))
+        .map(<[serde_json::Value]>::to_vec)
+        .collect()
+}
+
+#[derive(Serialize)]
+pub struct PaginateResult {
+    pub scenes_split: usize,
+    pub scenes_added: usize,
+    pub total_scenes: usize,
+    pub files: Vec<String>,
+}
+
+/// Split every scene whose `props.items` has more than `page_size` entries into
+/// consecutive scenes of the same template, one page of items each. The first
+/// page stays in the original file with its narration; continuation scenes copy
+/// the rest of the frontmatter and have no script. `scene_filter` holds 0-based
+/// indices of the scenes to consider (default: all).
+pub fn paginate_scenes(
+    project_path: &Path,
+    page_size: usize,
+    scene_filter: Option<&[usize]>,
+) -> VidgenResult<PaginateResult> {
+    if page_size == 0 {
+        return Err(VidgenError::Other("Page size must be at least 1".into()));
+    }
+    let paths = scene_file_paths(project_path)?;
+    let count = paths.len();
+    if let Some(&index) = scene_filter.and_then(|f| f.iter().find(|&&i| i >= count)) {
+        return Err(VidgenError::SceneIndexOutOfRange { index, count });
+    }
+
+    // Plan every split in memory first so nothing is written if a scene fails to parse
+    let mut combined: Vec<(String, PathBuf)> = Vec::new();
+    let mut writes: Vec<(scene::Scene, PathBuf)> = Vec::new();
+    let mut scenes_split = 0;
+    let mut counter = 0;
+    for (i, path) in paths.iter().enumerate() {
+        let slug = extract_scene_slug(path);
+        combined.push((slug.clone(), path.clone()));
+        if !scene_filter.map_or(true, |f| f.contains(&i)) {
+            continue;
+        }
+        let content = std::fs::read_to_string(path)?;
+        let mut first = scene::parse_scene(&content, path)?;
+        let pages = match first.frontmatter.props.get(ITEMS_PROP) {
+            Some(serde_json::Value::Array(items)) if items.len() > page_size => {
+                paginate_items(items, page_size)
+            }
+            _ => continue,
+        };
+        scenes_split += 1;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        for page in pages.iter().skip(1) {
+            let mut next = scene::parse_scene(&content, path)?;
+            next.script.clear();
+            next.frontmatter
+                .props
+                .insert(ITEMS_PROP.to_string(), page.clone().into());
+            let tmp_path = unused_staging_path(dir, "__page", &mut counter);
+            combined.push((slug.clone(), tmp_path.clone()));
+            writes.push((next, tmp_path));
+        }
+        first
+            .frontmatter
+            .props
+            .insert(ITEMS_PROP.to_string(), pages[0].clone().into());
+        writes.push((first, path.clone()));
+    }
+
+    let scenes_dir = project_path.join("scenes");
+    let files = if writes.is_empty() {
+        paths
+    } else {
+        // Refuse before writing anything if the new scenes can't be numbered in order
+        renumbered_paths(&scenes_dir, &combined)?;
+        let snapshot: Vec<(PathBuf, String)> = paths
+            .iter()
+            .map(|p| Ok((p.clone(), std::fs::read_to_string(p)?)))
+            .collect::<VidgenResult<_>>()?;
+        let written = writes
+            .iter()
+            .try_for_each(|(scene, path)| scene::write_scene(scene, path))
+            .and_then(|()| renumber_scene_files(&scenes_dir, &combined));
+        match written {
+            Ok(final_paths) => final_paths,
+            Err(e) => {
+                restore_scene_files(project_path, &snapshot)?;
+                return Err(e);
+            }
+        }
+    };
+
+    let files: Vec<String> = files
+        .iter()
+        .map(|p| scene_file_label(project_path, p))
+        .collect();
+    Ok(PaginateResult {
+        scenes_split,
+        scenes_added: files.len() - count,
+        total_scenes: files.len(),
+        files,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // list_voices
 // ---------------------------------------------------------------------------
@@ -510,18 +874,46 @@ pub fn list_voices() -> Vec<VoiceInfo> {
 #[derive(Serialize)]
 pub struct PreviewResult {
     pub scene_index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
     pub width: u32,
     pub height: u32,
     pub png_base64: String,
 }
 
+/// Resolve the scene and viewport for a preview. With `format`, use that
+/// format's dimensions and apply its `format_overrides`; otherwise use
+/// `video.width`/`video.height` and the scene as written. Either size is fitted
+/// within `render.max_dimension`.
+fn preview_target(
+    cfg: &config::ProjectConfig,
+    scene: scene::Scene,
+    format: Option<&str>,
+) -> VidgenResult<(scene::Scene, u32, u32)> {
+    let Some(name) = format else {
+        let (width, height) = capture_size(cfg, cfg.video.width, cfg.video.height);
+        return Ok((scene, width, height));
+    };
+    let formats = crate::render::resolve_formats(cfg, None);
+    let (_, width, height, _) = formats.iter().find(|(n, ..)| n == name).ok_or_else(|| {
+        let available: Vec<&str> = formats.iter().map(|(n, ..)| n.as_str()).collect();
+        VidgenError::Other(format!(
+            "Unknown format \"{name}\" (available: {})",
+            available.join(", ")
+        ))
+    })?;
+    let (width, height) = capture_size(cfg, *width, *height);
+    Ok((crate::render::apply_format_overrides(&scene, name), width, height))
+}
+
 pub async fn preview_scene(
     project_path: &Path,
     scene_index: usize,
     frame: Option<u32>,
+    format: Option<&str>,
 ) -> VidgenResult<PreviewResult> {
     let cfg = config::load_config(project_path)?;
-    let scenes = scene::load_scenes(project_path)?;
+    let mut scenes = scene::load_scenes_with_variables(project_path, &cfg.variables)?;
     let count = scenes.len();
 
     if scene_index >= count {
@@ -531,9 +923,7 @@ pub async fn preview_scene(
         });
     }
 
-    let scene = &scenes[scene_index];
-    let width = cfg.video.width;
-    let height = cfg.video.height;
+    let (scene, width, height) = preview_target(&cfg, scenes.swap_remove(scene_index), format)?;
     let total_frames = scene.total_frames(cfg.video.fps);
     let frame = frame.unwrap_or(0);
 
@@ -545,13 +935,15 @@ pub async fn preview_scene(
 
     let mut registry = TemplateRegistry::new()?;
     registry.register_project_templates(project_path)?;
-    let html = registry.render_scene_html(scene, &cfg.theme, width, height, frame, total_frames, Some(project_path))?;
+    registry.apply_emoji_fallback(&cfg.render, project_path);
+    let html = registry.render_scene_html(&scene, &cfg.theme, width, height, frame, total_frames, Some(project_path))?;
 
-    let screenshot = capture_single_frame(&html, width, height, frame, total_frames).await?;
+    let screenshot = capture_single_frame(&html, width, height, frame, total_frames, scene.frontmatter.wait_for.as_deref()).await?;
     let png_base64 = base64::engine::general_purpose::STANDARD.encode(&screenshot);
 
     Ok(PreviewResult {
         scene_index,
+        format: format.map(String::from),
         width,
         height,
         png_base64,
@@ -851,6 +1243,217 @@ mod tests {
         assert_eq!(scenes[2].script, "Second");
     }
 
+    #[test]
+    fn test_renumber_many_same_slug_scenes() {
+        let dir = tempfile::tempdir().unwrap();
+        let scripts = ["One", "Two", "Three", "Four", "Five"];
+        let project = setup_project(
+            dir.path(),
+            scripts
+                .iter()
+                .map(|s| make_scene("title-card", s))
+                .collect(),
+        );
+
+        let result = reorder_scenes(&project, &[4, 3, 2, 1, 0]).unwrap();
+        assert_eq!(
+            result.files,
+            (1..=5)
+                .map(|i| format!("{i:02}-title-card.md"))
+                .collect::<Vec<_>>()
+        );
+        let scenes = scene::load_scenes(&project).unwrap();
+        let order: Vec<&str> = scenes.iter().map(|s| s.script.trim()).collect();
+        assert_eq!(order, ["Five", "Four", "Three", "Two", "One"]);
+
+        // Renaming a content-text scene to the shared slug keeps every file and its position
+        add_scenes(
+            &project,
+            Some(2),
+            vec![SceneInput {
+                template: Some("content-text".to_string()),
+                script: "Middle".to_string(),
+                duration: None,
+                props: None,
+                transition: None,
+                voice: None,
+                background: None,
+            }],
+        )
+        .unwrap();
+        let update = SceneUpdate {
+            template: Some("title-card".to_string()),
+            script: None,
+            duration: None,
+            props: None,
+            transition_in: None,
+            transition_out: None,
+            voice: None,
+        };
+        let result = update_scene(&project, 2, update).unwrap();
+        assert_eq!(result.file, "03-title-card.md");
+
+        let paths = scene_file_paths(&project).unwrap();
+        assert_eq!(paths.len(), 6);
+        assert!(paths.iter().all(|p| extract_scene_slug(p) == "title-card"));
+        let scenes = scene::load_scenes(&project).unwrap();
+        let order: Vec<&str> = scenes.iter().map(|s| s.script.trim()).collect();
+        assert_eq!(order, ["Five", "Four", "Middle", "Three", "Two", "One"]);
+    }
+
+    #[test]
+    fn test_renumber_keeps_leftover_staging_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = setup_project(
+            dir.path(),
+            vec![make_scene("title-card", "A"), make_scene("title-card", "B")],
+        );
+        // A staging file left behind by an interrupted run is still a scene; it must not be clobbered
+        std::fs::write(
+            project.join("scenes/__tmp_0000.md"),
+            "---\ntemplate: title-card\n---\n\nLeftover\n",
+        )
+        .unwrap();
+
+        reorder_scenes(&project, &[1, 0, 2]).unwrap();
+        let scenes = scene::load_scenes(&project).unwrap();
+        let order: Vec<&str> = scenes.iter().map(|s| s.script.trim()).collect();
+        assert_eq!(order, ["B", "A", "Leftover"]);
+    }
+
+    #[test]
+    fn test_renumber_refuses_to_overwrite_ignored_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = setup_project(
+            dir.path(),
+            vec![make_scene("title-card", "A"), make_scene("title-card", "B")],
+        );
+        std::fs::write(project.join(scene::IGNORE_FILE), "03-*\n").unwrap();
+        std::fs::write(project.join("scenes/03-title-card.md"), "draft").unwrap();
+
+        let result = add_scenes(
+            &project,
+            None,
+            vec![SceneInput {
+                template: Some("title-card".to_string()),
+                script: "C".to_string(),
+                duration: None,
+                props: None,
+                transition: None,
+                voice: None,
+                background: None,
+            }],
+        );
+        let Err(err) = result else {
+            panic!("expected add_scenes to refuse overwriting the ignored file");
+        };
+        assert!(err.to_string().contains("03-title-card.md"));
+        assert_eq!(
+            std::fs::read_to_string(project.join("scenes/03-title-card.md")).unwrap(),
+            "draft"
+        );
+        assert_eq!(scene_file_paths(&project).unwrap().len(), 2);
+    }
+
+    /// `01-intro.md`, then a `02-body/` section with two scenes, then `03-outro.md`.
+    fn setup_sectioned_project(dir: &Path) -> PathBuf {
+        let project = setup_project(dir, vec![make_scene("title-card", "Intro")]);
+        let scene = |script: &str| format!("---\ntemplate: content-text\n---\n\n{script}\n");
+        std::fs::create_dir_all(project.join("scenes/02-body")).unwrap();
+        std::fs::write(
+            project.join("scenes/02-body/01-content-text.md"),
+            scene("Problem"),
+        )
+        .unwrap();
+        std::fs::write(
+            project.join("scenes/02-body/02-content-text.md"),
+            scene("Fix"),
+        )
+        .unwrap();
+        std::fs::write(project.join("scenes/03-outro.md"), scene("Outro")).unwrap();
+        project
+    }
+
+    fn script_order(project: &Path) -> Vec<String> {
+        scene::load_scenes(project)
+            .unwrap()
+            .iter()
+            .map(|s| s.script.trim().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_scene_operations_stay_within_sections() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = setup_sectioned_project(dir.path());
+        assert_eq!(script_order(&project), ["Intro", "Problem", "Fix", "Outro"]);
+
+        // Swapping two scenes inside the section renumbers them in their folder
+        let result = reorder_scenes(&project, &[0, 2, 1, 3]).unwrap();
+        assert_eq!(
+            result.files,
+            [
+                "01-title-card.md",
+                "02-body/01-content-text.md",
+                "02-body/02-content-text.md",
+                "03-outro.md",
+            ]
+        );
+        assert_eq!(script_order(&project), ["Intro", "Fix", "Problem", "Outro"]);
+
+        // A scene inserted after a section scene joins that section
+        add_scenes(
+            &project,
+            Some(2),
+            vec![SceneInput {
+                template: Some("title-card".to_string()),
+                script: "Detail".to_string(),
+                duration: None,
+                props: None,
+                transition: None,
+                voice: None,
+                background: None,
+            }],
+        )
+        .unwrap();
+        assert!(project.join("scenes/02-body/02-title-card.md").exists());
+        assert_eq!(
+            script_order(&project),
+            ["Intro", "Fix", "Detail", "Problem", "Outro"]
+        );
+
+        remove_scenes(&project, &[1]).unwrap();
+        assert_eq!(
+            script_order(&project),
+            ["Intro", "Detail", "Problem", "Outro"]
+        );
+        assert!(project.join("scenes/02-body/01-title-card.md").exists());
+    }
+
+    #[test]
+    fn test_reorder_across_sections_is_refused_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = setup_sectioned_project(dir.path());
+        let before = scene_file_paths(&project).unwrap();
+
+        // Moving the outro before the body section would need a folder move
+        let Err(err) = reorder_scenes(&project, &[0, 3, 1, 2]) else {
+            panic!("expected a cross-section reorder to be refused");
+        };
+        assert!(err.to_string().contains("section"));
+        assert_eq!(scene_file_paths(&project).unwrap(), before);
+    }
+
+    #[test]
+    fn test_extract_scene_slug_strips_only_numeric_prefix() {
+        assert_eq!(
+            extract_scene_slug(Path::new("01-title-card.md")),
+            "title-card"
+        );
+        assert_eq!(extract_scene_slug(Path::new("intro-card.md")), "intro-card");
+        assert_eq!(extract_scene_slug(Path::new("__tmp_0003.md")), "__tmp_0003");
+    }
+
     #[test]
     fn test_reorder_invalid_permutation() {
         let dir = tempfile::tempdir().unwrap();
@@ -926,12 +1529,13 @@ mod tests {
         assert_eq!(result.total_scenes, 2);
 
         let scenes = scene::load_scenes(&project).unwrap();
+        assert_eq!(scenes[1].frontmatter.transition_in.as_deref(), Some("fade"));
         assert_eq!(
-            scenes[1].frontmatter.transition_in.as_deref(),
-            Some("fade")
-        );
-        assert_eq!(
-            scenes[1].frontmatter.voice.as_ref().and_then(|v| v.voice_name()),
+            scenes[1]
+                .frontmatter
+                .voice
+                .as_ref()
+                .and_then(|v| v.voice_name()),
             Some("en-US-AriaNeural")
         );
         assert_eq!(
@@ -951,4 +1555,283 @@ mod tests {
         // Otherwise: fallback stub with at least 1 entry.
         assert!(!voices.is_empty());
     }
+
+    #[test]
+    fn test_preview_target_uses_format_dimensions_and_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_path = dir.path().join("test-project");
+        init::create_project(&CreateProjectOptions {
+            path: project_path.clone(),
+            name: Some("Test".to_string()),
+            fps: None,
+            width: None,
+            height: None,
+            quality: None,
+            voice: None,
+            formats: Some(vec!["landscape".into(), "portrait".into()]),
+            theme: None,
+            scenes: Some(vec![make_scene("title-card", "Hello")]),
+        })
+        .unwrap();
+        let cfg = config::load_config(&project_path).unwrap();
+        let parse = || {
+            scene::parse_scene(
+                "---\ntemplate: title-card\nprops:\n  title: Wide\nformat_overrides:\n  portrait:\n    props:\n      title: Tall\n---\n",
+                Path::new("01-intro.md"),
+            )
+            .unwrap()
+        };
+
+        let (portrait, width, height) = preview_target(&cfg, parse(), Some("portrait")).unwrap();
+        assert_eq!((width, height), (1080, 1920));
+        assert_eq!(portrait.frontmatter.props["title"], "Tall");
+
+        let (plain, width, height) = preview_target(&cfg, parse(), None).unwrap();
+        assert_eq!((width, height), (cfg.video.width, cfg.video.height));
+        assert_eq!(plain.frontmatter.props["title"], "Wide");
+
+        let err = preview_target(&cfg, parse(), Some("potrait")).unwrap_err();
+        assert!(err.to_string().contains("landscape, portrait"));
+
+        // Oversized sizes are capped before they reach Chromium, with or
+        // without auto_downscale
+        let mut big = cfg.clone();
+        big.video.width = 15360;
+        big.video.height = 8640;
+        big.render.auto_downscale = false;
+        let (_, width, height) = preview_target(&big, parse(), None).unwrap();
+        assert_eq!((width, height), (7680, 4320));
+        let portrait = big.video.formats.as_mut().unwrap().get_mut("portrait").unwrap();
+        portrait.width = 4320;
+        portrait.height = 15360;
+        let (_, width, height) = preview_target(&big, parse(), Some("portrait")).unwrap();
+        assert_eq!((width, height), (2160, 7680));
+    }
+
+    fn no_update() -> SceneUpdate {
+        SceneUpdate {
+            template: None,
+            script: None,
+            duration: None,
+            props: None,
+            transition_in: None,
+            transition_out: None,
+            voice: None,
+        }
+    }
+
+    #[test]
+    fn test_batch_edit_applies_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = setup_project(
+            dir.path(),
+            vec![
+                make_scene("title-card", "A"),
+                make_scene("content-text", "B"),
+                make_scene("content-text", "C"),
+            ],
+        );
+        let input = |script: &str| SceneInput {
+            template: Some("cta-card".into()),
+            script: script.into(),
+            duration: None,
+            props: None,
+            transition: None,
+            voice: None,
+            background: None,
+        };
+
+        let result = batch_edit(
+            &project,
+            vec![
+                SceneEdit::Add {
+                    insert_at: None,
+                    scenes: vec![input("D")],
+                },
+                // Indices see the scene added above
+                SceneEdit::Update {
+                    scene_index: 3,
+                    update: SceneUpdate {
+                        script: Some("D2".into()),
+                        ..no_update()
+                    },
+                },
+                SceneEdit::Remove { indices: vec![1] },
+                SceneEdit::Move { from: 2, to: 0 },
+                SceneEdit::Reorder {
+                    order: vec![0, 2, 1],
+                },
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(result.operations_applied, 5);
+        assert_eq!(result.total_scenes, 3);
+        assert_eq!(script_order(&project), ["D2", "C", "A"]);
+        assert_eq!(
+            result.files,
+            ["01-cta-card.md", "02-content-text.md", "03-title-card.md"]
+        );
+    }
+
+    #[test]
+    fn test_batch_edit_invalid_op_changes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = setup_project(
+            dir.path(),
+            vec![
+                make_scene("title-card", "A"),
+                make_scene("content-text", "B"),
+            ],
+        );
+        let before = scene_file_paths(&project).unwrap();
+
+        // The remove is valid on its own, but afterwards only one scene is left
+        let Err(err) = batch_edit(
+            &project,
+            vec![
+                SceneEdit::Remove { indices: vec![0] },
+                SceneEdit::Reorder { order: vec![1, 0] },
+            ],
+        ) else {
+            panic!("expected the batch to be rejected");
+        };
+        assert!(err.to_string().contains("operation 1 (reorder)"));
+        assert_eq!(scene_file_paths(&project).unwrap(), before);
+        assert_eq!(script_order(&project), ["A", "B"]);
+
+        let Err(err) = batch_edit(
+            &project,
+            vec![SceneEdit::Update {
+                scene_index: 2,
+                update: no_update(),
+            }],
+        ) else {
+            panic!("expected the batch to be rejected");
+        };
+        assert!(err.to_string().contains("out of range"), "{err}");
+    }
+
+    #[test]
+    fn test_batch_edit_rolls_back_failed_apply() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = setup_sectioned_project(dir.path());
+        let before = scene_file_paths(&project).unwrap();
+
+        // The update succeeds, then moving the outro into the body section is refused
+        let Err(err) = batch_edit(
+            &project,
+            vec![
+                SceneEdit::Update {
+                    scene_index: 0,
+                    update: SceneUpdate {
+                        script: Some("Changed".into()),
+                        ..no_update()
+                    },
+                },
+                SceneEdit::Move { from: 3, to: 1 },
+            ],
+        ) else {
+            panic!("expected the batch to be rejected");
+        };
+        assert!(err.to_string().contains("section"), "{err}");
+        assert_eq!(scene_file_paths(&project).unwrap(), before);
+        assert_eq!(script_order(&project), ["Intro", "Problem", "Fix", "Outro"]);
+    }
+
+    #[test]
+    fn test_paginate_items_pages_of_four() {
+        let items: Vec<serde_json::Value> = (1..=10).map(serde_json::Value::from).collect();
+        let pages = paginate_items(&items, 4);
+        let sizes: Vec<usize> = pages.iter().map(Vec::len).collect();
+        assert_eq!(sizes, [4, 4, 2]);
+        assert_eq!(pages[2], [serde_json::json!(9), serde_json::json!(10)]);
+        assert_eq!(paginate_items(&items, 10).len(), 1);
+    }
+
+    #[test]
+    fn test_paginate_scenes_splits_long_items() {
+        let dir = tempfile::tempdir().unwrap();
+        let items: Vec<String> = (1..=10).map(|i| format!("Point {i}")).collect();
+        let mut props = HashMap::new();
+        props.insert("title".to_string(), serde_json::json!("Agenda"));
+        props.insert(ITEMS_PROP.to_string(), serde_json::json!(items));
+        let project = setup_project(
+            dir.path(),
+            vec![
+                make_scene("title-card", "Intro"),
+                init::SceneInput {
+                    props: Some(props),
+                    ..make_scene("content-text", "Here is the agenda")
+                },
+                make_scene("cta-card", "Outro"),
+            ],
+        );
+
+        let result = paginate_scenes(&project, 4, None).unwrap();
+        assert_eq!(result.scenes_split, 1);
+        assert_eq!(result.scenes_added, 2);
+        assert_eq!(
+            result.files,
+            [
+                "01-title-card.md",
+                "02-content-text.md",
+                "03-content-text.md",
+                "04-content-text.md",
+                "05-cta-card.md"
+            ]
+        );
+
+        let scenes = scene::load_scenes(&project).unwrap();
+        let pages: Vec<usize> = scenes[1..4]
+            .iter()
+            .map(|s| s.frontmatter.props[ITEMS_PROP].as_array().unwrap().len())
+            .collect();
+        assert_eq!(pages, [4, 4, 2]);
+        assert_eq!(
+            scenes[3].frontmatter.props[ITEMS_PROP],
+            serde_json::json!(["Point 9", "Point 10"])
+        );
+        // Narration stays on the first page; other frontmatter is copied
+        assert_eq!(scenes[1].script.trim(), "Here is the agenda");
+        assert!(scenes[2].script.trim().is_empty());
+        assert_eq!(scenes[2].frontmatter.props["title"], "Agenda");
+        assert_eq!(scenes[2].frontmatter.duration, SceneDuration::Fixed(5.0));
+
+        // Nothing left to split
+        let again = paginate_scenes(&project, 4, None).unwrap();
+        assert_eq!(again.scenes_split, 0);
+        assert_eq!(again.total_scenes, 5);
+    }
+
+    #[test]
+    fn test_paginate_scenes_respects_filter() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut props = HashMap::new();
+        props.insert(ITEMS_PROP.to_string(), serde_json::json!([1, 2, 3]));
+        let scene = || init::SceneInput {
+            props: Some(props.clone()),
+            ..make_scene("content-text", "List")
+        };
+        let project = setup_project(dir.path(), vec![scene(), scene()]);
+
+        let result = paginate_scenes(&project, 2, Some(&[1])).unwrap();
+        assert_eq!(result.scenes_split, 1);
+        assert_eq!(result.total_scenes, 3);
+        let scenes = scene::load_scenes(&project).unwrap();
+        assert_eq!(
+            scenes[0].frontmatter.props[ITEMS_PROP],
+            serde_json::json!([1, 2, 3])
+        );
+        assert_eq!(
+            scenes[2].frontmatter.props[ITEMS_PROP],
+            serde_json::json!([3])
+        );
+
+        assert!(paginate_scenes(&project, 0, None).is_err());
+        let Err(err) = paginate_scenes(&project, 2, Some(&[7])) else {
+            panic!("expected out of range error");
+        };
+        assert!(err.to_string().contains("out of range"));
+    }
 }
@@ -0,0 +1,185 @@
+use crate::commands::render::format_file_size;
+use crate::config;
+use crate::error::{VidgenError, VidgenResult};
+use crate::render::encoder::probe_video_duration;
+use crate::scene::{self, SceneDuration};
+use colored::*;
+use std::path::{Path, PathBuf};
+
+/// A rendered video in the project's output directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedOutput {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    /// Length read with ffprobe; `None` when the file can't be probed
+    pub duration_secs: Option<f64>,
+}
+
+/// The rendered videos (any `output.container` extension) in `output_dir`,
+/// sorted by name. A missing directory has none.
+pub(crate) fn rendered_files(output_dir: &Path) -> VidgenResult<Vec<PathBuf>> {
+    if !output_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files: Vec<PathBuf> = std::fs::read_dir(output_dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.extension().is_some_and(|ext| {
+                config::OutputContainer::ALL
+                    .iter()
+                    .any(|c| ext == c.extension())
+            })
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// List the rendered videos in `output_dir` with their sizes and probed durations.
+pub fn rendered_outputs(
+    output_dir: &Path,
+    probe: impl Fn(&Path) -> VidgenResult<f64>,
+) -> VidgenResult<Vec<RenderedOutput>> {
+    rendered_files(output_dir)?
+        .into_iter()
+        .map(|path| {
+            let size_bytes = std::fs::metadata(&path)?.len();
+            let duration_secs = probe(&path).ok();
+            Ok(RenderedOutput {
+                path,
+                size_bytes,
+                duration_secs,
+            })
+        })
+        .collect()
+}
+
+/// One table row: file name, duration and size.
+fn output_row(output: &RenderedOutput) -> String {
+    let name = output
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let duration = output
+        .duration_secs
+        .map(|d| format!("{d:.1}s"))
+        .unwrap_or_else(|| "?".into());
+    format!(
+        "{:<40} {:>8} {:>10}",
+        name,
+        duration,
+        format_file_size(output.size_bytes)
+    )
+}
+
+/// Print the project's settings, scene summary and rendered outputs, as in
+/// the MCP `project_status` tool.
+pub fn run(project_path: &Path) -> VidgenResult<()> {
+    if !project_path.exists() {
+        return Err(VidgenError::ProjectNotFound(project_path.to_path_buf()));
+    }
+    let config = config::load_config(project_path)?;
+    let scenes = scene::load_scenes_with_variables(project_path, &config.variables)?;
+
+    println!("{} {}", "Project:".bold(), config.project.name);
+    println!(
+        "  Video:   {}x{} @ {}fps, transition {} ({}s)",
+        config.video.width,
+        config.video.height,
+        config.video.fps,
+        config.video.default_transition.as_deref().unwrap_or("none"),
+        config.video.default_transition_duration
+    );
+    println!(
+        "  Voice:   {} ({}), speed {}",
+        config.voice.engine,
+        config.voice.default_voice.as_deref().unwrap_or("default"),
+        config.voice.speed
+    );
+    println!("  Quality: {}", config.output.quality);
+
+    let mut fixed_secs = 0.0_f64;
+    let mut auto_count = 0_usize;
+    for s in &scenes {
+        let target = config.output.target_duration;
+        match (&s.frontmatter.duration, target) {
+            (SceneDuration::Fixed(d), _) => fixed_secs += d,
+            (SceneDuration::Percent(p), Some(t)) => fixed_secs += t * p / 100.0,
+            _ => auto_count += 1,
+        }
+    }
+    println!(
+        "\n{} {} ({:.1}s fixed, {} auto)",
+        "Scenes:".bold(),
+        scenes.len(),
+        fixed_secs,
+        auto_count
+    );
+
+    let output_rel = config
+        .output
+        .directory
+        .strip_prefix("./")
+        .unwrap_or(&config.output.directory);
+    let output_dir = project_path.join(output_rel);
+    let outputs = rendered_outputs(&output_dir, probe_video_duration)?;
+    println!("\n{} {}", "Output:".bold(), output_dir.display());
+    if outputs.is_empty() {
+        println!("  No rendered videos yet. Run `vidgen render` to create one.");
+        return Ok(());
+    }
+    println!(
+        "  {}",
+        format!("{:<40} {:>8} {:>10}", "FILE", "DURATION", "SIZE").dimmed()
+    );
+    for output in &outputs {
+        println!("  {}", output_row(output));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rendered_outputs_lists_mp4s_with_duration_and_size() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("my-video-portrait.mp4"), vec![0u8; 2048]).unwrap();
+        std::fs::write(dir.path().join("my-video.mp4"), vec![0u8; 10]).unwrap();
+        std::fs::write(dir.path().join("my-video.srt"), "1\n").unwrap();
+        std::fs::write(dir.path().join("my-video.webm"), vec![0u8; 10]).unwrap();
+        std::fs::write(dir.path().join("broken.mp4"), "").unwrap();
+
+        let probe = |p: &Path| {
+            if p.ends_with("broken.mp4") {
+                Err(VidgenError::Ffmpeg("no streams".into()))
+            } else {
+                Ok(12.5)
+            }
+        };
+        let outputs = rendered_outputs(dir.path(), probe).unwrap();
+        let names: Vec<_> = outputs
+            .iter()
+            .map(|o| o.path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            ["broken.mp4", "my-video-portrait.mp4", "my-video.mp4", "my-video.webm"]
+        );
+        assert_eq!(outputs[1].size_bytes, 2048);
+        assert_eq!(outputs[1].duration_secs, Some(12.5));
+        assert_eq!(outputs[0].duration_secs, None);
+
+        let row = output_row(&outputs[1]);
+        assert!(row.starts_with("my-video-portrait.mp4"));
+        assert!(row.contains("12.5s") && row.contains("2.0 KB"), "{row}");
+        assert!(output_row(&outputs[0]).contains('?'));
+
+        assert!(rendered_outputs(&dir.path().join("missing"), probe)
+            .unwrap()
+            .is_empty());
+    }
+}
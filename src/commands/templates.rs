@@ -17,6 +17,9 @@ const BUILTIN_TEMPLATES: &[&str] = &[
     "kinetic-text",
     "slideshow",
     "caption-overlay",
+    "bar-chart",
+    "code-block",
+    "rich-text",
 ];
 
 /// List available templates and optionally render preview thumbnails.
@@ -68,7 +71,7 @@ pub async fn run(project_path: Option<&Path>, output_dir: Option<&Path>) -> Vidg
             project_path,
         ) {
             Ok(html) => {
-                match capture_single_frame(&html, width, height, mid_frame, total_frames).await {
+                match capture_single_frame(&html, width, height, mid_frame, total_frames, None).await {
                     Ok(png_data) => {
                         let thumb_path = thumb_dir.join(format!("{name}.png"));
                         std::fs::write(&thumb_path, &png_data)?;
@@ -172,6 +175,33 @@ fn build_preview_scene(template_name: &str) -> Scene {
                 serde_json::json!("Caption overlay preview text"),
             );
         }
+        "bar-chart" => {
+            props.insert("heading".into(), serde_json::json!("Bar Chart"));
+            props.insert(
+                "bars".into(),
+                serde_json::json!([
+                    {"label": "Alpha", "value": 40},
+                    {"label": "Beta", "value": 75},
+                    {"label": "Gamma", "value": 55}
+                ]),
+            );
+        }
+        "code-block" => {
+            props.insert("language".into(), serde_json::json!("rust"));
+            props.insert(
+                "code".into(),
+                serde_json::json!("fn main() {\n    println!(\"Hello, vidgen!\");\n}"),
+            );
+        }
+        "rich-text" => {
+            props.insert("heading".into(), serde_json::json!("Release notes"));
+            props.insert(
+                "content".into(),
+                serde_json::json!(
+                    "- **Faster** renders\n- New `rich-text` template\n- *Fewer* bugs"
+                ),
+            );
+        }
         _ => {
             // Unknown template — provide generic title prop
             props.insert("title".into(), serde_json::json!(template_name));
@@ -194,6 +224,14 @@ fn build_preview_scene(template_name: &str) -> Scene {
             voice: None,
             audio: None,
             format_overrides: None,
+            wait_for: None,
+            fps: None,
+            tags: Vec::new(),
+            markdown: false,
+            quality: None,
+            motion_blur: false,
+            min_duration: None,
+            seed: None,
         },
         script: String::new(),
         source_path: PathBuf::from("preview.md"),
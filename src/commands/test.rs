@@ -23,7 +23,7 @@ fn png_diff_percent(a: &[u8], b: &[u8]) -> f64 {
 
 pub async fn run(project_path: &Path, update: bool) -> VidgenResult<()> {
     let cfg = config::load_config(project_path)?;
-    let scenes = scene::load_scenes(project_path)?;
+    let scenes = scene::load_scenes_with_variables(project_path, &cfg.variables)?;
     let mut registry = TemplateRegistry::new()?;
     registry.register_project_templates(project_path)?;
 
@@ -88,9 +88,15 @@ pub async fn run(project_path: &Path, update: bool) -> VidgenResult<()> {
                 Some(project_path),
             )?;
 
-            let png_data =
-                render::browser::capture_single_frame(&html, width, height, frame, total_frames)
-                    .await?;
+            let png_data = render::browser::capture_single_frame(
+                &html,
+                width,
+                height,
+                frame,
+                total_frames,
+                scene_obj.frontmatter.wait_for.as_deref(),
+            )
+            .await?;
 
             let snapshot_file = snapshot_dir.join(format!(
                 "{:02}-{}-{}.png",
@@ -0,0 +1,98 @@
+use crate::config;
+use crate::error::VidgenResult;
+use crate::render::browser::capture_single_frame;
+use crate::render::capture_size;
+use crate::scene::{self, Scene};
+use crate::template::{TemplateRegistry, THEME_PREVIEW};
+use colored::*;
+use std::path::{Path, PathBuf};
+
+/// Synthetic single-frame scene using the swatch template.
+fn theme_preview_scene() -> VidgenResult<Scene> {
+    scene::parse_scene(
+        &format!("---\ntemplate: {THEME_PREVIEW}\nduration: 1\n---\n"),
+        Path::new("theme-preview.md"),
+    )
+}
+
+/// Swatch sheet HTML for the project's theme at the project's dimensions.
+fn theme_preview_html(project_path: &Path, cfg: &config::ProjectConfig) -> VidgenResult<String> {
+    let mut registry = TemplateRegistry::new()?;
+    registry.register_project_templates(project_path)?;
+    let scene = theme_preview_scene()?;
+    let (width, height) = capture_size(cfg, cfg.video.width, cfg.video.height);
+    registry.render_scene_html(&scene, &cfg.theme, width, height, 0, 1, Some(project_path))
+}
+
+/// Render the theme's colors and fonts to a PNG at the project's dimensions.
+pub async fn run(project_path: &Path, output: Option<PathBuf>) -> VidgenResult<()> {
+    let cfg = config::load_config(project_path)?;
+    let (width, height) = capture_size(&cfg, cfg.video.width, cfg.video.height);
+    let html = theme_preview_html(project_path, &cfg)?;
+    let png = capture_single_frame(&html, width, height, 0, 1, None).await?;
+
+    let output_path = output.unwrap_or_else(|| PathBuf::from("theme-preview.png"));
+    std::fs::write(&output_path, &png)?;
+    eprintln!(
+        "{} Saved theme preview to {} ({}x{})",
+        "done:".green().bold(),
+        output_path.display(),
+        width,
+        height
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_preview_html_uses_project_theme_and_dimensions() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("palette");
+        crate::commands::init::run(&project, None, false).unwrap();
+        let mut cfg = config::load_config(&project).unwrap();
+        cfg.video.width = 1080;
+        cfg.video.height = 1920;
+        cfg.theme.primary = "#ff5500".into();
+        cfg.theme.secondary = "#00aaff".into();
+        cfg.theme.font_heading = "Playfair Display".into();
+
+        let html = theme_preview_html(&project, &cfg).unwrap();
+        assert!(html.contains("width: 1080px;"));
+        assert!(html.contains("height: 1920px;"));
+        for swatch in ["#ff5500", "#00aaff", &cfg.theme.background, &cfg.theme.text] {
+            assert!(
+                html.contains(&format!("<code>{swatch}</code>")),
+                "missing {swatch}"
+            );
+        }
+        assert!(html.contains("Heading: Playfair Display"));
+        assert!(html.contains(&format!("Body: {}", cfg.theme.font_body)));
+    }
+
+    #[tokio::test]
+    async fn test_theme_preview_png_matches_project_dimensions() {
+        // Needs a local Chromium; skip when none is installed
+        let Ok(session) = crate::render::browser::BrowserSession::launch(320, 180).await else {
+            eprintln!("skipping: no Chromium available");
+            return;
+        };
+        session.close();
+
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("palette");
+        crate::commands::init::run(&project, None, false).unwrap();
+        let update = config::ConfigUpdate {
+            width: Some(360),
+            height: Some(640),
+            ..Default::default()
+        };
+        config::update_config(&project, &update).unwrap();
+
+        let output = dir.path().join("swatches.png");
+        run(&project, Some(output.clone())).await.unwrap();
+        assert_eq!(image::image_dimensions(&output).unwrap(), (360, 640));
+    }
+}
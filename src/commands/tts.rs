@@ -0,0 +1,188 @@
+use crate::config;
+use crate::error::{VidgenError, VidgenResult};
+use crate::render::{synthesize_narration, SceneNarration};
+use crate::scene::{self, audio_file_name};
+use colored::*;
+use std::path::{Path, PathBuf};
+
+/// A scene's narration written by `vidgen tts`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneAudio {
+    /// 0-based scene index
+    pub index: usize,
+    pub path: PathBuf,
+    pub duration_secs: f64,
+}
+
+/// Run only the TTS synthesis pass of a render and copy each scene's narration
+/// into `output_dir`. `scene_filter` holds 0-based indices. Scenes without a
+/// script (or recorded voiceover) and scenes whose synthesis failed are skipped.
+pub fn synthesize_scene_audio(
+    project_path: &Path,
+    output_dir: &Path,
+    scene_filter: Option<&[usize]>,
+    force_tts: bool,
+    dry_tts: bool,
+) -> VidgenResult<Vec<SceneAudio>> {
+    let cfg = config::load_config(project_path)?;
+    let scenes = scene::load_scenes_with_variables(project_path, &cfg.variables)?;
+    let count = scenes.len();
+    if let Some(&index) = scene_filter.and_then(|f| f.iter().find(|&&i| i >= count)) {
+        return Err(VidgenError::SceneIndexOutOfRange { index, count });
+    }
+
+    let (indices, selected): (Vec<usize>, Vec<scene::Scene>) = scenes
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| scene_filter.map_or(true, |f| f.contains(i)))
+        .unzip();
+
+    let temp_dir = tempfile::tempdir()?;
+    let SceneNarration {
+        audio_paths,
+        durations,
+        ..
+    } = synthesize_narration(
+        &selected,
+        &cfg,
+        project_path,
+        temp_dir.path(),
+        dry_tts,
+        force_tts,
+        "tts:",
+    );
+
+    let mut written = Vec::new();
+    for (i, scene) in selected.iter().enumerate() {
+        let index = indices[i];
+        let (Some(audio_path), Some(duration_secs)) = (&audio_paths[i], durations[i]) else {
+            if scene.script.trim().is_empty() {
+                eprintln!("  Scene {}: no script, skipping", index + 1);
+            }
+            continue;
+        };
+        // Recorded voiceovers keep their own container (e.g. .mp3)
+        let ext = audio_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("wav");
+        let dest = output_dir.join(audio_file_name(index, &scene.source_path, ext));
+        std::fs::create_dir_all(output_dir)?;
+        std::fs::copy(audio_path, &dest)?;
+        written.push(SceneAudio {
+            index,
+            path: dest,
+            duration_secs,
+        });
+    }
+    Ok(written)
+}
+
+/// CLI entry point for `vidgen tts`.
+pub fn run(
+    project_path: &Path,
+    output: Option<PathBuf>,
+    scenes: Option<Vec<usize>>,
+    force_tts: bool,
+) -> VidgenResult<()> {
+    if !project_path.exists() {
+        return Err(VidgenError::ProjectNotFound(project_path.to_path_buf()));
+    }
+    let cfg = config::load_config(project_path)?;
+
+    // Load .env from project directory (if present) so keys like ELEVEN_API_KEY are available
+    let _ = dotenvy::from_path(project_path.join(".env"));
+
+    let output_dir = output.unwrap_or_else(|| {
+        project_path
+            .join(cfg.output.directory.trim_start_matches("./"))
+            .join("audio")
+    });
+    let dry_tts = std::env::var("VIDGEN_DRY_TTS").is_ok();
+    let written = synthesize_scene_audio(
+        project_path,
+        &output_dir,
+        scenes.as_deref(),
+        force_tts,
+        dry_tts,
+    )?;
+
+    for audio in &written {
+        eprintln!(
+            "  Scene {}: {:.1}s -> {}",
+            audio.index + 1,
+            audio.duration_secs,
+            audio.path.display()
+        );
+    }
+    eprintln!(
+        "{} Wrote {} audio file(s) to {}",
+        "done:".green().bold(),
+        written.len(),
+        output_dir.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audio_file_name() {
+        assert_eq!(
+            audio_file_name(0, Path::new("scenes/01-intro.md"), "wav"),
+            "01-intro.wav"
+        );
+        assert_eq!(
+            audio_file_name(11, Path::new("scenes/02-body/03-demo.md"), "mp3"),
+            "12-demo.mp3"
+        );
+    }
+
+    #[test]
+    fn test_two_scene_project_writes_two_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        std::fs::write(project.join("project.toml"), "[project]\nname = \"T\"\n").unwrap();
+        let scenes_dir = project.join("scenes");
+        std::fs::create_dir_all(&scenes_dir).unwrap();
+        for (file, script) in [
+            ("01-intro.md", "Welcome to the standalone narration test."),
+            ("02-outro.md", "Thanks for listening, see you next time."),
+            ("03-silent.md", ""),
+        ] {
+            std::fs::write(
+                scenes_dir.join(file),
+                format!("---\ntemplate: title-card\nprops:\n  title: X\n---\n\n{script}\n"),
+            )
+            .unwrap();
+        }
+
+        // Use the configured engine when it is installed, silent placeholders otherwise
+        let cfg = config::load_config(project).unwrap();
+        let dry_tts = crate::tts::create_engine(&cfg.voice).is_err();
+        let out = project.join("out");
+        let written = synthesize_scene_audio(project, &out, None, false, dry_tts).unwrap();
+
+        let names: Vec<String> = written
+            .iter()
+            .map(|a| a.path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["01-intro.wav", "02-outro.wav"]);
+        for audio in &written {
+            assert!(audio.path.exists());
+            assert!(audio.duration_secs > 0.5 && audio.duration_secs < 30.0);
+        }
+
+        let only_second =
+            synthesize_scene_audio(project, &out, Some(&[1]), false, dry_tts).unwrap();
+        assert_eq!(only_second.len(), 1);
+        assert_eq!(only_second[0].index, 1);
+
+        let Err(err) = synthesize_scene_audio(project, &out, Some(&[5]), false, dry_tts) else {
+            panic!("expected out of range error");
+        };
+        assert!(err.to_string().contains("out of range"));
+    }
+}
@@ -1,10 +1,15 @@
 use crate::config;
-use crate::error::VidgenResult;
+use crate::error::{VidgenError, VidgenResult};
+use crate::palette::contrast_ratio;
+use crate::render::encoder::TransitionType;
 use crate::scene::{self, Scene, SceneDuration};
 use crate::template::TemplateRegistry;
 use colored::*;
-use std::path::Path;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 
+/// Findings of `vidgen validate`; printed as JSON with `--json`.
+#[derive(Debug, Serialize)]
 struct ValidationResult {
     errors: Vec<String>,
     warnings: Vec<String>,
@@ -27,14 +32,65 @@ impl ValidationResult {
     }
 }
 
-pub fn run(project_path: &Path) -> VidgenResult<()> {
+pub fn run(project_path: &Path, fix: bool, json: bool) -> VidgenResult<()> {
     let project_name = project_path
         .file_name()
         .and_then(|s| s.to_str())
         .unwrap_or("unknown");
 
+    if fix {
+        let changes = fix_scenes(project_path)?;
+        if changes.is_empty() {
+            eprintln!("{} scenes already normalized", "fix:".green().bold());
+        }
+        for change in &changes {
+            eprintln!("{} {}", "fix:".green().bold(), change);
+        }
+    }
+
     eprintln!("Validating \"{}\"...", project_name);
 
+    let result = validate_project(project_path);
+
+    // Summary
+    let errors = result.errors.len();
+    let warnings = result.warnings.len();
+    eprintln!();
+    if errors == 0 && warnings == 0 {
+        eprintln!("  {}: no issues found", "Result".green().bold());
+    } else {
+        eprintln!(
+            "  {}: {} error(s), {} warning(s)",
+            "Result".cyan().bold(),
+            if errors > 0 {
+                format!("{errors}").red().bold().to_string()
+            } else {
+                "0".to_string()
+            },
+            if warnings > 0 {
+                format!("{warnings}").yellow().bold().to_string()
+            } else {
+                "0".to_string()
+            },
+        );
+    }
+
+    if json {
+        let json = serde_json::to_string_pretty(&result)
+            .map_err(|e| VidgenError::Other(format!("Failed to serialize report: {e}")))?;
+        println!("{json}");
+    }
+
+    if errors > 0 {
+        return Err(VidgenError::Other(format!(
+            "Validation failed with {errors} error(s)"
+        )));
+    }
+    Ok(())
+}
+
+/// Run every check against the project, printing each finding as it goes.
+fn validate_project(project_path: &Path) -> ValidationResult {
     let mut result = ValidationResult::new();
 
     // 1. Config loads
@@ -99,30 +155,72 @@ pub fn run(project_path: &Path) -> VidgenResult<()> {
         check_contrast(&cfg.theme, &mut result);
     }
 
-    // Summary
-    let errors = result.errors.len();
-    let warnings = result.warnings.len();
-    eprintln!();
-    if errors == 0 && warnings == 0 {
-        eprintln!("  {}: no issues found", "Result".green().bold());
-    } else {
-        eprintln!(
-            "  {}: {} error(s), {} warning(s)",
-            "Result".cyan().bold(),
-            if errors > 0 {
-                format!("{errors}").red().bold().to_string()
-            } else {
-                "0".to_string()
-            },
-            if warnings > 0 {
-                format!("{warnings}").yellow().bold().to_string()
-            } else {
-                "0".to_string()
-            },
-        );
+    // 9. Format override keys
+    if let (Some(ref cfg), Some(ref scenes)) = (&config, &scenes) {
+        check_format_overrides(cfg, scenes, &mut result);
     }
 
-    Ok(())
+    // 10. Scenes too short for their template's animation
+    if let (Some(ref cfg), Some(ref scenes)) = (&config, &scenes) {
+        check_animation_durations(cfg, scenes, &mut result);
+    }
+
+    // 11. Transition names
+    if let (Some(ref cfg), Some(ref scenes)) = (&config, &scenes) {
+        check_transitions(cfg, scenes, &mut result);
+    }
+
+    result
+}
+
+/// Normalize scene files in place for `validate --fix`: rewrite frontmatter in
+/// canonical field order (which also normalizes durations like `5s` to `5.0`),
+/// then renumber files contiguously. Returns a description of each change.
+///
+/// Files whose frontmatter has comments are left as they are, since rewriting
+/// would drop them.
+fn fix_scenes(project_path: &Path) -> VidgenResult<Vec<String>> {
+    let mut changes = Vec::new();
+    let scenes_dir = project_path.join("scenes");
+    let label = |path: &Path| {
+        path.strip_prefix(&scenes_dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+
+    for path in scene::scene_file_paths(project_path)? {
+        let content = std::fs::read_to_string(&path)?;
+        let scene = scene::parse_scene(&content, &path)?;
+        let canonical = scene::format_scene(&scene, &path)?;
+        if canonical == content {
+            continue;
+        }
+        if frontmatter_has_comments(&content) {
+            changes.push(format!("{}: skipped, frontmatter has comments", label(&path)));
+            continue;
+        }
+        std::fs::write(&path, canonical)?;
+        changes.push(format!("{}: rewrote frontmatter", label(&path)));
+    }
+
+    for (old, new) in crate::commands::scenes::normalize_scene_numbering(project_path)? {
+        changes.push(format!("{} -> {}", label(&old), label(&new)));
+    }
+    Ok(changes)
+}
+
+/// Whether a scene's YAML frontmatter contains a `#` comment (or might: a ` #`
+/// inside a quoted string counts too, which only means the file is left alone).
+fn frontmatter_has_comments(content: &str) -> bool {
+    content
+        .trim_start()
+        .trim_start_matches("---")
+        .split("\n---")
+        .next()
+        .unwrap_or_default()
+        .lines()
+        .any(|line| line.trim_start().starts_with('#') || line.contains(" #"))
 }
 
 fn check_templates(project_path: &Path, scenes: &[Scene], result: &mut ValidationResult) {
@@ -165,6 +263,20 @@ fn check_templates(project_path: &Path, scenes: &[Scene], result: &mut Validatio
                 template_name, scene_name
             ));
             all_found = false;
+            continue;
+        }
+        if let Err(e) = registry.validate_scene_props(scene) {
+            eprintln!("  {} {}", "\u{2717}".red(), e);
+            result.error(e.to_string());
+        }
+        for warning in registry.prop_type_warnings(scene) {
+            let scene_name = scene
+                .source_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("unknown");
+            eprintln!("  {} {} (scene {})", "\u{26A0}".yellow(), warning, scene_name);
+            result.warning(format!("{warning} (scene {scene_name})"));
         }
     }
     if all_found {
@@ -172,6 +284,40 @@ fn check_templates(project_path: &Path, scenes: &[Scene], result: &mut Validatio
     }
 }
 
+fn check_format_overrides(
+    config: &config::ProjectConfig,
+    scenes: &[Scene],
+    result: &mut ValidationResult,
+) {
+    let mut all_known = true;
+    for scene in scenes {
+        let scene_name = scene
+            .source_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown");
+        for key in crate::render::unknown_format_override_keys(scene, config) {
+            eprintln!(
+                "  {} Unknown format override: \"{}\" (scene {})",
+                "\u{2717}".red(),
+                key,
+                scene_name
+            );
+            result.error(format!(
+                "Unknown format override \"{}\" (scene {}); not in video.formats",
+                key, scene_name
+            ));
+            all_known = false;
+        }
+    }
+    if all_known {
+        eprintln!(
+            "  {} All format overrides match configured formats",
+            "\u{2713}".green()
+        );
+    }
+}
+
 fn check_asset_references(project_path: &Path, scenes: &[Scene], result: &mut ValidationResult) {
     let mut all_found = true;
     for scene in scenes {
@@ -186,48 +332,36 @@ fn check_asset_references(project_path: &Path, scenes: &[Scene], result: &mut Va
             check_asset_value(project_path, value, scene_name, key, result, &mut all_found);
         }
 
-        // Check video_source
-        if let Some(ref src) = scene.frontmatter.video_source {
-            if let Some(suffix) = src.strip_prefix("@assets/") {
-                let path = project_path.join("assets").join(suffix);
-                if !path.exists() {
-                    eprintln!(
-                        "  {} Asset not found: {} (scene {}, video_source)",
-                        "\u{2717}".red(),
-                        path.display(),
-                        scene_name
-                    );
-                    result.error(format!(
-                        "Asset not found: {} (scene {}, video_source)",
-                        path.display(),
-                        scene_name
-                    ));
-                    all_found = false;
-                }
-            }
-        }
-
-        // Check background image
-        if let Some(ref bg) = scene.frontmatter.background {
-            if let Some(ref img) = bg.image {
-                if let Some(suffix) = img.strip_prefix("@assets/") {
-                    let path = project_path.join("assets").join(suffix);
-                    if !path.exists() {
-                        eprintln!(
-                            "  {} Asset not found: {} (scene {}, background.image)",
-                            "\u{2717}".red(),
-                            path.display(),
-                            scene_name
-                        );
-                        result.error(format!(
-                            "Asset not found: {} (scene {}, background.image)",
-                            path.display(),
-                            scene_name
-                        ));
-                        all_found = false;
-                    }
-                }
-            }
+        // Check video_source and background image paths
+        let paths = [
+            ("video_source", scene.frontmatter.video_source.as_ref()),
+            (
+                "background.image",
+                scene
+                    .frontmatter
+                    .background
+                    .as_ref()
+                    .and_then(|bg| bg.image.as_ref()),
+            ),
+        ];
+        for (field, raw) in paths {
+            let Some(path) = raw.and_then(|raw| missing_asset(project_path, raw)) else {
+                continue;
+            };
+            eprintln!(
+                "  {} Asset not found: {} (scene {}, {})",
+                "\u{2717}".red(),
+                path.display(),
+                scene_name,
+                field
+            );
+            result.error(format!(
+                "Asset not found: {} (scene {}, {})",
+                path.display(),
+                scene_name,
+                field
+            ));
+            all_found = false;
         }
     }
     if all_found {
@@ -235,6 +369,17 @@ fn check_asset_references(project_path: &Path, scenes: &[Scene], result: &mut Va
     }
 }
 
+/// The resolved path of a local asset reference (`@assets/...` or relative to
+/// the project) that doesn't exist. URLs are downloaded at render time and
+/// aren't checked.
+fn missing_asset(project_path: &Path, raw: &str) -> Option<PathBuf> {
+    if scene::is_url(raw) {
+        return None;
+    }
+    let path = scene::resolve_asset_path(raw, project_path);
+    (!path.exists()).then_some(path)
+}
+
 fn check_asset_value(
     project_path: &Path,
     value: &serde_json::Value,
@@ -291,13 +436,19 @@ fn check_background_music(
     config: &config::ProjectConfig,
     result: &mut ValidationResult,
 ) {
-    if let Some(ref bg) = config.audio.background {
-        let resolved = scene::resolve_asset_path(&bg.file, project_path);
+    let music = config
+        .audio
+        .background
+        .as_ref()
+        .map(|bg| bg.file.as_str())
+        .or(config.audio.music.as_deref());
+    if let Some(file) = music {
+        let resolved = scene::resolve_asset_path(file, project_path);
         if resolved.exists() {
             let filename = resolved
                 .file_name()
                 .and_then(|s| s.to_str())
-                .unwrap_or(&bg.file);
+                .unwrap_or(file);
             eprintln!("  {} Background music: {}", "\u{2713}".green(), filename);
         } else {
             eprintln!(
@@ -346,6 +497,128 @@ fn check_duration_warnings(scenes: &[Scene], result: &mut ValidationResult) {
     }
 }
 
+/// A scene shorter than its template's recommended minimum.
+#[derive(Debug, PartialEq)]
+struct ShortAnimatedScene {
+    index: usize,
+    duration: f64,
+    min_duration: f64,
+}
+
+/// Scenes whose (estimated) length is below the template's `min_duration` from
+/// the template defaults. `auto` scenes are estimated from the script's word
+/// count, as with `--dry-tts`. Recorded narration and `match-music` lengths
+/// aren't known without probing, so those scenes are skipped.
+fn short_animated_scenes(
+    config: &config::ProjectConfig,
+    scenes: &[Scene],
+) -> Vec<ShortAnimatedScene> {
+    let voice = &config.voice;
+    scenes
+        .iter()
+        .enumerate()
+        .filter_map(|(index, scene)| {
+            let min_duration =
+                crate::template::template_defaults(&scene.frontmatter.template)?.min_duration?;
+            let fm = &scene.frontmatter;
+            let recorded = fm.audio.as_ref().is_some_and(|a| a.voice.is_some());
+            if fm.duration.is_match_music() || (fm.duration.is_auto() && recorded) {
+                return None;
+            }
+            let script = scene.script.trim();
+            let narration = (!script.is_empty()).then(|| {
+                let speed = fm
+                    .voice
+                    .as_ref()
+                    .and_then(|v| v.speed)
+                    .unwrap_or(voice.speed);
+                crate::tts::pauses::estimate_script_duration(script, speed)
+            });
+            let duration = fm.duration.resolve(
+                narration,
+                voice.padding_before,
+                voice.padding_after,
+                voice.auto_fallback_duration,
+                fm.min_duration,
+            );
+            (duration < min_duration).then_some(ShortAnimatedScene {
+                index,
+                duration,
+                min_duration,
+            })
+        })
+        .collect()
+}
+
+fn check_animation_durations(
+    config: &config::ProjectConfig,
+    scenes: &[Scene],
+    result: &mut ValidationResult,
+) {
+    for short in short_animated_scenes(config, scenes) {
+        let scene = &scenes[short.index];
+        let scene_name = scene
+            .source_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown");
+        let message = format!(
+            "Scene {:02} ({}): {} animation needs about {:.1}s but the scene lasts {:.1}s (set duration or min_duration)",
+            short.index + 1,
+            scene_name,
+            scene.frontmatter.template,
+            short.min_duration,
+            short.duration
+        );
+        eprintln!("  {} {}", "\u{26A0}".yellow(), message);
+        result.warning(message);
+    }
+}
+
+/// Warn about transition names that `TransitionType` doesn't know; a render
+/// falls back to a fade for them.
+fn check_transitions(
+    config: &config::ProjectConfig,
+    scenes: &[Scene],
+    result: &mut ValidationResult,
+) {
+    let mut unknown = Vec::new();
+    if let Some(ref name) = config.video.default_transition {
+        if TransitionType::parse(name).is_none() {
+            unknown.push(format!(
+                "Unknown transition \"{name}\" (video.default_transition); a fade is used instead"
+            ));
+        }
+    }
+    for scene in scenes {
+        let scene_name = scene
+            .source_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown");
+        let fm = &scene.frontmatter;
+        for (field, name) in [
+            ("transition_in", &fm.transition_in),
+            ("transition_out", &fm.transition_out),
+        ] {
+            if let Some(name) = name {
+                if TransitionType::parse(name).is_none() {
+                    unknown.push(format!(
+                        "Unknown transition \"{name}\" (scene {scene_name}, {field}); a fade is used instead"
+                    ));
+                }
+            }
+        }
+    }
+    if unknown.is_empty() {
+        eprintln!("  {} All transitions known", "\u{2713}".green());
+    }
+    for message in unknown {
+        eprintln!("  {} {}", "\u{26A0}".yellow(), message);
+        result.warning(message);
+    }
+}
+
 fn check_fonts(project_path: &Path, scenes: &[Scene], result: &mut ValidationResult) {
     let mut checked_fonts: std::collections::HashSet<String> = std::collections::HashSet::new();
     let mut all_found = true;
@@ -500,28 +773,164 @@ fn parse_hex_color(hex: &str) -> (f64, f64, f64) {
     (r, g, b)
 }
 
-fn relative_luminance(r: f64, g: f64, b: f64) -> f64 {
-    let r = if r <= 0.03928 {
-        r / 12.92
-    } else {
-        ((r + 0.055) / 1.055).powf(2.4)
-    };
-    let g = if g <= 0.03928 {
-        g / 12.92
-    } else {
-        ((g + 0.055) / 1.055).powf(2.4)
-    };
-    let b = if b <= 0.03928 {
-        b / 12.92
-    } else {
-        ((b + 0.055) / 1.055).powf(2.4)
-    };
-    0.2126 * r + 0.7152 * g + 0.0722 * b
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene(frontmatter: &str, script: &str) -> Scene {
+        let content = format!("---\n{frontmatter}\n---\n\n{script}\n");
+        scene::parse_scene(&content, Path::new("scenes/01-test.md")).unwrap()
+    }
+
+    fn project(scenes: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("project.toml"),
+            "[project]\nname = \"Test\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(dir.path().join("scenes")).unwrap();
+        for (name, content) in scenes {
+            std::fs::write(dir.path().join("scenes").join(name), content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_validate_clean_project() {
+        let dir = project(&[
+            (
+                "01-title-card.md",
+                "---\ntemplate: title-card\nduration: 3\ntransition_out: wipe\nprops:\n  title: Hello\n---\n",
+            ),
+            (
+                "02-quote-card.md",
+                "---\ntemplate: quote-card\nduration: 4\nprops:\n  quote: Less is more.\n---\n",
+            ),
+        ]);
+        let result = validate_project(dir.path());
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json, serde_json::json!({"errors": [], "warnings": []}));
+    }
+
+    #[test]
+    fn test_validate_reports_errors_and_warnings() {
+        let dir = project(&[
+            (
+                "01-missing.md",
+                "---\ntemplate: no-such-template\nduration: 3\n---\n",
+            ),
+            (
+                "02-title-card.md",
+                "---\ntemplate: title-card\nduration: 3\ntransition_in: spin\nprops:\n  title: Hi\nbackground:\n  image: \"@assets/missing.png\"\n---\n",
+            ),
+            (
+                "03-quote-card.md",
+                "---\ntemplate: quote-card\nduration: 4\n---\n",
+            ),
+        ]);
+        let result = validate_project(dir.path());
+        assert_eq!(result.errors.len(), 3, "{:?}", result.errors);
+        assert!(result.errors[0].contains("Template \"no-such-template\" not found"));
+        assert!(result.errors[1].contains("missing required prop(s): quote"));
+        assert!(result.errors[2].contains("missing.png (scene 02-title-card, background.image)"));
+        assert_eq!(
+            result.warnings,
+            ["Unknown transition \"spin\" (scene 02-title-card, transition_in); a fade is used instead"]
+        );
+
+        assert!(run(dir.path(), false, true).is_err());
+    }
+
+    #[test]
+    fn test_short_animated_scenes() {
+        let config: config::ProjectConfig =
+            toml::from_str("[project]\nname = \"Test\"\n").unwrap();
+        let scenes = vec![
+            // Fixed 1s is well below the slideshow minimum
+            scene("template: slideshow\nduration: 1", ""),
+            // Long fixed duration is fine
+            scene("template: slideshow\nduration: 8", ""),
+            // A short auto scene is estimated from its script
+            scene("template: kinetic-text", "Hi."),
+            // min_duration raises the auto scene above the template minimum
+            scene("template: kinetic-text\nmin_duration: 5", "Hi."),
+            // Plenty of narration to cover the animation
+            scene(
+                "template: kinetic-text",
+                "This scene has a long script that easily takes more than a few seconds to read aloud at the default speed.",
+            ),
+            // Templates without a recommended minimum are never flagged
+            scene("template: title-card\nduration: 0.5", ""),
+        ];
+
+        let short = short_animated_scenes(&config, &scenes);
+        let indices: Vec<_> = short.iter().map(|s| s.index).collect();
+        assert_eq!(indices, [0, 2]);
+        assert_eq!(short[0].duration, 1.0);
+        assert_eq!(short[0].min_duration, 4.0);
+        assert!(short[1].duration < short[1].min_duration);
+
+        let mut result = ValidationResult::new();
+        check_animation_durations(&config, &scenes, &mut result);
+        assert_eq!(result.warnings.len(), 2);
+        assert!(result.warnings[0].contains("slideshow animation needs about 4.0s"));
+    }
+
+    #[test]
+    fn test_fix_scenes() {
+        let dir = tempfile::tempdir().unwrap();
+        let scenes_dir = dir.path().join("scenes");
+        std::fs::create_dir_all(&scenes_dir).unwrap();
+        std::fs::write(
+            scenes_dir.join("01-title-card.md"),
+            "---\ntemplate: title-card\nduration: 3.0\n---\n\nHello.\n",
+        )
+        .unwrap();
+        // Keys out of order and a "5s" duration
+        std::fs::write(
+            scenes_dir.join("03-content-text.md"),
+            "---\nduration: 5s\ntemplate: content-text\n---\nBody.",
+        )
+        .unwrap();
+        std::fs::write(
+            scenes_dir.join("07-cta-card.md"),
+            "---\ntemplate: cta-card\nduration: 4 # hold the end card\n---\nBye.",
+        )
+        .unwrap();
+
+        let changes = fix_scenes(dir.path()).unwrap();
+        assert_eq!(
+            changes,
+            [
+                "03-content-text.md: rewrote frontmatter",
+                "07-cta-card.md: skipped, frontmatter has comments",
+                "03-content-text.md -> 02-content-text.md",
+                "07-cta-card.md -> 03-cta-card.md",
+            ]
+        );
 
-fn contrast_ratio(c1: (f64, f64, f64), c2: (f64, f64, f64)) -> f64 {
-    let l1 = relative_luminance(c1.0, c1.1, c1.2);
-    let l2 = relative_luminance(c2.0, c2.1, c2.2);
-    let (lighter, darker) = if l1 > l2 { (l1, l2) } else { (l2, l1) };
-    (lighter + 0.05) / (darker + 0.05)
+        let names: Vec<_> = scene::scene_file_paths(dir.path())
+            .unwrap()
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["01-title-card.md", "02-content-text.md", "03-cta-card.md"]);
+        assert_eq!(
+            std::fs::read_to_string(scenes_dir.join("02-content-text.md")).unwrap(),
+            "---\ntemplate: content-text\nduration: 5.0\n---\n\nBody.\n"
+        );
+        assert!(std::fs::read_to_string(scenes_dir.join("03-cta-card.md"))
+            .unwrap()
+            .contains("# hold the end card"));
+
+        // A second run only reports the file it can't rewrite
+        assert_eq!(
+            fix_scenes(dir.path()).unwrap(),
+            ["03-cta-card.md: skipped, frontmatter has comments"]
+        );
+    }
 }
@@ -1,12 +1,52 @@
 use crate::error::{VidgenError, VidgenResult};
 use colored::*;
 use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
-use std::path::Path;
+use notify::RecursiveMode;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 use std::time::Duration;
 
 /// Relevant file extensions for triggering rebuilds.
-const WATCH_EXTENSIONS: &[&str] = &["md", "html", "css", "toml"];
+const WATCH_EXTENSIONS: &[&str] = &["md", "html", "hbs", "css"];
+
+/// Project directories whose files trigger a rebuild: scenes, custom
+/// templates and partials, and stylesheets.
+const WATCH_DIRS: &[&str] = &["scenes", "templates", "styles"];
+
+/// Paths to register with the watcher. The project root is watched without
+/// recursion so `project.toml` is picked up even when an editor saves it by
+/// replacing the file; directories that don't exist yet are skipped.
+fn watch_targets(project_path: &Path) -> Vec<(PathBuf, RecursiveMode)> {
+    let mut targets = vec![(project_path.to_path_buf(), RecursiveMode::NonRecursive)];
+    for dir in WATCH_DIRS {
+        let path = project_path.join(dir);
+        if path.is_dir() {
+            targets.push((path, RecursiveMode::Recursive));
+        }
+    }
+    targets
+}
+
+/// Whether a changed path should trigger a rebuild: `project.toml`, or a
+/// scene, template or stylesheet under one of [`WATCH_DIRS`].
+fn is_watched_change(path: &Path, project_path: &Path) -> bool {
+    if path == project_path.join("project.toml") {
+        return true;
+    }
+    // Skip hidden files and temp files
+    if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+        if name.starts_with('.') || name.starts_with("__tmp_") {
+            return false;
+        }
+    }
+    WATCH_DIRS
+        .iter()
+        .any(|dir| path.starts_with(project_path.join(dir)))
+        && path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| WATCH_EXTENSIONS.contains(&ext))
+}
 
 /// Run the watch command: monitor project files and auto-preview or re-render on change.
 pub async fn run(
@@ -37,14 +77,19 @@ pub async fn run(
     let mut debouncer = new_debouncer(Duration::from_millis(500), tx)
         .map_err(|e| VidgenError::Other(format!("Failed to create file watcher: {e}")))?;
 
-    debouncer
-        .watcher()
-        .watch(project_path.as_ref(), notify::RecursiveMode::Recursive)
-        .map_err(|e| VidgenError::Other(format!("Failed to watch directory: {e}")))?;
+    for (path, mode) in watch_targets(&project_path) {
+        debouncer
+            .watcher()
+            .watch(&path, mode)
+            .map_err(|e| VidgenError::Other(format!("Failed to watch directory: {e}")))?;
+    }
 
     // Keep watcher alive
     let _debouncer = debouncer;
 
+    // Config and template changes can affect any scene; re-preview the last one
+    let mut last_scene = fixed_scene.unwrap_or(0);
+
     loop {
         match rx.recv() {
             Ok(Ok(events)) => {
@@ -52,23 +97,7 @@ pub async fn run(
                 let relevant: Vec<_> = events
                     .iter()
                     .filter(|e| e.kind == DebouncedEventKind::Any)
-                    .filter(|e| {
-                        let path = &e.path;
-                        // Skip output directory
-                        if path.starts_with(project_path.join("output")) {
-                            return false;
-                        }
-                        // Skip hidden files and temp files
-                        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                            if name.starts_with('.') || name.starts_with("__tmp_") {
-                                return false;
-                            }
-                        }
-                        // Check extension
-                        path.extension()
-                            .and_then(|ext| ext.to_str())
-                            .is_some_and(|ext| WATCH_EXTENSIONS.contains(&ext))
-                    })
+                    .filter(|e| is_watched_change(&e.path, &project_path))
                     .collect();
 
                 if relevant.is_empty() {
@@ -93,9 +122,7 @@ pub async fn run(
 
                 if full_render {
                     // Full render mode
-                    match crate::commands::render::run(&project_path, None, None, None, None, false, false, None, false, false, false, None, None)
-                        .await
-                    {
+                    match crate::commands::render::run(&project_path, &Default::default()).await {
                         Ok(()) => {}
                         Err(e) => {
                             eprintln!("{} Render failed: {}", "watch:".red().bold(), e);
@@ -104,7 +131,9 @@ pub async fn run(
                 } else {
                     // Preview mode: detect which scene changed, or use fixed_scene
                     let scene_index = fixed_scene
-                        .unwrap_or_else(|| detect_changed_scene(&relevant, &project_path));
+                        .or_else(|| detect_changed_scene(&relevant, &project_path))
+                        .unwrap_or(last_scene);
+                    last_scene = scene_index;
 
                     let output_dir = project_path.join("output");
                     std::fs::create_dir_all(&output_dir).ok();
@@ -117,6 +146,11 @@ pub async fn run(
                         Some(output_path),
                         false,
                         false,
+                        false,
+                        false,
+                        None,
+                        None,
+                        8,
                     )
                     .await
                     {
@@ -141,11 +175,11 @@ pub async fn run(
 }
 
 /// Try to detect which scene was changed based on the file paths.
-/// Returns the 0-based scene index, defaulting to 0.
+/// Returns the 0-based scene index, or `None` when no scene file changed.
 fn detect_changed_scene(
     events: &[&notify_debouncer_mini::DebouncedEvent],
     project_path: &Path,
-) -> usize {
+) -> Option<usize> {
     let scenes_dir = project_path.join("scenes");
 
     for event in events {
@@ -155,12 +189,62 @@ fn detect_changed_scene(
                 if let Some(num_str) = name.split('-').next() {
                     if let Ok(num) = num_str.parse::<usize>() {
                         // Scene files are 1-indexed (01-xxx.md), convert to 0-based
-                        return num.saturating_sub(1);
+                        return Some(num.saturating_sub(1));
                     }
                 }
             }
         }
     }
 
-    0
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch_targets_include_config_and_templates() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("scenes")).unwrap();
+        std::fs::create_dir_all(root.join("templates/components")).unwrap();
+
+        let targets = watch_targets(root);
+        assert_eq!(
+            targets,
+            [
+                (root.to_path_buf(), RecursiveMode::NonRecursive),
+                (root.join("scenes"), RecursiveMode::Recursive),
+                (root.join("templates"), RecursiveMode::Recursive),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_watched_change() {
+        let root = Path::new("/project");
+        for path in [
+            "project.toml",
+            "scenes/01-intro.md",
+            "scenes/02-body/01-problem.md",
+            "templates/components/my-card.html",
+            "templates/partials/footer.hbs",
+            "styles/theme.css",
+        ] {
+            assert!(is_watched_change(&root.join(path), root), "{path}");
+        }
+        for path in [
+            "output/preview-scene-0.png",
+            "output/notes.md",
+            ".vidgen/cache/frame.html",
+            "assets/images/photo.jpg",
+            "README.md",
+            "scenes/.01-intro.md.swp",
+            "templates/components/__tmp_card.html",
+            "scenes/notes.txt",
+        ] {
+            assert!(!is_watched_change(&root.join(path), root), "{path}");
+        }
+    }
 }
@@ -17,13 +17,128 @@ pub struct ProjectConfig {
     pub output: OutputConfig,
     #[serde(default)]
     pub audio: AudioConfig,
+    #[serde(default)]
+    pub render: RenderConfig,
+    #[serde(default)]
+    pub quality: QualityConfig,
+    /// Values substituted for `{{name}}` placeholders in scene scripts
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, String>,
 }
 
-/// Project-wide audio configuration (background music, etc.)
+/// Render-time safety limits and page settings (`[render]`).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RenderConfig {
+    /// Largest width or height (in pixels) any output format may render at (default: 7680)
+    #[serde(default = "default_max_dimension")]
+    pub max_dimension: u32,
+    /// Scale oversized formats down to fit `max_dimension` (keeping aspect) instead of failing
+    #[serde(default)]
+    pub auto_downscale: bool,
+    /// Embed `assets/fonts/NotoColorEmoji.ttf` as a fallback font so emoji and
+    /// flags render without the Twemoji CDN
+    #[serde(default)]
+    pub emoji_fallback: bool,
+    /// Seconds one frame capture (CSS update + screenshot) may take before it
+    /// counts as failed (default: 30)
+    #[serde(default = "default_capture_timeout")]
+    pub capture_timeout: f64,
+    /// Times a failed or timed-out frame capture is retried, reloading the
+    /// scene page first, before the scene fails (default: 2)
+    #[serde(default = "default_capture_retries")]
+    pub capture_retries: u32,
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        Self {
+            max_dimension: default_max_dimension(),
+            auto_downscale: false,
+            emoji_fallback: false,
+            capture_timeout: default_capture_timeout(),
+            capture_retries: default_capture_retries(),
+        }
+    }
+}
+
+fn default_max_dimension() -> u32 {
+    7680
+}
+
+fn default_capture_timeout() -> f64 {
+    30.0
+}
+
+fn default_capture_retries() -> u32 {
+    2
+}
+
+/// Project-defined quality presets (`[quality.presets.<name>]`), selectable by
+/// name like the built-in `draft`/`standard`/`high`.
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct QualityConfig {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub presets: BTreeMap<String, CustomQuality>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CustomQuality {
+    /// x264 constant rate factor (0-51, lower is better)
+    pub crf: u32,
+    /// x264 speed preset (e.g. "veryfast", "slow")
+    pub preset: String,
+}
+
+/// Project-wide audio configuration (background music, etc.)
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AudioConfig {
     #[serde(default)]
     pub background: Option<BackgroundMusicConfig>,
+    /// One continuous soundtrack mixed under the whole video after the scenes
+    /// are joined, looped or trimmed to its length (supports @assets/ prefix).
+    /// Unlike `background`, it doesn't restart at each scene.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub music: Option<String>,
+    /// Volume of `music` in dB relative to the narration (default: -12)
+    #[serde(default = "default_bg_volume")]
+    pub music_volume: f64,
+    /// Seconds `music` fades out at the end of the video (default: 0)
+    #[serde(default)]
+    pub fade_out: f64,
+    /// Raise the music for the opening seconds, then duck it under the narration.
+    #[serde(default)]
+    pub intro_boost: Option<IntroBoostConfig>,
+    /// Extra narration tracks (e.g. other languages) muxed into the final MP4
+    /// as selectable audio streams after the rendered narration.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tracks: Vec<AudioTrackConfig>,
+}
+
+/// An additional audio stream for the output video.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AudioTrackConfig {
+    /// Language tag for the stream (ISO 639, e.g. "de" or "deu")
+    pub language: String,
+    /// Full-length narration audio (supports @assets/ prefix)
+    pub file: String,
+    /// Optional stream title shown by players (e.g. "Deutsch")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// "Intro sting then duck to bed": music plays louder for the first `secs` of the
+/// video, after which a sidechain compressor ducks it whenever the voiceover speaks.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IntroBoostConfig {
+    /// Length of the boosted intro in seconds
+    pub secs: f64,
+    /// Extra music gain during the intro in dB (default: 6)
+    #[serde(default = "default_intro_boost_gain")]
+    pub gain: f64,
+}
+
+fn default_intro_boost_gain() -> f64 {
+    6.0
 }
 
 /// Background music configuration for the entire project.
@@ -46,6 +161,19 @@ fn default_bg_volume() -> f64 {
     -12.0
 }
 
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            background: None,
+            music: None,
+            music_volume: default_bg_volume(),
+            fade_out: 0.0,
+            intro_boost: None,
+            tracks: Vec::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ProjectInfo {
     pub name: String,
@@ -55,8 +183,14 @@ pub struct ProjectInfo {
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct VideoConfig {
+    /// Frame rate scenes are captured at
     #[serde(default = "default_fps")]
     pub fps: u32,
+    /// Frame rate of the encoded video, when it differs from the capture rate:
+    /// higher values interpolate new frames (`minterpolate`), lower values drop
+    /// frames. Frame counts and animation timing still follow `fps`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_fps: Option<u32>,
     #[serde(default = "default_width")]
     pub width: u32,
     #[serde(default = "default_height")]
@@ -69,6 +203,13 @@ pub struct VideoConfig {
     pub formats: Option<BTreeMap<String, FormatConfig>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub parallel_scenes: Option<usize>,
+    /// How scenes are joined without transitions: "auto", "demuxer", or "filter".
+    /// "auto" uses the concat filter when scenes differ in audio presence.
+    #[serde(default = "default_concat_method")]
+    pub concat_method: String,
+    /// Seconds of black and silence between scenes joined by a hard cut
+    #[serde(default)]
+    pub scene_gap_secs: f64,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -80,6 +221,9 @@ pub struct FormatConfig {
     /// Platform encoding preset name (e.g., "youtube-hd", "instagram-reels")
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub platform: Option<String>,
+    /// Output directory for this format, relative to the project (overrides `output.directory`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_dir: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -116,6 +260,14 @@ pub struct VoiceConfig {
     pub language: Option<String>,
     #[serde(default = "default_true")]
     pub normalize: bool,
+    /// Engines to try, in order, when the primary engine fails for a scene
+    /// (e.g. `["edge", "native"]`). The primary `engine` is always tried first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback: Vec<String>,
+    /// Directory of Piper `.onnx` voice models; voices are named by file stem
+    /// (e.g. `en_US-amy-medium`). Falls back to the `PIPER_VOICES_DIR` env var.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub piper_voices_dir: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -126,6 +278,180 @@ pub struct OutputConfig {
     pub quality: String,
     #[serde(default)]
     pub subtitles: SubtitleConfig,
+    /// Container and codecs of the final video file
+    #[serde(default)]
+    pub container: OutputContainer,
+    /// Hardware H.264 encoder used instead of libx264 for scenes and the
+    /// transition join; falls back to libx264 when FFmpeg doesn't have it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hwaccel: Option<HwAccel>,
+    /// Frame index (0-based) embedded as the MP4's cover image, which web
+    /// players show as the poster before playback
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub poster_frame: Option<u32>,
+    /// Also write `slug-loop.<ext>`: the finished video played this many times
+    #[serde(default, rename = "loop", skip_serializing_if = "Option::is_none")]
+    pub loop_count: Option<u32>,
+    /// Seconds of the ending crossfaded into the start, so each repeat of the
+    /// loop joins seamlessly
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loop_crossfade: Option<f64>,
+    /// Loudness normalization of the final mix (`[output.audio]`)
+    #[serde(default)]
+    pub audio: OutputAudioConfig,
+    /// Tags written into the finished file (`[output.metadata]`)
+    #[serde(default)]
+    pub metadata: OutputMetadata,
+    /// Intended length of the video in seconds; `duration: "20%"` scenes take
+    /// that share of it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_duration: Option<f64>,
+}
+
+/// `[output.metadata]`: container tags for media libraries and players.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct OutputMetadata {
+    /// Defaults to the project name; set to `""` to leave the title out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Free-form release date, e.g. `2024-05-01`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+}
+
+impl OutputMetadata {
+    /// FFmpeg `(key, value)` tags for the output. `author` is written as
+    /// `artist`, the MP4 tag players and libraries display.
+    pub fn tags(&self, project_name: &str) -> Vec<(&'static str, String)> {
+        let title = self.title.as_deref().unwrap_or(project_name);
+        [
+            ("title", Some(title)),
+            ("artist", self.author.as_deref()),
+            ("description", self.description.as_deref()),
+            ("comment", self.comment.as_deref()),
+            ("date", self.date.as_deref()),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let value = value?.trim();
+            (!value.is_empty()).then(|| (key, value.to_string()))
+        })
+        .collect()
+    }
+}
+
+/// `[output.audio]`: post-processing of the finished video's audio.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OutputAudioConfig {
+    /// Run FFmpeg's two-pass `loudnorm` over the final audio so projects with
+    /// different TTS engines and music end up equally loud
+    #[serde(default)]
+    pub normalize: bool,
+    /// Integrated loudness target in LUFS (-16 suits web/mobile, -14 YouTube)
+    #[serde(default = "default_target_lufs")]
+    pub target_lufs: f64,
+}
+
+fn default_target_lufs() -> f64 {
+    -16.0
+}
+
+impl Default for OutputAudioConfig {
+    fn default() -> Self {
+        Self {
+            normalize: false,
+            target_lufs: default_target_lufs(),
+        }
+    }
+}
+
+/// Container + codec combination for the final video. Scenes are always
+/// encoded and joined as H.264 MP4; other containers are converted at the end.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputContainer {
+    /// H.264 video, AAC audio
+    #[default]
+    Mp4,
+    /// ProRes 422 video, PCM audio, for import into editors
+    Mov,
+    /// VP9 video, Opus audio
+    Webm,
+    /// Animated GIF, without audio
+    Gif,
+}
+
+impl OutputContainer {
+    pub const ALL: [OutputContainer; 4] = [Self::Mp4, Self::Mov, Self::Webm, Self::Gif];
+
+    /// File extension, which is also the name used in config and on the command line.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Mp4 => "mp4",
+            Self::Mov => "mov",
+            Self::Webm => "webm",
+            Self::Gif => "gif",
+        }
+    }
+}
+
+impl std::fmt::Display for OutputContainer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+/// Hardware H.264 encoder for `output.hwaccel`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HwAccel {
+    /// NVIDIA NVENC (`h264_nvenc`)
+    Nvenc,
+    /// VA-API on Linux, e.g. Intel and AMD GPUs (`h264_vaapi`)
+    Vaapi,
+    /// Apple VideoToolbox on macOS (`h264_videotoolbox`)
+    Videotoolbox,
+}
+
+impl HwAccel {
+    /// FFmpeg encoder name.
+    pub fn codec(self) -> &'static str {
+        match self {
+            Self::Nvenc => "h264_nvenc",
+            Self::Vaapi => "h264_vaapi",
+            Self::Videotoolbox => "h264_videotoolbox",
+        }
+    }
+}
+
+impl std::fmt::Display for HwAccel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Nvenc => "nvenc",
+            Self::Vaapi => "vaapi",
+            Self::Videotoolbox => "videotoolbox",
+        })
+    }
+}
+
+impl std::str::FromStr for OutputContainer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|c| c.extension().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                let valid: Vec<_> = Self::ALL.iter().map(|c| c.extension()).collect();
+                format!("unknown output format \"{s}\" (valid: {})", valid.join(", "))
+            })
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -137,6 +463,41 @@ pub struct SubtitleConfig {
     /// Burn subtitles into the video via FFmpeg (post-process step)
     #[serde(default)]
     pub burn_in: bool,
+    /// With `burn_in`, keep the clean `slug.mp4` and write the burned version
+    /// to `slug-captioned.mp4` instead of replacing it
+    #[serde(default)]
+    pub keep_clean: bool,
+    /// Named burn-in style: youtube, tiktok-bold, minimal, clean
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    /// ASS `force_style` overrides layered on top of the preset (e.g. "FontSize=30,MarginV=60")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    /// Font file for burned-in captions (e.g. "@assets/fonts/Inter-Bold.ttf"); its
+    /// directory is passed to libass and its file stem becomes the ASS `FontName`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_file: Option<String>,
+    /// Also write `slug.vtt` with inline per-word timing tags for karaoke-style highlighting
+    #[serde(default)]
+    pub karaoke_vtt: bool,
+    /// Draw the current subtitle line into HTML scenes' frames as a themed caption
+    /// layer (active word highlighted), instead of burning ASS in afterwards
+    #[serde(default)]
+    pub html_overlay: bool,
+    /// Existing SRT (e.g. "@assets/final.srt") used for the sidecar and burn-in
+    /// instead of subtitles generated from the narration timings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_file: Option<String>,
+    /// Also write `subtitles/NN.srt` per scene, timed from the start of the scene
+    #[serde(default)]
+    pub per_scene: bool,
+    /// Mux the SRT into the MP4/MOV as a soft `mov_text` track players can toggle
+    #[serde(default)]
+    pub embed: bool,
+    /// Language tag of the embedded track (ISO 639-2, e.g. "eng"); defaults to
+    /// `voice.language`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 fn default_max_words() -> usize {
@@ -149,6 +510,16 @@ impl Default for SubtitleConfig {
             enabled: false,
             max_words_per_line: default_max_words(),
             burn_in: false,
+            keep_clean: false,
+            preset: None,
+            style: None,
+            font_file: None,
+            karaoke_vtt: false,
+            html_overlay: false,
+            source_file: None,
+            per_scene: false,
+            embed: false,
+            language: None,
         }
     }
 }
@@ -184,6 +555,9 @@ fn default_font() -> String {
 fn default_transition_duration() -> f64 {
     0.3
 }
+fn default_concat_method() -> String {
+    "auto".into()
+}
 fn default_voice_engine() -> String {
     "native".into()
 }
@@ -213,12 +587,15 @@ impl Default for VideoConfig {
     fn default() -> Self {
         Self {
             fps: default_fps(),
+            output_fps: None,
             width: default_width(),
             height: default_height(),
             default_transition: None,
             default_transition_duration: default_transition_duration(),
             formats: None,
             parallel_scenes: None,
+            concat_method: default_concat_method(),
+            scene_gap_secs: 0.0,
         }
     }
 }
@@ -247,6 +624,8 @@ impl Default for VoiceConfig {
             auto_fallback_duration: default_auto_fallback(),
             language: None,
             normalize: true,
+            fallback: Vec::new(),
+            piper_voices_dir: None,
         }
     }
 }
@@ -257,6 +636,14 @@ impl Default for OutputConfig {
             directory: default_output_dir(),
             quality: default_quality(),
             subtitles: SubtitleConfig::default(),
+            container: OutputContainer::default(),
+            hwaccel: None,
+            poster_frame: None,
+            loop_count: None,
+            loop_crossfade: None,
+            audio: OutputAudioConfig::default(),
+            metadata: OutputMetadata::default(),
+            target_duration: None,
         }
     }
 }
@@ -270,15 +657,22 @@ impl ProjectConfig {
                 self.video.fps
             )));
         }
-        if self.video.width < 1 || self.video.width > 7680 {
+        if let Some(output_fps) = self.video.output_fps {
+            if !(1..=240).contains(&output_fps) {
+                return Err(VidgenError::ConfigParse(format!(
+                    "Invalid output_fps: {output_fps}. Must be between 1 and 240."
+                )));
+            }
+        }
+        if self.video.width < 1 {
             return Err(VidgenError::ConfigParse(format!(
-                "Invalid width: {}. Must be between 1 and 7680.",
+                "Invalid width: {}. Must be >= 1.",
                 self.video.width
             )));
         }
-        if self.video.height < 1 || self.video.height > 7680 {
+        if self.video.height < 1 {
             return Err(VidgenError::ConfigParse(format!(
-                "Invalid height: {}. Must be between 1 and 7680.",
+                "Invalid height: {}. Must be >= 1.",
                 self.video.height
             )));
         }
@@ -288,6 +682,14 @@ impl ProjectConfig {
                 self.voice.speed
             )));
         }
+        for engine in &self.voice.fallback {
+            if !crate::tts::ENGINE_NAMES.contains(&engine.as_str()) {
+                return Err(VidgenError::ConfigParse(format!(
+                    "Invalid voice.fallback engine: \"{engine}\". Must be one of: {}.",
+                    crate::tts::ENGINE_NAMES.join(", ")
+                )));
+            }
+        }
         if self.voice.padding_before < 0.0 {
             return Err(VidgenError::ConfigParse(format!(
                 "Invalid padding_before: {}. Must be >= 0.",
@@ -306,14 +708,19 @@ impl ProjectConfig {
                 self.voice.auto_fallback_duration
             )));
         }
-        if self.video.default_transition.is_some()
-            && self.video.default_transition_duration <= 0.0
+        if self.video.default_transition.is_some() && self.video.default_transition_duration <= 0.0
         {
             return Err(VidgenError::ConfigParse(format!(
                 "Invalid default_transition_duration: {}. Must be > 0 when a default transition is set.",
                 self.video.default_transition_duration
             )));
         }
+        let gap = self.video.scene_gap_secs;
+        if !(0.0..=60.0).contains(&gap) {
+            return Err(VidgenError::ConfigParse(format!(
+                "Invalid scene_gap_secs: {gap}. Must be between 0 and 60."
+            )));
+        }
         if let Some(ref par) = self.video.parallel_scenes {
             if *par == 0 {
                 return Err(VidgenError::ConfigParse(
@@ -321,24 +728,209 @@ impl ProjectConfig {
                 ));
             }
         }
+        if !["auto", "demuxer", "filter"].contains(&self.video.concat_method.as_str()) {
+            return Err(VidgenError::ConfigParse(format!(
+                "Invalid concat_method: {}. Must be auto, demuxer, or filter.",
+                self.video.concat_method
+            )));
+        }
+        if let Some(ref boost) = self.audio.intro_boost {
+            if boost.secs <= 0.0 {
+                return Err(VidgenError::ConfigParse(format!(
+                    "Invalid intro_boost.secs: {}. Must be > 0.",
+                    boost.secs
+                )));
+            }
+        }
+        if self.output.poster_frame.is_some()
+            && !matches!(self.output.container, OutputContainer::Mp4 | OutputContainer::Mov)
+        {
+            return Err(VidgenError::ConfigParse(format!(
+                "output.poster_frame needs an mp4 or mov container, not {}.",
+                self.output.container
+            )));
+        }
+        if let Some(count) = self.output.loop_count {
+            if count < 2 {
+                return Err(VidgenError::ConfigParse(format!(
+                    "Invalid output.loop: {count}. Must be >= 2."
+                )));
+            }
+        }
+        if let Some(crossfade) = self.output.loop_crossfade {
+            if crossfade <= 0.0 || self.output.loop_count.is_none() {
+                return Err(VidgenError::ConfigParse(format!(
+                    "Invalid output.loop_crossfade: {crossfade}. Must be > 0 and needs output.loop."
+                )));
+            }
+        }
+        if let Some(target) = self.output.target_duration {
+            if !(target > 0.0 && target.is_finite()) {
+                return Err(VidgenError::ConfigParse(format!(
+                    "Invalid output.target_duration: {target}. Must be > 0."
+                )));
+            }
+        }
+        let lufs = self.output.audio.target_lufs;
+        if !(-70.0..=-5.0).contains(&lufs) {
+            return Err(VidgenError::ConfigParse(format!(
+                "Invalid output.audio.target_lufs: {lufs}. Must be between -70 and -5."
+            )));
+        }
+        if self.audio.music.is_some() && self.audio.background.is_some() {
+            return Err(VidgenError::ConfigParse(
+                "audio.music and [audio.background] both add background music. Use music for one \
+                 continuous track or background for music that restarts each scene."
+                    .into(),
+            ));
+        }
+        if self.audio.fade_out < 0.0 {
+            return Err(VidgenError::ConfigParse(format!(
+                "Invalid audio.fade_out: {}. Must be >= 0.",
+                self.audio.fade_out
+            )));
+        }
+        if self.output.container == OutputContainer::Gif && !self.audio.tracks.is_empty() {
+            return Err(VidgenError::ConfigParse(
+                "output.container = \"gif\" has no audio, so audio.tracks can't be added. Use mp4, mov or webm.".into(),
+            ));
+        }
+        for (i, track) in self.audio.tracks.iter().enumerate() {
+            if track.language.trim().is_empty() || track.file.trim().is_empty() {
+                return Err(VidgenError::ConfigParse(format!(
+                    "Invalid audio.tracks[{i}]: language and file are required."
+                )));
+            }
+        }
+        if let Some(ref preset) = self.output.subtitles.preset {
+            let names: Vec<&str> = crate::subtitle::SUBTITLE_PRESETS
+                .iter()
+                .map(|(n, _)| *n)
+                .collect();
+            if !names.contains(&preset.as_str()) {
+                return Err(VidgenError::ConfigParse(format!(
+                    "Invalid output.subtitles.preset: \"{preset}\". Must be one of: {}.",
+                    names.join(", ")
+                )));
+            }
+        }
+        let subs = &self.output.subtitles;
+        if subs.burn_in && !subs.enabled {
+            return Err(VidgenError::ConfigParse(
+                "Invalid output.subtitles: burn_in = true requires enabled = true.".into(),
+            ));
+        }
+        if subs.html_overlay && subs.burn_in {
+            return Err(VidgenError::ConfigParse(
+                "Invalid output.subtitles: html_overlay and burn_in both draw captions; enable only one."
+                    .into(),
+            ));
+        }
+        if let Some(ref source) = subs.source_file {
+            if !source.to_ascii_lowercase().ends_with(".srt") {
+                return Err(VidgenError::ConfigParse(format!(
+                    "Invalid output.subtitles.source_file: \"{source}\". Must be an .srt file."
+                )));
+            }
+            if !subs.enabled || subs.html_overlay {
+                return Err(VidgenError::ConfigParse(
+                    "Invalid output.subtitles: source_file requires enabled = true and can't be drawn by html_overlay."
+                        .into(),
+                ));
+            }
+        }
+        if subs.per_scene && (!subs.enabled || subs.source_file.is_some()) {
+            return Err(VidgenError::ConfigParse(
+                "Invalid output.subtitles: per_scene requires enabled = true and can't be combined with source_file."
+                    .into(),
+            ));
+        }
+        if subs.embed
+            && (!subs.enabled
+                || !matches!(self.output.container, OutputContainer::Mp4 | OutputContainer::Mov))
+        {
+            return Err(VidgenError::ConfigParse(format!(
+                "Invalid output.subtitles: embed requires enabled = true and an mp4 or mov container, not {}.",
+                self.output.container
+            )));
+        }
+        if self.render.max_dimension < 2 || self.render.max_dimension > 7680 {
+            return Err(VidgenError::ConfigParse(format!(
+                "Invalid render.max_dimension: {}. Must be between 2 and 7680.",
+                self.render.max_dimension
+            )));
+        }
+        let capture_timeout = self.render.capture_timeout;
+        if capture_timeout <= 0.0 || !capture_timeout.is_finite() {
+            return Err(VidgenError::ConfigParse(format!(
+                "Invalid render.capture_timeout: {capture_timeout}. Must be > 0."
+            )));
+        }
+        if self.render.capture_retries > 10 {
+            return Err(VidgenError::ConfigParse(format!(
+                "Invalid render.capture_retries: {}. Must be between 0 and 10.",
+                self.render.capture_retries
+            )));
+        }
+        for (name, q) in &self.quality.presets {
+            if q.crf > 51 {
+                return Err(VidgenError::ConfigParse(format!(
+                    "Invalid crf {} in quality \"{}\". Must be between 0 and 51.",
+                    q.crf, name
+                )));
+            }
+            if !X264_PRESETS.contains(&q.preset.as_str()) {
+                return Err(VidgenError::ConfigParse(format!(
+                    "Invalid preset \"{}\" in quality \"{}\". Must be one of: {}.",
+                    q.preset,
+                    name,
+                    X264_PRESETS.join(", ")
+                )));
+            }
+        }
         if let Some(ref formats) = self.video.formats {
             for (name, fc) in formats {
-                if fc.width < 1 || fc.width > 7680 {
+                if fc.width < 1 {
                     return Err(VidgenError::ConfigParse(format!(
-                        "Invalid width {} in format \"{}\". Must be between 1 and 7680.",
+                        "Invalid width {} in format \"{}\". Must be >= 1.",
                         fc.width, name
                     )));
                 }
-                if fc.height < 1 || fc.height > 7680 {
+                if fc.height < 1 {
                     return Err(VidgenError::ConfigParse(format!(
-                        "Invalid height {} in format \"{}\". Must be between 1 and 7680.",
+                        "Invalid height {} in format \"{}\". Must be >= 1.",
                         fc.height, name
                     )));
                 }
             }
         }
+        // Oversized formats are scaled down at format resolution with auto_downscale
+        if !self.render.auto_downscale {
+            for (format, width, height) in self.output_sizes() {
+                if width.max(height) > self.render.max_dimension {
+                    return Err(VidgenError::DimensionTooLarge {
+                        format,
+                        width,
+                        height,
+                        max: self.render.max_dimension,
+                    });
+                }
+            }
+        }
         Ok(())
     }
+
+    /// Configured `(name, width, height)` of every output format: the entries of
+    /// `video.formats`, or `video.width`/`height` as "default" without them.
+    pub fn output_sizes(&self) -> Vec<(String, u32, u32)> {
+        match &self.video.formats {
+            Some(formats) => formats
+                .iter()
+                .map(|(name, fc)| (name.clone(), fc.width, fc.height))
+                .collect(),
+            None => vec![("default".into(), self.video.width, self.video.height)],
+        }
+    }
 }
 
 /// Quality settings mapped from quality string
@@ -347,24 +939,35 @@ pub struct QualityPreset {
     pub preset: &'static str,
 }
 
+/// Built-in quality presets: name, crf, x264 preset.
+pub const BUILTIN_QUALITIES: &[(&str, u32, &str)] = &[
+    ("draft", 28, "ultrafast"),
+    ("standard", 23, "medium"),
+    ("high", 18, "slow"),
+];
+
+/// x264 speed presets accepted by custom qualities.
+pub const X264_PRESETS: &[&str] = &[
+    "ultrafast",
+    "superfast",
+    "veryfast",
+    "faster",
+    "fast",
+    "medium",
+    "slow",
+    "slower",
+    "veryslow",
+    "placebo",
+];
+
 impl QualityPreset {
+    /// Look up a built-in quality. Unknown names fall back to "standard" with a warning.
     pub fn from_name(name: &str) -> Self {
-        match name {
-            "draft" => Self {
-                crf: 28,
-                preset: "ultrafast",
-            },
-            "high" => Self {
-                crf: 18,
-                preset: "slow",
-            },
-            "standard" => Self {
-                crf: 23,
-                preset: "medium",
-            },
-            other => {
+        match BUILTIN_QUALITIES.iter().find(|(n, _, _)| *n == name) {
+            Some((_, crf, preset)) => Self { crf: *crf, preset },
+            None => {
                 warn!(
-                    "Unknown quality \"{other}\", using \"standard\". Valid: draft, standard, high"
+                    "Unknown quality \"{name}\", using \"standard\". Valid: draft, standard, high"
                 );
                 Self {
                     crf: 23,
@@ -373,6 +976,62 @@ impl QualityPreset {
             }
         }
     }
+
+    /// Look up a quality by name, checking the project's custom presets before the
+    /// built-ins.
+    pub fn resolve(name: &str, custom: &QualityConfig) -> Self {
+        let Some(q) = custom.presets.get(name) else {
+            if !custom.presets.is_empty() && !BUILTIN_QUALITIES.iter().any(|(n, _, _)| *n == name) {
+                let valid: Vec<&str> = list_qualities(custom).iter().map(|q| q.name).collect();
+                warn!("Custom qualities available: {}", valid.join(", "));
+            }
+            return Self::from_name(name);
+        };
+        match X264_PRESETS.iter().find(|p| **p == q.preset) {
+            Some(preset) => Self { crf: q.crf, preset },
+            None => {
+                warn!(
+                    "Quality \"{name}\" has unknown x264 preset \"{}\", using \"medium\"",
+                    q.preset
+                );
+                Self {
+                    crf: q.crf,
+                    preset: "medium",
+                }
+            }
+        }
+    }
+}
+
+/// One selectable quality, as listed by `vidgen qualities`.
+#[derive(Debug, Clone, Serialize)]
+pub struct QualityEntry<'a> {
+    pub name: &'a str,
+    pub crf: u32,
+    pub preset: &'a str,
+    /// Defined in the project's `[quality.presets]` (shadows a built-in of the same name)
+    pub custom: bool,
+}
+
+/// All quality names usable with `--quality` / `output.quality`: built-ins first,
+/// then the project's custom presets in name order.
+pub fn list_qualities(custom: &QualityConfig) -> Vec<QualityEntry<'_>> {
+    let builtins = BUILTIN_QUALITIES
+        .iter()
+        .filter(|(name, _, _)| !custom.presets.contains_key(*name))
+        .map(|(name, crf, preset)| QualityEntry {
+            name,
+            crf: *crf,
+            preset,
+            custom: false,
+        });
+    let customs = custom.presets.iter().map(|(name, q)| QualityEntry {
+        name,
+        crf: q.crf,
+        preset: &q.preset,
+        custom: true,
+    });
+    builtins.chain(customs).collect()
 }
 
 /// Full encoding parameters including audio settings, resolved from platform or quality.
@@ -381,6 +1040,8 @@ pub struct PlatformPreset {
     pub preset: &'static str,
     pub audio_bitrate: &'static str,
     pub audio_samplerate: u32,
+    /// Hardware encoder for the video stream; `None` encodes with libx264
+    pub hw_encoder: Option<HwAccel>,
 }
 
 impl PlatformPreset {
@@ -391,42 +1052,49 @@ impl PlatformPreset {
                 preset: "slow",
                 audio_bitrate: "384k",
                 audio_samplerate: 48000,
+                hw_encoder: None,
             },
             "youtube-4k" => Self {
                 crf: 18,
                 preset: "medium",
                 audio_bitrate: "384k",
                 audio_samplerate: 48000,
+                hw_encoder: None,
             },
             "instagram-reels" => Self {
                 crf: 20,
                 preset: "medium",
                 audio_bitrate: "128k",
                 audio_samplerate: 44100,
+                hw_encoder: None,
             },
             "tiktok" => Self {
                 crf: 20,
                 preset: "medium",
                 audio_bitrate: "128k",
                 audio_samplerate: 44100,
+                hw_encoder: None,
             },
             "whatsapp" => Self {
                 crf: 26,
                 preset: "fast",
                 audio_bitrate: "96k",
                 audio_samplerate: 44100,
+                hw_encoder: None,
             },
             "youtube-shorts" => Self {
                 crf: 20,
                 preset: "medium",
                 audio_bitrate: "256k",
                 audio_samplerate: 48000,
+                hw_encoder: None,
             },
             "twitter" => Self {
                 crf: 22,
                 preset: "medium",
                 audio_bitrate: "128k",
                 audio_samplerate: 44100,
+                hw_encoder: None,
             },
             _ => return None,
         })
@@ -438,6 +1106,7 @@ impl PlatformPreset {
             preset: quality.preset,
             audio_bitrate: "128k",
             audio_samplerate: 44100,
+            hw_encoder: None,
         }
     }
 }
@@ -457,6 +1126,7 @@ pub fn resolve_encoding(quality: &QualityPreset, platform: Option<&str>) -> Plat
 }
 
 /// All-optional struct for partial config updates.
+#[derive(Debug, Clone, Default)]
 pub struct ConfigUpdate {
     pub fps: Option<u32>,
     pub width: Option<u32>,
@@ -477,6 +1147,101 @@ pub struct ConfigUpdate {
     pub padding_after: Option<f64>,
     pub auto_fallback_duration: Option<f64>,
     pub formats: Option<BTreeMap<String, FormatConfig>>,
+    pub container: Option<OutputContainer>,
+    pub capture_timeout: Option<f64>,
+    pub capture_retries: Option<u32>,
+    pub scene_gap_secs: Option<f64>,
+}
+
+/// Dotted config paths accepted by `render --set key=value`.
+pub const CONFIG_OVERRIDE_KEYS: &[&str] = &[
+    "video.fps",
+    "video.width",
+    "video.height",
+    "video.default_transition",
+    "video.default_transition_duration",
+    "video.scene_gap_secs",
+    "output.quality",
+    "output.container",
+    "theme.primary",
+    "theme.secondary",
+    "theme.background",
+    "theme.text",
+    "theme.font_heading",
+    "theme.font_body",
+    "voice.engine",
+    "voice.default_voice",
+    "voice.speed",
+    "voice.padding_before",
+    "voice.padding_after",
+    "voice.auto_fallback_duration",
+    "render.capture_timeout",
+    "render.capture_retries",
+];
+
+fn parse_override_value<T: std::str::FromStr>(key: &str, value: &str) -> VidgenResult<T> {
+    value.trim().parse().map_err(|_| {
+        VidgenError::ConfigParse(format!("Invalid value for --set {key}: \"{value}\""))
+    })
+}
+
+impl ConfigUpdate {
+    /// Build an update from `key=value` overrides such as `video.fps=60`.
+    /// Later overrides of the same key win.
+    pub fn from_overrides(overrides: &[String]) -> VidgenResult<Self> {
+        let mut update = Self::default();
+        for entry in overrides {
+            let (key, value) = entry.split_once('=').ok_or_else(|| {
+                VidgenError::ConfigParse(format!("Invalid --set \"{entry}\": expected key=value"))
+            })?;
+            update.set(key.trim(), value)?;
+        }
+        Ok(update)
+    }
+
+    /// Set the field for one dotted config path (see [`CONFIG_OVERRIDE_KEYS`]).
+    pub fn set(&mut self, key: &str, value: &str) -> VidgenResult<()> {
+        let text = || Some(value.trim().to_string());
+        match key {
+            "video.fps" => self.fps = Some(parse_override_value(key, value)?),
+            "video.width" => self.width = Some(parse_override_value(key, value)?),
+            "video.height" => self.height = Some(parse_override_value(key, value)?),
+            "video.default_transition" => self.default_transition = text(),
+            "video.default_transition_duration" => {
+                self.default_transition_duration = Some(parse_override_value(key, value)?)
+            }
+            "video.scene_gap_secs" => self.scene_gap_secs = Some(parse_override_value(key, value)?),
+            "output.quality" => self.quality = text(),
+            "output.container" => self.container = Some(parse_override_value(key, value)?),
+            "theme.primary" => self.primary = text(),
+            "theme.secondary" => self.secondary = text(),
+            "theme.background" => self.background = text(),
+            "theme.text" => self.text = text(),
+            "theme.font_heading" => self.font_heading = text(),
+            "theme.font_body" => self.font_body = text(),
+            "voice.engine" => self.voice_engine = text(),
+            "voice.default_voice" => self.default_voice = text(),
+            "voice.speed" => self.voice_speed = Some(parse_override_value(key, value)?),
+            "voice.padding_before" => self.padding_before = Some(parse_override_value(key, value)?),
+            "voice.padding_after" => self.padding_after = Some(parse_override_value(key, value)?),
+            "voice.auto_fallback_duration" => {
+                self.auto_fallback_duration = Some(parse_override_value(key, value)?)
+            }
+            "render.capture_timeout" => {
+                self.capture_timeout = Some(parse_override_value(key, value)?)
+            }
+            "render.capture_retries" => {
+                self.capture_retries = Some(parse_override_value(key, value)?)
+            }
+            _ => {
+                return Err(VidgenError::ConfigParse(format!(
+                    "Unknown --set key \"{key}\". Valid: {}",
+                    CONFIG_OVERRIDE_KEYS.join(", ")
+                )))
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Save a `ProjectConfig` to `project_path/project.toml`.
@@ -490,7 +1255,13 @@ pub fn save_config(project_path: &Path, config: &ProjectConfig) -> VidgenResult<
 /// Load config, apply non-None fields from `update`, save, and return updated config.
 pub fn update_config(project_path: &Path, update: &ConfigUpdate) -> VidgenResult<ProjectConfig> {
     let mut config = load_config(project_path)?;
+    apply_config_update(&mut config, update);
+    save_config(project_path, &config)?;
+    Ok(config)
+}
 
+/// Apply the non-None fields of `update` to `config` in memory.
+pub fn apply_config_update(config: &mut ProjectConfig, update: &ConfigUpdate) {
     if let Some(fps) = update.fps {
         config.video.fps = fps;
     }
@@ -503,6 +1274,9 @@ pub fn update_config(project_path: &Path, update: &ConfigUpdate) -> VidgenResult
     if let Some(ref quality) = update.quality {
         config.output.quality = quality.clone();
     }
+    if let Some(container) = update.container {
+        config.output.container = container;
+    }
     if let Some(ref primary) = update.primary {
         config.theme.primary = primary.clone();
     }
@@ -548,9 +1322,15 @@ pub fn update_config(project_path: &Path, update: &ConfigUpdate) -> VidgenResult
     if let Some(ref formats) = update.formats {
         config.video.formats = Some(formats.clone());
     }
-
-    save_config(project_path, &config)?;
-    Ok(config)
+    if let Some(timeout) = update.capture_timeout {
+        config.render.capture_timeout = timeout;
+    }
+    if let Some(retries) = update.capture_retries {
+        config.render.capture_retries = retries;
+    }
+    if let Some(gap) = update.scene_gap_secs {
+        config.video.scene_gap_secs = gap;
+    }
 }
 
 pub fn load_config(project_path: &Path) -> VidgenResult<ProjectConfig> {
@@ -650,6 +1430,9 @@ name = "Minimal"
             theme: ThemeConfig::default(),
             output: OutputConfig::default(),
             audio: AudioConfig::default(),
+            render: RenderConfig::default(),
+            quality: QualityConfig::default(),
+            variables: BTreeMap::new(),
         };
         save_config(project_path, &config).unwrap();
         let loaded = load_config(project_path).unwrap();
@@ -675,6 +1458,9 @@ name = "Minimal"
             theme: ThemeConfig::default(),
             output: OutputConfig::default(),
             audio: AudioConfig::default(),
+            render: RenderConfig::default(),
+            quality: QualityConfig::default(),
+            variables: BTreeMap::new(),
         };
         save_config(project_path, &config).unwrap();
 
@@ -698,6 +1484,10 @@ name = "Minimal"
             padding_after: None,
             auto_fallback_duration: None,
             formats: None,
+            container: None,
+            capture_timeout: None,
+            capture_retries: None,
+            scene_gap_secs: None,
         };
         let updated = update_config(project_path, &update).unwrap();
         assert_eq!(updated.video.fps, 60);
@@ -756,6 +1546,9 @@ fps = 30
             theme: ThemeConfig::default(),
             output: OutputConfig::default(),
             audio: AudioConfig::default(),
+            render: RenderConfig::default(),
+            quality: QualityConfig::default(),
+            variables: BTreeMap::new(),
         };
         save_config(project_path, &config).unwrap();
 
@@ -779,6 +1572,10 @@ fps = 30
             padding_after: None,
             auto_fallback_duration: None,
             formats: None,
+            container: None,
+            capture_timeout: None,
+            capture_retries: None,
+            scene_gap_secs: None,
         };
         let updated = update_config(project_path, &update).unwrap();
         assert_eq!(
@@ -943,6 +1740,27 @@ enabled = true
         assert!(!config.output.subtitles.burn_in);
     }
 
+    #[test]
+    fn test_subtitle_preset_validation() {
+        let toml = r##"
+[project]
+name = "Preset Test"
+
+[output.subtitles]
+enabled = true
+burn_in = true
+preset = "tiktok-bold"
+style = "FontSize=32"
+"##;
+        let mut config: ProjectConfig = toml::from_str(toml).unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.output.subtitles.style.as_deref(), Some("FontSize=32"));
+
+        config.output.subtitles.preset = Some("karaoke".into());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("Invalid output.subtitles.preset: \"karaoke\""));
+    }
+
     #[test]
     fn test_parse_parallel_scenes_config() {
         let toml = r##"
@@ -979,6 +1797,9 @@ name = "No Parallel"
             theme: ThemeConfig::default(),
             output: OutputConfig::default(),
             audio: AudioConfig::default(),
+            render: RenderConfig::default(),
+            quality: QualityConfig::default(),
+            variables: BTreeMap::new(),
         };
         assert!(config.validate().is_ok());
     }
@@ -998,6 +1819,9 @@ name = "No Parallel"
             theme: ThemeConfig::default(),
             output: OutputConfig::default(),
             audio: AudioConfig::default(),
+            render: RenderConfig::default(),
+            quality: QualityConfig::default(),
+            variables: BTreeMap::new(),
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("Invalid fps: 0"));
@@ -1018,11 +1842,49 @@ name = "No Parallel"
             theme: ThemeConfig::default(),
             output: OutputConfig::default(),
             audio: AudioConfig::default(),
+            render: RenderConfig::default(),
+            quality: QualityConfig::default(),
+            variables: BTreeMap::new(),
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("Invalid fps: 300"));
     }
 
+    #[test]
+    fn test_output_fps() {
+        let mut config: ProjectConfig =
+            toml::from_str("[project]\nname = \"X\"\n[video]\nfps = 30\noutput_fps = 60\n")
+                .unwrap();
+        assert_eq!(config.video.fps, 30);
+        assert_eq!(config.video.output_fps, Some(60));
+        assert!(config.validate().is_ok());
+        config.video.output_fps = Some(0);
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid output_fps: 0"));
+    }
+
+    #[test]
+    fn test_scene_gap_secs() {
+        let mut config: ProjectConfig =
+            toml::from_str("[project]\nname = \"X\"\n[video]\nscene_gap_secs = 0.75\n").unwrap();
+        assert_eq!(config.video.scene_gap_secs, 0.75);
+        assert!(config.validate().is_ok());
+
+        let update = ConfigUpdate::from_overrides(&["video.scene_gap_secs=2".into()]).unwrap();
+        apply_config_update(&mut config, &update);
+        assert_eq!(config.video.scene_gap_secs, 2.0);
+
+        config.video.scene_gap_secs = -1.0;
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid scene_gap_secs"));
+    }
+
     #[test]
     fn test_validate_negative_padding() {
         let config = ProjectConfig {
@@ -1038,6 +1900,9 @@ name = "No Parallel"
             theme: ThemeConfig::default(),
             output: OutputConfig::default(),
             audio: AudioConfig::default(),
+            render: RenderConfig::default(),
+            quality: QualityConfig::default(),
+            variables: BTreeMap::new(),
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("padding_before"));
@@ -1058,6 +1923,9 @@ name = "No Parallel"
             theme: ThemeConfig::default(),
             output: OutputConfig::default(),
             audio: AudioConfig::default(),
+            render: RenderConfig::default(),
+            quality: QualityConfig::default(),
+            variables: BTreeMap::new(),
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("voice speed"));
@@ -1078,11 +1946,291 @@ name = "No Parallel"
             theme: ThemeConfig::default(),
             output: OutputConfig::default(),
             audio: AudioConfig::default(),
+            render: RenderConfig::default(),
+            quality: QualityConfig::default(),
+            variables: BTreeMap::new(),
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("parallel_scenes"));
     }
 
+    #[test]
+    fn test_validate_subtitle_conflicts() {
+        let check = |subtitles: &str| {
+            let toml = format!("[project]\nname = \"Subs\"\n\n[output.subtitles]\n{subtitles}");
+            toml::from_str::<ProjectConfig>(&toml).unwrap().validate()
+        };
+        let err = check("burn_in = true\n").unwrap_err().to_string();
+        assert!(
+            err.contains("burn_in = true requires enabled = true"),
+            "{err}"
+        );
+        let err = check("enabled = true\nburn_in = true\nhtml_overlay = true\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("html_overlay and burn_in"), "{err}");
+
+        check("enabled = true\nburn_in = true\nkeep_clean = true\n").unwrap();
+        // keep_clean alone is fine: `render --burn-in` turns burn-in on per run
+        check("keep_clean = true\n").unwrap();
+        check("html_overlay = true\n").unwrap();
+
+        let err = check("source_file = \"@assets/final.srt\"\n").unwrap_err().to_string();
+        assert!(err.contains("source_file requires enabled"), "{err}");
+        let err = check("enabled = true\nsource_file = \"final.vtt\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Must be an .srt file"), "{err}");
+        check("enabled = true\nburn_in = true\nsource_file = \"@assets/final.srt\"\n").unwrap();
+
+        let err = check("per_scene = true\n").unwrap_err().to_string();
+        assert!(err.contains("per_scene requires enabled"), "{err}");
+        let err = check("enabled = true\nper_scene = true\nsource_file = \"final.srt\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("can't be combined with source_file"), "{err}");
+        check("enabled = true\nper_scene = true\n").unwrap();
+
+        let err = check("embed = true\n").unwrap_err().to_string();
+        assert!(err.contains("embed requires enabled"), "{err}");
+        check("enabled = true\nembed = true\nlanguage = \"deu\"\n").unwrap();
+        let config: ProjectConfig = toml::from_str(
+            "[project]\nname = \"X\"\n[output]\ncontainer = \"webm\"\n[output.subtitles]\nenabled = true\nembed = true\n",
+        )
+        .unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("mp4 or mov container, not webm"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_padding_after_and_fallback_duration() {
+        let check = |voice: &str| {
+            let toml = format!("[project]\nname = \"Voice\"\n\n[voice]\n{voice}");
+            toml::from_str::<ProjectConfig>(&toml).unwrap().validate()
+        };
+        let err = check("padding_after = -0.5\n").unwrap_err().to_string();
+        assert!(err.contains("Invalid padding_after"), "{err}");
+        let err = check("auto_fallback_duration = 0\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Invalid auto_fallback_duration"), "{err}");
+        let err = check("speed = 0\n").unwrap_err().to_string();
+        assert!(err.contains("Invalid voice speed"), "{err}");
+    }
+
+    #[test]
+    fn test_output_hwaccel() {
+        let config: ProjectConfig = toml::from_str("[project]\nname = \"X\"\n").unwrap();
+        assert_eq!(config.output.hwaccel, None);
+        let config: ProjectConfig =
+            toml::from_str("[project]\nname = \"X\"\n[output]\nhwaccel = \"nvenc\"\n").unwrap();
+        assert_eq!(config.output.hwaccel, Some(HwAccel::Nvenc));
+        assert_eq!(HwAccel::Videotoolbox.codec(), "h264_videotoolbox");
+        assert!(toml::from_str::<ProjectConfig>(
+            "[project]\nname = \"X\"\n[output]\nhwaccel = \"qsv\"\n"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_output_container() {
+        let config: ProjectConfig = toml::from_str("[project]\nname = \"X\"\n").unwrap();
+        assert_eq!(config.output.container, OutputContainer::Mp4);
+        let config: ProjectConfig =
+            toml::from_str("[project]\nname = \"X\"\n[output]\ncontainer = \"webm\"\n").unwrap();
+        assert_eq!(config.output.container, OutputContainer::Webm);
+        assert!(toml::from_str::<ProjectConfig>(
+            "[project]\nname = \"X\"\n[output]\ncontainer = \"avi\"\n"
+        )
+        .is_err());
+
+        assert_eq!("MOV".parse::<OutputContainer>(), Ok(OutputContainer::Mov));
+        let err = "avi".parse::<OutputContainer>().unwrap_err();
+        assert!(err.contains("mp4, mov, webm, gif"), "{err}");
+
+        let update = ConfigUpdate::from_overrides(&["output.container=gif".into()]).unwrap();
+        assert_eq!(update.container, Some(OutputContainer::Gif));
+        assert!(ConfigUpdate::from_overrides(&["output.container=avi".into()]).is_err());
+
+        // GIF has no audio streams to carry extra language tracks
+        let mut config: ProjectConfig = toml::from_str(
+            "[project]\nname = \"X\"\n[[audio.tracks]]\nlanguage = \"de\"\nfile = \"de.wav\"\n",
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        config.output.container = OutputContainer::Gif;
+        assert!(config.validate().unwrap_err().to_string().contains("audio.tracks"));
+    }
+
+    #[test]
+    fn test_output_loop() {
+        let config: ProjectConfig = toml::from_str(
+            "[project]\nname = \"X\"\n[output]\nloop = 3\nloop_crossfade = 0.5\n",
+        )
+        .unwrap();
+        assert_eq!(config.output.loop_count, Some(3));
+        assert_eq!(config.output.loop_crossfade, Some(0.5));
+        assert!(config.validate().is_ok());
+
+        let once: ProjectConfig =
+            toml::from_str("[project]\nname = \"X\"\n[output]\nloop = 1\n").unwrap();
+        assert!(once.validate().unwrap_err().to_string().contains("output.loop"));
+
+        let no_loop: ProjectConfig =
+            toml::from_str("[project]\nname = \"X\"\n[output]\nloop_crossfade = 0.5\n")
+                .unwrap();
+        assert!(no_loop.validate().unwrap_err().to_string().contains("loop_crossfade"));
+    }
+
+    #[test]
+    fn test_output_target_duration() {
+        let config: ProjectConfig =
+            toml::from_str("[project]\nname = \"X\"\n[output]\ntarget_duration = 60\n").unwrap();
+        assert_eq!(config.output.target_duration, Some(60.0));
+        assert!(config.validate().is_ok());
+
+        let zero: ProjectConfig =
+            toml::from_str("[project]\nname = \"X\"\n[output]\ntarget_duration = 0\n").unwrap();
+        assert!(zero
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid output.target_duration: 0. Must be > 0."));
+    }
+
+    #[test]
+    fn test_output_audio_normalize() {
+        let config: ProjectConfig = toml::from_str(
+            "[project]\nname = \"X\"\n[output.audio]\nnormalize = true\ntarget_lufs = -14.0\n",
+        )
+        .unwrap();
+        assert!(config.output.audio.normalize);
+        assert_eq!(config.output.audio.target_lufs, -14.0);
+        assert!(config.validate().is_ok());
+
+        let default: ProjectConfig = toml::from_str("[project]\nname = \"X\"\n").unwrap();
+        assert!(!default.output.audio.normalize);
+        assert_eq!(default.output.audio.target_lufs, -16.0);
+
+        let loud: ProjectConfig = toml::from_str(
+            "[project]\nname = \"X\"\n[output.audio]\nnormalize = true\ntarget_lufs = 3.0\n",
+        )
+        .unwrap();
+        assert!(loud
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("target_lufs"));
+    }
+
+    #[test]
+    fn test_output_metadata_tags() {
+        let config: ProjectConfig = toml::from_str("[project]\nname = \"Launch Video\"\n").unwrap();
+        assert_eq!(
+            config.output.metadata.tags(&config.project.name),
+            [("title", "Launch Video".to_string())]
+        );
+
+        let config: ProjectConfig = toml::from_str(
+            "[project]\nname = \"X\"\n[output.metadata]\ntitle = \"Release Notes\"\n\
+             author = \"Docs Team\"\ndescription = \"What's new\"\ndate = \"2024-05-01\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.output.metadata.tags(&config.project.name),
+            [
+                ("title", "Release Notes".to_string()),
+                ("artist", "Docs Team".to_string()),
+                ("description", "What's new".to_string()),
+                ("date", "2024-05-01".to_string()),
+            ]
+        );
+
+        // An empty title drops the tag instead of falling back to the name
+        let config: ProjectConfig =
+            toml::from_str("[project]\nname = \"X\"\n[output.metadata]\ntitle = \"\"\n").unwrap();
+        assert!(config.output.metadata.tags(&config.project.name).is_empty());
+    }
+
+    #[test]
+    fn test_poster_frame_needs_mp4_or_mov() {
+        let mut config: ProjectConfig =
+            toml::from_str("[project]\nname = \"X\"\n[output]\nposter_frame = 30\n").unwrap();
+        assert_eq!(config.output.poster_frame, Some(30));
+        assert!(config.validate().is_ok());
+        config.output.container = OutputContainer::Mov;
+        assert!(config.validate().is_ok());
+        config.output.container = OutputContainer::Webm;
+        assert!(config.validate().unwrap_err().to_string().contains("poster_frame"));
+    }
+
+    #[test]
+    fn test_config_update_from_overrides() {
+        let overrides: Vec<String> = [
+            "video.fps=60",
+            "output.quality = high",
+            "voice.speed=1.25",
+            "video.fps=50",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let update = ConfigUpdate::from_overrides(&overrides).unwrap();
+        assert_eq!(update.fps, Some(50));
+        assert_eq!(update.quality.as_deref(), Some("high"));
+        assert_eq!(update.voice_speed, Some(1.25));
+        assert!(update.width.is_none());
+
+        let mut config: ProjectConfig = toml::from_str("[project]\nname = \"X\"\n").unwrap();
+        apply_config_update(&mut config, &update);
+        assert_eq!(config.video.fps, 50);
+        assert_eq!(config.output.quality, "high");
+
+        let err = |s: &str| {
+            ConfigUpdate::from_overrides(&[s.to_string()])
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(err("video.fps").contains("expected key=value"));
+        assert!(err("video.fps=fast").contains("Invalid value for --set video.fps"));
+        assert!(err("video.fsp=30").contains("Unknown --set key \"video.fsp\""));
+    }
+
+    #[test]
+    fn test_format_output_dir_parsing() {
+        let toml_str = r#"
+[project]
+name = "Formats"
+
+[video.formats.landscape]
+width = 1920
+height = 1080
+
+[video.formats.portrait]
+width = 1080
+height = 1920
+output_dir = "uploads/reels"
+"#;
+        let config: ProjectConfig = toml::from_str(toml_str).unwrap();
+        let formats = config.video.formats.unwrap();
+        assert!(formats["landscape"].output_dir.is_none());
+        assert_eq!(formats["portrait"].output_dir.as_deref(), Some("uploads/reels"));
+    }
+
+    #[test]
+    fn test_validate_concat_method() {
+        let mut config: ProjectConfig = toml::from_str("[project]\nname = \"Concat\"\n").unwrap();
+        assert_eq!(config.video.concat_method, "auto");
+        assert!(config.validate().is_ok());
+
+        config.video.concat_method = "filter".into();
+        assert!(config.validate().is_ok());
+
+        config.video.concat_method = "copy".into();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("concat_method"));
+    }
+
     #[test]
     fn test_validate_format_dimensions() {
         let mut formats = BTreeMap::new();
@@ -1093,6 +2241,7 @@ name = "No Parallel"
                 height: 1080,
                 label: None,
                 platform: None,
+                output_dir: None,
             },
         );
         let config = ProjectConfig {
@@ -1108,6 +2257,9 @@ name = "No Parallel"
             theme: ThemeConfig::default(),
             output: OutputConfig::default(),
             audio: AudioConfig::default(),
+            render: RenderConfig::default(),
+            quality: QualityConfig::default(),
+            variables: BTreeMap::new(),
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("width 0"));
@@ -1134,6 +2286,52 @@ name = "No Parallel"
         assert_eq!(p.audio_bitrate, "128k");
     }
 
+    #[test]
+    fn test_custom_quality_resolves_and_lists() {
+        let toml_content = r#"
+[project]
+name = "Quality Test"
+
+[output]
+quality = "myfast"
+
+[quality.presets.myfast]
+crf = 30
+preset = "veryfast"
+"#;
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("project.toml"), toml_content).unwrap();
+        let config = load_config(dir.path()).unwrap();
+        config.validate().unwrap();
+
+        let q = QualityPreset::resolve(&config.output.quality, &config.quality);
+        assert_eq!(q.crf, 30);
+        assert_eq!(q.preset, "veryfast");
+        // Built-ins still resolve alongside custom presets
+        assert_eq!(QualityPreset::resolve("high", &config.quality).crf, 18);
+
+        let names: Vec<&str> = list_qualities(&config.quality)
+            .iter()
+            .map(|q| q.name)
+            .collect();
+        assert_eq!(names, ["draft", "standard", "high", "myfast"]);
+        assert!(list_qualities(&config.quality)[3].custom);
+    }
+
+    #[test]
+    fn test_custom_quality_validation() {
+        let toml_content =
+            "[project]\nname = \"Q\"\n\n[quality.presets.bad]\ncrf = 60\npreset = \"fast\"\n";
+        let mut config: ProjectConfig = toml::from_str(toml_content).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("Invalid crf 60 in quality \"bad\""));
+
+        config.quality.presets.get_mut("bad").unwrap().crf = 20;
+        config.quality.presets.get_mut("bad").unwrap().preset = "turbo".into();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("Invalid preset \"turbo\""));
+    }
+
     #[test]
     fn test_background_music_config_parsing() {
         let toml_content = r##"
@@ -1185,5 +2383,161 @@ name = "No Music"
         std::fs::write(dir.path().join("project.toml"), toml_content).unwrap();
         let config = load_config(dir.path()).unwrap();
         assert!(config.audio.background.is_none());
+        assert!(config.audio.intro_boost.is_none());
+    }
+
+    #[test]
+    fn test_project_music_config() {
+        let config: ProjectConfig = toml::from_str(
+            "[project]\nname = \"X\"\n[audio]\nmusic = \"@assets/audio/bed.mp3\"\nfade_out = 2.5\n",
+        )
+        .unwrap();
+        assert_eq!(config.audio.music.as_deref(), Some("@assets/audio/bed.mp3"));
+        assert_eq!(config.audio.music_volume, -12.0); // default
+        assert_eq!(config.audio.fade_out, 2.5);
+        assert!(config.validate().is_ok());
+
+        let both: ProjectConfig = toml::from_str(
+            "[project]\nname = \"X\"\n[audio]\nmusic = \"a.mp3\"\n[audio.background]\nfile = \"b.mp3\"\n",
+        )
+        .unwrap();
+        assert!(both
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("audio.music"));
+    }
+
+    #[test]
+    fn test_intro_boost_config_parsing() {
+        let toml_content = r#"
+[project]
+name = "Intro Boost"
+
+[audio]
+intro_boost = { secs = 2.0, gain = 9 }
+
+[audio.background]
+file = "music.mp3"
+"#;
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("project.toml"), toml_content).unwrap();
+        let config = load_config(dir.path()).unwrap();
+        let boost = config.audio.intro_boost.unwrap();
+        assert_eq!(boost.secs, 2.0);
+        assert_eq!(boost.gain, 9.0);
+    }
+
+    #[test]
+    fn test_intro_boost_invalid_secs() {
+        let config = ProjectConfig {
+            project: ProjectInfo {
+                name: "Bad Boost".into(),
+                version: "1.0.0".into(),
+            },
+            video: VideoConfig::default(),
+            voice: VoiceConfig::default(),
+            theme: ThemeConfig::default(),
+            output: OutputConfig::default(),
+            audio: AudioConfig {
+                background: None,
+                intro_boost: Some(IntroBoostConfig {
+                    secs: 0.0,
+                    gain: 6.0,
+                }),
+                ..AudioConfig::default()
+            },
+            render: RenderConfig::default(),
+            quality: QualityConfig::default(),
+            variables: BTreeMap::new(),
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("intro_boost.secs"));
+    }
+
+    #[test]
+    fn test_render_config_parsing() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("project.toml"),
+            "[project]\nname = \"Defaults\"\n",
+        )
+        .unwrap();
+        let config = load_config(dir.path()).unwrap();
+        assert_eq!(config.render.max_dimension, 7680);
+        assert!(!config.render.auto_downscale);
+
+        std::fs::write(
+            dir.path().join("project.toml"),
+            "[project]\nname = \"Capped\"\n\n[render]\nmax_dimension = 1920\nauto_downscale = true\n",
+        )
+        .unwrap();
+        let config = load_config(dir.path()).unwrap();
+        assert_eq!(config.render.max_dimension, 1920);
+        assert!(config.render.auto_downscale);
+
+        std::fs::write(
+            dir.path().join("project.toml"),
+            "[project]\nname = \"Bad\"\n\n[render]\nmax_dimension = 0\n",
+        )
+        .unwrap();
+        let err = load_config(dir.path()).unwrap().validate().unwrap_err();
+        assert!(err.to_string().contains("render.max_dimension"));
+    }
+
+    #[test]
+    fn test_render_capture_retry_settings() {
+        let config: ProjectConfig = toml::from_str("[project]\nname = \"X\"\n").unwrap();
+        assert_eq!(config.render.capture_timeout, 30.0);
+        assert_eq!(config.render.capture_retries, 2);
+
+        let mut config: ProjectConfig = toml::from_str(
+            "[project]\nname = \"X\"\n[render]\ncapture_timeout = 5\ncapture_retries = 0\n",
+        )
+        .unwrap();
+        assert_eq!(config.render.capture_timeout, 5.0);
+        assert_eq!(config.render.capture_retries, 0);
+        assert!(config.validate().is_ok());
+
+        let update = ConfigUpdate::from_overrides(&[
+            "render.capture_timeout=12.5".into(),
+            "render.capture_retries=4".into(),
+        ])
+        .unwrap();
+        apply_config_update(&mut config, &update);
+        assert_eq!(config.render.capture_timeout, 12.5);
+        assert_eq!(config.render.capture_retries, 4);
+
+        config.render.capture_timeout = 0.0;
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("capture_timeout"));
+        config.render.capture_timeout = 30.0;
+        config.render.capture_retries = 11;
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("capture_retries"));
+    }
+
+    #[test]
+    fn test_voice_fallback_parsing_and_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("project.toml"),
+            "[project]\nname = \"Fallback\"\n\n[voice]\nengine = \"elevenlabs\"\nfallback = [\"edge\", \"native\"]\n",
+        )
+        .unwrap();
+        let config = load_config(dir.path()).unwrap();
+        assert_eq!(config.voice.fallback, ["edge", "native"]);
+        assert!(config.validate().is_ok());
+
+        let mut config = config;
+        config.voice.fallback.push("festival".into());
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("voice.fallback"));
     }
 }
@@ -1,6 +1,10 @@
 use std::path::PathBuf;
 use thiserror::Error;
 
+/// Start of the [`VidgenError::Browser`] message when no Chrome/Chromium
+/// executable can be found; such errors get an install hint.
+pub const BROWSER_NOT_FOUND: &str = "No Chrome/Chromium browser found";
+
 #[derive(Error, Debug)]
 pub enum VidgenError {
     #[error("Project not found: {0}")]
@@ -15,7 +19,7 @@ pub enum VidgenError {
     #[error("Scene file error in {path}: {message}")]
     SceneParse { path: PathBuf, message: String },
 
-    #[error("No scenes found in {0}")]
+    #[error("Project has no scenes: no .md scene files in {0}")]
     NoScenes(PathBuf),
 
     #[error("Template not found: {0}")]
@@ -33,12 +37,23 @@ pub enum VidgenError {
     #[error("Scene index out of range: {index} (project has {count} scenes)")]
     SceneIndexOutOfRange { index: usize, count: usize },
 
+    #[error("No scenes match {filter} (project has {total} scenes)")]
+    NoScenesMatch { filter: String, total: usize },
+
     #[error("Invalid scene order: {0}")]
     InvalidSceneOrder(String),
 
     #[error("Already initialized: {0} already exists")]
     AlreadyInitialized(PathBuf),
 
+    #[error("Output {format} is {width}x{height}, which exceeds render.max_dimension ({max})")]
+    DimensionTooLarge {
+        format: String,
+        width: u32,
+        height: u32,
+        max: u32,
+    },
+
     #[error("TTS error: {0}")]
     Tts(String),
 
@@ -60,7 +75,7 @@ impl VidgenError {
                 "A valid project needs a project.toml file. Run 'vidgen init' to create one.".into(),
             ),
             VidgenError::NoScenes(_) => Some(
-                "Add .md files to the scenes/ directory. Run 'vidgen init' for a starter project."
+                "Add a scene file such as scenes/01-intro.md (see 'vidgen templates' for templates), or create scenes from data with 'vidgen generate <path> --template <name> --data <file>'."
                     .into(),
             ),
             VidgenError::ConfigParse(msg) => {
@@ -72,7 +87,7 @@ impl VidgenError {
             }
             VidgenError::SceneParse { message, .. } => {
                 if message.contains("template") {
-                    Some("Built-in templates: title-card, content-text, quote-card, split-screen, lower-third, cta-card, kinetic-text, slideshow, caption-overlay. Custom templates go in templates/components/.".into())
+                    Some("Built-in templates: title-card, content-text, quote-card, split-screen, lower-third, cta-card, kinetic-text, slideshow, caption-overlay, bar-chart, code-block, rich-text. Custom templates go in templates/components/.".into())
                 } else if message.contains("frontmatter") || message.contains("---") {
                     Some("Scene files need YAML frontmatter between --- delimiters at the top of the file.".into())
                 } else {
@@ -80,7 +95,11 @@ impl VidgenError {
                 }
             }
             VidgenError::TemplateNotFound(_) => Some(
-                "Built-in templates: title-card, content-text, quote-card, split-screen, lower-third, cta-card, kinetic-text, slideshow, caption-overlay. Custom templates go in templates/components/.".into(),
+                "Built-in templates: title-card, content-text, quote-card, split-screen, lower-third, cta-card, kinetic-text, slideshow, caption-overlay, bar-chart, code-block, rich-text. Custom templates go in templates/components/.".into(),
+            ),
+            VidgenError::Browser(msg) if msg.starts_with(BROWSER_NOT_FOUND) => Some(
+                "Install Google Chrome or Chromium (e.g. apt install chromium), or set VIDGEN_CHROME_PATH to the browser executable."
+                    .into(),
             ),
             VidgenError::Browser(_) => Some(
                 "Ensure Chromium/Chrome is installed, or let chromiumoxide download it automatically."
@@ -92,12 +111,18 @@ impl VidgenError {
             VidgenError::SceneIndexOutOfRange { .. } => Some(
                 "Scene indices are 0-based. Use get_project_status to see available scenes.".into(),
             ),
+            VidgenError::NoScenesMatch { .. } => Some(
+                "Scene indices are 0-based and tags are case-sensitive. Run 'vidgen info <path>' to list scenes and their tags.".into(),
+            ),
             VidgenError::InvalidSceneOrder(_) => Some(
                 "Provide a complete permutation of scene indices (0-based).".into(),
             ),
             VidgenError::AlreadyInitialized(_) => Some(
                 "Use a different path, or delete the existing project first.".into(),
             ),
+            VidgenError::DimensionTooLarge { .. } => Some(
+                "Lower the width/height in [video] or [video.formats], raise [render] max_dimension, or set [render] auto_downscale = true.".into(),
+            ),
             VidgenError::Tts(_) => Some(
                 "Ensure a TTS engine is available. macOS: 'say' (built-in). Linux: install espeak-ng. For neural voices: pip install edge-tts. For local neural TTS: install piper (https://github.com/rhasspy/piper). For ElevenLabs: set ELEVEN_API_KEY env var or add it to .env in your project".into(),
             ),
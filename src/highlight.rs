@@ -0,0 +1,370 @@
+/// Token classes of the `code-block` template, rendered as `tok-<name>` CSS classes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    Keyword,
+    String,
+    Comment,
+    Number,
+}
+
+impl TokenKind {
+    fn class(self) -> &'static str {
+        match self {
+            Self::Keyword => "tok-keyword",
+            Self::String => "tok-string",
+            Self::Comment => "tok-comment",
+            Self::Number => "tok-number",
+        }
+    }
+}
+
+/// What the highlighter needs to know about a language.
+struct Syntax {
+    keywords: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe",
+    "use", "where", "while",
+];
+
+const JS_KEYWORDS: &[&str] = &[
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "from",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "of",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "type",
+    "typeof",
+    "undefined",
+    "var",
+    "void",
+    "while",
+    "yield",
+];
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif",
+    "else", "except", "False", "finally", "for", "from", "global", "if", "import", "in", "is",
+    "lambda", "None", "nonlocal", "not", "or", "pass", "raise", "return", "self", "True", "try",
+    "while", "with", "yield",
+];
+
+const GO_KEYWORDS: &[&str] = &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "false",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "nil",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "true",
+    "type",
+    "var",
+];
+
+const C_KEYWORDS: &[&str] = &[
+    "auto",
+    "bool",
+    "break",
+    "case",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "float",
+    "for",
+    "if",
+    "implements",
+    "import",
+    "int",
+    "long",
+    "namespace",
+    "new",
+    "null",
+    "nullptr",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "unsigned",
+    "void",
+    "while",
+];
+
+const SHELL_KEYWORDS: &[&str] = &[
+    "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if", "in",
+    "local", "return", "then", "until", "while",
+];
+
+const JSON_KEYWORDS: &[&str] = &["true", "false", "null"];
+
+/// Syntax for a `language` prop (case-insensitive, common aliases accepted).
+/// Unknown languages still get strings and numbers highlighted.
+fn syntax_for(language: &str) -> Syntax {
+    let c_like = |keywords| Syntax {
+        keywords,
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\''],
+    };
+    match language.trim().to_ascii_lowercase().as_str() {
+        "rust" | "rs" => Syntax {
+            // `'a` lifetimes make single quotes ambiguous
+            quotes: &['"'],
+            ..c_like(RUST_KEYWORDS)
+        },
+        "javascript" | "js" | "typescript" | "ts" | "jsx" | "tsx" => Syntax {
+            quotes: &['"', '\'', '`'],
+            ..c_like(JS_KEYWORDS)
+        },
+        "go" | "golang" => Syntax {
+            quotes: &['"', '`'],
+            ..c_like(GO_KEYWORDS)
+        },
+        "c" | "cpp" | "c++" | "java" | "csharp" | "cs" | "kotlin" | "swift" => c_like(C_KEYWORDS),
+        "python" | "py" => Syntax {
+            keywords: PYTHON_KEYWORDS,
+            line_comments: &["#"],
+            block_comment: None,
+            quotes: &['"', '\''],
+        },
+        "bash" | "sh" | "shell" | "zsh" => Syntax {
+            keywords: SHELL_KEYWORDS,
+            line_comments: &["#"],
+            block_comment: None,
+            quotes: &['"', '\''],
+        },
+        "json" => Syntax {
+            keywords: JSON_KEYWORDS,
+            line_comments: &[],
+            block_comment: None,
+            quotes: &['"'],
+        },
+        _ => Syntax {
+            keywords: &[],
+            line_comments: &[],
+            block_comment: None,
+            quotes: &['"', '\''],
+        },
+    }
+}
+
+fn push_escaped(out: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#x27;"),
+            _ => out.push(ch),
+        }
+    }
+}
+
+fn push_token(out: &mut String, kind: TokenKind, text: &str) {
+    out.push_str("<span class=\"");
+    out.push_str(kind.class());
+    out.push_str("\">");
+    push_escaped(out, text);
+    out.push_str("</span>");
+}
+
+fn is_ident_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// Byte length of the string starting at `rest` (which opens with `quote`),
+/// up to the closing quote or the end of the line.
+fn string_len(rest: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (i, ch) in rest.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == quote {
+            return i + ch.len_utf8();
+        }
+    }
+    rest.len()
+}
+
+/// Highlight `code` line by line: each entry is one source line as HTML, with
+/// text escaped and tokens wrapped in `<span class="tok-...">`. Block comments
+/// may span lines; strings end at the line end.
+pub fn highlight_lines(code: &str, language: &str) -> Vec<String> {
+    let syntax = syntax_for(language);
+    let mut in_block_comment = false;
+    code.lines()
+        .map(|line| {
+            let mut out = String::with_capacity(line.len());
+            let mut rest = line;
+            while !rest.is_empty() {
+                if let Some((open, close)) = syntax.block_comment {
+                    if in_block_comment || rest.starts_with(open) {
+                        let skip = if in_block_comment { 0 } else { open.len() };
+                        let len = match rest[skip..].find(close) {
+                            Some(pos) => {
+                                in_block_comment = false;
+                                skip + pos + close.len()
+                            }
+                            None => {
+                                in_block_comment = true;
+                                rest.len()
+                            }
+                        };
+                        push_token(&mut out, TokenKind::Comment, &rest[..len]);
+                        rest = &rest[len..];
+                        continue;
+                    }
+                }
+                if syntax.line_comments.iter().any(|c| rest.starts_with(c)) {
+                    push_token(&mut out, TokenKind::Comment, rest);
+                    break;
+                }
+
+                let ch = rest.chars().next().unwrap_or_default();
+                let (kind, len) = if syntax.quotes.contains(&ch) {
+                    (Some(TokenKind::String), string_len(rest, ch))
+                } else if ch.is_ascii_digit() {
+                    let len = rest
+                        .find(|c: char| !(is_ident_char(c) || c == '.'))
+                        .unwrap_or(rest.len());
+                    (Some(TokenKind::Number), len)
+                } else if is_ident_char(ch) {
+                    let len = rest.find(|c| !is_ident_char(c)).unwrap_or(rest.len());
+                    let keyword = syntax.keywords.contains(&&rest[..len]);
+                    (keyword.then_some(TokenKind::Keyword), len)
+                } else {
+                    (None, ch.len_utf8())
+                };
+                match kind {
+                    Some(kind) => push_token(&mut out, kind, &rest[..len]),
+                    None => push_escaped(&mut out, &rest[..len]),
+                }
+                rest = &rest[len..];
+            }
+            out
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_rust_line() {
+        let lines = highlight_lines("let n = 42; // answer", "rust");
+        assert_eq!(
+            lines,
+            [
+                "<span class=\"tok-keyword\">let</span> n = <span class=\"tok-number\">42</span>; \
+              <span class=\"tok-comment\">// answer</span>"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_highlight_escapes_and_strings() {
+        let lines = highlight_lines("if a < b: print(\"<b>\")", "python");
+        assert_eq!(
+            lines,
+            ["<span class=\"tok-keyword\">if</span> a &lt; b: print(\
+              <span class=\"tok-string\">&quot;&lt;b&gt;&quot;</span>)"]
+        );
+        // Identifiers that merely contain a keyword or a digit stay plain
+        assert_eq!(highlight_lines("format x2", "python"), ["format x2"]);
+    }
+
+    #[test]
+    fn test_highlight_block_comment_spans_lines() {
+        let lines = highlight_lines("/* start\nstill comment */ return", "js");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "<span class=\"tok-comment\">/* start</span>");
+        assert!(lines[1].starts_with("<span class=\"tok-comment\">still comment */</span>"));
+        assert!(lines[1].ends_with("<span class=\"tok-keyword\">return</span>"));
+    }
+
+    #[test]
+    fn test_unknown_language_keeps_plain_words() {
+        let lines = highlight_lines("let x = 'y'", "brainfudge");
+        assert_eq!(
+            lines,
+            ["let x = <span class=\"tok-string\">&#x27;y&#x27;</span>"]
+        );
+    }
+}
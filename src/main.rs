@@ -2,7 +2,9 @@ mod cli;
 mod commands;
 mod config;
 mod error;
+mod highlight;
 mod mcp;
+mod palette;
 mod render;
 mod scene;
 mod subtitle;
@@ -19,7 +21,7 @@ async fn main() {
     let cli = Cli::parse();
 
     // Initialize tracing based on CLI flags (not for MCP — would corrupt stdio JSON)
-    if !matches!(cli.command, Command::Mcp) {
+    if !matches!(cli.command, Command::Mcp { .. }) {
         let log_level = if cli.debug {
             Some("debug")
         } else if cli.verbose {
@@ -52,6 +54,9 @@ async fn main() {
     if let Some(ref dir) = cli.debug_dir {
         std::env::set_var("VIDGEN_DEBUG_DIR", dir.as_os_str());
     }
+    if cli.dry_tts {
+        std::env::set_var("VIDGEN_DRY_TTS", "1");
+    }
 
     if let Err(e) = run(cli).await {
         eprintln!("{} {}", "error:".red().bold(), e);
@@ -64,7 +69,11 @@ async fn main() {
 
 async fn run(cli: Cli) -> VidgenResult<()> {
     match cli.command {
-        Command::Init { path, preset } => commands::init::run(&path, preset.as_deref()),
+        Command::Init {
+            path,
+            preset,
+            ignore_file,
+        } => commands::init::run(&path, preset.as_deref(), ignore_file),
         Command::Asset { action } => {
             match action {
                 cli::AssetAction::Add {
@@ -72,18 +81,27 @@ async fn run(cli: Cli) -> VidgenResult<()> {
                     project,
                     category,
                 } => commands::asset::add(&source, &project, &category),
+                cli::AssetAction::Prune { path, dry_run } => {
+                    commands::asset::prune(&path, dry_run)
+                }
             }
         }
         Command::Templates { project, output } => {
             commands::templates::run(project.as_deref(), output.as_deref()).await
         }
-        Command::Mcp => commands::mcp::run().await,
+        Command::Mcp { http, host, port } => {
+            commands::mcp::run(commands::mcp::Transport::from_args(http, host, port)).await
+        }
         Command::Render {
             path,
             fps,
             quality,
             formats,
             scenes,
+            tag,
+            shuffle,
+            seed,
+            pin_ends,
             subtitles,
             burn_in,
             parallel,
@@ -92,9 +110,56 @@ async fn run(cli: Cli) -> VidgenResult<()> {
             gpu,
             speed,
             crop,
+            progress_json,
+            benchmark,
+            append,
+            only_changed_formats,
+            explain,
+            output_format,
+            capture_timeout,
+            capture_retries,
+            scene_gap,
+            set,
         } => {
-            commands::render::run(&path, fps, quality, formats, scenes, subtitles, burn_in, parallel, force_tts, no_cache, gpu, speed, crop.as_deref())
-                .await
+            let shuffle = shuffle.then(|| commands::render::ShuffleOptions::new(seed, pin_ends));
+            let mut overrides = config::ConfigUpdate::from_overrides(&set)?;
+            if output_format.is_some() {
+                overrides.container = output_format;
+            }
+            if capture_timeout.is_some() {
+                overrides.capture_timeout = capture_timeout;
+            }
+            if capture_retries.is_some() {
+                overrides.capture_retries = capture_retries;
+            }
+            if scene_gap.is_some() {
+                overrides.scene_gap_secs = scene_gap;
+            }
+            if explain {
+                return commands::render::explain(&path, scenes, tag, shuffle, speed, &overrides);
+            }
+            let options = commands::render::RenderOptions {
+                fps,
+                quality,
+                formats,
+                scenes,
+                tag,
+                shuffle,
+                subtitles: subtitles.then_some(true),
+                burn_in: burn_in.then_some(true),
+                parallel,
+                force_tts,
+                no_cache,
+                gpu,
+                only_changed_formats,
+                speed,
+                crop,
+                progress_json,
+                benchmark,
+                append,
+                config_overrides: overrides,
+            };
+            commands::render::run(&path, &options).await
         }
         Command::Preview {
             path,
@@ -103,7 +168,27 @@ async fn run(cli: Cli) -> VidgenResult<()> {
             output,
             all,
             gif,
-        } => commands::preview::run(&path, scene, frame, output, all, gif).await,
+            all_formats,
+            interactive,
+            platform_overlay,
+            strip,
+            count,
+        } => {
+            commands::preview::run(
+                &path,
+                scene,
+                frame,
+                output,
+                all,
+                gif,
+                all_formats,
+                interactive,
+                platform_overlay.as_deref(),
+                strip,
+                count,
+            )
+            .await
+        }
         Command::Watch {
             path,
             render,
@@ -166,9 +251,16 @@ async fn run(cli: Cli) -> VidgenResult<()> {
                 }
                 ExportAction::Mp4 { scene, output, force_tts } => {
                     let idx = scene.unwrap_or(0);
-                    commands::render::run(
-                        &path, None, None, None, Some(vec![idx]), false, false, None, force_tts, false, false, None, None,
-                    ).await?;
+                    let options = commands::render::RenderOptions {
+                        scenes: Some(vec![idx]),
+                        force_tts,
+                        config_overrides: config::ConfigUpdate {
+                            container: Some(config::OutputContainer::Mp4),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    };
+                    commands::render::run(&path, &options).await?;
                     if let Some(output_path) = output {
                         let cfg = config::load_config(&path)?;
                         let output_rel = cfg.output.directory.strip_prefix("./").unwrap_or(&cfg.output.directory);
@@ -196,8 +288,29 @@ async fn run(cli: Cli) -> VidgenResult<()> {
                 }
             }
         }
+        Command::ThemePreview { path, output } => commands::theme_preview::run(&path, output).await,
         Command::Info { path } => commands::info::run(&path).await,
-        Command::Validate { path } => commands::validate::run(&path),
+        Command::Status { path } => commands::status::run(&path),
+        Command::Tts {
+            path,
+            output,
+            scenes,
+            force_tts,
+        } => commands::tts::run(&path, output, scenes, force_tts),
+        Command::Validate { path, fix, json } => commands::validate::run(&path, fix, json),
+        Command::Generate {
+            path,
+            template,
+            data,
+            script_column,
+        } => commands::generate::run(&path, &template, &data, script_column.as_deref()),
+        Command::Paginate {
+            path,
+            page_size,
+            scenes,
+        } => commands::paginate::run(&path, page_size, scenes),
+        Command::Qualities { path, json } => commands::qualities::run(&path, json),
+        Command::Doctor { json } => commands::doctor::run(json),
         Command::Diff { path } => commands::diff::run(&path).await,
         Command::Test { path, update } => commands::test::run(&path, update).await,
     }
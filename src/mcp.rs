@@ -35,7 +35,8 @@ fn decode_uri_path(encoded: &str) -> String {
 /// tool and the `vidgen://projects/{path}` resource.
 fn build_project_status_json(project_path: &Path) -> Result<serde_json::Value, McpError> {
     let config = config::load_config(project_path).map_err(mc_err)?;
-    let scenes = scene::load_scenes(project_path).map_err(mc_err)?;
+    let scenes =
+        scene::load_scenes_with_variables(project_path, &config.variables).map_err(mc_err)?;
 
     // Check for rendered output files
     let output_rel = config
@@ -44,25 +45,21 @@ fn build_project_status_json(project_path: &Path) -> Result<serde_json::Value, M
         .strip_prefix("./")
         .unwrap_or(&config.output.directory);
     let output_dir = project_path.join(output_rel);
-    let output_files: Vec<String> = if output_dir.exists() {
-        std::fs::read_dir(&output_dir)
-            .map_err(mc_err)?
-            .filter_map(|e| e.ok())
-            .map(|e| e.path())
-            .filter(|p| p.extension().is_some_and(|ext| ext == "mp4"))
-            .map(|p| p.display().to_string())
-            .collect()
-    } else {
-        vec![]
-    };
+    let output_files: Vec<String> = crate::commands::status::rendered_files(&output_dir)
+        .map_err(mc_err)?
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect();
 
     // Duration summary
     let mut fixed_duration_secs = 0.0_f64;
     let mut auto_duration_count = 0_usize;
     for s in &scenes {
-        match &s.frontmatter.duration {
-            SceneDuration::Fixed(d) => fixed_duration_secs += d,
-            SceneDuration::Auto => auto_duration_count += 1,
+        let target = config.output.target_duration;
+        match (&s.frontmatter.duration, target) {
+            (SceneDuration::Fixed(d), _) => fixed_duration_secs += d,
+            (SceneDuration::Percent(p), Some(t)) => fixed_duration_secs += t * p / 100.0,
+            _ => auto_duration_count += 1,
         }
     }
 
@@ -71,7 +68,9 @@ fn build_project_status_json(project_path: &Path) -> Result<serde_json::Value, M
         .map(|s| {
             let duration_val: serde_json::Value = match &s.frontmatter.duration {
                 SceneDuration::Auto => serde_json::json!("auto"),
+                SceneDuration::MatchMusic => serde_json::json!("match-music"),
                 SceneDuration::Fixed(d) => serde_json::json!(d),
+                SceneDuration::Percent(p) => serde_json::json!(format!("{p}%")),
             };
             let mut summary = serde_json::json!({
                 "template": s.frontmatter.template,
@@ -169,22 +168,22 @@ pub struct CreateProjectParams {
 pub struct SceneParams {
     /// Template name. Defaults to title-card
     #[schemars(
-        description = "Template name (title-card, content-text, quote-card, split-screen, lower-third, cta-card, kinetic-text, slideshow, caption-overlay). Defaults to title-card"
+        description = "Template name (title-card, content-text, quote-card, split-screen, lower-third, cta-card, kinetic-text, slideshow, caption-overlay, bar-chart, code-block, rich-text). Defaults to title-card"
     )]
     pub template: Option<String>,
     /// Voiceover script / body text for this scene
     #[schemars(description = "Voiceover script / body text for this scene")]
     pub script: String,
-    /// Scene duration: "auto" (default, derives from TTS audio + padding) or a number in seconds
+    /// Scene duration: "auto" (default, derives from TTS audio + padding), "match-music", or a number in seconds
     #[schemars(
-        description = "Scene duration: \"auto\" (default, derives from TTS audio + padding) or a number in seconds"
+        description = "Scene duration: \"auto\" (default, derives from TTS audio + padding), \"match-music\" (length of the scene's background music), or a number in seconds"
     )]
     pub duration: Option<SceneDuration>,
     /// Template variables (optional)
     #[schemars(description = "Template variables as key-value pairs")]
     pub props: Option<HashMap<String, serde_json::Value>>,
-    /// Transition type (e.g. "fade", "slide-left", "slide-right", "zoom", "wipe")
-    #[schemars(description = "Transition type: fade, slide-left, slide-right, zoom, wipe, none")]
+    /// Transition type (e.g. "fade", "fade-black", "slide-left", "slide-right", "zoom", "wipe")
+    #[schemars(description = "Transition type: fade, fade-black, slide-left, slide-right, zoom, wipe, none")]
     pub transition: Option<String>,
     /// Voice ID override for this scene
     #[schemars(description = "Voice ID override for this scene's TTS")]
@@ -232,6 +231,9 @@ pub struct RenderParams {
     /// Scene indices to render (0-based). If omitted, renders all scenes.
     #[schemars(description = "0-based scene indices to render (e.g. [0, 2]). If omitted, renders all scenes.")]
     pub scenes: Option<Vec<usize>>,
+    /// Only render scenes carrying this tag
+    #[schemars(description = "Only render scenes whose frontmatter `tags` include this name. Combines with `scenes`.")]
+    pub tag: Option<String>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -268,8 +270,8 @@ pub struct UpdateSceneParams {
     /// New voiceover script / body text
     #[schemars(description = "New voiceover script / body text")]
     pub script: Option<String>,
-    /// New duration: "auto" or a number in seconds
-    #[schemars(description = "New duration: \"auto\" or a number in seconds")]
+    /// New duration: "auto", "match-music" or a number in seconds
+    #[schemars(description = "New duration: \"auto\", \"match-music\" or a number in seconds")]
     pub duration: Option<SceneDuration>,
     /// Props to merge into existing props
     #[schemars(description = "Props to merge into existing (key-value pairs)")]
@@ -340,9 +342,9 @@ pub struct SetProjectConfigParams {
     /// Body font family
     #[schemars(description = "Body font family")]
     pub font_body: Option<String>,
-    /// Default transition between scenes (fade, slide-left, slide-right, zoom, wipe, none)
+    /// Default transition between scenes (fade, fade-black, slide-left, slide-right, zoom, wipe, none)
     #[schemars(
-        description = "Default transition type: fade, slide-left, slide-right, zoom, wipe, none"
+        description = "Default transition type: fade, fade-black, slide-left, slide-right, zoom, wipe, none"
     )]
     pub default_transition: Option<String>,
     /// Default transition duration in seconds
@@ -391,6 +393,11 @@ pub struct PreviewSceneParams {
         description = "Animation progress 0.0-1.0. When set, overrides the frame parameter by calculating the frame from progress * total_frames."
     )]
     pub progress: Option<f32>,
+    /// Format name from `video.formats` (e.g. "portrait") to preview at
+    #[schemars(
+        description = "Format name from video.formats (e.g. \"portrait\"). Uses that format's dimensions and applies the scene's format_overrides. Default: video.width/height."
+    )]
+    pub format: Option<String>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -427,6 +434,107 @@ pub struct BatchOperation {
     pub params: serde_json::Value,
 }
 
+/// One operation of a `batch_edit` call, tagged by `op`.
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum SceneEditParams {
+    /// Insert scenes at `insert_at` (or append)
+    Add {
+        #[schemars(description = "Index to insert at (0-based). Omit to append")]
+        insert_at: Option<usize>,
+        #[schemars(description = "Array of scenes to add")]
+        scenes: Vec<SceneParams>,
+    },
+    /// Partially update one scene (props are merged)
+    Update {
+        #[schemars(description = "0-based scene index to update")]
+        scene_index: usize,
+        template: Option<String>,
+        script: Option<String>,
+        duration: Option<SceneDuration>,
+        props: Option<HashMap<String, serde_json::Value>>,
+        transition_in: Option<String>,
+        transition_out: Option<String>,
+        voice: Option<String>,
+    },
+    /// Remove scenes by index
+    Remove {
+        #[schemars(description = "Array of 0-based scene indices to remove")]
+        indices: Vec<usize>,
+    },
+    /// Reorder all scenes by a permutation of indices
+    Reorder {
+        #[schemars(description = "New order as a permutation of 0-based indices")]
+        order: Vec<usize>,
+    },
+    /// Move one scene to a new position
+    Move {
+        #[schemars(description = "0-based index of the scene to move")]
+        from: usize,
+        #[schemars(description = "0-based index it ends up at")]
+        to: usize,
+    },
+}
+
+impl From<SceneEditParams> for commands::scenes::SceneEdit {
+    fn from(params: SceneEditParams) -> Self {
+        use commands::scenes::SceneEdit;
+        match params {
+            SceneEditParams::Add { insert_at, scenes } => SceneEdit::Add {
+                insert_at,
+                scenes: scenes
+                    .into_iter()
+                    .map(|s| commands::scenes::SceneInput {
+                        template: s.template,
+                        script: s.script,
+                        duration: s.duration,
+                        props: s.props,
+                        transition: s.transition,
+                        voice: s.voice,
+                        background: s.background,
+                    })
+                    .collect(),
+            },
+            SceneEditParams::Update {
+                scene_index,
+                template,
+                script,
+                duration,
+                props,
+                transition_in,
+                transition_out,
+                voice,
+            } => SceneEdit::Update {
+                scene_index,
+                update: commands::scenes::SceneUpdate {
+                    template,
+                    script,
+                    duration,
+                    props,
+                    transition_in,
+                    transition_out,
+                    voice,
+                },
+            },
+            SceneEditParams::Remove { indices } => SceneEdit::Remove { indices },
+            SceneEditParams::Reorder { order } => SceneEdit::Reorder { order },
+            SceneEditParams::Move { from, to } => SceneEdit::Move { from, to },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct BatchEditParams {
+    /// Path to the project directory
+    #[schemars(description = "Path to the project directory")]
+    pub project_path: String,
+    /// Scene edits applied in order, all or nothing
+    #[schemars(
+        description = "Scene edits applied in order as one transaction: {op: add|update|remove|reorder|move, ...}. Indices refer to the scene order after the previous operations. If any operation is invalid, nothing is changed"
+    )]
+    pub operations: Vec<SceneEditParams>,
+}
+
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct BatchParams {
     /// Array of operations to execute sequentially
@@ -434,6 +542,20 @@ pub struct BatchParams {
     pub operations: Vec<BatchOperation>,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ExtractPaletteParams {
+    /// Image path or URL to analyze
+    #[schemars(
+        description = "Image to analyze: local path, @assets/ reference (requires project_path), or http(s) URL"
+    )]
+    pub image: String,
+    /// Project directory used to resolve @assets/ paths and cache downloads
+    #[schemars(
+        description = "Optional project directory: resolves @assets/ and relative paths, and caches downloaded images in assets/downloads/"
+    )]
+    pub project_path: Option<String>,
+}
+
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct GetRenderProgressParams {
     /// Path to the project directory
@@ -529,16 +651,16 @@ impl McServer {
             crate::render::RenderProgress::noop()
         };
 
-        let results = commands::render::render_project_with_progress(
-            path,
-            None,
-            params.quality,
-            params.formats,
-            params.scenes,
-            progress,
-        )
-        .await
-        .map_err(mc_err)?;
+        let options = commands::render::RenderOptions {
+            quality: params.quality,
+            formats: params.formats,
+            scenes: params.scenes,
+            tag: params.tag,
+            ..Default::default()
+        };
+        let results = commands::render::render_project(path, &options, progress)
+            .await
+            .map_err(mc_err)?;
         let text = serde_json::to_string_pretty(&results).map_err(mc_err)?;
         Ok(CallToolResult::success(vec![Content::text(text)]))
     }
@@ -634,6 +756,20 @@ impl McServer {
         Ok(CallToolResult::success(vec![Content::text(text)]))
     }
 
+    #[tool(
+        description = "Apply several scene edits (add, update, remove, reorder, move) to one project atomically, in order. All operations are validated first; if any fails, no scene files change."
+    )]
+    async fn batch_edit(
+        &self,
+        Parameters(params): Parameters<BatchEditParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let path = Path::new(&params.project_path);
+        let edits = params.operations.into_iter().map(Into::into).collect();
+        let result = commands::scenes::batch_edit(path, edits).map_err(mc_err)?;
+        let text = serde_json::to_string_pretty(&result).map_err(mc_err)?;
+        Ok(CallToolResult::success(vec![Content::text(text)]))
+    }
+
     #[tool(
         description = "Update project configuration: video settings (fps, resolution), output quality, theme (colors, fonts), and voice settings (padding, fallback duration). Only provided fields are changed."
     )]
@@ -662,6 +798,10 @@ impl McServer {
             padding_after: params.padding_after,
             auto_fallback_duration: params.auto_fallback_duration,
             formats: None,
+            container: None,
+            capture_timeout: None,
+            capture_retries: None,
+            scene_gap_secs: None,
         };
 
         let updated = config::update_config(path, &update).map_err(mc_err)?;
@@ -711,7 +851,7 @@ impl McServer {
     }
 
     #[tool(
-        description = "Preview a scene by rendering a specific frame as a PNG screenshot. Returns base64-encoded PNG data. Use `progress` (0.0-1.0) to preview at a specific animation point."
+        description = "Preview a scene by rendering a specific frame as a PNG screenshot. Returns base64-encoded PNG data. Use `progress` (0.0-1.0) to preview at a specific animation point and `format` to preview at a configured format's dimensions."
     )]
     async fn preview_scene(
         &self,
@@ -741,9 +881,14 @@ impl McServer {
             params.frame
         };
 
-        let result = commands::scenes::preview_scene(path, params.scene_index, frame)
-            .await
-            .map_err(mc_err)?;
+        let result = commands::scenes::preview_scene(
+            path,
+            params.scene_index,
+            frame,
+            params.format.as_deref(),
+        )
+        .await
+        .map_err(mc_err)?;
         let text = serde_json::to_string_pretty(&result).map_err(mc_err)?;
         Ok(CallToolResult::success(vec![Content::text(text)]))
     }
@@ -773,7 +918,8 @@ impl McServer {
                 // For PNG, render a single frame using the same pattern as preview_scene
                 let progress = params.progress.unwrap_or(0.0).clamp(0.0, 1.0);
                 let config = config::load_config(path).map_err(mc_err)?;
-                let scenes = scene::load_scenes(path).map_err(mc_err)?;
+                let scenes =
+                    scene::load_scenes_with_variables(path, &config.variables).map_err(mc_err)?;
                 if params.scene_index >= scenes.len() {
                     return Err(McpError::invalid_params(
                         format!(
@@ -785,8 +931,11 @@ impl McServer {
                     ));
                 }
                 let scene = &scenes[params.scene_index];
-                let width = config.video.width;
-                let height = config.video.height;
+                let (width, height) = crate::render::capture_size(
+                    &config,
+                    config.video.width,
+                    config.video.height,
+                );
                 let total_frames = scene.total_frames(config.video.fps);
                 let frame =
                     ((progress * total_frames as f32) as u32).min(total_frames.saturating_sub(1));
@@ -795,6 +944,7 @@ impl McServer {
                 registry
                     .register_project_templates(path)
                     .map_err(mc_err)?;
+                registry.apply_emoji_fallback(&config.render, path);
                 let html = registry
                     .render_scene_html(
                         scene,
@@ -807,10 +957,16 @@ impl McServer {
                     )
                     .map_err(mc_err)?;
 
-                let screenshot =
-                    crate::render::browser::capture_single_frame(&html, width, height, frame, total_frames)
-                        .await
-                        .map_err(mc_err)?;
+                let screenshot = crate::render::browser::capture_single_frame(
+                    &html,
+                    width,
+                    height,
+                    frame,
+                    total_frames,
+                    scene.frontmatter.wait_for.as_deref(),
+                )
+                .await
+                .map_err(mc_err)?;
                 let png_base64 =
                     base64::engine::general_purpose::STANDARD.encode(&screenshot);
 
@@ -1008,6 +1164,10 @@ impl McServer {
                         padding_after: p.padding_after,
                         auto_fallback_duration: p.auto_fallback_duration,
                         formats: None,
+                        container: None,
+                        capture_timeout: None,
+                        capture_retries: None,
+                        scene_gap_secs: None,
                     };
                     config::update_config(Path::new(&p.project_path), &update)
                         .map(|r| serde_json::to_value(r).unwrap_or_default())
@@ -1035,6 +1195,34 @@ impl McServer {
         Ok(CallToolResult::success(vec![Content::text(text)]))
     }
 
+    #[tool(
+        description = "Extract a color palette from a reference image (path or URL). Returns dominant colors plus suggested primary/secondary/background/text values that can be passed to set_project_config's theme."
+    )]
+    async fn extract_palette(
+        &self,
+        Parameters(params): Parameters<ExtractPaletteParams>,
+    ) -> Result<CallToolResult, McpError> {
+        // Downloads without a project land in a throwaway directory
+        let temp_dir = tempfile::tempdir().map_err(mc_err)?;
+        let base = params
+            .project_path
+            .as_deref()
+            .map(Path::new)
+            .unwrap_or(temp_dir.path());
+
+        let image_path = if scene::is_url(&params.image) {
+            scene::download_asset(&params.image, base).map_err(mc_err)?
+        } else if params.project_path.is_some() {
+            scene::resolve_asset_path(&params.image, base)
+        } else {
+            std::path::PathBuf::from(&params.image)
+        };
+
+        let palette = crate::palette::extract_palette(&image_path).map_err(mc_err)?;
+        let text = serde_json::to_string_pretty(&palette).map_err(mc_err)?;
+        Ok(CallToolResult::success(vec![Content::text(text)]))
+    }
+
     #[tool(
         description = "Poll render progress for a project. Returns the contents of .vidgen-progress.json from the output directory, or {\"status\": \"idle\"} if no render is in progress."
     )]
@@ -1179,7 +1367,7 @@ impl ServerHandler for McServer {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             instructions: Some(
-                "vidgen — AI-agent-first video production. 13 tools available: \
+                "vidgen — AI-agent-first video production. 15 tools available: \
                  create_project (create new project with inline scenes), \
                  render (render project to MP4), \
                  get_project_status (inspect project config/scenes/output), \
@@ -1187,12 +1375,14 @@ impl ServerHandler for McServer {
                  update_scene (partial update of a scene's properties), \
                  remove_scenes (delete scenes by index), \
                  reorder_scenes (rearrange scene order), \
+                 batch_edit (add/update/remove/reorder/move scenes atomically in one call), \
                  set_project_config (update video/theme/quality/voice settings), \
                  list_voices (available TTS voices), \
                  preview_scene (render frame as PNG, supports progress 0.0-1.0), \
                  export_media (export scene as PNG/GIF/WebP), \
                  batch (execute multiple tool operations in one call), \
-                 get_render_progress (poll render progress). \
+                 get_render_progress (poll render progress), \
+                 extract_palette (derive theme colors from a reference image). \
                  Typical workflow: create_project → add/update scenes → preview_scene → render. \
                  Duration: scenes default to \"auto\" — length derived from TTS audio + padding."
                     .into(),
@@ -1236,6 +1426,20 @@ impl ServerHandler for McServer {
                 },
                 None,
             ),
+            Annotated::new(
+                RawResourceTemplate {
+                    uri_template: "vidgen://templates/{name}/schema".into(),
+                    name: "Template prop schema".into(),
+                    title: None,
+                    description: Some(
+                        "JSON Schema for a built-in template's props (types, required, defaults)"
+                            .into(),
+                    ),
+                    mime_type: Some("application/schema+json".into()),
+                    icons: None,
+                },
+                None,
+            ),
         ];
         Ok(ListResourceTemplatesResult {
             resource_templates: templates,
@@ -1251,7 +1455,7 @@ impl ServerHandler for McServer {
     ) -> Result<ListResourcesResult, McpError> {
         let resources = vec![
             Annotated::new(
-                RawResource::new("vidgen://templates", "Built-in templates (all adapt to landscape/portrait/square via CSS container queries). Add custom .html files to templates/components/ — registered by file stem name."),
+                RawResource::new("vidgen://templates", "Built-in templates (all adapt to landscape/portrait/square via CSS container queries). Add custom .html files to templates/components/ — registered by file stem name. Templates can use the helpers {{upper x}}, {{lower x}}, {{truncate x 40}}, {{number_format x [decimals]}}, {{repeat x n}} and {{md x}}."),
                 None,
             ),
             Annotated::new(
@@ -1348,8 +1552,41 @@ impl ServerHandler for McServer {
                             "style": "Text style: outline (default), background-box, drop-shadow",
                             "position": "Caption position: bottom (default), top, center"
                         }
+                    },
+                    {
+                        "name": "bar-chart",
+                        "description": "Horizontal bar chart whose bars grow from zero to their value during the narration, one after another.",
+                        "props": {
+                            "bars": "Array of {label, value, color} objects (required). color defaults to the theme primary",
+                            "heading": "Chart heading (optional)",
+                            "max": "Value of a full-width bar (optional — defaults to the largest value)"
+                        }
+                    },
+                    {
+                        "name": "code-block",
+                        "description": "Syntax-highlighted code panel whose lines appear one by one during the narration. Highlights rust, python, js/ts, go, bash, json and C-like languages.",
+                        "props": {
+                            "code": "Source code (required)",
+                            "language": "Language for highlighting (optional — plain text when omitted)",
+                            "highlight_lines": "1-based line numbers to emphasize (optional)"
+                        }
+                    },
+                    {
+                        "name": "rich-text",
+                        "description": "Markdown content (paragraphs, lists, headings, emphasis, code) rendered as styled HTML, with an optional heading",
+                        "props": {
+                            "content": "Markdown text (required). Raw HTML is shown as text",
+                            "heading": "Heading above the content (optional)"
+                        }
                     }
                 ]);
+                let mut templates = templates;
+                for entry in templates.as_array_mut().into_iter().flatten() {
+                    let name = entry["name"].as_str().unwrap_or_default();
+                    if let Some(schema) = crate::template::prop_schema(name) {
+                        entry["schema"] = schema;
+                    }
+                }
                 let text = serde_json::to_string_pretty(&templates).map_err(mc_err)?;
                 Ok(ReadResourceResult {
                     contents: vec![ResourceContents::text(text, uri.clone())],
@@ -1373,6 +1610,20 @@ impl ServerHandler for McServer {
                     contents: vec![ResourceContents::text(text, uri.clone())],
                 })
             }
+            _ if uri.starts_with("vidgen://templates/") && uri.ends_with("/schema") => {
+                // vidgen://templates/{name}/schema
+                let name = &uri["vidgen://templates/".len()..uri.len() - "/schema".len()];
+                let schema = crate::template::prop_schema(name).ok_or_else(|| {
+                    McpError::resource_not_found(
+                        format!("No prop schema for template: {name}"),
+                        None,
+                    )
+                })?;
+                let text = serde_json::to_string_pretty(&schema).map_err(mc_err)?;
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::text(text, uri.clone())],
+                })
+            }
             _ if uri.starts_with("vidgen://projects/") => {
                 let rest = &uri["vidgen://projects/".len()..];
                 if let Some((path_part, scene_suffix)) = rest.rsplit_once("/scenes/") {
@@ -1456,9 +1707,9 @@ mod tests {
     fn test_list_resources_count() {
         // Verify the resource list construction includes both templates and voices.
         // We test the vec directly since calling the trait method requires a RequestContext.
-        let resources = vec![
+        let resources = [
             Annotated::new(
-                RawResource::new("vidgen://templates", "Built-in templates (all adapt to landscape/portrait/square via CSS container queries). Add custom .html files to templates/components/ — registered by file stem name."),
+                RawResource::new("vidgen://templates", "Built-in templates (all adapt to landscape/portrait/square via CSS container queries). Add custom .html files to templates/components/ — registered by file stem name. Templates can use the helpers {{upper x}}, {{lower x}}, {{truncate x 40}}, {{number_format x [decimals]}}, {{repeat x n}} and {{md x}}."),
                 None,
             ),
             Annotated::new(
@@ -1559,7 +1810,7 @@ mod tests {
 
     #[test]
     fn test_list_resource_templates() {
-        let templates = vec![
+        let templates = [
             Annotated::new(
                 RawResourceTemplate {
                     uri_template: "vidgen://projects/{path}".into(),
@@ -1582,10 +1833,25 @@ mod tests {
                 },
                 None,
             ),
+            Annotated::new(
+                RawResourceTemplate {
+                    uri_template: "vidgen://templates/{name}/schema".into(),
+                    name: "Template prop schema".into(),
+                    title: None,
+                    description: Some(
+                        "JSON Schema for a built-in template's props (types, required, defaults)"
+                            .into(),
+                    ),
+                    mime_type: Some("application/schema+json".into()),
+                    icons: None,
+                },
+                None,
+            ),
         ];
-        assert_eq!(templates.len(), 2);
+        assert_eq!(templates.len(), 3);
         assert!(templates[0].uri_template.contains("{path}"));
         assert!(templates[1].uri_template.contains("{index}"));
+        assert!(templates[2].uri_template.contains("{name}"));
     }
 
     #[test]
@@ -0,0 +1,247 @@
+use crate::error::{VidgenError, VidgenResult};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Images are downscaled to at most this many pixels per side before analysis.
+const SAMPLE_SIZE: u32 = 64;
+
+/// Minimum RGB distance for two colors to count as visually distinct.
+const MIN_DISTINCT_DISTANCE: f64 = 48.0;
+
+/// A color found in the image together with its share of sampled pixels.
+#[derive(Debug, Clone, Serialize)]
+pub struct PaletteColor {
+    pub hex: String,
+    /// Fraction of sampled pixels (0.0-1.0) that fall into this color's bucket.
+    pub share: f64,
+}
+
+/// Dominant colors of an image plus suggested theme roles.
+///
+/// The role fields use the same names as `[theme]` in project.toml, so an agent
+/// can pass them straight to `set_project_config`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Palette {
+    pub primary: String,
+    pub secondary: String,
+    pub background: String,
+    pub text: String,
+    /// All dominant colors, most frequent first.
+    pub dominant: Vec<PaletteColor>,
+}
+
+/// Load an image from disk and extract its palette.
+pub fn extract_palette(path: &Path) -> VidgenResult<Palette> {
+    let img = image::open(path).map_err(|e| {
+        VidgenError::Other(format!("Failed to read image {}: {e}", path.display()))
+    })?;
+    let thumb = img.thumbnail(SAMPLE_SIZE, SAMPLE_SIZE).to_rgb8();
+    let pixels: Vec<[u8; 3]> = thumb.pixels().map(|p| p.0).collect();
+    palette_from_pixels(&pixels)
+}
+
+/// Build a palette from raw RGB pixels.
+///
+/// Pixels are grouped into 4-bit-per-channel buckets; each bucket's color is the
+/// average of its members, so solid areas keep their exact color.
+fn palette_from_pixels(pixels: &[[u8; 3]]) -> VidgenResult<Palette> {
+    if pixels.is_empty() {
+        return Err(VidgenError::Other("Image has no pixels".into()));
+    }
+
+    let mut buckets: HashMap<(u8, u8, u8), (u64, [u64; 3])> = HashMap::new();
+    for [r, g, b] in pixels {
+        let entry = buckets.entry((r >> 4, g >> 4, b >> 4)).or_insert((0, [0; 3]));
+        entry.0 += 1;
+        entry.1[0] += *r as u64;
+        entry.1[1] += *g as u64;
+        entry.1[2] += *b as u64;
+    }
+
+    let total = pixels.len() as f64;
+    let mut colors: Vec<([u8; 3], f64)> = buckets
+        .values()
+        .map(|(count, sum)| {
+            let avg = [
+                (sum[0] / count) as u8,
+                (sum[1] / count) as u8,
+                (sum[2] / count) as u8,
+            ];
+            (avg, *count as f64 / total)
+        })
+        .collect();
+    // Most frequent first; tie-break on the color value for deterministic output
+    colors.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    // Merge near-identical buckets so the dominant list only holds distinct colors
+    let mut distinct: Vec<([u8; 3], f64)> = Vec::new();
+    for (rgb, share) in colors {
+        match distinct
+            .iter_mut()
+            .find(|(d, _)| color_distance(*d, rgb) < MIN_DISTINCT_DISTANCE)
+        {
+            Some(existing) => existing.1 += share,
+            None => distinct.push((rgb, share)),
+        }
+    }
+
+    let background = distinct[0].0;
+
+    // Accent colors: prefer saturated colors that stand out from the background
+    let mut accents: Vec<&([u8; 3], f64)> = distinct[1..].iter().collect();
+    accents.sort_by(|a, b| {
+        let score_a = a.1 * (0.25 + saturation(a.0));
+        let score_b = b.1 * (0.25 + saturation(b.0));
+        score_b.total_cmp(&score_a)
+    });
+    let primary = accents.first().map(|c| c.0).unwrap_or(background);
+    let secondary = accents.get(1).map(|c| c.0).unwrap_or(primary);
+
+    Ok(Palette {
+        primary: to_hex(primary),
+        secondary: to_hex(secondary),
+        background: to_hex(background),
+        text: to_hex(best_text_color(background, &distinct)),
+        dominant: distinct
+            .iter()
+            .take(8)
+            .map(|(rgb, share)| PaletteColor {
+                hex: to_hex(*rgb),
+                share: (*share * 1000.0).round() / 1000.0,
+            })
+            .collect(),
+    })
+}
+
+/// Pick a readable text color for the background: a near-neutral palette color if
+/// one reaches WCAG AA (4.5:1), otherwise white or black, whichever contrasts more.
+fn best_text_color(background: [u8; 3], candidates: &[([u8; 3], f64)]) -> [u8; 3] {
+    let bg = normalized(background);
+    if let Some((rgb, _)) = candidates.iter().find(|(rgb, _)| {
+        saturation(*rgb) < 0.25 && contrast_ratio(bg, normalized(*rgb)) >= 4.5
+    }) {
+        return *rgb;
+    }
+    let white = [255, 255, 255];
+    let black = [0, 0, 0];
+    if contrast_ratio(bg, normalized(white)) >= contrast_ratio(bg, normalized(black)) {
+        white
+    } else {
+        black
+    }
+}
+
+fn color_distance(a: [u8; 3], b: [u8; 3]) -> f64 {
+    let dr = a[0] as f64 - b[0] as f64;
+    let dg = a[1] as f64 - b[1] as f64;
+    let db = a[2] as f64 - b[2] as f64;
+    (dr * dr + dg * dg + db * db).sqrt()
+}
+
+/// HSV saturation in 0.0-1.0.
+fn saturation(rgb: [u8; 3]) -> f64 {
+    let max = *rgb.iter().max().unwrap() as f64;
+    let min = *rgb.iter().min().unwrap() as f64;
+    if max == 0.0 {
+        0.0
+    } else {
+        (max - min) / max
+    }
+}
+
+fn normalized(rgb: [u8; 3]) -> (f64, f64, f64) {
+    (
+        rgb[0] as f64 / 255.0,
+        rgb[1] as f64 / 255.0,
+        rgb[2] as f64 / 255.0,
+    )
+}
+
+fn to_hex(rgb: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2])
+}
+
+/// WCAG relative luminance of a color with channels in `[0, 1]`.
+fn relative_luminance(r: f64, g: f64, b: f64) -> f64 {
+    let r = if r <= 0.03928 {
+        r / 12.92
+    } else {
+        ((r + 0.055) / 1.055).powf(2.4)
+    };
+    let g = if g <= 0.03928 {
+        g / 12.92
+    } else {
+        ((g + 0.055) / 1.055).powf(2.4)
+    };
+    let b = if b <= 0.03928 {
+        b / 12.92
+    } else {
+        ((b + 0.055) / 1.055).powf(2.4)
+    };
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// WCAG contrast ratio (1-21) between two colors with channels in `[0, 1]`.
+pub(crate) fn contrast_ratio(c1: (f64, f64, f64), c2: (f64, f64, f64)) -> f64 {
+    let l1 = relative_luminance(c1.0, c1.1, c1.2);
+    let l2 = relative_luminance(c2.0, c2.1, c2.2);
+    let (lighter, darker) = if l1 > l2 { (l1, l2) } else { (l2, l1) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solid_color_image_is_dominant() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("solid.png");
+        image::RgbImage::from_pixel(32, 32, image::Rgb([0x3b, 0x82, 0xf6]))
+            .save(&path)
+            .unwrap();
+
+        let palette = extract_palette(&path).unwrap();
+        assert_eq!(palette.dominant.len(), 1);
+        assert_eq!(palette.dominant[0].hex, "#3b82f6");
+        assert!((palette.dominant[0].share - 1.0).abs() < f64::EPSILON);
+        assert_eq!(palette.background, "#3b82f6");
+    }
+
+    #[test]
+    fn test_palette_roles() {
+        // 70% dark navy background, 20% saturated orange, 10% teal
+        let mut pixels = vec![[0x10, 0x18, 0x30]; 70];
+        pixels.extend(vec![[0xf9, 0x73, 0x16]; 20]);
+        pixels.extend(vec![[0x14, 0xb8, 0xa6]; 10]);
+
+        let palette = palette_from_pixels(&pixels).unwrap();
+        assert_eq!(palette.background, "#101830");
+        assert_eq!(palette.primary, "#f97316");
+        assert_eq!(palette.secondary, "#14b8a6");
+        assert_eq!(palette.dominant.len(), 3);
+        assert!((palette.dominant[0].share - 0.7).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_text_color_is_readable() {
+        let pixels = vec![[0x10, 0x10, 0x10]; 10];
+        let palette = palette_from_pixels(&pixels).unwrap();
+        assert_eq!(palette.text, "#ffffff");
+
+        let pixels = vec![[0xf5, 0xf5, 0xf5]; 10];
+        let palette = palette_from_pixels(&pixels).unwrap();
+        assert_eq!(palette.text, "#000000");
+    }
+
+    #[test]
+    fn test_empty_pixels_rejected() {
+        assert!(palette_from_pixels(&[]).is_err());
+    }
+
+    #[test]
+    fn test_missing_image_file() {
+        assert!(extract_palette(Path::new("/nonexistent/image.png")).is_err());
+    }
+}
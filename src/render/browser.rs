@@ -1,15 +1,17 @@
-use crate::config::{PlatformPreset, ThemeConfig};
-use crate::error::{VidgenError, VidgenResult};
-use crate::render::encoder::SceneEncoder;
+use crate::config::{PlatformPreset, RenderConfig, ThemeConfig};
+use crate::error::{VidgenError, VidgenResult, BROWSER_NOT_FOUND};
+use crate::render::encoder::{MusicDucking, MusicFade, SceneEncoder};
 use crate::render::frame_cache;
 use crate::scene::Scene;
+use crate::subtitle::SceneCaptions;
 use crate::template::TemplateRegistry;
 use chromiumoxide::browser::{Browser, BrowserConfig};
 use chromiumoxide::cdp::browser_protocol::emulation::SetDeviceMetricsOverrideParams;
 use chromiumoxide::page::ScreenshotParams;
 use futures::StreamExt;
+use std::future::Future;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
 
 /// Write HTML to a temporary file and return the handle + file:// URL.
@@ -56,94 +58,315 @@ async fn wait_for_page_ready(page: &chromiumoxide::Page) -> VidgenResult<()> {
         }
 
         // Combined check: page loaded AND template ready (or no async flag set)
-        match page
+        // An Err means the page context isn't ready for JS yet (mid-navigation)
+        if let Ok(result) = page
             .evaluate(
                 "document.readyState === 'complete' && window.__VIDGEN_READY__ !== false",
             )
             .await
         {
-            Ok(result) => {
-                if result.into_value::<bool>().unwrap_or(false) {
-                    return Ok(());
-                }
+            if result.into_value::<bool>().unwrap_or(false) {
+                return Ok(());
             }
-            Err(_) => {} // Page context not ready for JS yet (mid-navigation)
         }
 
         tokio::time::sleep(Duration::from_millis(100)).await;
     }
 }
 
+/// Promise that resolves once every `<img>` and CSS `background-image` on the
+/// page is decoded and web fonts are loaded. Broken images resolve too, so a
+/// missing asset can't stall the render.
+const PRELOAD_IMAGES_JS: &str = r#"(async () => {
+  const urls = new Set();
+  for (const el of document.querySelectorAll('*')) {
+    const bg = getComputedStyle(el).backgroundImage;
+    for (const m of bg.matchAll(/url\(["']?(.*?)["']?\)/g)) urls.add(m[1]);
+  }
+  const pending = [...document.images].map(img => img.decode().catch(() => {}));
+  for (const url of urls) {
+    const img = new Image();
+    img.src = url;
+    pending.push(img.decode().catch(() => {}));
+  }
+  await Promise.all(pending);
+  if (document.fonts) await document.fonts.ready;
+  window.__VIDGEN_IMAGES_DECODED__ = true;
+  return true;
+})()"#;
+
+/// Wait for the scene's images (including backgrounds) to decode, so frame 0
+/// never shows a half-loaded background. Gives up after 15s.
+async fn preload_images(page: &chromiumoxide::Page) -> VidgenResult<()> {
+    let timeout = std::time::Duration::from_secs(15);
+    match tokio::time::timeout(timeout, page.evaluate(PRELOAD_IMAGES_JS)).await {
+        Ok(Ok(_)) => {}
+        Ok(Err(e)) => warn!("Image preload failed ({e}) — capturing anyway"),
+        Err(_) => warn!("Images not decoded after 15s — capturing anyway"),
+    }
+    Ok(())
+}
+
+/// Everything a freshly loaded page needs before its first screenshot: page
+/// and template readiness, the scene's `wait_for` selector, then decoded images
+/// and fonts. Runs once per page load, not per frame.
+async fn prepare_page(page: &chromiumoxide::Page, wait_for: Option<&str>) -> VidgenResult<()> {
+    wait_for_page_ready(page).await?;
+    if let Some(selector) = wait_for {
+        wait_for_selector(page, selector).await?;
+    }
+    preload_images(page).await
+}
+
+/// JS expression that is true once `selector` matches an element in the DOM.
+fn selector_exists_js(selector: &str) -> String {
+    // JSON string literal doubles as a safely-escaped JS string literal
+    let quoted = serde_json::to_string(selector).unwrap_or_else(|_| "\"\"".into());
+    format!("document.querySelector({quoted}) !== null")
+}
+
+/// Poll until `selector` exists in the page (scene `wait_for`). Templates that
+/// build DOM from JavaScript would otherwise race the screenshot. Fails after
+/// 10s rather than capturing an incomplete page.
+async fn wait_for_selector(page: &chromiumoxide::Page, selector: &str) -> VidgenResult<()> {
+    use std::time::{Duration, Instant};
+
+    let start = Instant::now();
+    let timeout = Duration::from_secs(10);
+    let js = selector_exists_js(selector);
+
+    loop {
+        if let Ok(result) = page.evaluate(js.as_str()).await {
+            if result.into_value::<bool>().unwrap_or(false) {
+                return Ok(());
+            }
+        }
+
+        if start.elapsed() > timeout {
+            return Err(VidgenError::Browser(format!(
+                "wait_for selector '{selector}' not found after 10s"
+            )));
+        }
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+/// Per-frame capture reliability settings (`[render] capture_timeout` and
+/// `capture_retries`, or `--capture-timeout` / `--capture-retries`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaptureRetry {
+    /// Extra attempts after the first one fails
+    pub retries: u32,
+    /// Longest one attempt may take
+    pub timeout: std::time::Duration,
+}
+
+impl CaptureRetry {
+    pub fn from_config(render: &RenderConfig) -> Self {
+        Self {
+            retries: render.capture_retries,
+            timeout: std::time::Duration::from_secs_f64(render.capture_timeout),
+        }
+    }
+}
+
+impl Default for CaptureRetry {
+    fn default() -> Self {
+        Self::from_config(&RenderConfig::default())
+    }
+}
+
+/// Capture one frame with `capture`, bounded by `retry.timeout`. A failed or
+/// timed-out attempt runs `reload` (reloading the scene page) and tries again,
+/// up to `retry.retries` times. `capture` gets the attempt number (0 first).
+async fn capture_with_retry<C, CF, R, RF>(
+    retry: CaptureRetry,
+    frame: u32,
+    mut capture: C,
+    mut reload: R,
+) -> VidgenResult<Vec<u8>>
+where
+    C: FnMut(u32) -> CF,
+    CF: Future<Output = VidgenResult<Vec<u8>>>,
+    R: FnMut() -> RF,
+    RF: Future<Output = VidgenResult<()>>,
+{
+    let mut attempt = 0;
+    loop {
+        let result = tokio::time::timeout(retry.timeout, capture(attempt))
+            .await
+            .unwrap_or_else(|_| {
+                Err(VidgenError::Browser(format!(
+                    "Frame {frame} capture timed out after {:.1}s",
+                    retry.timeout.as_secs_f64()
+                )))
+            });
+        match result {
+            Ok(png) => return Ok(png),
+            Err(e) if attempt < retry.retries => {
+                attempt += 1;
+                warn!(
+                    "Frame {frame} capture failed ({e}) — retrying ({attempt}/{})",
+                    retry.retries
+                );
+                reload().await?;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Re-navigate to the scene HTML and wait for it again, after a failed capture.
+async fn reload_page(
+    page: &chromiumoxide::Page,
+    url: &str,
+    wait_for: Option<&str>,
+) -> VidgenResult<()> {
+    page.goto(url)
+        .await
+        .map_err(|e| VidgenError::Browser(format!("Failed to reload scene HTML: {e}")))?;
+    prepare_page(page, wait_for).await
+}
+
+/// Take a viewport screenshot as PNG.
+async fn screenshot_png(page: &chromiumoxide::Page) -> VidgenResult<Vec<u8>> {
+    page.screenshot(ScreenshotParams::builder().full_page(false).build())
+        .await
+        .map_err(|e| VidgenError::Browser(format!("Screenshot failed: {e}")))
+}
+
+/// A launched browser kept warm across several single-frame captures, so callers
+/// that screenshot many frames (e.g. `preview --interactive`) launch Chromium once.
+pub struct BrowserSession {
+    browser: Browser,
+    handler_handle: tokio::task::JoinHandle<()>,
+    width: u32,
+    height: u32,
+    captures: usize,
+}
+
+impl BrowserSession {
+    /// Launch a headless browser with a `width`x`height` viewport.
+    pub async fn launch(width: u32, height: u32) -> VidgenResult<Self> {
+        let (browser, handler_handle) = launch_browser(width, height).await?;
+        Ok(Self {
+            browser,
+            handler_handle,
+            width,
+            height,
+            captures: 0,
+        })
+    }
+
+    /// Number of frames captured with this browser so far.
+    pub fn captures(&self) -> usize {
+        self.captures
+    }
+
+    /// Render `html` in a fresh tab, set the frame's CSS custom properties and
+    /// return a PNG screenshot. The browser itself stays open.
+    pub async fn capture_frame(
+        &mut self,
+        html: &str,
+        frame: u32,
+        total_frames: u32,
+        wait_for: Option<&str>,
+    ) -> VidgenResult<Vec<u8>> {
+        // Write HTML to temp file so the page gets a file:// origin,
+        // enabling JS fetch() for local assets (e.g., Three.js loading GLB models)
+        let (_temp_file, file_url) = write_temp_html(html)?;
+
+        let page = self
+            .browser
+            .new_page(&file_url)
+            .await
+            .map_err(|e| VidgenError::Browser(format!("Failed to create page: {e}")))?;
+
+        page.execute(SetDeviceMetricsOverrideParams::new(
+            self.width as i64,
+            self.height as i64,
+            1.0,
+            false,
+        ))
+        .await
+        .map_err(|e| VidgenError::Browser(format!("Failed to set viewport: {e}")))?;
+
+        prepare_page(&page, wait_for).await?;
+
+        // Inject CSS custom properties
+        let progress = if total_frames > 0 {
+            frame as f64 / total_frames as f64
+        } else {
+            0.0
+        };
+        let js = format!(
+            "document.documentElement.style.setProperty('--frame', '{}');\
+             document.documentElement.style.setProperty('--total-frames', '{}');\
+             document.documentElement.style.setProperty('--progress', '{}');\
+             document.documentElement.style.setProperty('--content-progress', '{}');",
+            frame, total_frames, progress, progress
+        );
+        page.evaluate(js)
+            .await
+            .map_err(|e| VidgenError::Browser(format!("JS injection failed: {e}")))?;
+
+        let screenshot = page
+            .screenshot(ScreenshotParams::builder().full_page(false).build())
+            .await
+            .map_err(|e| VidgenError::Browser(format!("Screenshot failed: {e}")))?;
+
+        let _ = page.close().await;
+        self.captures += 1;
+        Ok(screenshot)
+    }
+
+    /// Shut the browser down.
+    pub fn close(self) {
+        drop(self.browser);
+        self.handler_handle.abort();
+    }
+}
+
 /// Capture a single frame as PNG bytes. Launches a browser, renders the HTML,
 /// injects CSS custom properties, takes a screenshot, and returns PNG data.
 ///
 /// This is the shared helper used by both the `preview` CLI command and
-/// the MCP `preview_scene` tool.
+/// the MCP `preview_scene` tool. `wait_for` is the scene's selector to wait
+/// for before the screenshot.
 pub async fn capture_single_frame(
     html: &str,
     width: u32,
     height: u32,
     frame: u32,
     total_frames: u32,
+    wait_for: Option<&str>,
 ) -> VidgenResult<Vec<u8>> {
-    let (browser, handler_handle) = launch_browser(width, height).await?;
-
-    // Write HTML to temp file so the page gets a file:// origin,
-    // enabling JS fetch() for local assets (e.g., Three.js loading GLB models)
-    let (_temp_file, file_url) = write_temp_html(html)?;
-
-    let page = browser
-        .new_page(&file_url)
-        .await
-        .map_err(|e| VidgenError::Browser(format!("Failed to create page: {e}")))?;
-
-    page.execute(SetDeviceMetricsOverrideParams::new(
-        width as i64,
-        height as i64,
-        1.0,
-        false,
-    ))
-    .await
-    .map_err(|e| VidgenError::Browser(format!("Failed to set viewport: {e}")))?;
-
-    wait_for_page_ready(&page).await?;
-
-    // Inject CSS custom properties
-    let progress = if total_frames > 0 {
-        frame as f64 / total_frames as f64
-    } else {
-        0.0
-    };
-    let js = format!(
-        "document.documentElement.style.setProperty('--frame', '{}');\
-         document.documentElement.style.setProperty('--total-frames', '{}');\
-         document.documentElement.style.setProperty('--progress', '{}');\
-         document.documentElement.style.setProperty('--content-progress', '{}');",
-        frame, total_frames, progress, progress
-    );
-    page.evaluate(js)
-        .await
-        .map_err(|e| VidgenError::Browser(format!("JS injection failed: {e}")))?;
-
-    let screenshot = page
-        .screenshot(ScreenshotParams::builder().full_page(false).build())
-        .await
-        .map_err(|e| VidgenError::Browser(format!("Screenshot failed: {e}")))?;
+    let mut session = BrowserSession::launch(width, height).await?;
+    let result = session.capture_frame(html, frame, total_frames, wait_for).await;
+    session.close();
+    result
+}
 
-    let _ = page.close().await;
-    drop(browser);
-    handler_handle.abort();
+/// Environment variable naming the Chrome/Chromium executable to launch,
+/// for machines where auto-detection finds none (e.g. CI images).
+pub const CHROME_PATH_ENV: &str = "VIDGEN_CHROME_PATH";
 
-    Ok(screenshot)
+/// The browser executable set via `VIDGEN_CHROME_PATH`, if any.
+pub fn chrome_path_override() -> Option<PathBuf> {
+    std::env::var_os(CHROME_PATH_ENV)
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
 }
 
-/// Launch a headless Chromium browser instance.
-pub async fn launch_browser(
+/// Launch config for a headless browser. `chrome_path` skips auto-detection;
+/// a missing executable either way is reported as [`BROWSER_NOT_FOUND`].
+fn browser_config(
     width: u32,
     height: u32,
-) -> VidgenResult<(Browser, tokio::task::JoinHandle<()>)> {
-    debug!("Launching headless browser ({}x{})", width, height);
-    let config = BrowserConfig::builder()
+    chrome_path: Option<&Path>,
+) -> VidgenResult<BrowserConfig> {
+    let mut builder = BrowserConfig::builder()
         .window_size(width, height)
         .viewport(None) // We'll set viewport per-page via CDP
         .arg("--hide-scrollbars")
@@ -153,9 +376,32 @@ pub async fn launch_browser(
         .arg("--no-sandbox")
         .arg("--disable-dev-shm-usage")
         .arg("--allow-file-access-from-files")
-        .arg("--allow-file-access")
-        .build()
-        .map_err(|e| VidgenError::Browser(format!("Failed to configure browser: {e}")))?;
+        .arg("--allow-file-access");
+    if let Some(path) = chrome_path {
+        if !path.is_file() {
+            return Err(VidgenError::Browser(format!(
+                "{BROWSER_NOT_FOUND}: {CHROME_PATH_ENV} is {}, which doesn't exist",
+                path.display()
+            )));
+        }
+        builder = builder.chrome_executable(path);
+    }
+    builder.build().map_err(|e| {
+        if e.contains("Could not auto detect") {
+            VidgenError::Browser(format!("{BROWSER_NOT_FOUND}: {e}"))
+        } else {
+            VidgenError::Browser(format!("Failed to configure browser: {e}"))
+        }
+    })
+}
+
+/// Launch a headless Chromium browser instance.
+pub async fn launch_browser(
+    width: u32,
+    height: u32,
+) -> VidgenResult<(Browser, tokio::task::JoinHandle<()>)> {
+    debug!("Launching headless browser ({}x{})", width, height);
+    let config = browser_config(width, height, chrome_path_override().as_deref())?;
 
     let (browser, mut handler) = Browser::launch(config)
         .await
@@ -174,6 +420,7 @@ pub async fn launch_browser(
 }
 
 /// Capture all frames for a scene: render HTML per frame, screenshot, pipe to encoder.
+/// Frames are captured at `fps`; the encoder converts them to `output_fps`.
 #[allow(clippy::too_many_arguments)]
 pub async fn capture_scene_frames(
     browser: &Browser,
@@ -184,6 +431,7 @@ pub async fn capture_scene_frames(
     width: u32,
     height: u32,
     fps: u32,
+    output_fps: u32,
     platform: &PlatformPreset,
     output_path: &Path,
     audio_path: Option<&Path>,
@@ -193,7 +441,9 @@ pub async fn capture_scene_frames(
     audio_delay_secs: f64,
     content_padding_after: f64,
     project_path: Option<&Path>,
-    use_gpu: bool,
+    ducking: Option<&MusicDucking>,
+    captions: Option<&SceneCaptions>,
+    retry: CaptureRetry,
 ) -> VidgenResult<std::path::PathBuf> {
     let total_frames = Scene::total_frames_for_duration(effective_duration, fps);
     debug!(
@@ -201,6 +451,8 @@ pub async fn capture_scene_frames(
         scene_index, total_frames, effective_duration
     );
 
+    let music_fade = MusicFade::for_scene(scene, effective_duration);
+
     // Create a new page (tab) for this scene
     let page = browser
         .new_page("about:blank")
@@ -218,15 +470,22 @@ pub async fn capture_scene_frames(
     .map_err(|e| VidgenError::Browser(format!("Failed to set viewport: {e}")))?;
 
     // Render frame 0 to check if the scene is static
-    let html_frame0 = registry.render_scene_html(scene, theme, width, height, 0, total_frames, project_path)?;
-    let is_static = frame_cache::is_static_scene(&html_frame0);
+    let mut html_frame0 = registry.render_scene_html(scene, theme, width, height, 0, total_frames, project_path)?;
+    // A caption layer changes with the narration, so captioned scenes are never static
+    let is_static = captions.is_none() && frame_cache::is_static_scene(&html_frame0);
+    if let Some(c) = captions {
+        html_frame0 = c.frame_html(&html_frame0, theme, 0, fps);
+    }
 
     // Load HTML via file:// URL (enables JS fetch for local assets like 3D models)
     let (_temp_file, file_url) = write_temp_html(&html_frame0)?;
     page.goto(&file_url)
         .await
         .map_err(|e| VidgenError::Browser(format!("Failed to navigate to scene HTML: {e}")))?;
-    wait_for_page_ready(&page).await?;
+    // Wait for the scene's selector and decode background images once up front;
+    // later frames only change CSS properties
+    let wait_for = scene.frontmatter.wait_for.as_deref();
+    prepare_page(&page, wait_for).await?;
 
     if is_static {
         // Static scene: capture one frame, pipe it N times to the encoder.
@@ -238,17 +497,23 @@ pub async fn capture_scene_frames(
             effective_duration
         );
 
-        let screenshot = page
-            .screenshot(ScreenshotParams::builder().full_page(false).build())
-            .await
-            .map_err(|e| VidgenError::Browser(format!("Screenshot failed: {e}")))?;
+        let page_ref = &page;
+        let screenshot = capture_with_retry(
+            retry,
+            0,
+            |_| screenshot_png(page_ref),
+            || reload_page(page_ref, &file_url, wait_for),
+        )
+        .await?;
 
+        // Nothing moves, so skip rate conversion and repeat the frame at the output rate
         let mut encoder = SceneEncoder::new(
-            output_path, fps, width, height, platform,
-            audio_path, music_path, music_volume, audio_delay_secs,
-            Some(effective_duration), use_gpu,
+            output_path, output_fps, output_fps, width, height, platform,
+            audio_path, music_path, music_volume, &music_fade, audio_delay_secs,
+            Some(effective_duration), ducking,
+            scene.frontmatter.motion_blur,
         )?;
-        for _ in 0..total_frames {
+        for _ in 0..Scene::total_frames_for_duration(effective_duration, output_fps) {
             encoder.write_frame(&screenshot)?;
         }
         let output = encoder.finish()?;
@@ -262,18 +527,23 @@ pub async fn capture_scene_frames(
     let mut encoder = SceneEncoder::new(
         output_path,
         fps,
+        output_fps,
         width,
         height,
         platform,
         audio_path,
         music_path,
         music_volume,
+        &music_fade,
         audio_delay_secs,
         Some(effective_duration),
-        use_gpu,
+        ducking,
+        scene.frontmatter.motion_blur,
     )?;
 
-    // Compute content-progress boundaries (voice window within full scene duration)
+    // Compute content-progress boundaries (voice window within full scene duration).
+    // Text animations and background.image motion both key off --content-progress,
+    // so they finish together and hold still through padding_after.
     let content_start_frame = audio_delay_secs * fps as f64;
     let content_end_frame = (effective_duration - content_padding_after) * fps as f64;
 
@@ -287,6 +557,7 @@ pub async fn capture_scene_frames(
     // HTML already loaded via page.goto() above — the template output is identical
     // across frames; only the CSS custom properties change (injected via JS below).
 
+    let mut caption_markup = captions.map(|c| c.markup_at(0.0));
     for frame in 0..total_frames {
         // Inject CSS custom properties via JavaScript for dynamic animation
         let content_range = content_end_frame - content_start_frame;
@@ -295,7 +566,7 @@ pub async fn capture_scene_frames(
         } else {
             frame as f64 / total_frames as f64
         };
-        let js = format!(
+        let mut js = format!(
             "document.documentElement.style.setProperty('--frame', '{}');\
              document.documentElement.style.setProperty('--total-frames', '{}');\
              document.documentElement.style.setProperty('--progress', '{}');\
@@ -305,15 +576,36 @@ pub async fn capture_scene_frames(
             frame as f64 / total_frames as f64,
             content_progress
         );
-        page.evaluate(js)
-            .await
-            .map_err(|e| VidgenError::Browser(format!("JS injection failed: {e}")))?;
+        // Swap the caption line only when it changes
+        if let Some(c) = captions {
+            let markup = c.markup_at(frame as f64 / fps as f64);
+            if caption_markup.as_ref() != Some(&markup) {
+                js.push_str(&SceneCaptions::update_js(&markup));
+                caption_markup = Some(markup);
+            }
+        }
+        // A reload brings back frame 0's caption line, so a retry sets the current one
+        let retry_js = match (captions, &caption_markup) {
+            (Some(_), Some(markup)) => format!("{js}{}", SceneCaptions::update_js(markup)),
+            _ => js.clone(),
+        };
 
-        // Take screenshot as PNG
-        let screenshot = page
-            .screenshot(ScreenshotParams::builder().full_page(false).build())
-            .await
-            .map_err(|e| VidgenError::Browser(format!("Screenshot failed: {e}")))?;
+        // Take screenshot as PNG, reloading the page and retrying on failure
+        let page_ref = &page;
+        let (js, retry_js) = (js.as_str(), retry_js.as_str());
+        let screenshot = capture_with_retry(
+            retry,
+            frame,
+            |attempt| async move {
+                page_ref
+                    .evaluate(if attempt == 0 { js } else { retry_js })
+                    .await
+                    .map_err(|e| VidgenError::Browser(format!("JS injection failed: {e}")))?;
+                screenshot_png(page_ref).await
+            },
+            || reload_page(page_ref, &file_url, wait_for),
+        )
+        .await?;
 
         // Pipe PNG bytes to encoder
         encoder.write_frame(&screenshot)?;
@@ -344,3 +636,205 @@ pub async fn capture_scene_frames(
 
     Ok(output)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selector_exists_js() {
+        assert_eq!(
+            selector_exists_js(".ready"),
+            "document.querySelector(\".ready\") !== null"
+        );
+    }
+
+    #[test]
+    fn test_selector_exists_js_escapes_quotes() {
+        let js = selector_exists_js(r#"[data-state="done"]"#);
+        assert_eq!(js, r#"document.querySelector("[data-state=\"done\"]") !== null"#);
+    }
+
+    #[test]
+    fn test_preload_js_decodes_images_and_backgrounds() {
+        assert!(PRELOAD_IMAGES_JS.contains("document.images"));
+        assert!(PRELOAD_IMAGES_JS.contains("backgroundImage"));
+        assert!(PRELOAD_IMAGES_JS.contains(".decode().catch("));
+        assert!(PRELOAD_IMAGES_JS.contains("document.fonts.ready"));
+    }
+
+    fn quick_retry(retries: u32) -> CaptureRetry {
+        CaptureRetry {
+            retries,
+            timeout: std::time::Duration::from_millis(200),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_capture_retry_recovers_and_writes_frame() {
+        let attempts = std::cell::Cell::new(0);
+        let reloads = std::cell::Cell::new(0);
+        let mut written: Vec<Vec<u8>> = Vec::new();
+
+        // The first screenshot fails transiently, the second succeeds
+        let png = capture_with_retry(
+            quick_retry(2),
+            7,
+            |attempt| {
+                attempts.set(attempts.get() + 1);
+                async move {
+                    if attempt == 0 {
+                        Err(VidgenError::Browser(
+                            "Screenshot failed: target closed".into(),
+                        ))
+                    } else {
+                        Ok(b"\x89PNG frame 7".to_vec())
+                    }
+                }
+            },
+            || {
+                reloads.set(reloads.get() + 1);
+                async { Ok(()) }
+            },
+        )
+        .await
+        .unwrap();
+        written.push(png);
+
+        assert_eq!(attempts.get(), 2);
+        assert_eq!(reloads.get(), 1);
+        assert_eq!(written, [b"\x89PNG frame 7".to_vec()]);
+    }
+
+    #[tokio::test]
+    async fn test_capture_retry_times_out_hung_attempt() {
+        let png = capture_with_retry(
+            quick_retry(1),
+            0,
+            |attempt| async move {
+                if attempt == 0 {
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                }
+                Ok(vec![1])
+            },
+            || async { Ok(()) },
+        )
+        .await
+        .unwrap();
+        assert_eq!(png, [1]);
+    }
+
+    #[tokio::test]
+    async fn test_capture_retry_gives_up_after_retries() {
+        let attempts = std::cell::Cell::new(0);
+        let err = capture_with_retry(
+            quick_retry(2),
+            3,
+            |_| {
+                attempts.set(attempts.get() + 1);
+                async { Err::<Vec<u8>, _>(VidgenError::Browser("Screenshot failed".into())) }
+            },
+            || async { Ok(()) },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(attempts.get(), 3);
+        assert!(err.to_string().contains("Screenshot failed"));
+
+        let timed_out = capture_with_retry(
+            quick_retry(0),
+            3,
+            |_| async {
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                Ok(Vec::new())
+            },
+            || async { Ok(()) },
+        )
+        .await
+        .unwrap_err();
+        assert!(timed_out.to_string().contains("Frame 3 capture timed out"));
+    }
+
+    #[test]
+    fn test_missing_chrome_path_gives_hinted_browser_error() {
+        let err = browser_config(320, 180, Some(Path::new("/nonexistent/chrome"))).unwrap_err();
+        assert!(matches!(err, VidgenError::Browser(_)));
+        assert!(err.to_string().contains("/nonexistent/chrome"), "{err}");
+        let hint = err.hint().unwrap();
+        assert!(hint.contains("VIDGEN_CHROME_PATH"), "{hint}");
+
+        // Other browser failures keep the generic hint
+        let other = VidgenError::Browser("Failed to launch browser: boom".into());
+        assert!(!other.hint().unwrap().contains("VIDGEN_CHROME_PATH"));
+    }
+
+    #[tokio::test]
+    async fn test_prepare_page_decodes_images_before_capture() {
+        // Needs a local Chromium; skip when none is installed
+        let Ok((browser, handle)) = launch_browser(320, 180).await else {
+            eprintln!("skipping: no Chromium available");
+            return;
+        };
+        // A 1x1 PNG as both an <img> and a CSS background
+        let png = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNk+M9QDwADhgGAWjR9awAAAABJRU5ErkJggg==";
+        let html = format!(
+            "<!DOCTYPE html><html><body style=\"margin:0;background-image:url('{png}')\"><img src=\"{png}\"></body></html>"
+        );
+        let (_temp, url) = write_temp_html(&html).unwrap();
+        let page = browser.new_page(url.as_str()).await.unwrap();
+        prepare_page(&page, None).await.unwrap();
+
+        // The decode wait has finished by the time the first screenshot would be taken
+        let ready: bool = page
+            .evaluate("window.__VIDGEN_IMAGES_DECODED__ === true && [...document.images].every(i => i.complete)")
+            .await
+            .unwrap()
+            .into_value()
+            .unwrap();
+        assert!(ready);
+        let _ = page.close().await;
+        drop(browser);
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_session_captures_multiple_frames_with_one_launch() {
+        // Needs a local Chromium; skip when none is installed
+        let Ok(mut session) = BrowserSession::launch(320, 180).await else {
+            eprintln!("skipping: no Chromium available");
+            return;
+        };
+        let html = "<!DOCTYPE html><html><head></head><body style=\"margin:0;background:#123456\"></body></html>";
+        for frame in 0..3 {
+            let png = session.capture_frame(html, frame, 3, None).await.unwrap();
+            assert!(png.starts_with(b"\x89PNG"));
+        }
+        assert_eq!(session.captures(), 3);
+        session.close();
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_selector_runs_before_first_capture() {
+        // Needs a local Chromium; skip when none is installed
+        let Ok(mut session) = BrowserSession::launch(64, 64).await else {
+            eprintln!("skipping: no Chromium available");
+            return;
+        };
+        // The page turns green and adds the awaited element only after a delay
+        let html = r#"<!DOCTYPE html><html><head></head><body style="margin:0;background:#ff0000">
+<script>setTimeout(() => {
+  document.body.style.background = '#00ff00';
+  const el = document.createElement('div');
+  el.dataset.state = 'done';
+  document.body.appendChild(el);
+}, 300);</script></body></html>"#;
+        let png = session
+            .capture_frame(html, 0, 1, Some(r#"[data-state="done"]"#))
+            .await
+            .unwrap();
+        session.close();
+
+        let frame = image::load_from_memory(&png).unwrap().to_rgb8();
+        assert_eq!(frame.get_pixel(32, 32).0, [0, 255, 0]);
+    }
+}
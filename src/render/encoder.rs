@@ -1,4 +1,4 @@
-use crate::config::{PlatformPreset, VideoConfig};
+use crate::config::{HwAccel, IntroBoostConfig, OutputContainer, PlatformPreset, VideoConfig};
 use crate::error::{VidgenError, VidgenResult};
 use crate::scene::Scene;
 use std::io::{Read, Write};
@@ -15,6 +15,8 @@ use tracing::{debug, warn};
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TransitionType {
     Fade,
+    /// Dip to black between scenes, e.g. as a section divider.
+    FadeBlack,
     SlideLeft,
     SlideRight,
     Zoom,
@@ -25,17 +27,23 @@ pub enum TransitionType {
 impl TransitionType {
     /// Parse a transition name from scene frontmatter / config strings.
     pub fn from_str(s: &str) -> Self {
+        Self::parse(s).unwrap_or_else(|| {
+            warn!("Unknown transition \"{}\", defaulting to fade", s.to_lowercase());
+            Self::Fade
+        })
+    }
+
+    /// Parse a transition name, or `None` when it isn't one `from_str` knows.
+    pub fn parse(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
-            "fade" => Self::Fade,
-            "slide-left" | "slideleft" | "slide_left" => Self::SlideLeft,
-            "slide-right" | "slideright" | "slide_right" => Self::SlideRight,
-            "zoom" => Self::Zoom,
-            "wipe" | "wipeleft" | "wipe-left" => Self::Wipe,
-            "none" | "" => Self::None,
-            other => {
-                warn!("Unknown transition \"{other}\", defaulting to fade");
-                Self::Fade
-            }
+            "fade" => Some(Self::Fade),
+            "fade-black" | "fadeblack" | "fade_black" => Some(Self::FadeBlack),
+            "slide-left" | "slideleft" | "slide_left" => Some(Self::SlideLeft),
+            "slide-right" | "slideright" | "slide_right" => Some(Self::SlideRight),
+            "zoom" => Some(Self::Zoom),
+            "wipe" | "wipeleft" | "wipe-left" => Some(Self::Wipe),
+            "none" | "" => Some(Self::None),
+            _ => None,
         }
     }
 
@@ -43,6 +51,7 @@ impl TransitionType {
     pub fn ffmpeg_name(&self) -> &'static str {
         match self {
             Self::Fade => "fade",
+            Self::FadeBlack => "fadeblack",
             Self::SlideLeft => "slideleft",
             Self::SlideRight => "slideright",
             Self::Zoom => "smoothup",
@@ -59,9 +68,79 @@ pub struct SceneTransition {
     pub duration: f64,
 }
 
+/// Which setting decided a transition (or its duration) at a scene boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionSource {
+    /// `transition_out` / `transition_duration` on the outgoing scene
+    SceneOut,
+    /// `transition_in` / `transition_duration` on the incoming scene
+    SceneIn,
+    /// `[video] default_transition` / `default_transition_duration`
+    ConfigDefault,
+    /// The default transition of the outgoing or incoming scene's template
+    TemplateDefault,
+}
+
+/// The transition name set for a boundary and where it came from, before parsing.
+///
+/// Priority: scene_out.transition_out > scene_in.transition_in > config default >
+/// template default (outgoing scene's template, then incoming) > None.
+pub fn transition_setting<'a>(
+    scene_out: &'a Scene,
+    scene_in: &'a Scene,
+    video_config: &'a VideoConfig,
+) -> Option<(&'a str, TransitionSource)> {
+    scene_out
+        .frontmatter
+        .transition_out
+        .as_deref()
+        .map(|name| (name, TransitionSource::SceneOut))
+        .or_else(|| {
+            scene_in
+                .frontmatter
+                .transition_in
+                .as_deref()
+                .map(|name| (name, TransitionSource::SceneIn))
+        })
+        .or_else(|| {
+            video_config
+                .default_transition
+                .as_deref()
+                .map(|name| (name, TransitionSource::ConfigDefault))
+        })
+        .or_else(|| {
+            crate::template::template_default_transition(&scene_out.frontmatter.template)
+                .or_else(|| {
+                    crate::template::template_default_transition(&scene_in.frontmatter.template)
+                })
+                .map(|name| (name, TransitionSource::TemplateDefault))
+        })
+}
+
+/// The transition duration for a boundary and where it came from: scene-level
+/// `transition_duration` (preferring the outgoing scene), else the config default.
+pub fn transition_duration_setting(
+    scene_out: &Scene,
+    scene_in: &Scene,
+    video_config: &VideoConfig,
+) -> (f64, TransitionSource) {
+    if let Some(d) = scene_out.frontmatter.transition_duration {
+        (d, TransitionSource::SceneOut)
+    } else if let Some(d) = scene_in.frontmatter.transition_duration {
+        (d, TransitionSource::SceneIn)
+    } else {
+        (
+            video_config.default_transition_duration,
+            TransitionSource::ConfigDefault,
+        )
+    }
+}
+
 /// Resolve the transition between scene N (out) and scene N+1 (in).
 ///
-/// Priority: scene_out.transition_out > scene_in.transition_in > config default > None.
+/// Priority: scene_out.transition_out > scene_in.transition_in > config default >
+/// template default > None.
 /// Duration: scene-level transition_duration (if set on either scene, preferring out),
 /// else config default_transition_duration.
 pub fn resolve_transition(
@@ -69,27 +148,14 @@ pub fn resolve_transition(
     scene_in: &Scene,
     video_config: &VideoConfig,
 ) -> Option<SceneTransition> {
-    // Determine the transition type string
-    let transition_name = scene_out
-        .frontmatter
-        .transition_out
-        .as_deref()
-        .or(scene_in.frontmatter.transition_in.as_deref())
-        .or(video_config.default_transition.as_deref());
-
-    let transition_name = transition_name?;
+    let (transition_name, _) = transition_setting(scene_out, scene_in, video_config)?;
 
     let transition_type = TransitionType::from_str(transition_name);
     if transition_type == TransitionType::None {
         return None;
     }
 
-    // Determine duration: prefer scene_out's duration, then scene_in's, then config default
-    let duration = scene_out
-        .frontmatter
-        .transition_duration
-        .or(scene_in.frontmatter.transition_duration)
-        .unwrap_or(video_config.default_transition_duration);
+    let (duration, _) = transition_duration_setting(scene_out, scene_in, video_config);
 
     Some(SceneTransition {
         transition_type,
@@ -97,23 +163,208 @@ pub fn resolve_transition(
     })
 }
 
-/// Detect available hardware video encoders by querying FFmpeg.
-/// Returns the best available H.264 hardware encoder, or None if only software is available.
-pub fn detect_hw_encoder() -> Option<&'static str> {
-    let output = Command::new("ffmpeg")
+/// Resolve the first scene's `transition_in` as a fade from black at the start
+/// of the video. Only an explicit `transition_in` counts (defaults apply between
+/// scenes), and every transition type becomes a plain fade. Duration follows
+/// the scene's `transition_duration`, else the config default, capped at the
+/// scene's length.
+pub fn resolve_intro_transition(
+    first: &Scene,
+    scene_duration: f64,
+    video_config: &VideoConfig,
+) -> Option<SceneTransition> {
+    let name = first.frontmatter.transition_in.as_deref()?;
+    if TransitionType::from_str(name) == TransitionType::None {
+        return None;
+    }
+    let duration = first
+        .frontmatter
+        .transition_duration
+        .unwrap_or(video_config.default_transition_duration)
+        .min(scene_duration);
+    (duration > 0.0).then_some(SceneTransition {
+        transition_type: TransitionType::Fade,
+        duration,
+    })
+}
+
+/// The encoder list printed by `ffmpeg -encoders` (empty if FFmpeg can't be run).
+fn ffmpeg_encoders() -> String {
+    Command::new("ffmpeg")
         .args(["-hide_banner", "-encoders"])
         .output()
-        .ok()?;
-    let stdout = String::from_utf8_lossy(&output.stdout);
+        .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+        .unwrap_or_default()
+}
+
+/// Pick the hardware encoder from `encoders` (an `ffmpeg -encoders` listing):
+/// the `requested` one (`output.hwaccel`) if FFmpeg has it, otherwise with
+/// `--gpu` the best one available. `None` means libx264.
+fn select_hw_encoder(requested: Option<HwAccel>, use_gpu: bool, encoders: &str) -> Option<HwAccel> {
+    let available = |hw: &HwAccel| encoders.contains(hw.codec());
+    match requested {
+        Some(hw) => Some(hw).filter(available),
+        None if use_gpu => [HwAccel::Videotoolbox, HwAccel::Nvenc, HwAccel::Vaapi]
+            .into_iter()
+            .find(available),
+        None => None,
+    }
+}
+
+/// Resolve the hardware encoder for a render from `output.hwaccel` and `--gpu`
+/// by querying FFmpeg. Returns `None` (libx264) when neither is set or the
+/// encoder isn't available.
+pub fn resolve_hw_encoder(requested: Option<HwAccel>, use_gpu: bool) -> Option<HwAccel> {
+    if requested.is_none() && !use_gpu {
+        return None;
+    }
+    select_hw_encoder(requested, use_gpu, &ffmpeg_encoders())
+}
+
+/// VA-API render node used for `h264_vaapi`.
+const VAAPI_DEVICE: &str = "/dev/dri/renderD128";
+
+/// Global FFmpeg args that set up the hardware device for `platform`'s encoder.
+/// They go before the inputs.
+fn hw_device_args(platform: &PlatformPreset) -> Vec<String> {
+    match platform.hw_encoder {
+        Some(HwAccel::Vaapi) => vec!["-vaapi_device".into(), VAAPI_DEVICE.into()],
+        _ => Vec::new(),
+    }
+}
+
+/// Filter that uploads frames to the GPU, appended to the video filter chain
+/// for encoders that only take hardware frames (VA-API).
+fn hw_upload_filter(platform: &PlatformPreset) -> Option<&'static str> {
+    (platform.hw_encoder == Some(HwAccel::Vaapi)).then_some("format=nv12,hwupload")
+}
+
+// ---------------------------------------------------------------------------
+// Music automation (intro boost + ducking)
+// ---------------------------------------------------------------------------
+
+/// Sidechain compressor settings used to duck music under the voiceover.
+const DUCK_COMPRESSOR: &str = "sidechaincompress=threshold=0.05:ratio=8:attack=20:release=400";
+
+/// Per-scene music automation derived from `audio.intro_boost`: the music is
+/// boosted for an opening window, then ducked whenever the voice speaks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MusicDucking {
+    /// Scene-local seconds during which the music is boosted (0 = no boost in this scene).
+    pub boost_secs: f64,
+    /// Linear gain applied to the music during the boost window.
+    pub boost_gain: f64,
+}
+
+impl MusicDucking {
+    /// Build the automation for a scene that starts `scene_start` seconds into the video.
+    /// Only scenes overlapping the intro window get a boost; all of them are ducked.
+    pub fn for_scene(boost: &IntroBoostConfig, scene_start: f64) -> Self {
+        Self {
+            boost_secs: (boost.secs - scene_start).max(0.0),
+            boost_gain: 10.0_f64.powf(boost.gain / 20.0),
+        }
+    }
+}
+
+/// Per-scene music fades from `audio.music_fade_in`/`music_fade_out`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MusicFade {
+    /// Seconds to fade in from the scene start (0 = none).
+    pub fade_in: f64,
+    /// Seconds to fade out before the scene ends (0 = none).
+    pub fade_out: f64,
+    /// Scene length in seconds, where the fade-out finishes.
+    pub duration: f64,
+}
+
+impl MusicFade {
+    /// The fades configured on `scene` for a scene lasting `duration` seconds.
+    pub fn for_scene(scene: &Scene, duration: f64) -> Self {
+        let audio = scene.frontmatter.audio.as_ref();
+        Self {
+            fade_in: audio.and_then(|a| a.music_fade_in).unwrap_or(0.0),
+            fade_out: audio.and_then(|a| a.music_fade_out).unwrap_or(0.0),
+            duration,
+        }
+    }
+
+    /// `afade` filters to append to the music chain (empty when there are no fades).
+    /// Fades longer than the scene are shortened to fit.
+    fn filters(&self) -> String {
+        let mut filters = String::new();
+        if self.fade_in > 0.0 {
+            let d = self.fade_in.min(self.duration);
+            filters.push_str(&format!(",afade=t=in:st=0:d={d:.3}"));
+        }
+        if self.fade_out > 0.0 && self.duration > 0.0 {
+            let d = self.fade_out.min(self.duration);
+            let st = self.duration - d;
+            filters.push_str(&format!(",afade=t=out:st={st:.3}:d={d:.3}"));
+        }
+        filters
+    }
+}
+
+/// Build the music filter chain: base volume, fades, then the time-gated intro boost.
+fn music_chain(
+    input: &str,
+    music_volume: f64,
+    fade: &MusicFade,
+    ducking: Option<&MusicDucking>,
+    label: &str,
+) -> String {
+    let mut chain = format!("{input}volume={music_volume:.2}{}", fade.filters());
+    if let Some(d) = ducking.filter(|d| d.boost_secs > 0.0) {
+        chain.push_str(&format!(
+            ",volume={:.3}:enable='lt(t,{:.3})'",
+            d.boost_gain, d.boost_secs
+        ));
+    }
+    chain.push_str(label);
+    chain
+}
+
+/// Duck `[music]` under `[voice]`. The voice is split so it feeds both the
+/// compressor's sidechain and the final mix; outputs are `[voicemix]` and `[ducked]`.
+fn duck_stage() -> String {
+    format!("[voice]asplit=2[voicemix][voicesc];[music][voicesc]{DUCK_COMPRESSOR}[ducked]")
+}
 
-    if stdout.contains("h264_videotoolbox") {
-        Some("h264_videotoolbox")
-    } else if stdout.contains("h264_nvenc") {
-        Some("h264_nvenc")
-    } else if stdout.contains("h264_vaapi") {
-        Some("h264_vaapi")
+/// Filter graph for a scene with both voice (input 1) and music (input 2).
+fn voice_music_filter(
+    delay_ms: u64,
+    music_volume: f64,
+    fade: &MusicFade,
+    ducking: Option<&MusicDucking>,
+) -> String {
+    // apad pads voice with silence to fill the full scene duration
+    let voice_chain = if delay_ms > 0 {
+        format!("[1:a]adelay={delay_ms}|{delay_ms},volume=1.0,apad[voice]")
     } else {
-        None
+        "[1:a]volume=1.0,apad[voice]".to_string()
+    };
+    let music = music_chain("[2:a]", music_volume, fade, ducking, "[music]");
+    let mix = "amix=inputs=2:duration=first:dropout_transition=2:normalize=0[aout]";
+    if ducking.is_some() {
+        format!("{voice_chain};{music};{};[voicemix][ducked]{mix}", duck_stage())
+    } else {
+        format!("{voice_chain};{music};[voice][music]{mix}")
+    }
+}
+
+/// Frame-blending filter for scenes with `motion_blur: true`. Each output frame
+/// averages itself with the previous one, softening motion at low frame rates.
+pub const MOTION_BLUR_FILTER: &str = "tblend=all_mode=average";
+
+/// Filter converting video captured at `capture_fps` to `output_fps`:
+/// `minterpolate` synthesizes in-between frames when raising the rate, `fps`
+/// drops frames when lowering it. `None` when the rates match.
+pub fn frame_rate_filter(capture_fps: u32, output_fps: u32) -> Option<String> {
+    match output_fps.cmp(&capture_fps) {
+        std::cmp::Ordering::Equal => None,
+        std::cmp::Ordering::Greater => Some(format!("minterpolate=fps={output_fps}:mi_mode=mci")),
+        std::cmp::Ordering::Less => Some(format!("fps={output_fps}")),
     }
 }
 
@@ -127,23 +378,95 @@ pub struct SceneEncoder {
 impl SceneEncoder {
     /// Spawn an FFmpeg process that accepts PNG frames on stdin.
     /// If `audio_path` is provided (TTS voice), the audio file is muxed into the output.
-    /// If `music_path` is provided, the music file is mixed in at the given volume.
-    /// When both are present, they are combined via `amix`.
+    /// If `music_path` is provided, the music file is mixed in at the given volume,
+    /// with `music_fade` applied. When both are present, they are combined via `amix`.
+    /// `ducking` boosts the music intro and ducks it under the voice.
+    /// `motion_blur` adds [`MOTION_BLUR_FILTER`] to the video chain.
+    /// Frames arrive at `fps`; the MP4 is encoded at `output_fps` (see [`frame_rate_filter`]).
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         output_path: &Path,
         fps: u32,
+        output_fps: u32,
         width: u32,
         height: u32,
         platform: &PlatformPreset,
         audio_path: Option<&Path>,
         music_path: Option<&Path>,
         music_volume: f64,
+        music_fade: &MusicFade,
         audio_delay_secs: f64,
         effective_duration: Option<f64>,
-        use_gpu: bool,
+        ducking: Option<&MusicDucking>,
+        motion_blur: bool,
     ) -> VidgenResult<Self> {
+        let mut cmd = Self::command(
+            output_path,
+            fps,
+            output_fps,
+            width,
+            height,
+            platform,
+            audio_path,
+            music_path,
+            music_volume,
+            music_fade,
+            audio_delay_secs,
+            effective_duration,
+            ducking,
+            motion_blur,
+        );
+
+        debug!(
+            "Spawning FFmpeg encoder: {}x{} @ {}fps (output {}fps), codec={}, crf={}",
+            width,
+            height,
+            fps,
+            output_fps,
+            platform.hw_encoder.map_or("libx264", HwAccel::codec),
+            platform.crf
+        );
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg: {e}")))?;
+
+        // Drain stderr in a background thread to prevent pipe deadlock
+        let stderr_handle = child.stderr.take().map(|mut stderr| {
+            std::thread::spawn(move || {
+                let mut buf = String::new();
+                let _ = stderr.read_to_string(&mut buf);
+                buf
+            })
+        });
+
+        Ok(Self {
+            child,
+            output_path: output_path.to_path_buf(),
+            stderr_handle,
+        })
+    }
+
+    /// Build the FFmpeg command for `new` without spawning it.
+    #[allow(clippy::too_many_arguments)]
+    fn command(
+        output_path: &Path,
+        fps: u32,
+        output_fps: u32,
+        width: u32,
+        height: u32,
+        platform: &PlatformPreset,
+        audio_path: Option<&Path>,
+        music_path: Option<&Path>,
+        music_volume: f64,
+        music_fade: &MusicFade,
+        audio_delay_secs: f64,
+        effective_duration: Option<f64>,
+        ducking: Option<&MusicDucking>,
+        motion_blur: bool,
+    ) -> Command {
         let mut cmd = Command::new("ffmpeg");
+        cmd.args(hw_device_args(platform));
         cmd.args([
             "-y", // Overwrite output
             "-f",
@@ -169,51 +492,28 @@ impl SceneEncoder {
             cmd.args(["-i"]).arg(music.as_os_str());
         }
 
-        // Select video codec: hardware-accelerated if --gpu and available, otherwise libx264
-        let hw_encoder = if use_gpu { detect_hw_encoder() } else { None };
-        if let Some(hw_codec) = hw_encoder {
-            debug!("Using hardware encoder: {}", hw_codec);
-            cmd.args([
-                "-c:v",
-                hw_codec,
-                "-pix_fmt",
-                "yuv420p",
-                "-b:v",
-                "5M", // HW encoders don't all support CRF, use bitrate instead
-                "-movflags",
-                "+faststart",
-            ]);
-        } else {
-            cmd.args([
-                "-c:v",
-                "libx264", // H.264 codec
-                "-pix_fmt",
-                "yuv420p", // Pixel format for compatibility
-                "-crf",
-                &platform.crf.to_string(), // Quality
-                "-preset",
-                platform.preset, // Speed/quality tradeoff
-                "-movflags",
-                "+faststart", // Web-optimized
-            ]);
+        // Blend at the capture rate, then convert to the output rate
+        let video_filters: Vec<String> = motion_blur
+            .then(|| MOTION_BLUR_FILTER.to_string())
+            .into_iter()
+            .chain(frame_rate_filter(fps, output_fps))
+            .chain(hw_upload_filter(platform).map(String::from))
+            .collect();
+        if !video_filters.is_empty() {
+            cmd.args(["-vf", &video_filters.join(",")]);
+            cmd.args(["-r", &output_fps.to_string()]);
         }
 
+        // Select video codec: the platform's hardware encoder if any, otherwise libx264
+        cmd.args(video_codec_args(platform));
+
         // Audio mixing: voice + music, only voice, only music, or none
         // When audio_delay_secs > 0, insert an adelay filter to shift the voice track
         let delay_ms = (audio_delay_secs * 1000.0).round() as u64;
         match (has_voice, has_music) {
             (true, true) => {
                 // Voice is input 1, music is input 2
-                // apad pads voice with silence to fill the full scene duration
-                let voice_chain = if delay_ms > 0 {
-                    format!("[1:a]adelay={delay_ms}|{delay_ms},volume=1.0,apad[voice]")
-                } else {
-                    "[1:a]volume=1.0,apad[voice]".to_string()
-                };
-                let filter = format!(
-                    "{voice_chain};[2:a]volume={music_volume:.2}[music];\
-                     [voice][music]amix=inputs=2:duration=first:dropout_transition=2:normalize=0[aout]"
-                );
+                let filter = voice_music_filter(delay_ms, music_volume, music_fade, ducking);
                 cmd.args(["-filter_complex", &filter, "-map", "0:v", "-map", "[aout]"]);
                 cmd.args([
                     "-c:a", "aac", "-ac", "2",
@@ -236,7 +536,7 @@ impl SceneEncoder {
             }
             (false, true) => {
                 // Music only is input 1
-                let filter = format!("[1:a]volume={music_volume:.2}[aout]");
+                let filter = music_chain("[1:a]", music_volume, music_fade, ducking, "[aout]");
                 cmd.args(["-filter_complex", &filter, "-map", "0:v", "-map", "[aout]"]);
                 cmd.args([
                     "-c:a", "aac", "-ac", "2",
@@ -257,30 +557,7 @@ impl SceneEncoder {
         cmd.stdin(Stdio::piped());
         cmd.stdout(Stdio::null());
         cmd.stderr(Stdio::piped());
-
-        debug!(
-            "Spawning FFmpeg encoder: {}x{} @ {}fps, codec={}, crf={}",
-            width, height, fps, hw_encoder.unwrap_or("libx264"), platform.crf
-        );
-
-        let mut child = cmd
-            .spawn()
-            .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg: {e}")))?;
-
-        // Drain stderr in a background thread to prevent pipe deadlock
-        let stderr_handle = child.stderr.take().map(|mut stderr| {
-            std::thread::spawn(move || {
-                let mut buf = String::new();
-                let _ = stderr.read_to_string(&mut buf);
-                buf
-            })
-        });
-
-        Ok(Self {
-            child,
-            output_path: output_path.to_path_buf(),
-            stderr_handle,
-        })
+        cmd
     }
 
     /// Write a single PNG frame to FFmpeg's stdin.
@@ -336,12 +613,30 @@ impl SceneEncoder {
 /// scenes (from SceneEncoder) and video clip scenes (from prepare_video_clip).
 /// This is slower than stream copy but produces correct output every time,
 /// even with mixed scene types (BUG-001).
-pub fn concat_scenes(scene_files: &[PathBuf], output_path: &Path) -> VidgenResult<()> {
+///
+/// `concat_method` ("auto", "demuxer", "filter") selects the join strategy; in
+/// "auto" mode the concat filter is used when only some scenes have audio,
+/// since the demuxer can't line up segments with differing stream layouts.
+/// The output is normalized to `fps`, so scenes with an fps override join cleanly.
+/// The concat filter encodes with `platform`'s codec settings.
+pub fn concat_scenes(
+    scene_files: &[PathBuf],
+    output_path: &Path,
+    platform: &PlatformPreset,
+    fps: u32,
+    concat_method: &str,
+) -> VidgenResult<()> {
     if scene_files.len() == 1 {
         std::fs::copy(&scene_files[0], output_path)?;
         return Ok(());
     }
 
+    let has_audio: Vec<bool> = scene_files.iter().map(|f| has_audio_stream(f)).collect();
+    if use_filter_concat(concat_method, &has_audio) {
+        debug!("Using concat filter (audio presence: {:?})", has_audio);
+        return concat_scenes_filter(scene_files, &has_audio, output_path, platform, fps);
+    }
+
     // Write concat list file for the demuxer
     let concat_dir = output_path.parent().unwrap_or(Path::new("."));
     let concat_list_path = concat_dir.join(".vidgen-concat-list.txt");
@@ -361,6 +656,7 @@ pub fn concat_scenes(scene_files: &[PathBuf], output_path: &Path) -> VidgenResul
     cmd.args([
         "-c:v", "libx264", "-pix_fmt", "yuv420p",
         "-crf", "23", "-preset", "fast",
+        "-r", &fps.to_string(),
         "-c:a", "aac", "-ac", "2", "-ar", "44100", "-b:a", "192k",
         "-movflags", "+faststart",
     ]);
@@ -387,8 +683,107 @@ pub fn concat_scenes(scene_files: &[PathBuf], output_path: &Path) -> VidgenResul
     Ok(())
 }
 
+/// Decide whether to join scenes with the concat filter instead of the demuxer.
+fn use_filter_concat(concat_method: &str, has_audio: &[bool]) -> bool {
+    match concat_method {
+        "filter" => true,
+        "demuxer" => false,
+        // auto: mixed audio presence breaks the demuxer
+        _ => has_audio.iter().any(|&a| a) && has_audio.iter().any(|&a| !a),
+    }
+}
+
+/// Build the filter graph for `concat_scenes_filter`. Scenes without audio get a
+/// silent track of their duration so every segment has matching streams.
+fn concat_filter_graph(has_audio: &[bool], durations: &[f64], fps: u32) -> String {
+    let any_audio = has_audio.iter().any(|&a| a);
+    let mut filter_parts: Vec<String> = Vec::new();
+    let mut concat_inputs = String::new();
+
+    for (i, &has) in has_audio.iter().enumerate() {
+        filter_parts.push(format!(
+            "[{i}:v]fps={fps},format=yuv420p,setsar=1,setpts=PTS-STARTPTS[v{i}]"
+        ));
+        concat_inputs.push_str(&format!("[v{i}]"));
+        if !any_audio {
+            continue;
+        }
+        if has {
+            filter_parts.push(format!(
+                "[{i}:a]aformat=sample_rates=44100:channel_layouts=stereo,asetpts=PTS-STARTPTS[a{i}]"
+            ));
+        } else {
+            let dur = durations.get(i).copied().unwrap_or(0.0);
+            filter_parts.push(format!(
+                "anullsrc=cl=stereo:r=44100,atrim=0:{dur:.3},asetpts=PTS-STARTPTS[a{i}]"
+            ));
+        }
+        concat_inputs.push_str(&format!("[a{i}]"));
+    }
+
+    let n = has_audio.len();
+    if any_audio {
+        filter_parts.push(format!("{concat_inputs}concat=n={n}:v=1:a=1[vout][aout]"));
+    } else {
+        filter_parts.push(format!("{concat_inputs}concat=n={n}:v=1:a=0[vout]"));
+    }
+    filter_parts.join(";")
+}
+
+/// Concatenate scenes with FFmpeg's concat filter (full re-encode).
+fn concat_scenes_filter(
+    scene_files: &[PathBuf],
+    has_audio: &[bool],
+    output_path: &Path,
+    platform: &PlatformPreset,
+    fps: u32,
+) -> VidgenResult<()> {
+    let durations: Vec<f64> = scene_files
+        .iter()
+        .map(|f| probe_video_duration(f).unwrap_or(0.0))
+        .collect();
+    let filter = concat_filter_graph(has_audio, &durations, fps);
+
+    let mut cmd = Command::new("ffmpeg");
+    cmd.args(hw_device_args(platform));
+    cmd.arg("-y");
+    for path in scene_files {
+        cmd.arg("-i").arg(path.as_os_str());
+    }
+    match hw_upload_filter(platform) {
+        Some(upload) => {
+            let graph = format!("{filter};[vout]{upload}[vhw]");
+            cmd.args(["-filter_complex", &graph, "-map", "[vhw]"]);
+        }
+        None => {
+            cmd.args(["-filter_complex", &filter, "-map", "[vout]"]);
+        }
+    }
+    cmd.args(video_codec_args(platform));
+    if has_audio.iter().any(|&a| a) {
+        cmd.args(["-map", "[aout]", "-c:a", "aac", "-ac", "2", "-ar", "44100", "-b:a", "192k"]);
+    }
+    cmd.arg(output_path.as_os_str());
+    cmd.stdout(Stdio::null());
+    cmd.stderr(Stdio::piped());
+
+    let output = cmd
+        .output()
+        .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg concat filter: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(VidgenError::Ffmpeg(format!(
+            "FFmpeg concat filter failed: {}",
+            stderr.lines().last().unwrap_or("unknown error")
+        )));
+    }
+
+    Ok(())
+}
+
 /// Check if a media file has an audio stream.
-fn has_audio_stream(path: &Path) -> bool {
+pub fn has_audio_stream(path: &Path) -> bool {
     Command::new("ffprobe")
         .args([
             "-v",
@@ -411,12 +806,18 @@ fn has_audio_stream(path: &Path) -> bool {
 /// - Single scene → just copy
 /// - No transitions → delegate to fast `concat_scenes()` (no re-encode)
 /// - Has transitions → build FFmpeg xfade filter graph and re-encode
+///
+/// `gap` is joined in at every hard cut (see [`interleave_scene_gaps`]).
+#[allow(clippy::too_many_arguments)]
 pub fn concat_scenes_with_transitions(
     scene_files: &[PathBuf],
     scene_durations: &[f64],
     transitions: &[Option<SceneTransition>],
     output_path: &Path,
     platform: &PlatformPreset,
+    fps: u32,
+    concat_method: &str,
+    gap: Option<&SceneGap>,
 ) -> VidgenResult<()> {
     debug!(
         "Concatenating {} scenes to {}",
@@ -430,9 +831,6 @@ pub fn concat_scenes_with_transitions(
 
     // Check if there are any actual transitions
     let has_transitions = transitions.iter().any(|t| t.is_some());
-    if !has_transitions {
-        return concat_scenes(scene_files, output_path);
-    }
 
     // BUG-001: xfade transitions produce truncated output when mixing
     // HTML-rendered scenes with video clip scenes (prepare_video_clip output).
@@ -442,7 +840,8 @@ pub fn concat_scenes_with_transitions(
     // tend to be much larger than HTML-rendered scenes.
     // The concat filter path works correctly, so use it as fallback.
     // TODO: fix xfade with mixed scene types
-    if scene_files.len() > 1 {
+    let mut mixed_scene_types = false;
+    if has_transitions && scene_files.len() > 1 {
         let sizes: Vec<u64> = scene_files.iter()
             .map(|f| std::fs::metadata(f).map(|m| m.len()).unwrap_or(0))
             .collect();
@@ -451,44 +850,126 @@ pub fn concat_scenes_with_transitions(
         // If largest scene is >10x smallest, likely mixed HTML + clip scenes
         if *min_size > 0 && *max_size > min_size * 10 {
             warn!("Mixed scene types detected — using hard cuts instead of transitions (BUG-001 workaround)");
-            return concat_scenes(scene_files, output_path);
+            mixed_scene_types = true;
         }
     }
 
+    // Gap clips are checked after the size heuristic, which they would trip
+    let (scene_files, scene_durations, transitions) = match gap {
+        Some(gap) => interleave_scene_gaps(scene_files, scene_durations, transitions, gap),
+        None => (scene_files.to_vec(), scene_durations.to_vec(), transitions.to_vec()),
+    };
+    if !has_transitions || mixed_scene_types {
+        return concat_scenes(&scene_files, output_path, platform, fps, concat_method);
+    }
+
     // Check which scene files have audio streams
     let has_audio: Vec<bool> = scene_files.iter().map(|f| has_audio_stream(f)).collect();
     let any_audio = has_audio.iter().any(|&a| a);
 
-    // Normalize all video inputs to prevent DTS/PTS mismatches between
-    // HTML-rendered scenes and video clip scenes (different timebases).
-    let n = scene_files.len();
-    let mut filter_parts: Vec<String> = Vec::new();
-
-    for i in 0..n {
-        filter_parts.push(format!(
-            "[{i}:v]fps=30,format=yuv420p,setpts=PTS-STARTPTS[vin{i}]"
-        ));
-    }
+    let filter_graph = transition_filter_graph(
+        &scene_durations,
+        &transitions,
+        &has_audio,
+        fps,
+        platform.audio_samplerate,
+    );
 
-    // Build FFmpeg xfade filter graph for video
-    let mut offset = 0.0_f64;
+    let mut cmd = Command::new("ffmpeg");
+    cmd.args(hw_device_args(platform));
+    cmd.arg("-y");
 
-    for i in 0..n - 1 {
-        let trans = &transitions[i];
-        let (trans_name, trans_dur) = match trans {
-            Some(t) => (t.transition_type.ffmpeg_name(), t.duration),
-            None => ("fade", 0.001), // instant cut
-        };
+    // Add all input files
+    for file in &scene_files {
+        cmd.args(["-i"]).arg(file.as_os_str());
+    }
 
-        if i == 0 {
-            offset = scene_durations[0] - trans_dur;
-        } else {
-            offset += scene_durations[i] - trans_dur;
+    match hw_upload_filter(platform) {
+        Some(upload) => {
+            let graph = format!("{filter_graph};[vout]{upload}[vhw]");
+            cmd.args(["-filter_complex", &graph, "-map", "[vhw]"]);
+        }
+        None => {
+            cmd.args(["-filter_complex", &filter_graph, "-map", "[vout]"]);
         }
+    }
 
-        let offset_val = offset.max(0.0);
+    if any_audio {
+        cmd.args(["-map", "[aout]"]);
+    }
 
-        let input_a = if i == 0 {
+    cmd.args(video_codec_args(platform));
+
+    if any_audio {
+        cmd.args([
+            "-c:a", "aac", "-ac", "2",
+            "-b:a", platform.audio_bitrate,
+            "-ar", &platform.audio_samplerate.to_string(),
+        ]);
+    }
+
+    cmd.arg(output_path.as_os_str());
+    cmd.stdout(Stdio::null());
+    cmd.stderr(Stdio::piped());
+
+    let output = cmd
+        .output()
+        .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg xfade: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(VidgenError::Ffmpeg(format!(
+            "FFmpeg xfade concat failed: {}",
+            stderr.lines().last().unwrap_or("unknown error")
+        )));
+    }
+
+    Ok(())
+}
+
+/// Build the `-filter_complex` graph for `concat_scenes_with_transitions`: an
+/// xfade chain for video and an acrossfade chain for audio (when any scene has
+/// audio). Each boundary uses its own transition, so dips to black and
+/// crossfades can be mixed freely; boundaries without one become instant cuts.
+fn transition_filter_graph(
+    scene_durations: &[f64],
+    transitions: &[Option<SceneTransition>],
+    has_audio: &[bool],
+    fps: u32,
+    sample_rate: u32,
+) -> String {
+    let any_audio = has_audio.iter().any(|&a| a);
+
+    // Normalize all video inputs to prevent DTS/PTS mismatches between
+    // HTML-rendered scenes and video clip scenes (different timebases).
+    let n = scene_durations.len();
+    let mut filter_parts: Vec<String> = Vec::new();
+
+    for i in 0..n {
+        filter_parts.push(format!(
+            "[{i}:v]fps={fps},format=yuv420p,setpts=PTS-STARTPTS[vin{i}]"
+        ));
+    }
+
+    // Build FFmpeg xfade filter graph for video
+    let mut offset = 0.0_f64;
+
+    for i in 0..n - 1 {
+        let trans = &transitions[i];
+        let (trans_name, trans_dur) = match trans {
+            Some(t) => (t.transition_type.ffmpeg_name(), t.duration),
+            None => ("fade", 0.001), // instant cut
+        };
+
+        if i == 0 {
+            offset = scene_durations[0] - trans_dur;
+        } else {
+            offset += scene_durations[i] - trans_dur;
+        }
+
+        let offset_val = offset.max(0.0);
+
+        let input_a = if i == 0 {
             "[vin0]".to_string()
         } else {
             format!("[xv{i}]")
@@ -511,7 +992,7 @@ pub fn concat_scenes_with_transitions(
         // Use anullsrc → atrim to produce a silent segment, then normalize all
         // audio streams to the same format before crossfading.
         // Use platform sample rate for consistency with per-scene encoding.
-        let sr = platform.audio_samplerate;
+        let sr = sample_rate;
         for (i, (&has, dur)) in has_audio.iter().zip(scene_durations.iter()).enumerate() {
             if !has {
                 filter_parts.push(format!(
@@ -550,60 +1031,7 @@ pub fn concat_scenes_with_transitions(
         }
     }
 
-    let filter_graph = filter_parts.join(";");
-
-    let mut cmd = Command::new("ffmpeg");
-    cmd.arg("-y");
-
-    // Add all input files
-    for file in scene_files {
-        cmd.args(["-i"]).arg(file.as_os_str());
-    }
-
-    cmd.args(["-filter_complex", &filter_graph, "-map", "[vout]"]);
-
-    if any_audio {
-        cmd.args(["-map", "[aout]"]);
-    }
-
-    cmd.args([
-        "-c:v",
-        "libx264",
-        "-pix_fmt",
-        "yuv420p",
-        "-crf",
-        &platform.crf.to_string(),
-        "-preset",
-        platform.preset,
-        "-movflags",
-        "+faststart",
-    ]);
-
-    if any_audio {
-        cmd.args([
-            "-c:a", "aac", "-ac", "2",
-            "-b:a", platform.audio_bitrate,
-            "-ar", &platform.audio_samplerate.to_string(),
-        ]);
-    }
-
-    cmd.arg(output_path.as_os_str());
-    cmd.stdout(Stdio::null());
-    cmd.stderr(Stdio::piped());
-
-    let output = cmd
-        .output()
-        .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg xfade: {e}")))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(VidgenError::Ffmpeg(format!(
-            "FFmpeg xfade concat failed: {}",
-            stderr.lines().last().unwrap_or("unknown error")
-        )));
-    }
-
-    Ok(())
+    filter_parts.join(";")
 }
 
 /// Probe the duration of a video file in seconds using ffprobe.
@@ -654,8 +1082,10 @@ pub fn prepare_video_clip(
     audio_path: Option<&Path>,
     music_path: Option<&Path>,
     music_volume: f64,
+    music_fade: &MusicFade,
     audio_delay_secs: f64,
     source_volume: f64,
+    ducking: Option<&MusicDucking>,
 ) -> VidgenResult<PathBuf> {
     let mut cmd = Command::new("ffmpeg");
     cmd.arg("-y");
@@ -718,9 +1148,14 @@ pub fn prepare_video_clip(
         mix_labels.push("[src]".into());
     }
     if has_music {
-        filter_parts.push(format!("[{music_idx}:a]volume={music_volume:.2}[music]"));
+        filter_parts.push(music_chain(&format!("[{music_idx}:a]"), music_volume, music_fade, ducking, "[music]"));
         mix_labels.push("[music]".into());
     }
+    if has_voice && has_music && ducking.is_some() {
+        filter_parts.push(duck_stage());
+        replace_label(&mut mix_labels, "[voice]", "[voicemix]");
+        replace_label(&mut mix_labels, "[music]", "[ducked]");
+    }
 
     let has_any_audio = !mix_labels.is_empty();
 
@@ -789,6 +1224,13 @@ pub fn prepare_video_clip(
     Ok(output_path.to_path_buf())
 }
 
+/// Swap a stream label in an `amix` input list (used after inserting the duck stage).
+fn replace_label(labels: &mut [String], from: &str, to: &str) {
+    if let Some(l) = labels.iter_mut().find(|l| *l == from) {
+        *l = to.to_string();
+    }
+}
+
 /// Mix voiceover and/or music onto a video file (post-process).
 /// The video may already have audio (e.g., from source clips in a sequence).
 /// Renames the original to a temp file, re-encodes with audio mix, then removes the temp.
@@ -798,8 +1240,10 @@ pub fn mix_audio_onto_video(
     voice_path: Option<&Path>,
     music_path: Option<&Path>,
     music_volume: f64,
+    music_fade: &MusicFade,
     voice_delay_secs: f64,
     platform: &PlatformPreset,
+    ducking: Option<&MusicDucking>,
 ) -> VidgenResult<()> {
     if voice_path.is_none() && music_path.is_none() {
         return Ok(());
@@ -851,9 +1295,14 @@ pub fn mix_audio_onto_video(
         mix_labels.push("[voice]".into());
     }
     if let Some(mi) = music_idx {
-        filter_parts.push(format!("[{mi}:a]volume={music_volume:.2}[music]"));
+        filter_parts.push(music_chain(&format!("[{mi}:a]"), music_volume, music_fade, ducking, "[music]"));
         mix_labels.push("[music]".into());
     }
+    if has_voice && has_music && ducking.is_some() {
+        filter_parts.push(duck_stage());
+        replace_label(&mut mix_labels, "[voice]", "[voicemix]");
+        replace_label(&mut mix_labels, "[music]", "[ducked]");
+    }
 
     let inputs = mix_labels.len();
     let labels = mix_labels.join("");
@@ -890,39 +1339,98 @@ pub fn mix_audio_onto_video(
     Ok(())
 }
 
-/// Burn subtitles into a video file via FFmpeg's `subtitles` filter (post-process).
-/// Renames the original video to a temp file, re-encodes with subtitles, then removes the temp.
-pub fn burn_in_subtitles(video_path: &Path, srt_path: &Path) -> VidgenResult<()> {
+/// Burn subtitles into a rendered video.
+///
+/// With `keep_clean`, the captioned copy is written next to the video as
+/// `<stem>-captioned.mp4` and the clean video is preserved; otherwise the video
+/// is replaced in place. Returns the path of the captioned video.
+pub fn burn_in_subtitles(
+    video_path: &Path,
+    srt_path: &Path,
+    keep_clean: bool,
+    force_style: &str,
+    fonts_dir: Option<&Path>,
+) -> VidgenResult<PathBuf> {
+    burn_in_subtitles_with(video_path, srt_path, keep_clean, |input, srt, output| {
+        ffmpeg_burn_subtitles(input, srt, output, force_style, fonts_dir)
+    })
+}
+
+/// Path of the captioned copy kept alongside a clean video (`slug.mp4` → `slug-captioned.mp4`).
+pub fn captioned_path(video_path: &Path) -> PathBuf {
+    let stem = video_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = video_path
+        .extension()
+        .map(|e| e.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "mp4".into());
+    video_path.with_file_name(format!("{stem}-captioned.{ext}"))
+}
+
+/// `burn_in_subtitles` with the FFmpeg step (`burn(input, srt, output)`) injected.
+fn burn_in_subtitles_with(
+    video_path: &Path,
+    srt_path: &Path,
+    keep_clean: bool,
+    burn: impl FnOnce(&Path, &Path, &Path) -> VidgenResult<()>,
+) -> VidgenResult<PathBuf> {
+    if keep_clean {
+        let captioned = captioned_path(video_path);
+        burn(video_path, srt_path, &captioned)?;
+        return Ok(captioned);
+    }
+
     let tmp_path = video_path.with_extension("tmp.mp4");
     std::fs::rename(video_path, &tmp_path)?;
+    let result = burn(&tmp_path, srt_path, video_path);
+    // Remove temp file regardless of success
+    let _ = std::fs::remove_file(&tmp_path);
+    result.map(|()| video_path.to_path_buf())
+}
 
-    // Escape path for FFmpeg subtitles filter (backslashes and colons need escaping)
-    let srt_escaped = srt_path
-        .display()
+/// Escape a path for use inside an FFmpeg filter option (backslashes and colons need escaping).
+fn escape_filter_path(path: &Path) -> String {
+    path.display()
         .to_string()
         .replace('\\', "/")
-        .replace(':', "\\:");
+        .replace(':', "\\:")
+}
 
-    let subtitle_filter = format!(
-        "subtitles=filename='{}':force_style='FontSize=24,PrimaryColour=&H00FFFFFF,Alignment=2'",
-        srt_escaped
-    );
+/// FFmpeg `subtitles` filter for `srt_path` with the given ASS `force_style`.
+/// `fonts_dir` lets libass find project fonts that aren't installed system-wide.
+pub fn subtitle_filter(srt_path: &Path, force_style: &str, fonts_dir: Option<&Path>) -> String {
+    let mut filter = format!("subtitles=filename='{}'", escape_filter_path(srt_path));
+    if let Some(dir) = fonts_dir {
+        filter.push_str(&format!(":fontsdir='{}'", escape_filter_path(dir)));
+    }
+    filter.push_str(&format!(":force_style='{force_style}'"));
+    filter
+}
 
-    let output = Command::new("ffmpeg")
+/// Run FFmpeg's `subtitles` filter from `input` to `output` with the given ASS `force_style`.
+fn ffmpeg_burn_subtitles(
+    input: &Path,
+    srt_path: &Path,
+    output: &Path,
+    force_style: &str,
+    fonts_dir: Option<&Path>,
+) -> VidgenResult<()> {
+    let subtitle_filter = subtitle_filter(srt_path, force_style, fonts_dir);
+
+    let result = Command::new("ffmpeg")
         .args(["-y", "-i"])
-        .arg(&tmp_path)
+        .arg(input)
         .args(["-vf", &subtitle_filter, "-c:a", "copy"])
-        .arg(video_path)
+        .arg(output)
         .stdout(Stdio::null())
         .stderr(Stdio::piped())
         .output()
         .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg burn-in: {e}")))?;
 
-    // Remove temp file regardless of success
-    let _ = std::fs::remove_file(&tmp_path);
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
         return Err(VidgenError::Ffmpeg(format!(
             "FFmpeg subtitle burn-in failed: {}",
             stderr.lines().last().unwrap_or("unknown error")
@@ -932,36 +1440,348 @@ pub fn burn_in_subtitles(video_path: &Path, srt_path: &Path) -> VidgenResult<()>
     Ok(())
 }
 
-/// Apply audio fade-in and/or fade-out to a video file (post-process).
-/// Used for project-wide background music fades.
-pub fn apply_audio_fades(
+/// An extra audio stream (e.g. narration in another language) for the final video.
+#[derive(Debug, Clone)]
+pub struct AudioTrack {
+    pub path: PathBuf,
+    pub language: String,
+    pub title: Option<String>,
+}
+
+/// Build FFmpeg args that keep the video stream (and its own narration, if any)
+/// and add each track as a separate audio stream tagged with its language.
+/// The first audio stream is the default; the rest are selectable alternatives.
+pub fn multi_audio_args(
+    video: &Path,
+    video_has_audio: bool,
+    primary_language: Option<&str>,
+    tracks: &[AudioTrack],
+    output: &Path,
+) -> Vec<String> {
+    let mut args: Vec<String> = vec!["-y".into(), "-i".into(), video.display().to_string()];
+    for track in tracks {
+        args.push("-i".into());
+        args.push(track.path.display().to_string());
+    }
+
+    args.extend(["-map".into(), "0:v".into()]);
+    if video_has_audio {
+        args.extend(["-map".into(), "0:a".into()]);
+    }
+    for i in 0..tracks.len() {
+        args.extend(["-map".into(), format!("{}:a", i + 1)]);
+    }
+    args.extend(["-c:v".into(), "copy".into()]);
+
+    let offset = usize::from(video_has_audio);
+    if video_has_audio {
+        args.extend(["-c:a:0".into(), "copy".into()]);
+        if let Some(lang) = primary_language {
+            args.extend(["-metadata:s:a:0".into(), format!("language={lang}")]);
+        }
+    }
+    for (i, track) in tracks.iter().enumerate() {
+        let n = offset + i;
+        args.extend([format!("-c:a:{n}"), "aac".into(), format!("-b:a:{n}"), "192k".into()]);
+        args.extend([format!("-metadata:s:a:{n}"), format!("language={}", track.language)]);
+        if let Some(ref title) = track.title {
+            args.extend([format!("-metadata:s:a:{n}"), format!("title={title}")]);
+        }
+    }
+    for n in 0..offset + tracks.len() {
+        let disposition = if n == 0 { "default" } else { "0" };
+        args.extend([format!("-disposition:a:{n}"), disposition.into()]);
+    }
+
+    args.push(output.display().to_string());
+    args
+}
+
+/// Mux extra audio tracks into a finished video in place (final pass).
+pub fn mux_audio_tracks(
     video_path: &Path,
-    total_duration: f64,
-    fade_in: f64,
-    fade_out: f64,
+    primary_language: Option<&str>,
+    tracks: &[AudioTrack],
 ) -> VidgenResult<()> {
-    if fade_in <= 0.0 && fade_out <= 0.0 {
+    if tracks.is_empty() {
         return Ok(());
     }
+    for track in tracks {
+        if !track.path.exists() {
+            return Err(VidgenError::Ffmpeg(format!(
+                "Audio track ({}) not found: {}",
+                track.language,
+                track.path.display()
+            )));
+        }
+    }
 
-    let tmp_path = video_path.with_extension("fade-tmp.mp4");
+    let tmp_path = video_path.with_extension("tmp.mp4");
     std::fs::rename(video_path, &tmp_path)?;
+    let args = multi_audio_args(
+        &tmp_path,
+        has_audio_stream(&tmp_path),
+        primary_language,
+        tracks,
+        video_path,
+    );
+    let output = Command::new("ffmpeg")
+        .args(&args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output();
+
+    let failure = match output {
+        Ok(o) if o.status.success() => None,
+        Ok(o) => Some(format!(
+            "FFmpeg audio track mux failed: {}",
+            String::from_utf8_lossy(&o.stderr).lines().last().unwrap_or("unknown error")
+        )),
+        Err(e) => Some(format!("Failed to spawn ffmpeg for audio track mux: {e}")),
+    };
+    match failure {
+        None => {
+            let _ = std::fs::remove_file(&tmp_path);
+            Ok(())
+        }
+        Some(msg) => {
+            // Keep the un-muxed video rather than losing the render
+            std::fs::rename(&tmp_path, video_path)?;
+            Err(VidgenError::Ffmpeg(msg))
+        }
+    }
+}
 
-    let mut filter_parts = Vec::new();
-    if fade_in > 0.0 {
-        filter_parts.push(format!("afade=t=in:st=0:d={fade_in:.2}"));
+/// FFmpeg args that write frame `frame` (0-based) of `video` to the JPEG `output`.
+/// `select` counts decoded frames, so the index is exact rather than a seek.
+pub fn poster_frame_args(video: &Path, frame: u32, output: &Path) -> Vec<String> {
+    vec![
+        "-y".into(),
+        "-i".into(),
+        video.display().to_string(),
+        "-vf".into(),
+        format!("select=eq(n\\,{frame})"),
+        "-frames:v".into(),
+        "1".into(),
+        "-q:v".into(),
+        "2".into(),
+        output.display().to_string(),
+    ]
+}
+
+/// FFmpeg args that copy `video` into `output` with `poster` added as a cover
+/// image (`attached_pic`), which players show before playback starts.
+pub fn attach_poster_args(video: &Path, poster: &Path, output: &Path) -> Vec<String> {
+    vec![
+        "-y".into(),
+        "-i".into(),
+        video.display().to_string(),
+        "-i".into(),
+        poster.display().to_string(),
+        "-map".into(),
+        "0".into(),
+        "-map".into(),
+        "1".into(),
+        "-c".into(),
+        "copy".into(),
+        "-disposition:v:1".into(),
+        "attached_pic".into(),
+        "-movflags".into(),
+        "+faststart".into(),
+        output.display().to_string(),
+    ]
+}
+
+/// Embed frame `frame` of a finished MP4/MOV as its poster image, in place (final pass).
+pub fn embed_poster_frame(video_path: &Path, frame: u32) -> VidgenResult<()> {
+    let run = |args: Vec<String>, step: &str| -> VidgenResult<()> {
+        let output = Command::new("ffmpeg")
+            .args(&args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .output()
+            .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg for {step}: {e}")))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(VidgenError::Ffmpeg(format!(
+                "FFmpeg {step} failed: {}",
+                String::from_utf8_lossy(&output.stderr).lines().last().unwrap_or("unknown error")
+            )))
+        }
+    };
+
+    let poster = video_path.with_extension("poster.jpg");
+    run(poster_frame_args(video_path, frame, &poster), "poster frame extraction")?;
+    if !poster.exists() {
+        return Err(VidgenError::Ffmpeg(format!(
+            "output.poster_frame {frame} is past the end of {}",
+            video_path.display()
+        )));
     }
-    if fade_out > 0.0 {
-        let start = (total_duration - fade_out).max(0.0);
-        filter_parts.push(format!("afade=t=out:st={start:.2}:d={fade_out:.2}"));
+
+    let ext = video_path
+        .extension()
+        .map(|e| e.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "mp4".into());
+    let tmp_path = video_path.with_extension(format!("poster-tmp.{ext}"));
+    let muxed = run(attach_poster_args(video_path, &poster, &tmp_path), "poster mux");
+    let _ = std::fs::remove_file(&poster);
+    match muxed {
+        Ok(()) => {
+            std::fs::rename(&tmp_path, video_path)?;
+            Ok(())
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&tmp_path);
+            Err(e)
+        }
     }
-    let af = filter_parts.join(",");
+}
+
+/// FFmpeg args that copy `video` into `output` and add `srt` as a soft
+/// `mov_text` subtitle stream tagged with `language`. It isn't shown by default.
+pub fn embed_subtitle_args(video: &Path, srt: &Path, language: &str, output: &Path) -> Vec<String> {
+    vec![
+        "-y".into(),
+        "-i".into(),
+        video.display().to_string(),
+        "-i".into(),
+        srt.display().to_string(),
+        "-map".into(),
+        "0".into(),
+        "-map".into(),
+        "1:s".into(),
+        "-c".into(),
+        "copy".into(),
+        "-c:s".into(),
+        "mov_text".into(),
+        "-metadata:s:s:0".into(),
+        format!("language={language}"),
+        "-disposition:s:0".into(),
+        "0".into(),
+        "-movflags".into(),
+        "+faststart".into(),
+        output.display().to_string(),
+    ]
+}
 
+/// Mux an SRT into a finished MP4/MOV as a soft subtitle track, in place (final pass).
+pub fn embed_subtitles(video_path: &Path, srt_path: &Path, language: &str) -> VidgenResult<()> {
+    let ext = video_path
+        .extension()
+        .map(|e| e.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "mp4".into());
+    let tmp_path = video_path.with_extension(format!("subs-tmp.{ext}"));
     let output = Command::new("ffmpeg")
-        .args(["-y", "-i"])
-        .arg(tmp_path.as_os_str())
-        .args(["-c:v", "copy", "-af", &af])
-        .arg(video_path.as_os_str())
+        .args(embed_subtitle_args(
+            video_path, srt_path, language, &tmp_path,
+        ))
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| {
+            VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg for subtitle mux: {e}"))
+        })?;
+    if output.status.success() {
+        std::fs::rename(&tmp_path, video_path)?;
+        Ok(())
+    } else {
+        let _ = std::fs::remove_file(&tmp_path);
+        Err(VidgenError::Ffmpeg(format!(
+            "FFmpeg subtitle mux failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .last()
+                .unwrap_or("unknown error")
+        )))
+    }
+}
+
+/// FFmpeg args that copy `video` into `output` with the global `tags`
+/// (see [`crate::config::OutputMetadata::tags`]) set. Streams are not re-encoded.
+pub fn metadata_args(video: &Path, tags: &[(&str, String)], output: &Path) -> Vec<String> {
+    let mut args: Vec<String> = vec![
+        "-y".into(),
+        "-i".into(),
+        video.display().to_string(),
+        "-map".into(),
+        "0".into(),
+        "-c".into(),
+        "copy".into(),
+    ];
+    for (key, value) in tags {
+        args.extend(["-metadata".into(), format!("{key}={value}")]);
+    }
+    args.extend([
+        "-movflags".into(),
+        "+faststart".into(),
+        output.display().to_string(),
+    ]);
+    args
+}
+
+/// Tag a finished MP4 with `tags`, in place (remux pass).
+pub fn apply_metadata(video_path: &Path, tags: &[(&str, String)]) -> VidgenResult<()> {
+    if tags.is_empty() {
+        return Ok(());
+    }
+
+    let tmp_path = video_path.with_extension("metadata-tmp.mp4");
+    std::fs::rename(video_path, &tmp_path)?;
+
+    let output = Command::new("ffmpeg")
+        .args(metadata_args(&tmp_path, tags, video_path))
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg for metadata: {e}")))?;
+
+    if !output.status.success() {
+        // Keep the untagged video rather than losing the render
+        std::fs::rename(&tmp_path, video_path)?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(VidgenError::Ffmpeg(format!(
+            "FFmpeg metadata pass failed: {}",
+            stderr.lines().last().unwrap_or("unknown error")
+        )));
+    }
+    let _ = std::fs::remove_file(&tmp_path);
+
+    Ok(())
+}
+
+/// FFmpeg args that fade the video in from black over its first `duration`
+/// seconds. The video is re-encoded; audio is copied unchanged.
+pub fn fade_from_black_args(
+    input: &Path,
+    duration: f64,
+    platform: &PlatformPreset,
+    output: &Path,
+) -> Vec<String> {
+    let mut args: Vec<String> = vec![
+        "-y".into(),
+        "-i".into(),
+        input.to_string_lossy().into_owned(),
+        "-vf".into(),
+        format!("fade=t=in:st=0:d={duration:.3}"),
+    ];
+    args.extend(h264_video_args(platform));
+    args.extend(["-c:a".into(), "copy".into(), output.to_string_lossy().into_owned()]);
+    args
+}
+
+/// Fade a finished video in from black, in place (see [`resolve_intro_transition`]).
+pub fn apply_fade_from_black(
+    video_path: &Path,
+    duration: f64,
+    platform: &PlatformPreset,
+) -> VidgenResult<()> {
+    let tmp_path = video_path.with_extension("intro-tmp.mp4");
+    std::fs::rename(video_path, &tmp_path)?;
+
+    let output = Command::new("ffmpeg")
+        .args(fade_from_black_args(&tmp_path, duration, platform, video_path))
         .stdout(Stdio::null())
         .stderr(Stdio::piped())
         .output()
@@ -972,7 +1792,7 @@ pub fn apply_audio_fades(
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(VidgenError::Ffmpeg(format!(
-            "FFmpeg audio fade failed: {}",
+            "FFmpeg fade from black failed: {}",
             stderr.lines().last().unwrap_or("unknown error")
         )));
     }
@@ -980,14 +1800,587 @@ pub fn apply_audio_fades(
     Ok(())
 }
 
-/// Crop a video to a target aspect ratio (e.g., "9:16") using FFmpeg.
-pub fn apply_crop(video_path: &Path, aspect: &str) -> VidgenResult<()> {
-    let parts: Vec<u32> = aspect.split(':').filter_map(|s| s.parse().ok()).collect();
-    if parts.len() != 2 || parts[0] == 0 || parts[1] == 0 {
-        return Err(VidgenError::Other(format!("Invalid aspect ratio: {aspect}")));
+/// How [`loop_video`] joins the repeats of a finished video (`output.loop`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoopMode {
+    /// Repeat the file unchanged with stream copy
+    Copy,
+    /// Crossfade the last `d` seconds into the start, so every repeat joins
+    /// seamlessly. One cycle is re-encoded; it starts `d` seconds in.
+    Crossfade(f64),
+}
+
+impl LoopMode {
+    /// Crossfade when `output.loop_crossfade` is set and leaves room for it
+    /// (shorter than half the video), otherwise stream copy.
+    pub fn resolve(crossfade: Option<f64>, duration: f64) -> Self {
+        match crossfade {
+            Some(d) if d > 0.0 && d * 2.0 < duration => Self::Crossfade(d),
+            _ => Self::Copy,
+        }
     }
+}
 
-    // Probe source dimensions
+/// Path of the looped copy written alongside a video (`slug.mp4` → `slug-loop.mp4`).
+pub fn loop_path(video_path: &Path) -> PathBuf {
+    let stem = video_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = video_path
+        .extension()
+        .map(|e| e.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "mp4".into());
+    video_path.with_file_name(format!("{stem}-loop.{ext}"))
+}
+
+/// Concat demuxer list that plays `video` `count` times.
+pub fn loop_concat_list(video: &Path, count: u32) -> String {
+    format!("file '{}'\n", video.display()).repeat(count as usize)
+}
+
+/// Filter graph for one seamless loop cycle of a `duration`-second video: the
+/// part after the first `crossfade` seconds, whose end crossfades into that
+/// opening. The cycle is `crossfade` seconds shorter than the input.
+pub fn loop_cycle_filter(duration: f64, crossfade: f64, has_audio: bool) -> String {
+    let d = crossfade;
+    let offset = duration - 2.0 * d;
+    let mut graph = format!(
+        "[0:v]split[vh][vb];\
+         [vh]trim=0:{d:.3},setpts=PTS-STARTPTS[head];\
+         [vb]trim={d:.3},setpts=PTS-STARTPTS[body];\
+         [body][head]xfade=transition=fade:duration={d:.3}:offset={offset:.3}[vout]"
+    );
+    if has_audio {
+        graph.push_str(&format!(
+            ";[0:a]asplit[ah][ab];\
+             [ah]atrim=0:{d:.3},asetpts=PTS-STARTPTS[ahead];\
+             [ab]atrim={d:.3},asetpts=PTS-STARTPTS[abody];\
+             [abody][ahead]acrossfade=d={d:.3}[aout]"
+        ));
+    }
+    graph
+}
+
+/// Write `slug-loop.mp4`: the finished video repeated `count` times with a
+/// stream-copy concat. With [`LoopMode::Crossfade`] a seamless cycle is
+/// encoded first and that is repeated instead.
+pub fn loop_video(
+    video_path: &Path,
+    count: u32,
+    mode: LoopMode,
+    platform: &PlatformPreset,
+) -> VidgenResult<PathBuf> {
+    let run = |args: Vec<String>, step: &str| -> VidgenResult<()> {
+        let output = Command::new("ffmpeg")
+            .args(&args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .output()
+            .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg for {step}: {e}")))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(VidgenError::Ffmpeg(format!(
+                "FFmpeg {step} failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+                    .lines()
+                    .last()
+                    .unwrap_or("unknown error")
+            )))
+        }
+    };
+
+    let cycle_path = video_path.with_extension("loop-cycle.mp4");
+    let source = match mode {
+        LoopMode::Copy => video_path.to_path_buf(),
+        LoopMode::Crossfade(d) => {
+            let duration = probe_video_duration(video_path)?;
+            let has_audio = has_audio_stream(video_path);
+            let mut args: Vec<String> = vec![
+                "-y".into(),
+                "-i".into(),
+                video_path.display().to_string(),
+                "-filter_complex".into(),
+                loop_cycle_filter(duration, d, has_audio),
+                "-map".into(),
+                "[vout]".into(),
+            ];
+            if has_audio {
+                args.extend(
+                    [
+                        "-map",
+                        "[aout]",
+                        "-c:a",
+                        "aac",
+                        "-b:a",
+                        platform.audio_bitrate,
+                    ]
+                    .map(String::from),
+                );
+            }
+            args.extend(h264_video_args(platform));
+            args.push(cycle_path.display().to_string());
+            run(args, "loop crossfade")?;
+            cycle_path.clone()
+        }
+    };
+
+    let list_path = video_path.with_extension("loop-list.txt");
+    std::fs::write(&list_path, loop_concat_list(&source, count))?;
+    let output_path = loop_path(video_path);
+    let result = run(
+        vec![
+            "-y".into(),
+            "-f".into(),
+            "concat".into(),
+            "-safe".into(),
+            "0".into(),
+            "-i".into(),
+            list_path.display().to_string(),
+            "-map".into(),
+            "0".into(),
+            "-c".into(),
+            "copy".into(),
+            "-movflags".into(),
+            "+faststart".into(),
+            output_path.display().to_string(),
+        ],
+        "loop concat",
+    );
+    let _ = std::fs::remove_file(&list_path);
+    let _ = std::fs::remove_file(&cycle_path);
+    result.map(|()| output_path)
+}
+
+/// Apply audio fade-in and/or fade-out to a video file (post-process).
+/// Used for project-wide background music fades.
+pub fn apply_audio_fades(
+    video_path: &Path,
+    total_duration: f64,
+    fade_in: f64,
+    fade_out: f64,
+) -> VidgenResult<()> {
+    if fade_in <= 0.0 && fade_out <= 0.0 {
+        return Ok(());
+    }
+
+    let tmp_path = video_path.with_extension("fade-tmp.mp4");
+    std::fs::rename(video_path, &tmp_path)?;
+
+    let mut filter_parts = Vec::new();
+    if fade_in > 0.0 {
+        filter_parts.push(format!("afade=t=in:st=0:d={fade_in:.2}"));
+    }
+    if fade_out > 0.0 {
+        let start = (total_duration - fade_out).max(0.0);
+        filter_parts.push(format!("afade=t=out:st={start:.2}:d={fade_out:.2}"));
+    }
+    let af = filter_parts.join(",");
+
+    let output = Command::new("ffmpeg")
+        .args(["-y", "-i"])
+        .arg(tmp_path.as_os_str())
+        .args(["-c:v", "copy", "-af", &af])
+        .arg(video_path.as_os_str())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg fade: {e}")))?;
+
+    let _ = std::fs::remove_file(&tmp_path);
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(VidgenError::Ffmpeg(format!(
+            "FFmpeg audio fade failed: {}",
+            stderr.lines().last().unwrap_or("unknown error")
+        )));
+    }
+
+    Ok(())
+}
+
+/// Start time of each scene in the joined video. A transition after scene `i`
+/// overlaps the start of scene `i + 1` with the end of scene `i`.
+pub fn scene_start_times(
+    durations: &[f64],
+    transitions: &[Option<SceneTransition>],
+    scene_gap: f64,
+) -> Vec<f64> {
+    let gaps = scene_gaps(transitions, durations.len(), scene_gap);
+    let mut start = 0.0;
+    durations
+        .iter()
+        .enumerate()
+        .map(|(i, dur)| {
+            let scene_start = start;
+            let overlap = transitions
+                .get(i)
+                .and_then(|t| t.as_ref())
+                .map_or(0.0, |t| t.duration);
+            start += dur - overlap + gaps[i];
+            scene_start
+        })
+        .collect()
+}
+
+/// Length of the joined video: the last scene's start plus its duration.
+pub fn joined_duration(
+    durations: &[f64],
+    transitions: &[Option<SceneTransition>],
+    scene_gap: f64,
+) -> f64 {
+    scene_start_times(durations, transitions, scene_gap)
+        .last()
+        .zip(durations.last())
+        .map_or(0.0, |(start, dur)| start + dur)
+}
+
+/// Seconds of `video.scene_gap_secs` pause after each of `scene_count` scenes.
+/// Gaps go at hard cuts only; a boundary with a transition keeps its crossfade.
+pub fn scene_gaps(
+    transitions: &[Option<SceneTransition>],
+    scene_count: usize,
+    scene_gap: f64,
+) -> Vec<f64> {
+    (0..scene_count)
+        .map(|i| {
+            let is_cut = !matches!(transitions.get(i), Some(Some(_)));
+            if scene_gap > 0.0 && i + 1 < scene_count && is_cut {
+                scene_gap
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// A black (and, when the scenes have audio, silent) clip inserted between
+/// scenes for `video.scene_gap_secs`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneGap {
+    pub path: PathBuf,
+    pub duration: f64,
+}
+
+/// FFmpeg args that encode a `duration`-second black clip matching the scene
+/// encodes, with a silent stereo track at `audio_samplerate` when given.
+pub fn gap_clip_args(
+    duration: f64,
+    width: u32,
+    height: u32,
+    fps: u32,
+    audio_samplerate: Option<u32>,
+    platform: &PlatformPreset,
+    output: &Path,
+) -> Vec<String> {
+    let mut args: Vec<String> = vec![
+        "-y".into(),
+        "-f".into(),
+        "lavfi".into(),
+        "-t".into(),
+        format!("{duration:.3}"),
+        "-i".into(),
+        format!("color=c=black:s={width}x{height}:r={fps}"),
+    ];
+    if let Some(rate) = audio_samplerate {
+        args.extend([
+            "-f".into(),
+            "lavfi".into(),
+            "-t".into(),
+            format!("{duration:.3}"),
+            "-i".into(),
+            format!("anullsrc=r={rate}:cl=stereo"),
+        ]);
+    }
+    args.extend(h264_video_args(platform));
+    if audio_samplerate.is_some() {
+        args.extend(["-c:a", "aac", "-b:a", platform.audio_bitrate].map(String::from));
+    }
+    args.push(output.display().to_string());
+    args
+}
+
+/// Encode the gap clip for `video.scene_gap_secs` into `dir`.
+#[allow(clippy::too_many_arguments)]
+pub fn create_scene_gap(
+    dir: &Path,
+    duration: f64,
+    width: u32,
+    height: u32,
+    fps: u32,
+    with_audio: bool,
+    platform: &PlatformPreset,
+) -> VidgenResult<SceneGap> {
+    let path = dir.join("scene-gap.mp4");
+    let samplerate = with_audio.then_some(platform.audio_samplerate);
+    let output = Command::new("ffmpeg")
+        .args(gap_clip_args(
+            duration, width, height, fps, samplerate, platform, &path,
+        ))
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg for scene gap: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(VidgenError::Ffmpeg(format!(
+            "FFmpeg scene gap failed: {}",
+            stderr.lines().last().unwrap_or("unknown error")
+        )));
+    }
+    Ok(SceneGap { path, duration })
+}
+
+/// Insert `gap` into the segment list at every boundary [`scene_gaps`] pauses,
+/// returning the files, durations and per-boundary transitions to join.
+pub fn interleave_scene_gaps(
+    scene_files: &[PathBuf],
+    scene_durations: &[f64],
+    transitions: &[Option<SceneTransition>],
+    gap: &SceneGap,
+) -> (Vec<PathBuf>, Vec<f64>, Vec<Option<SceneTransition>>) {
+    let gaps = scene_gaps(transitions, scene_files.len(), gap.duration);
+    let mut files = Vec::new();
+    let mut durations = Vec::new();
+    let mut joins = Vec::new();
+    for (i, file) in scene_files.iter().enumerate() {
+        files.push(file.clone());
+        durations.push(scene_durations.get(i).copied().unwrap_or(0.0));
+        if gaps[i] > 0.0 {
+            joins.push(None);
+            files.push(gap.path.clone());
+            durations.push(gap.duration);
+            joins.push(None);
+        } else if i + 1 < scene_files.len() {
+            joins.push(transitions.get(i).cloned().flatten());
+        }
+    }
+    (files, durations, joins)
+}
+
+/// Filter graph for `[audio] music`: input 1 (looped with `-stream_loop`) is
+/// trimmed to `total` seconds, set to `volume_db`, silenced during `muted`
+/// (start, end) ranges and faded out over the last `fade_out` seconds, then
+/// mixed with the video's audio (input 0) when it has any.
+pub fn project_music_filter(
+    total: f64,
+    volume_db: f64,
+    fade_out: f64,
+    muted: &[(f64, f64)],
+    has_audio: bool,
+) -> String {
+    let gain = 10.0_f64.powf(volume_db / 20.0);
+    let mut chain = format!("[1:a]atrim=duration={total:.3},volume={gain:.4}");
+    if !muted.is_empty() {
+        let ranges: Vec<String> = muted
+            .iter()
+            .map(|(start, end)| format!("between(t,{start:.3},{end:.3})"))
+            .collect();
+        chain.push_str(&format!(",volume=0:enable='{}'", ranges.join("+")));
+    }
+    if fade_out > 0.0 {
+        let start = (total - fade_out).max(0.0);
+        chain.push_str(&format!(",afade=t=out:st={start:.3}:d={fade_out:.3}"));
+    }
+    if has_audio {
+        format!(
+            "{chain}[music];[0:a][music]amix=inputs=2:duration=first:dropout_transition=0:normalize=0[aout]"
+        )
+    } else {
+        format!("{chain}[aout]")
+    }
+}
+
+/// Mix one continuous soundtrack (`[audio] music`) under a finished video, in
+/// place. The track loops if it is shorter than `total` seconds and is cut off
+/// if longer. Scenes with their own music are given as `muted` ranges, where
+/// the soundtrack steps aside. The video is copied.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_project_music(
+    video_path: &Path,
+    music_path: &Path,
+    total: f64,
+    volume_db: f64,
+    fade_out: f64,
+    muted: &[(f64, f64)],
+    platform: &PlatformPreset,
+) -> VidgenResult<()> {
+    if !music_path.exists() {
+        return Err(VidgenError::Other(format!(
+            "audio.music not found: {}",
+            music_path.display()
+        )));
+    }
+    let has_audio = has_audio_stream(video_path);
+    let filter = project_music_filter(total, volume_db, fade_out, muted, has_audio);
+
+    let tmp_path = video_path.with_extension("music-tmp.mp4");
+    std::fs::rename(video_path, &tmp_path)?;
+
+    let output = Command::new("ffmpeg")
+        .args(["-y", "-i"])
+        .arg(tmp_path.as_os_str())
+        .args(["-stream_loop", "-1", "-i"])
+        .arg(music_path.as_os_str())
+        .args(["-filter_complex", &filter, "-map", "0:v", "-map", "[aout]"])
+        .args([
+            "-c:v",
+            "copy",
+            "-c:a",
+            "aac",
+            "-ac",
+            "2",
+            "-b:a",
+            platform.audio_bitrate,
+            "-ar",
+            &platform.audio_samplerate.to_string(),
+            "-shortest",
+        ])
+        .arg(video_path.as_os_str())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg music mix: {e}")))?;
+
+    if !output.status.success() {
+        let _ = std::fs::rename(&tmp_path, video_path);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(VidgenError::Ffmpeg(format!(
+            "FFmpeg project music mix failed: {}",
+            stderr.lines().last().unwrap_or("unknown error")
+        )));
+    }
+    let _ = std::fs::remove_file(&tmp_path);
+
+    Ok(())
+}
+
+/// True-peak ceiling (dBTP) and loudness range (LU) used with `output.audio.target_lufs`.
+const LOUDNORM_TRUE_PEAK: f64 = -1.5;
+const LOUDNORM_RANGE: f64 = 11.0;
+
+/// Loudness of the input as measured by a first `loudnorm` pass.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoudnessStats {
+    pub input_i: f64,
+    pub input_tp: f64,
+    pub input_lra: f64,
+    pub input_thresh: f64,
+    pub target_offset: f64,
+}
+
+/// `loudnorm` filter aiming at `target_lufs`. Without `measured` it is the
+/// analysis (or single-pass) filter; with the first pass's stats it applies a
+/// linear gain, which keeps dynamics intact.
+pub fn loudnorm_filter(target_lufs: f64, measured: Option<&LoudnessStats>) -> String {
+    let base = format!("loudnorm=I={target_lufs}:TP={LOUDNORM_TRUE_PEAK}:LRA={LOUDNORM_RANGE}");
+    match measured {
+        None => base,
+        Some(m) => format!(
+            "{base}:measured_I={}:measured_TP={}:measured_LRA={}:measured_thresh={}:offset={}:linear=true",
+            m.input_i, m.input_tp, m.input_lra, m.input_thresh, m.target_offset
+        ),
+    }
+}
+
+/// Parse the JSON block `loudnorm=...:print_format=json` prints at the end of
+/// FFmpeg's stderr. `None` if it's missing or a value isn't finite (silent audio).
+pub fn parse_loudnorm_stats(stderr: &str) -> Option<LoudnessStats> {
+    let json = stderr.get(stderr.rfind('{')?..=stderr.rfind('}')?)?;
+    let map: serde_json::Map<String, serde_json::Value> = serde_json::from_str(json).ok()?;
+    let field = |key: &str| {
+        map.get(key)?
+            .as_str()?
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite())
+    };
+    Some(LoudnessStats {
+        input_i: field("input_i")?,
+        input_tp: field("input_tp")?,
+        input_lra: field("input_lra")?,
+        input_thresh: field("input_thresh")?,
+        target_offset: field("target_offset")?,
+    })
+}
+
+/// Normalize the loudness of a finished video's audio to `target_lufs`, in place
+/// (`[output.audio] normalize`). Two-pass: the first pass measures, the second
+/// applies the correction; falls back to single-pass `loudnorm` if the
+/// measurement can't be read. Video is copied. Returns `false` without touching
+/// the file when it has no audio stream.
+pub fn normalize_loudness(
+    video_path: &Path,
+    target_lufs: f64,
+    platform: &PlatformPreset,
+) -> VidgenResult<bool> {
+    if !has_audio_stream(video_path) {
+        return Ok(false);
+    }
+
+    let analysis = Command::new("ffmpeg")
+        .args(["-hide_banner", "-i"])
+        .arg(video_path.as_os_str())
+        .args([
+            "-vn",
+            "-af",
+            &format!("{}:print_format=json", loudnorm_filter(target_lufs, None)),
+            "-f",
+            "null",
+            "-",
+        ])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg loudnorm: {e}")))?;
+    let measured = parse_loudnorm_stats(&String::from_utf8_lossy(&analysis.stderr));
+    let af = loudnorm_filter(target_lufs, measured.as_ref());
+
+    let tmp_path = video_path.with_extension("loudnorm-tmp.mp4");
+    std::fs::rename(video_path, &tmp_path)?;
+
+    let output = Command::new("ffmpeg")
+        .args(["-y", "-i"])
+        .arg(tmp_path.as_os_str())
+        .args(["-c:v", "copy", "-af", &af])
+        .args([
+            "-c:a",
+            "aac",
+            "-b:a",
+            platform.audio_bitrate,
+            "-ar",
+            &platform.audio_samplerate.to_string(),
+        ])
+        .arg(video_path.as_os_str())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg loudnorm: {e}")))?;
+
+    if !output.status.success() {
+        let _ = std::fs::rename(&tmp_path, video_path);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(VidgenError::Ffmpeg(format!(
+            "FFmpeg loudness normalization failed: {}",
+            stderr.lines().last().unwrap_or("unknown error")
+        )));
+    }
+    let _ = std::fs::remove_file(&tmp_path);
+
+    Ok(true)
+}
+
+/// Crop a video to a target aspect ratio (e.g., "9:16") using FFmpeg.
+pub fn apply_crop(video_path: &Path, aspect: &str) -> VidgenResult<()> {
+    let parts: Vec<u32> = aspect.split(':').filter_map(|s| s.parse().ok()).collect();
+    if parts.len() != 2 || parts[0] == 0 || parts[1] == 0 {
+        return Err(VidgenError::Other(format!("Invalid aspect ratio: {aspect}")));
+    }
+
+    // Probe source dimensions
     let probe = Command::new("ffprobe")
         .args(["-v", "quiet", "-select_streams", "v:0",
                "-show_entries", "stream=width,height", "-of", "csv=p=0"])
@@ -1001,33 +2394,373 @@ pub fn apply_crop(video_path: &Path, aspect: &str) -> VidgenResult<()> {
     }
     let (src_w, src_h) = (dims[0], dims[1]);
 
-    let target_ratio = parts[0] as f64 / parts[1] as f64;
-    let source_ratio = src_w as f64 / src_h as f64;
-    let (crop_w, crop_h) = if target_ratio < source_ratio {
-        let h = src_h;
-        let w = ((h as f64 * target_ratio) as u32) & !1; // even
-        (w, h)
-    } else {
-        let w = src_w;
-        let h = ((w as f64 / target_ratio) as u32) & !1; // even
-        (w, h)
+    let target_ratio = parts[0] as f64 / parts[1] as f64;
+    let source_ratio = src_w as f64 / src_h as f64;
+    let (crop_w, crop_h) = if target_ratio < source_ratio {
+        let h = src_h;
+        let w = ((h as f64 * target_ratio) as u32) & !1; // even
+        (w, h)
+    } else {
+        let w = src_w;
+        let h = ((w as f64 / target_ratio) as u32) & !1; // even
+        (w, h)
+    };
+    let x = (src_w - crop_w) / 2;
+    let y = (src_h - crop_h) / 2;
+
+    let tmp = video_path.with_extension("crop-tmp.mp4");
+    std::fs::rename(video_path, &tmp)?;
+    let status = Command::new("ffmpeg")
+        .args(["-y", "-i"])
+        .arg(&tmp)
+        .args(["-vf", &format!("crop={crop_w}:{crop_h}:{x}:{y}"), "-c:a", "copy"])
+        .arg(video_path)
+        .stdout(Stdio::null()).stderr(Stdio::null())
+        .status()
+        .map_err(|e| VidgenError::Ffmpeg(format!("crop failed: {e}")))?;
+    let _ = std::fs::remove_file(&tmp);
+    if !status.success() {
+        return Err(VidgenError::Ffmpeg("FFmpeg crop failed".into()));
+    }
+    Ok(())
+}
+
+/// Stream parameters that must match for a stream-copy concat (`render --append`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamParams {
+    pub width: u32,
+    pub height: u32,
+    /// FFmpeg `r_frame_rate`, e.g. "30/1".
+    pub frame_rate: String,
+    pub video_codec: String,
+    pub pix_fmt: String,
+    /// `(codec, sample_rate, channels)` of the first audio stream, if any.
+    pub audio: Option<(String, u32, u32)>,
+}
+
+impl StreamParams {
+    /// Parse `ffprobe -show_streams -of json` output.
+    fn from_ffprobe_json(data: &serde_json::Value) -> Option<Self> {
+        let streams = data["streams"].as_array()?;
+        let video = streams.iter().find(|s| s["codec_type"] == "video")?;
+        let audio = streams
+            .iter()
+            .find(|s| s["codec_type"] == "audio")
+            .map(|a| {
+                (
+                    a["codec_name"].as_str().unwrap_or("").to_string(),
+                    a["sample_rate"]
+                        .as_str()
+                        .and_then(|r| r.parse().ok())
+                        .unwrap_or(0),
+                    a["channels"].as_u64().unwrap_or(0) as u32,
+                )
+            });
+        Some(Self {
+            width: video["width"].as_u64()? as u32,
+            height: video["height"].as_u64()? as u32,
+            frame_rate: video["r_frame_rate"].as_str().unwrap_or("").to_string(),
+            video_codec: video["codec_name"].as_str().unwrap_or("").to_string(),
+            pix_fmt: video["pix_fmt"].as_str().unwrap_or("").to_string(),
+            audio,
+        })
+    }
+}
+
+/// Probe the stream parameters relevant to appending onto `path`.
+pub fn probe_stream_params(path: &Path) -> VidgenResult<StreamParams> {
+    let output = Command::new("ffprobe")
+        .args(["-v", "quiet", "-show_streams", "-of", "json"])
+        .arg(path.as_os_str())
+        .output()
+        .map_err(|e| VidgenError::Ffmpeg(format!("Failed to run ffprobe: {e}")))?;
+    let data: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| VidgenError::Ffmpeg(format!("Failed to parse ffprobe JSON: {e}")))?;
+    StreamParams::from_ffprobe_json(&data)
+        .ok_or_else(|| VidgenError::Ffmpeg(format!("No video stream found in {}", path.display())))
+}
+
+/// Describe why `addition` can't be stream-copied onto `existing`, or `None`
+/// if the concat demuxer can join them without re-encoding.
+pub fn append_incompatibility(existing: &StreamParams, addition: &StreamParams) -> Option<String> {
+    let mut diffs = Vec::new();
+    if (existing.width, existing.height) != (addition.width, addition.height) {
+        diffs.push(format!(
+            "resolution {}x{} vs {}x{}",
+            existing.width, existing.height, addition.width, addition.height
+        ));
+    }
+    if existing.frame_rate != addition.frame_rate {
+        diffs.push(format!(
+            "frame rate {} vs {}",
+            existing.frame_rate, addition.frame_rate
+        ));
+    }
+    if existing.video_codec != addition.video_codec {
+        diffs.push(format!(
+            "video codec {} vs {}",
+            existing.video_codec, addition.video_codec
+        ));
+    }
+    if existing.pix_fmt != addition.pix_fmt {
+        diffs.push(format!(
+            "pixel format {} vs {}",
+            existing.pix_fmt, addition.pix_fmt
+        ));
+    }
+    if existing.audio != addition.audio {
+        let describe = |a: &Option<(String, u32, u32)>| match a {
+            Some((codec, rate, channels)) => format!("{codec} {rate}Hz {channels}ch"),
+            None => "no audio".to_string(),
+        };
+        diffs.push(format!(
+            "audio {} vs {}",
+            describe(&existing.audio),
+            describe(&addition.audio)
+        ));
+    }
+    if diffs.is_empty() {
+        None
+    } else {
+        Some(diffs.join(", "))
+    }
+}
+
+/// FFmpeg args that join the files listed in `concat_list` by stream copy.
+pub fn append_concat_args(concat_list: &Path, output: &Path) -> Vec<String> {
+    vec![
+        "-y".into(),
+        "-f".into(),
+        "concat".into(),
+        "-safe".into(),
+        "0".into(),
+        "-i".into(),
+        concat_list.display().to_string(),
+        "-c".into(),
+        "copy".into(),
+        "-movflags".into(),
+        "+faststart".into(),
+        output.display().to_string(),
+    ]
+}
+
+/// FFmpeg args that cut `start_at`..`end_at` (seconds into the file) out of
+/// `input` into a PCM WAV. Seeking happens on the input, so `-to` is a
+/// position in the source rather than a length.
+pub fn audio_trim_args(
+    input: &Path,
+    output: &Path,
+    start_at: Option<f64>,
+    end_at: Option<f64>,
+) -> Vec<String> {
+    let mut args = vec!["-y".to_string()];
+    if let Some(start) = start_at {
+        args.extend(["-ss".into(), format!("{start:.3}")]);
+    }
+    if let Some(end) = end_at {
+        args.extend(["-to".into(), format!("{end:.3}")]);
+    }
+    args.extend([
+        "-i".into(),
+        input.display().to_string(),
+        "-c:a".into(),
+        "pcm_s16le".into(),
+        output.display().to_string(),
+    ]);
+    args
+}
+
+/// Write the `start_at`..`end_at` window of an audio file to `output`.
+pub fn trim_audio(
+    input: &Path,
+    output: &Path,
+    start_at: Option<f64>,
+    end_at: Option<f64>,
+) -> VidgenResult<()> {
+    let result = Command::new("ffmpeg")
+        .args(audio_trim_args(input, output, start_at, end_at))
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg trim: {e}")))?;
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        return Err(VidgenError::Ffmpeg(format!(
+            "FFmpeg audio trim failed: {}",
+            stderr.lines().last().unwrap_or("unknown error")
+        )));
+    }
+    Ok(())
+}
+
+/// Join `existing` and `addition` into `output` without re-encoding. Fails
+/// before touching any file if their stream parameters differ.
+pub fn append_videos(existing: &Path, addition: &Path, output: &Path) -> VidgenResult<()> {
+    let base = probe_stream_params(existing)?;
+    let new = probe_stream_params(addition)?;
+    if let Some(reason) = append_incompatibility(&base, &new) {
+        return Err(VidgenError::Ffmpeg(format!(
+            "Cannot append to {}: new scenes don't match the existing output ({reason})",
+            existing.display()
+        )));
+    }
+
+    let concat_list = output.with_extension("append-list.txt");
+    std::fs::write(
+        &concat_list,
+        format!(
+            "file '{}'\nfile '{}'\n",
+            existing.display(),
+            addition.display()
+        ),
+    )?;
+    let tmp = output.with_extension("append-tmp.mp4");
+    let result = Command::new("ffmpeg")
+        .args(append_concat_args(&concat_list, &tmp))
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output();
+    let _ = std::fs::remove_file(&concat_list);
+
+    match result {
+        Ok(o) if o.status.success() => {
+            std::fs::rename(&tmp, output)?;
+            Ok(())
+        }
+        Ok(o) => {
+            let _ = std::fs::remove_file(&tmp);
+            Err(VidgenError::Ffmpeg(format!(
+                "FFmpeg append failed: {}",
+                String::from_utf8_lossy(&o.stderr)
+                    .lines()
+                    .last()
+                    .unwrap_or("unknown error")
+            )))
+        }
+        Err(e) => Err(VidgenError::Ffmpeg(format!(
+            "Failed to spawn ffmpeg append: {e}"
+        ))),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Output containers
+// ---------------------------------------------------------------------------
+
+/// libx264 args for scene files and MP4 output.
+fn h264_video_args(platform: &PlatformPreset) -> Vec<String> {
+    vec![
+        "-c:v".into(),
+        "libx264".into(), // H.264 codec
+        "-pix_fmt".into(),
+        "yuv420p".into(), // Pixel format for compatibility
+        "-crf".into(),
+        platform.crf.to_string(), // Quality
+        "-preset".into(),
+        platform.preset.into(), // Speed/quality tradeoff
+        "-movflags".into(),
+        "+faststart".into(), // Web-optimized
+    ]
+}
+
+/// Video codec args for scene files and the transition join: libx264 (see
+/// [`h264_video_args`]) or the platform's hardware encoder. Hardware encoders
+/// don't take `-crf`, so the CRF maps to each one's constant-quality flag.
+fn video_codec_args(platform: &PlatformPreset) -> Vec<String> {
+    let Some(hw) = platform.hw_encoder else {
+        return h264_video_args(platform);
+    };
+    let crf = platform.crf.to_string();
+    let mut args: Vec<String> = vec!["-c:v".into(), hw.codec().into()];
+    match hw {
+        HwAccel::Nvenc => args.extend(
+            [
+                "-pix_fmt", "yuv420p", "-rc", "vbr", "-cq", &crf, "-b:v", "0",
+            ]
+            .map(String::from),
+        ),
+        // Frames arrive as nv12 hardware surfaces via `hw_upload_filter`
+        HwAccel::Vaapi => args.extend(["-rc_mode", "CQP", "-qp", &crf].map(String::from)),
+        HwAccel::Videotoolbox => {
+            // -q:v runs 1-100 with higher meaning better, the reverse of CRF's 0-51
+            let q = (100 - platform.crf.min(51) * 100 / 51).max(1);
+            args.extend([
+                "-pix_fmt".into(),
+                "yuv420p".into(),
+                "-q:v".into(),
+                q.to_string(),
+            ]);
+        }
+    }
+    args.extend(["-movflags".into(), "+faststart".into()]);
+    args
+}
+
+/// Codec args for the final file in `container`. Every audio stream is kept,
+/// so extra language tracks survive the conversion (GIF drops audio).
+pub fn container_codec_args(container: OutputContainer, platform: &PlatformPreset) -> Vec<String> {
+    let map_streams = ["-map", "0:v:0", "-map", "0:a?"].map(String::from);
+    let mut args: Vec<String> = match container {
+        OutputContainer::Mp4 => {
+            let mut args = h264_video_args(platform);
+            args.extend(["-c:a", "aac", "-b:a", platform.audio_bitrate].map(String::from));
+            args
+        }
+        OutputContainer::Mov => [
+            "-c:v", "prores_ks", "-profile:v", "2", "-pix_fmt", "yuv422p10le",
+            "-c:a", "pcm_s16le",
+        ]
+        .map(String::from)
+        .to_vec(),
+        OutputContainer::Webm => {
+            let crf = platform.crf.to_string();
+            [
+                "-c:v", "libvpx-vp9", "-pix_fmt", "yuv420p", "-crf", &crf, "-b:v", "0",
+                "-row-mt", "1", "-c:a", "libopus", "-b:a", platform.audio_bitrate,
+            ]
+            .map(String::from)
+            .to_vec()
+        }
+        OutputContainer::Gif => {
+            return [
+                "-filter_complex",
+                "[0:v]split[a][b];[a]palettegen=stats_mode=diff[p];[b][p]paletteuse=dither=bayer",
+                "-an",
+                "-loop",
+                "0",
+            ]
+            .map(String::from)
+            .to_vec();
+        }
     };
-    let x = (src_w - crop_w) / 2;
-    let y = (src_h - crop_h) / 2;
+    args.splice(0..0, map_streams);
+    args
+}
 
-    let tmp = video_path.with_extension("crop-tmp.mp4");
-    std::fs::rename(video_path, &tmp)?;
-    let status = Command::new("ffmpeg")
+/// Re-encode the finished MP4 at `input` into `output` with the codecs for `container`.
+pub fn convert_container(
+    input: &Path,
+    output: &Path,
+    container: OutputContainer,
+    platform: &PlatformPreset,
+) -> VidgenResult<()> {
+    let result = Command::new("ffmpeg")
         .args(["-y", "-i"])
-        .arg(&tmp)
-        .args(["-vf", &format!("crop={crop_w}:{crop_h}:{x}:{y}"), "-c:a", "copy"])
-        .arg(video_path)
-        .stdout(Stdio::null()).stderr(Stdio::null())
-        .status()
-        .map_err(|e| VidgenError::Ffmpeg(format!("crop failed: {e}")))?;
-    let _ = std::fs::remove_file(&tmp);
-    if !status.success() {
-        return Err(VidgenError::Ffmpeg("FFmpeg crop failed".into()));
+        .arg(input)
+        .args(container_codec_args(container, platform))
+        .arg(output)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| VidgenError::Ffmpeg(format!("Failed to spawn ffmpeg: {e}")))?;
+    if !result.status.success() {
+        return Err(VidgenError::Ffmpeg(format!(
+            "FFmpeg {container} conversion failed: {}",
+            String::from_utf8_lossy(&result.stderr)
+                .lines()
+                .last()
+                .unwrap_or("unknown error")
+        )));
     }
     Ok(())
 }
@@ -1078,6 +2811,199 @@ mod tests {
         assert_eq!(TransitionType::Wipe.ffmpeg_name(), "wipeleft");
     }
 
+    #[test]
+    fn test_loop_concat_list_repeats_video() {
+        let list = loop_concat_list(Path::new("/out/v.mp4"), 3);
+        assert_eq!(list.lines().count(), 3);
+        assert!(list.lines().all(|l| l == "file '/out/v.mp4'"));
+        assert_eq!(loop_path(Path::new("/out/v.mp4")), PathBuf::from("/out/v-loop.mp4"));
+    }
+
+    #[test]
+    fn test_loop_mode_selects_crossfade() {
+        assert_eq!(LoopMode::resolve(None, 10.0), LoopMode::Copy);
+        assert_eq!(LoopMode::resolve(Some(0.5), 10.0), LoopMode::Crossfade(0.5));
+        // No room for the crossfade: fall back to a plain repeat
+        assert_eq!(LoopMode::resolve(Some(6.0), 10.0), LoopMode::Copy);
+        assert_eq!(LoopMode::resolve(Some(0.0), 10.0), LoopMode::Copy);
+
+        let graph = loop_cycle_filter(10.0, 0.5, true);
+        assert!(graph.contains("[vb]trim=0.500,"));
+        assert!(graph.contains("xfade=transition=fade:duration=0.500:offset=9.000[vout]"));
+        assert!(graph.contains("acrossfade=d=0.500[aout]"));
+        assert!(!loop_cycle_filter(10.0, 0.5, false).contains("[0:a]"));
+    }
+
+    #[test]
+    fn test_joined_duration_feeds_music_trim() {
+        let fade = |duration| {
+            Some(SceneTransition {
+                transition_type: TransitionType::Fade,
+                duration,
+            })
+        };
+        let durations = [5.0, 4.0, 6.0];
+        // Hard cut, then a 0.5s crossfade: the third scene starts 0.5s early
+        let transitions = [None, fade(0.5)];
+        assert_eq!(scene_start_times(&durations, &transitions, 0.0), [0.0, 5.0, 8.5]);
+        let total = joined_duration(&durations, &transitions, 0.0);
+        assert!((total - 14.5).abs() < 1e-9);
+        assert_eq!(joined_duration(&durations, &[], 0.0), 15.0);
+        assert_eq!(joined_duration(&[], &[], 0.0), 0.0);
+
+        let filter = project_music_filter(total, -12.0, 2.0, &[], true);
+        assert!(filter.starts_with("[1:a]atrim=duration=14.500,volume=0.2512"));
+        assert!(filter.contains("afade=t=out:st=12.500:d=2.000[music]"));
+        assert!(filter.ends_with(
+            "[0:a][music]amix=inputs=2:duration=first:dropout_transition=0:normalize=0[aout]"
+        ));
+    }
+
+    #[test]
+    fn test_scene_gap_extends_joined_duration() {
+        let durations = [5.0, 4.0, 6.0];
+        let cuts = [None, None];
+        // Each of the scenes - 1 boundaries gains the gap
+        assert_eq!(joined_duration(&durations, &cuts, 0.0), 15.0);
+        assert_eq!(joined_duration(&durations, &cuts, 1.5), 15.0 + 1.5 * 2.0);
+        assert_eq!(scene_start_times(&durations, &cuts, 1.5), [0.0, 6.5, 12.0]);
+        assert_eq!(scene_gaps(&cuts, 3, 1.5), [1.5, 1.5, 0.0]);
+        assert_eq!(scene_gaps(&cuts, 3, 0.0), [0.0, 0.0, 0.0]);
+
+        // A boundary with a transition keeps its crossfade and gets no gap
+        let fade = Some(SceneTransition {
+            transition_type: TransitionType::Fade,
+            duration: 0.5,
+        });
+        let mixed = [fade, None];
+        assert_eq!(scene_gaps(&mixed, 3, 1.5), [0.0, 1.5, 0.0]);
+        assert_eq!(scene_start_times(&durations, &mixed, 1.5), [0.0, 4.5, 10.0]);
+    }
+
+    #[test]
+    fn test_interleave_scene_gaps() {
+        let files: Vec<PathBuf> = ["a.mp4", "b.mp4", "c.mp4"].map(PathBuf::from).to_vec();
+        let durations = [5.0, 4.0, 6.0];
+        let gap = SceneGap {
+            path: PathBuf::from("scene-gap.mp4"),
+            duration: 1.5,
+        };
+
+        let (seq, durs, joins) = interleave_scene_gaps(&files, &durations, &[None, None], &gap);
+        let names: Vec<&str> = seq.iter().map(|p| p.to_str().unwrap()).collect();
+        assert_eq!(
+            names,
+            ["a.mp4", "scene-gap.mp4", "b.mp4", "scene-gap.mp4", "c.mp4"]
+        );
+        assert_eq!(durs, [5.0, 1.5, 4.0, 1.5, 6.0]);
+        assert_eq!(durs.iter().sum::<f64>(), 15.0 + 1.5 * 2.0);
+        assert_eq!(joins.len(), seq.len() - 1);
+        assert!(joins.iter().all(|j| j.is_none()));
+
+        // The crossfaded boundary stays a direct join with its transition
+        let fade = Some(SceneTransition {
+            transition_type: TransitionType::Fade,
+            duration: 0.5,
+        });
+        let (seq, _, joins) = interleave_scene_gaps(&files, &durations, &[fade, None], &gap);
+        let names: Vec<&str> = seq.iter().map(|p| p.to_str().unwrap()).collect();
+        assert_eq!(names, ["a.mp4", "b.mp4", "scene-gap.mp4", "c.mp4"]);
+        assert_eq!(joins.len(), 3);
+        assert!(joins[0].is_some() && joins[1].is_none() && joins[2].is_none());
+    }
+
+    #[test]
+    fn test_gap_clip_args() {
+        let quality = crate::config::QualityPreset::from_name("standard");
+        let platform = crate::config::resolve_encoding(&quality, None);
+        let out = Path::new("/tmp/scene-gap.mp4");
+        let args = gap_clip_args(1.5, 1920, 1080, 30, Some(48000), &platform, out);
+        assert!(has_pair(&args, "-i", "color=c=black:s=1920x1080:r=30"));
+        assert!(has_pair(&args, "-i", "anullsrc=r=48000:cl=stereo"));
+        assert!(has_pair(&args, "-t", "1.500"));
+        assert!(has_pair(&args, "-c:a", "aac"));
+        assert_eq!(args.last().unwrap(), "/tmp/scene-gap.mp4");
+
+        let silent = gap_clip_args(1.5, 1920, 1080, 30, None, &platform, out);
+        assert!(!silent
+            .iter()
+            .any(|a| a.starts_with("anullsrc") || a == "-c:a"));
+    }
+
+    #[test]
+    fn test_project_music_filter_mutes_scenes_with_own_music() {
+        let filter = project_music_filter(10.0, 0.0, 0.0, &[(2.0, 4.5), (8.0, 10.0)], false);
+        assert_eq!(
+            filter,
+            "[1:a]atrim=duration=10.000,volume=1.0000,volume=0:enable='between(t,2.000,4.500)+between(t,8.000,10.000)'[aout]"
+        );
+    }
+
+    #[test]
+    fn test_loudnorm_filter_uses_target() {
+        assert_eq!(
+            loudnorm_filter(-16.0, None),
+            "loudnorm=I=-16:TP=-1.5:LRA=11"
+        );
+        let stats = LoudnessStats {
+            input_i: -27.2,
+            input_tp: -4.5,
+            input_lra: 6.1,
+            input_thresh: -37.6,
+            target_offset: 0.3,
+        };
+        let second = loudnorm_filter(-14.0, Some(&stats));
+        assert!(second.starts_with("loudnorm=I=-14:TP=-1.5:LRA=11:"));
+        assert!(second.contains(
+            "measured_I=-27.2:measured_TP=-4.5:measured_LRA=6.1:measured_thresh=-37.6:offset=0.3"
+        ));
+        assert!(second.ends_with(":linear=true"));
+    }
+
+    #[test]
+    fn test_parse_loudnorm_stats() {
+        let stderr = "size=N/A time=00:00:05.00\n[Parsed_loudnorm_0 @ 0x1]\n{\n\t\"input_i\" : \"-27.20\",\n\t\"input_tp\" : \"-4.50\",\n\t\"input_lra\" : \"6.10\",\n\t\"input_thresh\" : \"-37.60\",\n\t\"output_i\" : \"-16.02\",\n\t\"normalization_type\" : \"dynamic\",\n\t\"target_offset\" : \"0.30\"\n}\n";
+        let stats = parse_loudnorm_stats(stderr).unwrap();
+        assert_eq!(stats.input_i, -27.2);
+        assert_eq!(stats.target_offset, 0.3);
+
+        // Silent input reports -inf, which can't drive the second pass
+        assert!(parse_loudnorm_stats(&stderr.replace("-27.20", "-inf")).is_none());
+        assert!(parse_loudnorm_stats("no json here").is_none());
+        assert!(parse_loudnorm_stats("} {").is_none());
+    }
+
+    #[test]
+    fn test_resolve_intro_transition() {
+        let config = VideoConfig::default();
+        let first = make_scene("---\ntemplate: title-card\ntransition_in: fade\n---\nA");
+        let intro = resolve_intro_transition(&first, 5.0, &config).unwrap();
+        assert_eq!(intro.transition_type, TransitionType::Fade);
+        assert_eq!(intro.duration, config.default_transition_duration);
+
+        let quality = crate::config::QualityPreset::from_name("standard");
+        let platform = crate::config::resolve_encoding(&quality, None);
+        let args = fade_from_black_args(Path::new("in.mp4"), intro.duration, &platform, Path::new("out.mp4"));
+        assert!(has_pair(&args, "-vf", "fade=t=in:st=0:d=0.300"));
+        assert!(has_pair(&args, "-c:a", "copy"));
+
+        // Any type fades from black; the duration is capped at the scene length
+        let wipe = make_scene("---\ntemplate: title-card\ntransition_in: wipe\ntransition_duration: 3\n---\nA");
+        let intro = resolve_intro_transition(&wipe, 2.0, &config).unwrap();
+        assert_eq!(intro.transition_type, TransitionType::Fade);
+        assert_eq!(intro.duration, 2.0);
+
+        // No transition_in (or "none") means a hard start, even with a config default
+        let plain = make_scene("---\ntemplate: title-card\n---\nA");
+        let with_default = VideoConfig {
+            default_transition: Some("fade".into()),
+            ..VideoConfig::default()
+        };
+        assert!(resolve_intro_transition(&plain, 5.0, &with_default).is_none());
+        let none = make_scene("---\ntemplate: title-card\ntransition_in: none\n---\nA");
+        assert!(resolve_intro_transition(&none, 5.0, &config).is_none());
+    }
+
     #[test]
     fn test_resolve_transition_scene_out_priority() {
         let scene_out = make_scene("---\ntemplate: title-card\ntransition_out: slide-left\n---\nA");
@@ -1146,4 +3072,620 @@ mod tests {
         let result = resolve_transition(&scene_out, &scene_in, &config).unwrap();
         assert!((result.duration - 1.5).abs() < f64::EPSILON);
     }
+
+    #[test]
+    fn test_resolve_transition_template_default() {
+        let slideshow = make_scene("---\ntemplate: slideshow\nprops:\n  slides: []\n---\nA");
+        let plain = make_scene("---\ntemplate: title-card\n---\nB");
+        let config = VideoConfig::default();
+
+        // Nothing else set: the slideshow's default applies on both sides of it
+        let out = resolve_transition(&slideshow, &plain, &config).unwrap();
+        assert_eq!(out.transition_type, TransitionType::SlideLeft);
+        assert!((out.duration - config.default_transition_duration).abs() < f64::EPSILON);
+        let into = resolve_transition(&plain, &slideshow, &config).unwrap();
+        assert_eq!(into.transition_type, TransitionType::SlideLeft);
+        assert_eq!(
+            transition_setting(&plain, &slideshow, &config),
+            Some(("slide-left", TransitionSource::TemplateDefault))
+        );
+        assert!(resolve_transition(&plain, &plain, &config).is_none());
+
+        // Config and scene settings still win over the template default
+        let with_default = VideoConfig {
+            default_transition: Some("wipe".into()),
+            ..Default::default()
+        };
+        let t = resolve_transition(&slideshow, &plain, &with_default).unwrap();
+        assert_eq!(t.transition_type, TransitionType::Wipe);
+        let cut = make_scene("---\ntemplate: title-card\ntransition_in: none\n---\nC");
+        assert!(resolve_transition(&slideshow, &cut, &config).is_none());
+    }
+
+    fn intro_boost(secs: f64, gain: f64) -> IntroBoostConfig {
+        IntroBoostConfig { secs, gain }
+    }
+
+    #[test]
+    fn test_music_ducking_for_scene() {
+        let boost = intro_boost(3.0, 6.0);
+
+        let first = MusicDucking::for_scene(&boost, 0.0);
+        assert!((first.boost_secs - 3.0).abs() < f64::EPSILON);
+        assert!((first.boost_gain - 1.995).abs() < 0.001);
+
+        // Second scene starts 2s in: only the remaining second is boosted
+        let second = MusicDucking::for_scene(&boost, 2.0);
+        assert!((second.boost_secs - 1.0).abs() < f64::EPSILON);
+
+        let later = MusicDucking::for_scene(&boost, 10.0);
+        assert_eq!(later.boost_secs, 0.0);
+    }
+
+    #[test]
+    fn test_voice_music_filter_boost_before_duck() {
+        let ducking = MusicDucking::for_scene(&intro_boost(2.0, 6.0), 0.0);
+        let filter = voice_music_filter(500, 0.25, &MusicFade::default(), Some(&ducking));
+
+        let boost = filter.find("volume=1.995:enable='lt(t,2.000)'").unwrap();
+        let duck = filter.find("sidechaincompress").unwrap();
+        assert!(boost < duck, "boost must precede ducking: {filter}");
+        assert!(filter.contains("[voicemix][ducked]amix=inputs=2"));
+        assert!(filter.starts_with("[1:a]adelay=500|500"));
+    }
+
+    #[test]
+    fn test_voice_music_filter_without_ducking() {
+        let filter = voice_music_filter(0, 0.25, &MusicFade::default(), None);
+        assert!(!filter.contains("sidechaincompress"));
+        assert!(!filter.contains("enable="));
+        assert!(filter.contains("[2:a]volume=0.25[music];[voice][music]amix"));
+    }
+
+    #[test]
+    fn test_music_chain_skips_boost_after_intro() {
+        let ducking = MusicDucking::for_scene(&intro_boost(2.0, 6.0), 5.0);
+        assert_eq!(
+            music_chain("[2:a]", 0.5, &MusicFade::default(), Some(&ducking), "[music]"),
+            "[2:a]volume=0.50[music]"
+        );
+    }
+
+    #[test]
+    fn test_music_chain_fades() {
+        let content = "---\ntemplate: title-card\naudio:\n  music: song.mp3\n  music_fade_in: 1.5\n  music_fade_out: 2\n---\n";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let fade = MusicFade::for_scene(&scene, 8.0);
+        assert_eq!(
+            music_chain("[1:a]", 0.3, &fade, None, "[aout]"),
+            "[1:a]volume=0.30,afade=t=in:st=0:d=1.500,afade=t=out:st=6.000:d=2.000[aout]"
+        );
+
+        // Fades come after the base volume and before the intro boost and ducking
+        let ducking = MusicDucking::for_scene(&intro_boost(2.0, 6.0), 0.0);
+        let filter = voice_music_filter(0, 0.25, &fade, Some(&ducking));
+        let fade_in = filter.find("afade=t=in").unwrap();
+        assert!(filter.find("volume=0.25").unwrap() < fade_in);
+        assert!(fade_in < filter.find("enable=").unwrap());
+
+        // Unset fades leave the chain untouched
+        let plain = parse_scene("---\ntemplate: title-card\n---\n", Path::new("test.md")).unwrap();
+        assert!(!music_chain("[1:a]", 0.3, &MusicFade::for_scene(&plain, 8.0), None, "[aout]").contains("afade"));
+    }
+
+    #[test]
+    fn test_mixed_audio_presence_uses_filter_concat() {
+        assert!(use_filter_concat("auto", &[true, false, true]));
+        assert!(!use_filter_concat("auto", &[true, true, true]));
+        assert!(!use_filter_concat("auto", &[false, false]));
+    }
+
+    #[test]
+    fn test_explicit_concat_method() {
+        assert!(use_filter_concat("filter", &[true, true]));
+        assert!(!use_filter_concat("demuxer", &[true, false]));
+    }
+
+    #[test]
+    fn test_concat_filter_graph_fills_silence() {
+        let graph = concat_filter_graph(&[true, false], &[3.0, 2.5], 30);
+        assert!(graph.contains("[0:a]aformat=sample_rates=44100:channel_layouts=stereo"));
+        assert!(graph.contains("anullsrc=cl=stereo:r=44100,atrim=0:2.500,asetpts=PTS-STARTPTS[a1]"));
+        assert!(graph.ends_with("[v0][a0][v1][a1]concat=n=2:v=1:a=1[vout][aout]"));
+    }
+
+    #[test]
+    fn test_concat_filter_graph_video_only() {
+        let graph = concat_filter_graph(&[false, false, false], &[1.0, 1.0, 1.0], 30);
+        assert!(!graph.contains("anullsrc"));
+        assert!(graph.ends_with("[v0][v1][v2]concat=n=3:v=1:a=0[vout]"));
+    }
+
+    #[test]
+    fn test_concat_filter_graph_normalizes_fps() {
+        // A 60fps scene between 30fps scenes is resampled to the project rate
+        let graph = concat_filter_graph(&[true, true, true], &[2.0, 2.0, 2.0], 30);
+        for i in 0..3 {
+            assert!(graph.contains(&format!("[{i}:v]fps=30,format=yuv420p")));
+        }
+    }
+
+    /// Stand-in for FFmpeg: "burns" by copying the input and appending a marker.
+    fn fake_burn(input: &Path, _srt: &Path, output: &Path) -> VidgenResult<()> {
+        let mut data = std::fs::read(input)?;
+        data.extend_from_slice(b"+subs");
+        std::fs::write(output, data)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_container_codec_args() {
+        let platform = PlatformPreset {
+            crf: 23,
+            preset: "medium",
+            audio_bitrate: "192k",
+            audio_samplerate: 48000,
+            hw_encoder: None,
+        };
+        let args = |c| container_codec_args(c, &platform).join(" ");
+
+        let mp4 = args(OutputContainer::Mp4);
+        assert!(mp4.starts_with("-map 0:v:0 -map 0:a? -c:v libx264"), "{mp4}");
+        assert!(mp4.contains("-crf 23") && mp4.contains("-c:a aac -b:a 192k"));
+
+        let mov = args(OutputContainer::Mov);
+        assert!(mov.contains("-c:v prores_ks") && mov.contains("-c:a pcm_s16le"), "{mov}");
+
+        let webm = args(OutputContainer::Webm);
+        assert!(webm.contains("-c:v libvpx-vp9") && webm.contains("-crf 23 -b:v 0"), "{webm}");
+        assert!(webm.contains("-c:a libopus"));
+
+        let gif = args(OutputContainer::Gif);
+        assert!(gif.contains("palettegen") && gif.contains("paletteuse"), "{gif}");
+        assert!(gif.contains("-an") && !gif.contains("-map"));
+    }
+
+    #[test]
+    fn test_metadata_args_from_config() {
+        let config: crate::config::ProjectConfig = toml::from_str(
+            "[project]\nname = \"Demo\"\n[output.metadata]\nauthor = \"Ada\"\ncomment = \"v2 cut\"\n",
+        )
+        .unwrap();
+        let tags = config.output.metadata.tags(&config.project.name);
+        let args = metadata_args(Path::new("/out/v.tmp.mp4"), &tags, Path::new("/out/v.mp4"));
+        assert!(has_pair(&args, "-metadata", "title=Demo"));
+        assert!(has_pair(&args, "-metadata", "artist=Ada"));
+        assert!(has_pair(&args, "-metadata", "comment=v2 cut"));
+        assert!(has_pair(&args, "-c", "copy"));
+        assert_eq!(args.iter().filter(|a| *a == "-metadata").count(), 3);
+        assert_eq!(args.last().unwrap(), "/out/v.mp4");
+    }
+
+    #[test]
+    fn test_poster_frame_args_select_index() {
+        let args = poster_frame_args(Path::new("/out/v.mp4"), 45, Path::new("/out/v.poster.jpg"));
+        let vf = args.iter().position(|a| a == "-vf").unwrap();
+        assert_eq!(args[vf + 1], "select=eq(n\\,45)");
+        assert!(args.windows(2).any(|w| w == ["-frames:v", "1"]));
+        assert_eq!(args.last().unwrap(), "/out/v.poster.jpg");
+    }
+
+    #[test]
+    fn test_attach_poster_args_maps_cover_stream() {
+        let args = attach_poster_args(
+            Path::new("/out/v.mp4"),
+            Path::new("/out/v.poster.jpg"),
+            Path::new("/out/v.poster-tmp.mp4"),
+        );
+        let joined = args.join(" ");
+        assert!(joined.contains("-i /out/v.mp4 -i /out/v.poster.jpg -map 0 -map 1 -c copy"), "{joined}");
+        assert!(args.windows(2).any(|w| w == ["-disposition:v:1", "attached_pic"]));
+        assert_eq!(args.last().unwrap(), "/out/v.poster-tmp.mp4");
+    }
+
+    #[test]
+    fn test_captioned_path_keeps_extension() {
+        assert_eq!(
+            captioned_path(Path::new("/out/v.webm")),
+            Path::new("/out/v-captioned.webm")
+        );
+    }
+
+    #[test]
+    fn test_burn_in_keep_clean_writes_both_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let video = dir.path().join("my-video.mp4");
+        let srt = dir.path().join("my-video.srt");
+        std::fs::write(&video, b"clean").unwrap();
+
+        let captioned = burn_in_subtitles_with(&video, &srt, true, fake_burn).unwrap();
+        assert_eq!(captioned, dir.path().join("my-video-captioned.mp4"));
+        assert_eq!(std::fs::read(&video).unwrap(), b"clean");
+        assert_eq!(std::fs::read(&captioned).unwrap(), b"clean+subs");
+    }
+
+    #[test]
+    fn test_burn_in_without_keep_clean_replaces_video() {
+        let dir = tempfile::tempdir().unwrap();
+        let video = dir.path().join("my-video.mp4");
+        let srt = dir.path().join("my-video.srt");
+        std::fs::write(&video, b"clean").unwrap();
+
+        let burned = burn_in_subtitles_with(&video, &srt, false, fake_burn).unwrap();
+        assert_eq!(burned, video);
+        assert_eq!(std::fs::read(&video).unwrap(), b"clean+subs");
+        assert!(!dir.path().join("my-video-captioned.mp4").exists());
+        assert!(!video.with_extension("tmp.mp4").exists());
+    }
+
+    fn tracks() -> Vec<AudioTrack> {
+        vec![
+            AudioTrack {
+                path: PathBuf::from("/p/de.wav"),
+                language: "deu".into(),
+                title: Some("Deutsch".into()),
+            },
+            AudioTrack {
+                path: PathBuf::from("/p/fr.wav"),
+                language: "fra".into(),
+                title: None,
+            },
+        ]
+    }
+
+    /// Find `flag value` pairs in an argument list.
+    fn has_pair(args: &[String], flag: &str, value: &str) -> bool {
+        args.windows(2).any(|w| w[0] == flag && w[1] == value)
+    }
+
+    #[test]
+    fn test_multi_audio_mapping() {
+        let args = multi_audio_args(
+            Path::new("/out/v.tmp.mp4"),
+            true,
+            Some("eng"),
+            &tracks(),
+            Path::new("/out/v.mp4"),
+        );
+        let inputs: Vec<&String> = args.windows(2).filter(|w| w[0] == "-i").map(|w| &w[1]).collect();
+        assert_eq!(inputs, ["/out/v.tmp.mp4", "/p/de.wav", "/p/fr.wav"]);
+        let maps: Vec<&String> = args.windows(2).filter(|w| w[0] == "-map").map(|w| &w[1]).collect();
+        assert_eq!(maps, ["0:v", "0:a", "1:a", "2:a"]);
+        assert!(has_pair(&args, "-c:v", "copy"));
+        assert!(has_pair(&args, "-c:a:0", "copy"));
+        assert!(has_pair(&args, "-c:a:1", "aac"));
+        assert!(has_pair(&args, "-disposition:a:0", "default"));
+        assert!(has_pair(&args, "-disposition:a:2", "0"));
+        assert_eq!(args.last().unwrap(), "/out/v.mp4");
+    }
+
+    #[test]
+    fn test_embed_subtitle_args_map_mov_text_with_language() {
+        let args = embed_subtitle_args(
+            Path::new("/out/v.mp4"),
+            Path::new("/out/v.srt"),
+            "deu",
+            Path::new("/out/v.subs-tmp.mp4"),
+        );
+        let inputs: Vec<&String> = args
+            .windows(2)
+            .filter(|w| w[0] == "-i")
+            .map(|w| &w[1])
+            .collect();
+        assert_eq!(inputs, ["/out/v.mp4", "/out/v.srt"]);
+        let maps: Vec<&String> = args
+            .windows(2)
+            .filter(|w| w[0] == "-map")
+            .map(|w| &w[1])
+            .collect();
+        assert_eq!(maps, ["0", "1:s"]);
+        assert!(has_pair(&args, "-c", "copy"));
+        assert!(has_pair(&args, "-c:s", "mov_text"));
+        assert!(has_pair(&args, "-metadata:s:s:0", "language=deu"));
+        assert_eq!(args.last().unwrap(), "/out/v.subs-tmp.mp4");
+    }
+
+    #[test]
+    fn test_multi_audio_language_metadata() {
+        let args = multi_audio_args(Path::new("v.mp4"), true, Some("eng"), &tracks(), Path::new("o.mp4"));
+        assert!(has_pair(&args, "-metadata:s:a:0", "language=eng"));
+        assert!(has_pair(&args, "-metadata:s:a:1", "language=deu"));
+        assert!(has_pair(&args, "-metadata:s:a:1", "title=Deutsch"));
+        assert!(has_pair(&args, "-metadata:s:a:2", "language=fra"));
+
+        // Silent video: the tracks become streams 0 and 1
+        let args = multi_audio_args(Path::new("v.mp4"), false, None, &tracks(), Path::new("o.mp4"));
+        assert!(!args.iter().any(|a| a == "0:a"));
+        assert!(has_pair(&args, "-metadata:s:a:0", "language=deu"));
+        assert!(has_pair(&args, "-metadata:s:a:1", "language=fra"));
+        assert!(has_pair(&args, "-disposition:a:0", "default"));
+    }
+
+    #[test]
+    fn test_scene_encoder_motion_blur_filter() {
+        let quality = crate::config::QualityPreset::from_name("standard");
+        let platform = crate::config::resolve_encoding(&quality, None);
+        let args = |motion_blur: bool| -> Vec<String> {
+            SceneEncoder::command(
+                Path::new("scene.mp4"),
+                30,
+                30,
+                1920,
+                1080,
+                &platform,
+                None,
+                None,
+                0.0,
+                &MusicFade::default(),
+                0.0,
+                Some(3.0),
+                None,
+                motion_blur,
+            )
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+        };
+        assert!(has_pair(&args(true), "-vf", MOTION_BLUR_FILTER));
+        assert!(!args(false).iter().any(|a| a == "-vf"));
+    }
+
+    #[test]
+    fn test_video_codec_args_per_hwaccel() {
+        let quality = crate::config::QualityPreset::from_name("standard");
+        let args = |hw_encoder: Option<HwAccel>| {
+            let platform = PlatformPreset {
+                hw_encoder,
+                ..crate::config::resolve_encoding(&quality, None)
+            };
+            video_codec_args(&platform)
+        };
+
+        let x264 = args(None);
+        assert!(has_pair(&x264, "-c:v", "libx264"));
+        assert!(has_pair(&x264, "-crf", "23"));
+
+        let nvenc = args(Some(HwAccel::Nvenc));
+        assert!(has_pair(&nvenc, "-c:v", "h264_nvenc"));
+        assert!(has_pair(&nvenc, "-cq", "23"));
+        assert!(!nvenc.iter().any(|a| a == "-crf" || a == "-preset"));
+
+        let vaapi = args(Some(HwAccel::Vaapi));
+        assert!(has_pair(&vaapi, "-c:v", "h264_vaapi"));
+        assert!(has_pair(&vaapi, "-qp", "23"));
+        assert!(!vaapi.iter().any(|a| a == "-crf" || a == "-pix_fmt"));
+
+        let videotoolbox = args(Some(HwAccel::Videotoolbox));
+        assert!(has_pair(&videotoolbox, "-c:v", "h264_videotoolbox"));
+        assert!(has_pair(&videotoolbox, "-q:v", "55"));
+        assert!(!videotoolbox.iter().any(|a| a == "-crf"));
+    }
+
+    #[test]
+    fn test_scene_encoder_vaapi_uploads_frames() {
+        let quality = crate::config::QualityPreset::from_name("standard");
+        let platform = PlatformPreset {
+            hw_encoder: Some(HwAccel::Vaapi),
+            ..crate::config::resolve_encoding(&quality, None)
+        };
+        let args: Vec<String> = SceneEncoder::command(
+            Path::new("scene.mp4"),
+            30,
+            30,
+            1920,
+            1080,
+            &platform,
+            None,
+            None,
+            0.0,
+            &MusicFade::default(),
+            0.0,
+            Some(2.0),
+            None,
+            false,
+        )
+        .get_args()
+        .map(|a| a.to_string_lossy().into_owned())
+        .collect();
+        assert_eq!(args[..2], ["-vaapi_device", VAAPI_DEVICE]);
+        assert!(has_pair(&args, "-vf", "format=nv12,hwupload"));
+        assert!(has_pair(&args, "-c:v", "h264_vaapi"));
+    }
+
+    #[test]
+    fn test_select_hw_encoder() {
+        let encoders = " V....D h264_nvenc           NVIDIA NVENC H.264 encoder\n \
+                        V....D h264_vaapi           H.264/AVC (VAAPI)\n";
+        let select = |requested, use_gpu| select_hw_encoder(requested, use_gpu, encoders);
+
+        assert_eq!(select(Some(HwAccel::Vaapi), false), Some(HwAccel::Vaapi));
+        // Not in FFmpeg: fall back to libx264
+        assert_eq!(select(Some(HwAccel::Videotoolbox), true), None);
+        // --gpu picks the first available in preference order
+        assert_eq!(select(None, true), Some(HwAccel::Nvenc));
+        assert_eq!(select(None, false), None);
+        assert_eq!(select_hw_encoder(None, true, ""), None);
+    }
+
+    #[test]
+    fn test_scene_encoder_output_fps() {
+        let quality = crate::config::QualityPreset::from_name("standard");
+        let platform = crate::config::resolve_encoding(&quality, None);
+        let args = |output_fps: u32, motion_blur: bool| -> Vec<String> {
+            SceneEncoder::command(
+                Path::new("scene.mp4"),
+                30,
+                output_fps,
+                1920,
+                1080,
+                &platform,
+                None,
+                None,
+                0.0,
+                &MusicFade::default(),
+                0.0,
+                Some(2.0),
+                None,
+                motion_blur,
+            )
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+        };
+
+        // Frames are captured (and piped in) at 30fps either way
+        assert_eq!(crate::scene::Scene::total_frames_for_duration(2.0, 30), 60);
+        let up = args(60, false);
+        assert!(has_pair(&up, "-framerate", "30"));
+        assert!(has_pair(&up, "-vf", "minterpolate=fps=60:mi_mode=mci"));
+        assert!(has_pair(&up, "-r", "60"));
+
+        let down = args(24, true);
+        assert!(has_pair(
+            &down,
+            "-vf",
+            &format!("{MOTION_BLUR_FILTER},fps=24")
+        ));
+        assert!(has_pair(&down, "-r", "24"));
+
+        let same = args(30, false);
+        assert!(!same.iter().any(|a| a == "-vf" || a == "-r"));
+    }
+
+    #[test]
+    fn test_frame_rate_filter() {
+        assert_eq!(frame_rate_filter(30, 30), None);
+        assert_eq!(frame_rate_filter(30, 24).as_deref(), Some("fps=24"));
+        assert_eq!(
+            frame_rate_filter(30, 60).as_deref(),
+            Some("minterpolate=fps=60:mi_mode=mci")
+        );
+    }
+
+    fn stream_params() -> StreamParams {
+        StreamParams {
+            width: 1920,
+            height: 1080,
+            frame_rate: "30/1".into(),
+            video_codec: "h264".into(),
+            pix_fmt: "yuv420p".into(),
+            audio: Some(("aac".into(), 44100, 2)),
+        }
+    }
+
+    #[test]
+    fn test_append_incompatibility() {
+        assert_eq!(
+            append_incompatibility(&stream_params(), &stream_params()),
+            None
+        );
+
+        let portrait = StreamParams {
+            width: 1080,
+            height: 1920,
+            frame_rate: "60/1".into(),
+            ..stream_params()
+        };
+        let reason = append_incompatibility(&stream_params(), &portrait).unwrap();
+        assert_eq!(
+            reason,
+            "resolution 1920x1080 vs 1080x1920, frame rate 30/1 vs 60/1"
+        );
+
+        let silent = StreamParams {
+            audio: None,
+            ..stream_params()
+        };
+        let reason = append_incompatibility(&stream_params(), &silent).unwrap();
+        assert_eq!(reason, "audio aac 44100Hz 2ch vs no audio");
+    }
+
+    #[test]
+    fn test_stream_params_from_ffprobe_json() {
+        let data = serde_json::json!({
+            "streams": [
+                {"codec_type": "video", "codec_name": "h264", "width": 1920, "height": 1080,
+                 "r_frame_rate": "30/1", "pix_fmt": "yuv420p"},
+                {"codec_type": "audio", "codec_name": "aac", "sample_rate": "44100", "channels": 2}
+            ]
+        });
+        assert_eq!(
+            StreamParams::from_ffprobe_json(&data),
+            Some(stream_params())
+        );
+        let empty = serde_json::json!({"streams": []});
+        assert_eq!(StreamParams::from_ffprobe_json(&empty), None);
+    }
+
+    #[test]
+    fn test_append_concat_args_stream_copy() {
+        let args = append_concat_args(Path::new("/tmp/list.txt"), Path::new("/out/v.mp4"));
+        assert!(has_pair(&args, "-f", "concat"));
+        assert!(has_pair(&args, "-i", "/tmp/list.txt"));
+        assert!(has_pair(&args, "-c", "copy"));
+        assert!(!args.iter().any(|a| a == "libx264"));
+        assert_eq!(args.last().unwrap(), "/out/v.mp4");
+    }
+
+    #[test]
+    fn test_audio_trim_args_seek_input() {
+        let args = audio_trim_args(
+            Path::new("/a/voice.wav"),
+            Path::new("/tmp/out.wav"),
+            Some(2.0),
+            Some(10.0),
+        );
+        assert!(has_pair(&args, "-ss", "2.000"));
+        assert!(has_pair(&args, "-to", "10.000"));
+        // Both must precede -i to act on the input
+        let input = args.iter().position(|a| a == "-i").unwrap();
+        assert!(args.iter().position(|a| a == "-ss").unwrap() < input);
+        assert!(args.iter().position(|a| a == "-to").unwrap() < input);
+        assert_eq!(args[input + 1], "/a/voice.wav");
+        assert_eq!(args.last().unwrap(), "/tmp/out.wav");
+
+        let tail_only = audio_trim_args(Path::new("a.wav"), Path::new("b.wav"), Some(1.5), None);
+        assert!(has_pair(&tail_only, "-ss", "1.500"));
+        assert!(!tail_only.iter().any(|a| a == "-to"));
+    }
+
+    #[test]
+    fn test_fade_black_transition_name() {
+        for name in ["fade-black", "fadeblack", "Fade_Black"] {
+            assert_eq!(TransitionType::from_str(name), TransitionType::FadeBlack);
+        }
+        assert_eq!(TransitionType::FadeBlack.ffmpeg_name(), "fadeblack");
+    }
+
+    #[test]
+    fn test_transition_graph_mixes_fade_black_and_crossfade() {
+        let transition = |transition_type, duration| {
+            Some(SceneTransition {
+                transition_type,
+                duration,
+            })
+        };
+        let transitions = [
+            transition(TransitionType::FadeBlack, 1.0),
+            transition(TransitionType::Fade, 0.5),
+        ];
+        let graph = transition_filter_graph(
+            &[4.0, 3.0, 5.0],
+            &transitions,
+            &[true, false, true],
+            30,
+            44100,
+        );
+        let parts: Vec<&str> = graph.split(';').collect();
+
+        // Boundary 1 dips to black, boundary 2 crossfades onto the first result
+        let dip = "[vin0][vin1]xfade=transition=fadeblack:duration=1.000:offset=3.000[xv1]";
+        let crossfade = "[xv1][vin2]xfade=transition=fade:duration=0.500:offset=5.500[vout]";
+        assert!(parts.contains(&dip));
+        assert!(parts.contains(&crossfade));
+        assert!(parts.contains(&"[sa0][sa1]acrossfade=d=1.000:c1=tri:c2=tri[a1]"));
+        assert!(parts.contains(&"[a1][sa2]acrossfade=d=0.500:c1=tri:c2=tri[aout]"));
+        // The silent middle scene gets a generated track
+        assert!(graph.contains("anullsrc=cl=stereo:r=44100[silence1]"));
+    }
 }
@@ -0,0 +1,454 @@
+//! Machine-readable account of how scene durations, voices and transitions are
+//! decided (`vidgen render --explain`). Duration resolution here is the same code
+//! the render pipeline runs.
+
+use crate::config::ProjectConfig;
+use crate::error::VidgenResult;
+use crate::render::encoder::{self, TransitionSource};
+use crate::scene::{self, Scene};
+use serde::Serialize;
+use std::path::Path;
+
+/// What a scene's effective duration came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DurationSource {
+    /// `duration: <secs>` in frontmatter
+    Fixed,
+    /// `duration: auto`: narration length + `padding_before` + `padding_after`
+    TtsPadding,
+    /// `duration: auto` with no narration: `voice.auto_fallback_duration`
+    Fallback,
+    /// `duration: auto` on a video clip: the probed clip length
+    VideoProbe,
+    /// `duration: match-music`: the probed music length
+    MusicProbe,
+    /// Sequence scene: sum of the resolved sub-scene durations
+    SubScenes,
+    /// `duration: auto` raised to the scene's `min_duration`
+    MinDuration,
+    /// `duration: "N%"`: that share of `output.target_duration`
+    Percent,
+}
+
+impl DurationSource {
+    /// Short label for log lines.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Fixed => "fixed",
+            Self::TtsPadding => "TTS + padding",
+            Self::Fallback => "fallback",
+            Self::VideoProbe => "video probe",
+            Self::MusicProbe => "music probe",
+            Self::SubScenes => "sub-scenes",
+            Self::MinDuration => "min_duration",
+            Self::Percent => "percent of target_duration",
+        }
+    }
+}
+
+/// Music length for a `duration: match-music` scene, or `None` for other scenes
+/// and when the music can't be probed (the scene then behaves like `auto`).
+pub fn scene_music_duration(
+    scene: &Scene,
+    index: usize,
+    config: &ProjectConfig,
+    project_path: &Path,
+    probe: impl Fn(&Path) -> VidgenResult<f64>,
+) -> Option<f64> {
+    if !scene.frontmatter.duration.is_match_music() {
+        return None;
+    }
+    let music = super::scene_music_path(scene, config, project_path)?;
+    match probe(&music) {
+        Ok(dur) => Some(dur),
+        Err(e) => {
+            eprintln!(
+                "  Scene {}: could not probe music duration ({}), using auto duration",
+                index + 1,
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Effective duration of a scene and what decided it.
+pub fn resolve_scene_duration(
+    scene: &Scene,
+    index: usize,
+    tts_duration: Option<f64>,
+    music_duration: Option<f64>,
+    config: &ProjectConfig,
+    project_path: &Path,
+    probe: impl Fn(&Path) -> VidgenResult<f64>,
+) -> (f64, DurationSource) {
+    let voice = &config.voice;
+    // For sequence scenes, sum resolved sub-scene durations
+    if scene.is_sequence() {
+        let sub_scenes = scene.frontmatter.sub_scenes.as_ref().unwrap();
+        match scene::resolve_sub_scene_durations(
+            sub_scenes,
+            tts_duration,
+            voice.padding_before,
+            voice.padding_after,
+            voice.auto_fallback_duration,
+        ) {
+            Ok(durs) => return (durs.iter().sum(), DurationSource::SubScenes),
+            Err(e) => {
+                eprintln!(
+                    "  Scene {}: sub-scene duration error ({}), using fallback",
+                    index + 1,
+                    e
+                );
+            }
+        }
+    }
+
+    let duration = &scene.frontmatter.duration;
+    if let Some(secs) = config
+        .output
+        .target_duration
+        .and_then(|target| duration.percent_of(target))
+    {
+        return (secs, DurationSource::Percent);
+    }
+
+    // `match-music` scenes without a usable music file behave exactly like `auto`
+    let auto_timed = duration.is_auto() || (duration.is_match_music() && music_duration.is_none());
+
+    // For video-clip scenes with auto duration, probe the source video
+    if scene.is_video_clip() && auto_timed {
+        if let Some(ref src) = scene.frontmatter.video_source {
+            let resolved = scene::resolve_asset_path(src, project_path);
+            match probe(&resolved) {
+                Ok(dur) => return (dur, DurationSource::VideoProbe),
+                Err(e) => {
+                    eprintln!(
+                        "  Scene {}: could not probe video duration ({}), using fallback",
+                        index + 1,
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    let secs = duration.resolve_with_music(
+        tts_duration,
+        music_duration,
+        voice.padding_before,
+        voice.padding_after,
+        voice.auto_fallback_duration,
+        scene.frontmatter.min_duration,
+    );
+    let raised = scene
+        .frontmatter
+        .min_duration
+        .is_some_and(|min| min >= secs);
+    let source = if music_duration.is_some() && duration.is_match_music() {
+        DurationSource::MusicProbe
+    } else if !auto_timed {
+        DurationSource::Fixed
+    } else if raised {
+        DurationSource::MinDuration
+    } else if tts_duration.is_some() {
+        DurationSource::TtsPadding
+    } else {
+        DurationSource::Fallback
+    };
+    (secs, source)
+}
+
+/// Narration chosen for a scene.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VoiceChoice {
+    pub engine: String,
+    /// Voice name; the engine's default voice when none is configured
+    pub voice: Option<String>,
+    pub speed: f32,
+    /// Recorded voiceover (`audio.voice`) used instead of TTS
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recorded: Option<String>,
+}
+
+/// Per-scene explanation.
+#[derive(Debug, Clone, Serialize)]
+pub struct SceneExplanation {
+    pub index: usize,
+    pub file: String,
+    pub duration_secs: f64,
+    pub duration_source: DurationSource,
+    /// Narration length that fed `tts_padding`, before padding
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub narration_secs: Option<f64>,
+    /// `None` when the scene has no script and no recording
+    pub voice: Option<VoiceChoice>,
+}
+
+/// Explanation of one scene boundary (`from` → `from + 1`).
+#[derive(Debug, Clone, Serialize)]
+pub struct TransitionExplanation {
+    pub from: usize,
+    pub to: usize,
+    /// Transition name as written in the winning setting (e.g. "slide-left", "none")
+    pub setting: Option<String>,
+    /// Which setting won: scene_out, scene_in or config_default
+    pub source: Option<TransitionSource>,
+    /// FFmpeg xfade name actually used, or `None` for a hard cut
+    pub xfade: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_source: Option<TransitionSource>,
+}
+
+/// Full `--explain` output.
+#[derive(Debug, Clone, Serialize)]
+pub struct Explanation {
+    pub scenes: Vec<SceneExplanation>,
+    pub transitions: Vec<TransitionExplanation>,
+}
+
+/// Voice a scene narrates with: the per-scene override, else the project voice.
+pub fn voice_choice(scene: &Scene, config: &ProjectConfig) -> Option<VoiceChoice> {
+    let recorded = scene
+        .frontmatter
+        .audio
+        .as_ref()
+        .and_then(|a| a.voice.clone());
+    if recorded.is_none() && scene.script.trim().is_empty() {
+        return None;
+    }
+    let scene_voice = scene.frontmatter.voice.as_ref();
+    let engine = scene_voice
+        .and_then(|v| v.engine.clone())
+        .unwrap_or_else(|| config.voice.engine.clone());
+    Some(VoiceChoice {
+        voice: scene_voice
+            .and_then(|v| v.voice_name())
+            .or(config.voice.default_voice.as_deref())
+            .or(crate::tts::default_voice_for(&engine))
+            .map(str::to_string),
+        engine,
+        speed: scene_voice
+            .and_then(|v| v.speed)
+            .unwrap_or(config.voice.speed),
+        recorded,
+    })
+}
+
+/// Explain the boundary between `scenes[from]` and `scenes[from + 1]`.
+pub fn explain_transition(
+    scenes: &[Scene],
+    from: usize,
+    config: &ProjectConfig,
+) -> TransitionExplanation {
+    let (scene_out, scene_in) = (&scenes[from], &scenes[from + 1]);
+    let setting = encoder::transition_setting(scene_out, scene_in, &config.video);
+    let resolved = encoder::resolve_transition(scene_out, scene_in, &config.video);
+    let duration = resolved
+        .as_ref()
+        .map(|_| encoder::transition_duration_setting(scene_out, scene_in, &config.video));
+    TransitionExplanation {
+        from,
+        to: from + 1,
+        setting: setting.map(|(name, _)| name.to_string()),
+        source: setting.map(|(_, source)| source),
+        xfade: resolved.map(|t| t.transition_type.ffmpeg_name().to_string()),
+        duration_secs: duration.map(|(secs, _)| secs),
+        duration_source: duration.map(|(_, source)| source),
+    }
+}
+
+/// Explain every scene and boundary. `tts_durations` holds each scene's
+/// narration length (see [`crate::commands::info`]); `probe` reads media lengths.
+pub fn explain(
+    config: &ProjectConfig,
+    scenes: &[Scene],
+    tts_durations: &[Option<f64>],
+    project_path: &Path,
+    probe: impl Fn(&Path) -> VidgenResult<f64> + Copy,
+) -> Explanation {
+    let scenes_explained = scenes
+        .iter()
+        .enumerate()
+        .map(|(i, scene)| {
+            let music = scene_music_duration(scene, i, config, project_path, probe);
+            let (duration_secs, duration_source) = resolve_scene_duration(
+                scene,
+                i,
+                tts_durations[i],
+                music,
+                config,
+                project_path,
+                probe,
+            );
+            SceneExplanation {
+                index: i,
+                file: scene
+                    .source_path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                duration_secs,
+                duration_source,
+                narration_secs: (duration_source == DurationSource::TtsPadding)
+                    .then_some(tts_durations[i])
+                    .flatten(),
+                voice: voice_choice(scene, config),
+            }
+        })
+        .collect();
+    let transitions = (0..scenes.len().saturating_sub(1))
+        .map(|i| explain_transition(scenes, i, config))
+        .collect();
+    Explanation {
+        scenes: scenes_explained,
+        transitions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::VidgenError;
+
+    fn project_config(toml: &str) -> ProjectConfig {
+        toml::from_str(&format!("[project]\nname = \"Explain\"\n\n{toml}")).unwrap()
+    }
+
+    fn scenes(contents: &[&str]) -> Vec<Scene> {
+        contents
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let path = format!("scenes/{:02}-scene.md", i + 1);
+                scene::parse_scene(c, Path::new(&path)).unwrap()
+            })
+            .collect()
+    }
+
+    fn no_probe(_: &Path) -> VidgenResult<f64> {
+        Err(VidgenError::Other("no media".into()))
+    }
+
+    #[test]
+    fn test_explain_attributes_config_default_vs_scene_transition() {
+        let config = project_config(
+            "[video]\ndefault_transition = \"fade\"\ndefault_transition_duration = 0.5\n",
+        );
+        let scenes = scenes(&[
+            "---\ntemplate: title-card\n---\nA",
+            "---\ntemplate: title-card\ntransition_out: slide-left\ntransition_duration: 1.2\n---\nB",
+            "---\ntemplate: title-card\ntransition_in: none\n---\nC",
+            "---\ntemplate: title-card\n---\nD",
+        ]);
+
+        let explained = explain(&config, &scenes, &[None; 4], Path::new("."), no_probe);
+        let t = &explained.transitions;
+        assert_eq!(t.len(), 3);
+
+        // 0 → 1: nothing on either scene, so the project default applies
+        assert_eq!(t[0].setting.as_deref(), Some("fade"));
+        assert_eq!(t[0].source, Some(TransitionSource::ConfigDefault));
+        assert_eq!(t[0].xfade.as_deref(), Some("fade"));
+        // ...but B's transition_duration also covers its incoming boundary
+        assert_eq!(t[0].duration_secs, Some(1.2));
+        assert_eq!(t[0].duration_source, Some(TransitionSource::SceneIn));
+
+        // 1 → 2: scene_out's transition_out beats scene_in's "none"
+        assert_eq!(t[1].setting.as_deref(), Some("slide-left"));
+        assert_eq!(t[1].source, Some(TransitionSource::SceneOut));
+        assert_eq!(t[1].xfade.as_deref(), Some("slideleft"));
+        assert_eq!(t[1].duration_secs, Some(1.2));
+        assert_eq!(t[1].duration_source, Some(TransitionSource::SceneOut));
+
+        // 2 → 3: C's transition_in only touches 1 → 2, so the defaults apply again
+        assert_eq!(t[2].source, Some(TransitionSource::ConfigDefault));
+        assert_eq!(t[2].duration_secs, Some(0.5));
+        assert_eq!(t[2].duration_source, Some(TransitionSource::ConfigDefault));
+
+        // Without a project default, an unset boundary is a hard cut
+        let none = explain_transition(&scenes, 0, &project_config(""));
+        assert_eq!(none.setting, None);
+        assert_eq!(none.source, None);
+        assert_eq!(none.xfade, None);
+        assert_eq!(none.duration_secs, None);
+    }
+
+    #[test]
+    fn test_explain_scene_transition_in_hard_cut() {
+        let config = project_config("[video]\ndefault_transition = \"wipe\"\n");
+        let scenes = scenes(&[
+            "---\ntemplate: title-card\n---\nA",
+            "---\ntemplate: title-card\ntransition_in: none\n---\nB",
+        ]);
+        let t = explain_transition(&scenes, 0, &config);
+        assert_eq!(t.setting.as_deref(), Some("none"));
+        assert_eq!(t.source, Some(TransitionSource::SceneIn));
+        assert_eq!(t.xfade, None);
+        assert_eq!(t.duration_secs, None);
+    }
+
+    #[test]
+    fn test_voice_choice_falls_back_to_engine_default() {
+        let config = project_config("[voice]\nengine = \"edge\"\n");
+        let scenes = scenes(&[
+            "---\ntemplate: title-card\n---\nHello.",
+            "---\ntemplate: title-card\nvoice:\n  engine: piper\n---\nHello.",
+        ]);
+        let voice = |i: usize| voice_choice(&scenes[i], &config).unwrap().voice;
+        assert_eq!(voice(0).as_deref(), Some("en-US-AriaNeural"));
+        assert_eq!(voice(1).as_deref(), Some("en_US-amy-medium"));
+    }
+
+    #[test]
+    fn test_explain_duration_sources_and_voice() {
+        let config = project_config(
+            "[voice]\nengine = \"edge\"\ndefault_voice = \"en-US-AriaNeural\"\npadding_before = 0.5\npadding_after = 1.0\nauto_fallback_duration = 4.0\n",
+        );
+        let scenes = scenes(&[
+            "---\ntemplate: title-card\nduration: auto\n---\nNarrated.",
+            "---\ntemplate: title-card\nduration: auto\n---\n",
+            "---\ntemplate: title-card\nduration: 3\nvoice:\n  engine: native\n  speed: 1.2\n---\nFixed.",
+        ]);
+        let explained = explain(
+            &config,
+            &scenes,
+            &[Some(2.0), None, Some(9.0)],
+            Path::new("."),
+            no_probe,
+        );
+        let s = &explained.scenes;
+
+        assert_eq!(s[0].duration_source, DurationSource::TtsPadding);
+        assert!((s[0].duration_secs - 3.5).abs() < 1e-9);
+        assert_eq!(s[0].narration_secs, Some(2.0));
+        let voice = s[0].voice.as_ref().unwrap();
+        assert_eq!(voice.engine, "edge");
+        assert_eq!(voice.voice.as_deref(), Some("en-US-AriaNeural"));
+
+        assert_eq!(s[1].duration_source, DurationSource::Fallback);
+        assert!((s[1].duration_secs - 4.0).abs() < 1e-9);
+        assert!(s[1].voice.is_none());
+
+        assert_eq!(s[2].duration_source, DurationSource::Fixed);
+        assert!((s[2].duration_secs - 3.0).abs() < 1e-9);
+        assert_eq!(s[2].narration_secs, None);
+        let voice = s[2].voice.as_ref().unwrap();
+        assert_eq!(voice.engine, "native");
+        assert!((voice.speed - 1.2).abs() < 1e-6);
+        assert_eq!(s[2].file, "03-scene.md");
+    }
+
+    #[test]
+    fn test_explain_percent_duration_uses_target() {
+        let config = project_config("[output]\ntarget_duration = 60.0\n");
+        let scenes = scenes(&["---\ntemplate: title-card\nduration: \"20%\"\n---\nNarrated."]);
+        let explained = explain(&config, &scenes, &[Some(2.0)], Path::new("."), no_probe);
+        let s = &explained.scenes[0];
+        assert_eq!(s.duration_source, DurationSource::Percent);
+        assert!((s.duration_secs - 12.0).abs() < 1e-9);
+    }
+}
@@ -1,11 +1,15 @@
 pub mod browser;
 pub mod encoder;
+pub mod explain;
 pub mod frame_cache;
 pub mod overlay;
 pub mod sequence;
 
-use crate::config::{resolve_encoding, ProjectConfig, QualityPreset, ThemeConfig, VoiceConfig};
-use crate::error::VidgenResult;
+use crate::config::{
+    resolve_encoding, PlatformPreset, ProjectConfig, QualityConfig, QualityPreset,
+    SubtitleConfig, ThemeConfig, VoiceConfig,
+};
+use crate::error::{VidgenError, VidgenResult};
 use crate::render::encoder::{resolve_transition, SceneTransition};
 use crate::scene::{Scene, SceneFrontmatter};
 use crate::subtitle;
@@ -22,10 +26,21 @@ use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 /// Render progress reporter. Sends MCP progress notifications when running
-/// via the MCP server, or does nothing (noop) when running from the CLI.
+/// via the MCP server, prints NDJSON events to stdout for `--progress-json`,
+/// or does nothing (noop) when running from the CLI.
 pub struct RenderProgress {
     peer: Option<Peer<RoleServer>>,
     token: Option<rmcp::model::ProgressToken>,
+    /// Highest progress printed so far, when emitting JSON events.
+    json_last: Option<std::sync::Mutex<f64>>,
+}
+
+/// One `--progress-json` event, mirroring the MCP progress notification fields.
+#[derive(Debug, Serialize)]
+struct ProgressEvent<'a> {
+    progress: f64,
+    total: f64,
+    message: &'a str,
 }
 
 impl RenderProgress {
@@ -34,6 +49,7 @@ impl RenderProgress {
         Self {
             peer: None,
             token: None,
+            json_last: None,
         }
     }
 
@@ -42,11 +58,37 @@ impl RenderProgress {
         Self {
             peer: Some(peer),
             token: Some(token),
+            json_last: None,
+        }
+    }
+
+    /// Create a progress reporter that prints newline-delimited JSON events to stdout.
+    pub fn json() -> Self {
+        Self {
+            peer: None,
+            token: None,
+            json_last: Some(std::sync::Mutex::new(0.0)),
         }
     }
 
-    /// Report progress. No-op if no peer/token is set.
+    /// Serialize a JSON progress event. Scenes finish out of order when rendered
+    /// in parallel, so progress is clamped to never go backwards.
+    fn json_event(&self, progress: f64, total: f64, message: &str) -> Option<String> {
+        let mut last = self.json_last.as_ref()?.lock().ok()?;
+        *last = last.max(progress);
+        serde_json::to_string(&ProgressEvent {
+            progress: *last,
+            total,
+            message,
+        })
+        .ok()
+    }
+
+    /// Report progress. No-op if no peer/token is set and JSON output is off.
     pub async fn report(&self, progress: f64, total: f64, message: &str) {
+        if let Some(line) = self.json_event(progress, total, message) {
+            println!("{line}");
+        }
         if let (Some(peer), Some(token)) = (&self.peer, &self.token) {
             let _ = peer
                 .notify_progress(ProgressNotificationParam {
@@ -68,12 +110,32 @@ pub struct FormatOutput {
     pub effective_durations: Vec<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subtitle_path: Option<PathBuf>,
+    /// Burned-in copy written alongside the clean video (`output.subtitles.keep_clean`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub captioned_path: Option<PathBuf>,
+    /// Looped copy written alongside the video (`output.loop`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loop_path: Option<PathBuf>,
+    /// SHA-256 (lowercase hex) of `output_path` as written, for downstream verification.
+    pub sha256: String,
+    pub stats: RenderStats,
+}
+
+/// Wall-clock seconds spent in each render phase for one format.
+/// TTS runs once per render, so every format reports the same `tts_secs`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct RenderStats {
+    pub tts_secs: f64,
+    /// Per-scene capture (including per-scene encoding and overlays).
+    pub capture_secs: f64,
+    /// Concatenation and post-processing (fades, subtitles, audio tracks).
+    pub encode_secs: f64,
 }
 
 /// Apply format-specific overrides to a scene's frontmatter, returning a modified clone.
 /// Merges `format_overrides[fmt_name].props` into the scene's props and replaces the
 /// background if specified.
-fn apply_format_overrides(scene: &Scene, fmt_name: &str) -> Scene {
+pub(crate) fn apply_format_overrides(scene: &Scene, fmt_name: &str) -> Scene {
     let overrides = scene
         .frontmatter
         .format_overrides
@@ -90,18 +152,21 @@ fn apply_format_overrides(scene: &Scene, fmt_name: &str) -> Scene {
                 sub_scenes: scene.frontmatter.sub_scenes.clone(),
                 overlay: scene.frontmatter.overlay.clone(),
                 props: scene.frontmatter.props.clone(),
-                background: scene.frontmatter.background.as_ref().map(|bg| {
-                    crate::scene::BackgroundConfig {
-                        color: bg.color.clone(),
-                        image: bg.image.clone(),
-                    }
-                }),
+                background: scene.frontmatter.background.clone(),
                 transition_in: scene.frontmatter.transition_in.clone(),
                 transition_out: scene.frontmatter.transition_out.clone(),
                 transition_duration: scene.frontmatter.transition_duration,
                 voice: scene.frontmatter.voice.clone(),
                 audio: scene.frontmatter.audio.clone(),
                 format_overrides: scene.frontmatter.format_overrides.clone(),
+                wait_for: scene.frontmatter.wait_for.clone(),
+                fps: scene.frontmatter.fps,
+                tags: scene.frontmatter.tags.clone(),
+                markdown: scene.frontmatter.markdown,
+                quality: scene.frontmatter.quality.clone(),
+                motion_blur: scene.frontmatter.motion_blur,
+                min_duration: scene.frontmatter.min_duration,
+                seed: scene.frontmatter.seed,
             },
             script: scene.script.clone(),
             source_path: scene.source_path.clone(),
@@ -115,19 +180,8 @@ fn apply_format_overrides(scene: &Scene, fmt_name: &str) -> Scene {
             }
             let background = fo
                 .background
-                .as_ref()
-                .map(|bg| crate::scene::BackgroundConfig {
-                    color: bg.color.clone(),
-                    image: bg.image.clone(),
-                })
-                .or_else(|| {
-                    scene.frontmatter.background.as_ref().map(|bg| {
-                        crate::scene::BackgroundConfig {
-                            color: bg.color.clone(),
-                            image: bg.image.clone(),
-                        }
-                    })
-                });
+                .clone()
+                .or_else(|| scene.frontmatter.background.clone());
 
             Scene {
                 frontmatter: SceneFrontmatter {
@@ -145,6 +199,14 @@ fn apply_format_overrides(scene: &Scene, fmt_name: &str) -> Scene {
                     voice: scene.frontmatter.voice.clone(),
                     audio: scene.frontmatter.audio.clone(),
                     format_overrides: scene.frontmatter.format_overrides.clone(),
+                    wait_for: scene.frontmatter.wait_for.clone(),
+                    fps: scene.frontmatter.fps,
+                    tags: scene.frontmatter.tags.clone(),
+                    markdown: scene.frontmatter.markdown,
+                    quality: scene.frontmatter.quality.clone(),
+                    motion_blur: scene.frontmatter.motion_blur,
+                    min_duration: scene.frontmatter.min_duration,
+                    seed: scene.frontmatter.seed,
                 },
                 script: scene.script.clone(),
                 source_path: scene.source_path.clone(),
@@ -153,23 +215,55 @@ fn apply_format_overrides(scene: &Scene, fmt_name: &str) -> Scene {
     }
 }
 
+/// Word timings of a scene's narration, relative to the voice start: the engine's
+/// own timings when it reports them, otherwise estimated from the TTS duration
+/// (voice only, not the effective duration with padding). `None` without narration.
+fn narration_words(
+    script: &str,
+    engine_words: Option<&Vec<tts::timestamps::WordTimestamp>>,
+    tts_duration: Option<f64>,
+) -> Option<Vec<tts::timestamps::WordTimestamp>> {
+    let script = script.trim();
+    let duration = tts_duration.filter(|_| !script.is_empty())?;
+    Some(
+        engine_words
+            .cloned()
+            .unwrap_or_else(|| tts::timestamps::estimate_word_timestamps(script, duration)),
+    )
+}
+
 /// Compute a SHA256 content hash for a scene that captures everything affecting its rendered output.
 /// Used for incremental rendering: if the hash matches a cached scene MP4, we can skip re-rendering.
+#[allow(clippy::too_many_arguments)]
 fn scene_content_hash(
     scene: &Scene,
     width: u32,
     height: u32,
     fps: u32,
+    output_fps: u32,
     theme: &ThemeConfig,
     voice_config: &VoiceConfig,
     effective_duration: f64,
     fmt_name: &str,
+    ducking: Option<&encoder::MusicDucking>,
+    captions: Option<&subtitle::SceneCaptions>,
+    emoji_font: Option<&Path>,
 ) -> String {
     let mut hasher = Sha256::new();
 
     // Template + props
     hasher.update(scene.frontmatter.template.as_bytes());
     hasher.update(format!("{:?}", scene.frontmatter.props).as_bytes());
+    if scene.frontmatter.markdown {
+        hasher.update(b"markdown");
+    }
+    if let Some(ref q) = scene.frontmatter.quality {
+        hasher.update(format!("quality:{q}").as_bytes());
+    }
+    if scene.frontmatter.motion_blur {
+        hasher.update(b"motion_blur");
+    }
+    hasher.update(format!("seed:{}", scene.rand_seed()).as_bytes());
 
     // Voiceover script
     hasher.update(scene.script.as_bytes());
@@ -196,7 +290,7 @@ fn scene_content_hash(
     hasher.update(theme.font_heading.as_bytes());
 
     // Video dimensions and format
-    hasher.update(format!("{}x{}@{}", width, height, fps).as_bytes());
+    hasher.update(format!("{}x{}@{}->{}", width, height, fps, output_fps).as_bytes());
     hasher.update(fmt_name.as_bytes());
 
     // Background config
@@ -217,6 +311,17 @@ fn scene_content_hash(
         hasher.update(format!("{:?}", subs).as_bytes());
     }
 
+    // Caption layer drawn into the frames (output.subtitles.html_overlay)
+    if let Some(c) = captions {
+        hasher.update(format!("{:?}", c).as_bytes());
+        hasher.update(theme.font_body.as_bytes());
+    }
+
+    // Emoji font embedded in the page (render.emoji_fallback)
+    if let Some(font) = emoji_font {
+        hasher.update(format!("emoji_font:{}", font.display()).as_bytes());
+    }
+
     // Transitions
     if let Some(ref t) = scene.frontmatter.transition_in {
         hasher.update(t.as_bytes());
@@ -232,6 +337,9 @@ fn scene_content_hash(
     if let Some(ref a) = scene.frontmatter.audio {
         hasher.update(format!("{:?}", a).as_bytes());
     }
+    if let Some(d) = ducking {
+        hasher.update(format!("{:?}", d).as_bytes());
+    }
 
     // Overlay
     if let Some(ref ov) = scene.frontmatter.overlay {
@@ -242,11 +350,466 @@ fn scene_content_hash(
     format!("{:x}", result)[..16].to_string()
 }
 
-/// Resolve format list from config. Returns `(name, width, height, platform)` tuples.
-fn resolve_formats(
+/// Narration for a scene with a recorded voiceover (`audio.voice`). The file
+/// stands in for TTS and its length, read with `probe` and narrowed to
+/// `audio.start_at`/`end_at`, drives auto duration. Returns `None` when the
+/// scene has no recording.
+pub(crate) fn recorded_narration(
+    scene: &Scene,
+    project_path: &Path,
+    probe: impl Fn(&Path) -> VidgenResult<f64>,
+) -> Option<VidgenResult<tts::SynthesisResult>> {
+    let audio = scene.frontmatter.audio.as_ref()?;
+    let path = crate::scene::resolve_asset_path(audio.voice.as_deref()?, project_path);
+    if !path.exists() {
+        return Some(Err(VidgenError::Other(format!(
+            "recorded voiceover not found: {}",
+            path.display()
+        ))));
+    }
+    let full = match probe(&path) {
+        Ok(d) => d,
+        Err(e) => return Some(Err(e)),
+    };
+    // With start_at/end_at only the trimmed window is heard
+    let duration_secs = audio.trimmed_length(full);
+    if duration_secs <= 0.0 {
+        return Some(Err(VidgenError::Other(format!(
+            "audio.start_at/end_at select nothing of {} ({:.1}s long)",
+            path.display(),
+            full
+        ))));
+    }
+    Some(Ok(tts::SynthesisResult {
+        audio_path: path,
+        duration_secs,
+        cached: false,
+        word_timestamps: None,
+    }))
+}
+
+/// Narration for every scene from the TTS synthesis pass, by scene index.
+/// Entries are `None` for scenes without a script or whose synthesis failed.
+pub(crate) struct SceneNarration {
+    pub audio_paths: Vec<Option<PathBuf>>,
+    pub durations: Vec<Option<f64>>,
+    /// Word timings reported by the engine, when it provides them
+    pub words: Vec<Option<Vec<tts::timestamps::WordTimestamp>>>,
+}
+
+/// TTS synthesis pass: voice each scene's script with its engine/voice/speed
+/// overrides and the `voice.fallback` chain, through the voiceover cache.
+/// Recorded voiceovers (`audio.voice`) stand in for TTS. Audio is written to
+/// `work_dir`; with `dry_tts` silent placeholders replace every engine.
+/// Progress lines are prefixed with `label`.
+pub(crate) fn synthesize_narration(
+    scenes: &[Scene],
+    config: &ProjectConfig,
+    project_path: &Path,
+    work_dir: &Path,
+    dry_tts: bool,
+    force_tts: bool,
+    label: &str,
+) -> SceneNarration {
+    let tts_engines: Vec<Box<dyn tts::TtsEngine>> = if dry_tts {
+        vec![Box::new(tts::silent::SilentTtsEngine)]
+    } else {
+        tts::create_engine_chain(&config.voice)
+    };
+    if dry_tts {
+        eprintln!(
+            "{} TTS engine: {} (--dry-tts, {} wpm estimate)",
+            label.cyan().bold(),
+            tts::silent::ENGINE_NAME,
+            tts::silent::WORDS_PER_MINUTE,
+        );
+    } else if tts_engines.is_empty() {
+        let reason = tts::create_engine(&config.voice)
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_else(|| "no engine in voice.fallback is available".into());
+        eprintln!(
+            "{} TTS unavailable ({}), skipping voiceover",
+            label.cyan().bold(),
+            reason
+        );
+    } else {
+        let names: Vec<&str> = tts_engines.iter().map(|e| e.engine_name()).collect();
+        eprintln!(
+            "{} TTS engine: {}{}",
+            label.cyan().bold(),
+            names[0],
+            if names.len() > 1 {
+                format!(" (fallback: {})", names[1..].join(", "))
+            } else {
+                String::new()
+            }
+        );
+    }
+
+    let mut audio_paths: Vec<Option<PathBuf>> = Vec::new();
+    let mut tts_durations: Vec<Option<f64>> = Vec::new();
+    // Word timings reported by the engine, when it provides them
+    let mut tts_words: Vec<Option<Vec<tts::timestamps::WordTimestamp>>> = Vec::new();
+    // Engines for scenes that override `voice.engine`, created once per engine name
+    // (`None` when creation failed, so the failure is reported once)
+    let mut scene_engines: std::collections::HashMap<String, Option<Box<dyn tts::TtsEngine>>> =
+        std::collections::HashMap::new();
+    for (i, scene) in scenes.iter().enumerate() {
+        if let Some(recorded) = recorded_narration(scene, project_path, tts::ffprobe_duration) {
+            // Cut the start_at/end_at window once so every encode path gets the same audio
+            let recorded = recorded.and_then(|mut result| {
+                let audio = scene.frontmatter.audio.as_ref().unwrap();
+                if audio.is_trimmed() {
+                    let trimmed = work_dir.join(format!("scene-{i:03}-voice.wav"));
+                    encoder::trim_audio(
+                        &result.audio_path,
+                        &trimmed,
+                        audio.start_at,
+                        audio.end_at,
+                    )?;
+                    result.audio_path = trimmed;
+                }
+                Ok(result)
+            });
+            match recorded {
+                Ok(result) => {
+                    eprintln!(
+                        "  TTS scene {}: {:.1}s recorded voiceover, skipping TTS",
+                        i + 1,
+                        result.duration_secs
+                    );
+                    tts_durations.push(Some(result.duration_secs));
+                    tts_words.push(None);
+                    audio_paths.push(Some(result.audio_path));
+                }
+                Err(e) => {
+                    eprintln!("  TTS scene {}: {}, skipping audio", i + 1, e);
+                    audio_paths.push(None);
+                    tts_durations.push(None);
+                    tts_words.push(None);
+                }
+            }
+            continue;
+        }
+
+        let script = scene.script.trim();
+        if script.is_empty() || tts_engines.is_empty() {
+            audio_paths.push(None);
+            tts_durations.push(None);
+            tts_words.push(None);
+            continue;
+        }
+        let wav_path = work_dir.join(format!("scene-{i:03}.wav"));
+
+        // Determine per-scene engine/voice/speed overrides
+        let scene_voice_cfg = scene.frontmatter.voice.as_ref();
+        let scene_engine_override = scene_voice_cfg
+            .and_then(|v| v.engine.as_deref())
+            .filter(|_| !dry_tts);
+        let voice = scene_voice_cfg
+            .and_then(|v| v.voice_name())
+            .or(config.voice.default_voice.as_deref());
+        let speed = scene_voice_cfg
+            .and_then(|v| v.speed)
+            .unwrap_or(config.voice.speed);
+
+        // Use a per-scene engine if the scene overrides the engine, otherwise use the project engine
+        let scene_engine: Option<&dyn tts::TtsEngine> = match scene_engine_override {
+            Some(engine_name) => scene_engines
+                .entry(engine_name.to_string())
+                .or_insert_with(|| {
+                    let mut voice_cfg = config.voice.clone();
+                    voice_cfg.engine = engine_name.to_string();
+                    match tts::create_engine(&voice_cfg) {
+                        Ok(eng) => Some(eng),
+                        Err(e) => {
+                            eprintln!("  TTS scene {}: engine '{}' failed ({}), using default", i + 1, engine_name, e);
+                            None
+                        }
+                    }
+                })
+                .as_deref(),
+            None => None,
+        };
+
+        // Try the scene engine (if any), then the project chain. The voice only
+        // applies to the engine it was chosen for; fallbacks use their default voice.
+        let intended_engine = scene_engine_override.unwrap_or(&config.voice.engine);
+        let candidates: Vec<(&dyn tts::TtsEngine, Option<&str>)> = scene_engine
+            .into_iter()
+            .chain(
+                tts_engines
+                    .iter()
+                    .map(|e| e.as_ref())
+                    .filter(|e| scene_engine.is_none() || e.engine_name() != intended_engine),
+            )
+            .map(|e| (e, voice.filter(|_| e.engine_name() == intended_engine)))
+            .collect();
+
+        match tts::synthesize_with_fallback(&candidates, |engine, voice| {
+            if dry_tts {
+                // Placeholders are cheap to regenerate; keep them out of the TTS cache
+                return tts::pauses::synthesize_script(engine, script, voice, speed, &wav_path);
+            }
+            tts::cache::synthesize_cached_with_options(
+                engine,
+                script,
+                voice,
+                speed,
+                &wav_path,
+                project_path,
+                force_tts,
+            )
+        }) {
+            Ok((result, engine_name)) => {
+                let tag = if result.cached { " (cached)" } else { "" };
+                eprintln!(
+                    "  TTS scene {}: {:.1}s audio via {}{}",
+                    i + 1,
+                    result.duration_secs,
+                    engine_name,
+                    tag
+                );
+                tts_durations.push(Some(result.duration_secs));
+                tts_words.push(result.word_timestamps);
+                audio_paths.push(Some(result.audio_path));
+            }
+            Err(e) => {
+                eprintln!("  TTS scene {}: failed ({}), skipping audio", i + 1, e);
+                audio_paths.push(None);
+                tts_durations.push(None);
+                tts_words.push(None);
+            }
+        }
+    }
+
+    SceneNarration {
+        audio_paths,
+        durations: tts_durations,
+        words: tts_words,
+    }
+}
+
+/// Contents of `output.subtitles.source_file`, which replaces the subtitles
+/// generated from narration timings. `None` when no source file is set.
+pub(crate) fn external_subtitles(
+    subs: &SubtitleConfig,
+    project_path: &Path,
+) -> VidgenResult<Option<String>> {
+    let Some(ref source) = subs.source_file else {
+        return Ok(None);
+    };
+    let path = crate::scene::resolve_asset_path(source, project_path);
+    std::fs::read_to_string(&path).map(Some).map_err(|e| {
+        VidgenError::Other(format!(
+            "output.subtitles.source_file {} can't be read: {e}",
+            path.display()
+        ))
+    })
+}
+
+/// Write the `slug.srt` sidecar when subtitles are enabled: `external_srt` as
+/// given, else entries grouped from the narration `words`. Returns its path,
+/// which burn-in uses.
+fn write_srt_sidecar(
+    subs: &SubtitleConfig,
+    external_srt: Option<&str>,
+    words: &[tts::timestamps::WordTimestamp],
+    output_path: &Path,
+) -> VidgenResult<Option<PathBuf>> {
+    if !subs.enabled {
+        return Ok(None);
+    }
+    let content = match external_srt {
+        Some(srt) => srt.to_string(),
+        None if words.is_empty() => return Ok(None),
+        None => subtitle::to_srt(&subtitle::group_into_subtitles(
+            words,
+            subs.max_words_per_line,
+        )),
+    };
+    let srt_path = output_path.with_extension("srt");
+    std::fs::write(&srt_path, content)?;
+    Ok(Some(srt_path))
+}
+
+/// Shift each scene's scene-local words by the scene's start in the joined
+/// video (`offsets`), giving the timeline of the combined subtitles.
+fn combined_words(
+    scene_words: &[Vec<tts::timestamps::WordTimestamp>],
+    offsets: &[f64],
+) -> Vec<tts::timestamps::WordTimestamp> {
+    scene_words
+        .iter()
+        .zip(offsets)
+        .flat_map(|(words, offset)| {
+            words.iter().cloned().map(move |mut w| {
+                w.start_secs += offset;
+                w.end_secs += offset;
+                w
+            })
+        })
+        .collect()
+}
+
+/// Directory of the `output.subtitles.per_scene` files, next to the video.
+fn per_scene_subtitle_dir(output_path: &Path) -> PathBuf {
+    output_path.with_file_name("subtitles")
+}
+
+/// Write `NN.srt` (1-based scene number) into `dir` for every scene with
+/// narration, keeping each scene's own timing. Returns the written paths.
+fn write_scene_srts(
+    scene_words: &[Vec<tts::timestamps::WordTimestamp>],
+    max_words_per_line: usize,
+    dir: &Path,
+) -> VidgenResult<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+    let mut written = Vec::new();
+    for (i, words) in scene_words.iter().enumerate() {
+        if words.is_empty() {
+            continue;
+        }
+        let path = dir.join(format!("{:02}.srt", i + 1));
+        let entries = subtitle::group_into_subtitles(words, max_words_per_line);
+        std::fs::write(&path, subtitle::to_srt(&entries))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// ASS `force_style` and fonts directory for burning in subtitles. A configured
+/// `font_file` sets `FontName` from its file stem; an explicit `FontName` in
+/// `style` still wins.
+fn burn_in_style(subs: &SubtitleConfig, project_path: &Path) -> (String, Option<PathBuf>) {
+    let font = subs
+        .font_file
+        .as_deref()
+        .map(|raw| crate::scene::resolve_asset_path(raw, project_path));
+    let font_name = font
+        .as_ref()
+        .and_then(|f| f.file_stem())
+        .map(|stem| format!("FontName={}", stem.to_string_lossy()));
+    let overrides = [font_name.as_deref(), subs.style.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(",");
+    let style = subtitle::force_style(subs.preset.as_deref(), Some(&overrides));
+    let fonts_dir = font.and_then(|f| f.parent().map(Path::to_path_buf));
+    (style, fonts_dir)
+}
+
+/// SHA-256 of a file's contents as lowercase hex. The file is streamed, so
+/// long videos are never held in memory.
+pub fn file_sha256(path: &Path) -> VidgenResult<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Hash of everything that goes into one format's final video: its scene hashes
+/// plus the joins and post-processing applied after capture (transitions, music,
+/// subtitles, extra audio tracks). A matching stamp from the last render lets
+/// `--only-changed-formats` reuse the existing output.
+fn format_content_hash(
+    scene_hashes: &[String],
+    scene_platforms: &[PlatformPreset],
+    transitions: &[Option<SceneTransition>],
+    config: &ProjectConfig,
+    external_srt: Option<&str>,
+    emoji_font: Option<&Path>,
+    output_path: &Path,
+) -> String {
+    let mut hasher = Sha256::new();
+    for hash in scene_hashes {
+        hasher.update(hash.as_bytes());
+    }
+    for p in scene_platforms {
+        hasher.update(
+            format!(
+                "{}:{}:{}:{}:{:?}",
+                p.crf, p.preset, p.audio_bitrate, p.audio_samplerate, p.hw_encoder
+            )
+            .as_bytes(),
+        );
+    }
+    hasher.update(format!("{:?}", transitions).as_bytes());
+    hasher.update(format!("{:?}", config.audio).as_bytes());
+    hasher.update(format!("{:?}", config.output.subtitles).as_bytes());
+    hasher.update(external_srt.unwrap_or_default().as_bytes());
+    hasher.update(format!("{:?}", config.output.poster_frame).as_bytes());
+    hasher.update(format!("{:?}", config.output.loop_count).as_bytes());
+    hasher.update(format!("{:?}", config.output.loop_crossfade).as_bytes());
+    hasher.update(format!("{:?}", config.output.audio).as_bytes());
+    hasher.update(format!("{:?}", config.output.metadata.tags(&config.project.name)).as_bytes());
+    hasher.update(format!("{:?}", config.voice.language).as_bytes());
+    hasher.update(config.video.concat_method.as_bytes());
+    hasher.update(format!("gap:{}", config.video.scene_gap_secs).as_bytes());
+    hasher.update(format!("emoji:{}:{:?}", config.render.emoji_fallback, emoji_font).as_bytes());
+    hasher.update(output_path.to_string_lossy().as_bytes());
+
+    let result = hasher.finalize();
+    format!("{:x}", result)[..16].to_string()
+}
+
+/// Subtitle and captioned paths of a previous render of a format, or `None` if
+/// the video or any file (or per-scene subtitle directory) the subtitle
+/// settings produce is missing.
+fn reusable_format_output(
+    output_path: &Path,
+    subs: &crate::config::SubtitleConfig,
+) -> Option<(Option<PathBuf>, Option<PathBuf>)> {
+    let subtitle_path = subs.enabled.then(|| output_path.with_extension("srt"));
+    let captioned_path =
+        (subs.burn_in && subs.keep_clean).then(|| encoder::captioned_path(output_path));
+    let scene_srt_dir = subs.per_scene.then(|| per_scene_subtitle_dir(output_path));
+    let all_present = std::iter::once(output_path)
+        .chain(subtitle_path.as_deref())
+        .chain(captioned_path.as_deref())
+        .chain(scene_srt_dir.as_deref())
+        .all(Path::exists);
+    all_present.then_some((subtitle_path, captioned_path))
+}
+
+/// A resolved output format: `(name, width, height, platform)`.
+pub(crate) type ResolvedFormat = (String, u32, u32, Option<String>);
+
+/// `format_overrides` keys in `scene` that don't name a configured format. Without
+/// `video.formats` the only format is "default". Sorted for stable reporting.
+pub(crate) fn unknown_format_override_keys(scene: &Scene, config: &ProjectConfig) -> Vec<String> {
+    let Some(overrides) = scene.frontmatter.format_overrides.as_ref() else {
+        return Vec::new();
+    };
+    let known: Vec<String> = resolve_formats(config, None)
+        .into_iter()
+        .map(|(name, ..)| name)
+        .collect();
+    let mut unknown: Vec<String> = overrides
+        .keys()
+        .filter(|key| !known.contains(key))
+        .cloned()
+        .collect();
+    unknown.sort();
+    unknown
+}
+
+/// Resolve format list from config. With `render.auto_downscale`, formats larger
+/// than `render.max_dimension` are scaled down to fit; otherwise
+/// `ProjectConfig::validate` rejects them.
+pub(crate) fn resolve_formats(
     config: &ProjectConfig,
     format_filter: Option<&[String]>,
-) -> Vec<(String, u32, u32, Option<String>)> {
+) -> Vec<ResolvedFormat> {
+    let fit = |width: u32, height: u32| {
+        if config.render.auto_downscale {
+            fit_within_max(width, height, config.render.max_dimension)
+        } else {
+            (width, height)
+        }
+    };
     match &config.video.formats {
         Some(formats) => formats
             .iter()
@@ -255,17 +818,134 @@ fn resolve_formats(
                     .map(|f| f.iter().any(|n| n == *name))
                     .unwrap_or(true)
             })
-            .map(|(name, fc)| (name.clone(), fc.width, fc.height, fc.platform.clone()))
+            .map(|(name, fc)| {
+                let (width, height) = fit(fc.width, fc.height);
+                (name.clone(), width, height, fc.platform.clone())
+            })
             .collect(),
-        None => vec![(
-            "default".into(),
-            config.video.width,
-            config.video.height,
-            None,
-        )],
+        None => {
+            let (width, height) = fit(config.video.width, config.video.height);
+            vec![("default".into(), width, height, None)]
+        }
+    }
+}
+
+/// Scale `(width, height)` down so the longer side fits within `max`, keeping the
+/// aspect ratio. Dimensions are rounded down to even numbers for yuv420p.
+fn fit_within_max(width: u32, height: u32, max: u32) -> (u32, u32) {
+    let longest = width.max(height);
+    if longest <= max {
+        return (width, height);
+    }
+    let scale = max as f64 / longest as f64;
+    let fit = |v: u32| (((v as f64 * scale) as u32) & !1).max(2);
+    (fit(width), fit(height))
+}
+
+/// Browser size for a capture at `(width, height)` that doesn't go through
+/// [`resolve_formats`] (previews, single-frame exports): fitted within
+/// `render.max_dimension`, since Chromium can't open a larger window.
+pub(crate) fn capture_size(config: &ProjectConfig, width: u32, height: u32) -> (u32, u32) {
+    fit_within_max(width, height, config.render.max_dimension)
+}
+
+/// Encoding parameters for one scene: its frontmatter `quality` override (if any)
+/// applied to the format's platform, otherwise the project-wide `quality`.
+pub(crate) fn scene_encoding(
+    scene: &Scene,
+    quality: &QualityPreset,
+    custom: &QualityConfig,
+    platform_name: Option<&str>,
+) -> PlatformPreset {
+    match scene.frontmatter.quality.as_deref() {
+        Some(name) => resolve_encoding(&QualityPreset::resolve(name, custom), platform_name),
+        None => resolve_encoding(quality, platform_name),
+    }
+}
+
+/// Warn about each configured format that `render.auto_downscale` shrinks to fit
+/// `render.max_dimension` (see [`resolve_formats`]).
+fn warn_downscaled_formats(config: &ProjectConfig, format_filter: Option<&[String]>) {
+    let limits = &config.render;
+    if !limits.auto_downscale {
+        return;
+    }
+    for (name, width, height) in config.output_sizes() {
+        if format_filter.is_some_and(|f| !f.contains(&name)) {
+            continue;
+        }
+        if width.max(height) <= limits.max_dimension {
+            continue;
+        }
+        let (w, h) = fit_within_max(width, height, limits.max_dimension);
+        eprintln!(
+            "{} Format \"{}\" is {}x{}, downscaling to {}x{} (render.max_dimension = {})",
+            "warning:".yellow().bold(),
+            name,
+            width,
+            height,
+            w,
+            h,
+            limits.max_dimension,
+        );
+    }
+}
+
+/// Resolve the output directory for a format. A format-level `output_dir`
+/// (relative to the project) overrides the project-wide output directory.
+fn format_output_dir(
+    config: &ProjectConfig,
+    fmt_name: &str,
+    default_dir: &Path,
+    project_path: &Path,
+) -> PathBuf {
+    config
+        .video
+        .formats
+        .as_ref()
+        .and_then(|f| f.get(fmt_name))
+        .and_then(|fc| fc.output_dir.as_deref())
+        .map(|dir| project_path.join(dir.strip_prefix("./").unwrap_or(dir)))
+        .unwrap_or_else(|| default_dir.to_path_buf())
+}
+
+/// Final video path for a format: `slug-format.mp4`, or just `slug.mp4` for a
+/// project without `video.formats`. The extension follows `output.container`.
+pub(crate) fn format_output_path(
+    config: &ProjectConfig,
+    fmt_name: &str,
+    total_formats: usize,
+    default_dir: &Path,
+    project_path: &Path,
+) -> PathBuf {
+    let project_slug = config
+        .project
+        .name
+        .to_lowercase()
+        .replace(|c: char| !c.is_alphanumeric(), "-")
+        .trim_matches('-')
+        .to_string();
+    let dir = format_output_dir(config, fmt_name, default_dir, project_path);
+    let ext = config.output.container.extension();
+    if total_formats == 1 && fmt_name == "default" {
+        dir.join(format!("{project_slug}.{ext}"))
+    } else {
+        dir.join(format!("{project_slug}-{fmt_name}.{ext}"))
     }
 }
 
+/// Resolve the music file for a scene. Scene-level `audio.music` overrides the
+/// project-wide background music.
+fn scene_music_path(scene: &Scene, config: &ProjectConfig, project_path: &Path) -> Option<PathBuf> {
+    scene
+        .frontmatter
+        .audio
+        .as_ref()
+        .and_then(|a| a.music.as_deref())
+        .or_else(|| config.audio.background.as_ref().map(|bg| bg.file.as_str()))
+        .map(|m| crate::scene::resolve_asset_path(m, project_path))
+}
+
 /// Render a complete project: all scenes → per-scene MP4 → concatenated output.
 /// Supports multi-format: renders once per format (different viewport/encoding).
 #[allow(clippy::too_many_arguments)]
@@ -281,13 +961,39 @@ pub async fn render_project(
     force_tts: bool,
     no_cache: bool,
     use_gpu: bool,
+    only_changed_formats: bool,
 ) -> VidgenResult<Vec<FormatOutput>> {
-    let quality = QualityPreset::from_name(quality_name);
+    // Nothing to concatenate; fail before TTS or the browser start
+    if scenes.is_empty() {
+        return Err(VidgenError::NoScenes(project_path.join("scenes")));
+    }
+    crate::scene::validate_percent_durations(scenes, config.output.target_duration)?;
+    let quality = QualityPreset::resolve(quality_name, &config.quality);
     let mut registry = TemplateRegistry::new()?;
     registry.register_project_templates(project_path)?;
+    registry.apply_emoji_fallback(&config.render, project_path);
 
+    warn_downscaled_formats(config, format_filter);
     let formats = resolve_formats(config, format_filter);
 
+    for scene in scenes {
+        for key in unknown_format_override_keys(scene, config) {
+            eprintln!(
+                "{} Scene {} has format_overrides.{} but no such format is configured",
+                "warning:".yellow().bold(),
+                scene.source_path.display(),
+                key,
+            );
+        }
+    }
+
+    // A missing required prop would render blank; fail before TTS or the browser start
+    for (fmt_name, ..) in &formats {
+        for scene in scenes {
+            registry.validate_scene_props(&apply_format_overrides(scene, fmt_name))?;
+        }
+    }
+
     eprintln!(
         "{} Rendering \"{}\" — {} scene(s), {} format(s), @ {}fps, quality={}",
         "render:".cyan().bold(),
@@ -298,19 +1004,25 @@ pub async fn render_project(
         quality_name,
     );
 
-    // Print GPU encoder status
-    if use_gpu {
-        match encoder::detect_hw_encoder() {
-            Some(enc) => eprintln!(
-                "{} GPU encoding enabled: {}",
-                "render:".cyan().bold(),
-                enc,
-            ),
-            None => eprintln!(
-                "{} GPU requested but no hardware encoder found, using libx264",
-                "render:".cyan().bold(),
-            ),
-        }
+    // Hardware encoder from output.hwaccel or --gpu, falling back to libx264
+    let hw_encoder = encoder::resolve_hw_encoder(config.output.hwaccel, use_gpu);
+    match (hw_encoder, config.output.hwaccel) {
+        (Some(hw), _) => eprintln!(
+            "{} GPU encoding enabled: {}",
+            "render:".cyan().bold(),
+            hw.codec(),
+        ),
+        (None, Some(requested)) => eprintln!(
+            "{} output.hwaccel = \"{}\" but FFmpeg has no {} encoder, using libx264",
+            "warning:".yellow().bold(),
+            requested,
+            requested.codec(),
+        ),
+        (None, None) if use_gpu => eprintln!(
+            "{} GPU requested but no hardware encoder found, using libx264",
+            "render:".cyan().bold(),
+        ),
+        (None, None) => {}
     }
 
     // Print cache status
@@ -338,108 +1050,61 @@ pub async fn render_project(
     // Load .env from project directory (if present) so keys like ELEVEN_API_KEY are available
     let _ = dotenvy::from_path(project_path.join(".env"));
 
+    // A provided SRT replaces generated subtitles; read it before any rendering
+    let external_srt = external_subtitles(&config.output.subtitles, project_path)?;
+
     // TTS synthesis pass — runs once (format-independent)
+    let tts_start = Instant::now();
     debug!("Starting TTS synthesis pass for {} scenes", scenes.len());
-    let tts_engine = match tts::create_engine(&config.voice) {
-        Ok(engine) => {
-            eprintln!(
-                "{} TTS engine: {}",
-                "render:".cyan().bold(),
-                engine.engine_name()
-            );
-            Some(engine)
-        }
-        Err(e) => {
-            eprintln!(
-                "{} TTS unavailable ({}), skipping voiceover",
-                "render:".cyan().bold(),
-                e
-            );
-            None
+    // --dry-tts swaps every engine for silent placeholders sized from the script
+    let dry_tts = std::env::var("VIDGEN_DRY_TTS").is_ok();
+    let SceneNarration {
+        audio_paths,
+        durations: tts_durations,
+        words: tts_words,
+    } = synthesize_narration(
+        scenes,
+        config,
+        project_path,
+        temp_dir.path(),
+        dry_tts,
+        force_tts,
+        "render:",
+    );
+
+    let tts_secs = tts_start.elapsed().as_secs_f64();
+
+    // Hash dry-TTS scenes under their own engine so silent placeholders and
+    // real voiceover never reuse each other's cached scene MP4s
+    let hash_voice = if dry_tts {
+        crate::config::VoiceConfig {
+            engine: tts::silent::ENGINE_NAME.into(),
+            ..config.voice.clone()
         }
+    } else {
+        config.voice.clone()
     };
 
-    let mut audio_paths: Vec<Option<PathBuf>> = Vec::new();
-    let mut tts_durations: Vec<Option<f64>> = Vec::new();
-    for (i, scene) in scenes.iter().enumerate() {
-        let script = scene.script.trim();
-        if script.is_empty() || tts_engine.is_none() {
-            audio_paths.push(None);
-            tts_durations.push(None);
-            continue;
-        }
-        let wav_path = temp_dir.path().join(format!("scene-{i:03}.wav"));
-
-        // Determine per-scene engine/voice/speed overrides
-        let scene_voice_cfg = scene.frontmatter.voice.as_ref();
-        let scene_engine_override = scene_voice_cfg.and_then(|v| v.engine.as_deref());
-        let voice = scene_voice_cfg
-            .and_then(|v| v.voice_name())
-            .or(config.voice.default_voice.as_deref());
-        let speed = scene_voice_cfg
-            .and_then(|v| v.speed)
-            .unwrap_or(config.voice.speed);
-
-        // Use a per-scene engine if the scene overrides the engine, otherwise use the project engine
-        let scene_engine: Option<Box<dyn tts::TtsEngine>> = if let Some(engine_name) = scene_engine_override {
-            let mut voice_cfg = config.voice.clone();
-            voice_cfg.engine = engine_name.to_string();
-            match tts::create_engine(&voice_cfg) {
-                Ok(eng) => Some(eng),
-                Err(e) => {
-                    eprintln!("  TTS scene {}: engine '{}' failed ({}), using default", i + 1, engine_name, e);
-                    None
-                }
-            }
-        } else {
-            None
-        };
-        let effective_engine: &dyn tts::TtsEngine = scene_engine
-            .as_deref()
-            .unwrap_or_else(|| tts_engine.as_ref().unwrap().as_ref());
-
-        match tts::cache::synthesize_cached_with_options(
-            effective_engine,
-            script,
-            voice,
-            speed,
-            &wav_path,
-            project_path,
-            force_tts,
-        ) {
-            Ok(result) => {
-                let tag = if result.cached { " (cached)" } else { "" };
-                eprintln!(
-                    "  TTS scene {}: {:.1}s audio{}",
-                    i + 1,
-                    result.duration_secs,
-                    tag
-                );
-                tts_durations.push(Some(result.duration_secs));
-                audio_paths.push(Some(result.audio_path));
-            }
-            Err(e) => {
-                eprintln!("  TTS scene {}: failed ({}), skipping audio", i + 1, e);
-                audio_paths.push(None);
-                tts_durations.push(None);
-            }
-        }
-    }
-
-    // Copy TTS audio files to output/audio/ for standalone access
-    let audio_output_dir = output_dir.join("audio");
-    let mut audio_copied = false;
+    // Copy TTS audio files to output/audio/ for standalone access
+    // Named like `vidgen tts` output, numbered by the scene's position in the
+    // whole project so a `--scenes` render overwrites the same files
+    let audio_output_dir = output_dir.join("audio");
+    let project_scene_paths = crate::scene::scene_file_paths(project_path).unwrap_or_default();
+    let mut audio_copied = false;
     for (i, scene) in scenes.iter().enumerate() {
         if let Some(ref audio_path) = audio_paths[i] {
             if !audio_copied {
                 std::fs::create_dir_all(&audio_output_dir)?;
                 audio_copied = true;
             }
-            let fallback_name = format!("scene-{:02}", i + 1);
-            let scene_name = scene.source_path.file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or(&fallback_name);
-            let dest = audio_output_dir.join(format!("{scene_name}.wav"));
+            let index = project_scene_paths
+                .iter()
+                .position(|p| *p == scene.source_path)
+                .unwrap_or(i);
+            // Recorded voiceovers keep their own container (e.g. .mp3)
+            let ext = audio_path.extension().and_then(|e| e.to_str()).unwrap_or("wav");
+            let dest =
+                audio_output_dir.join(crate::scene::audio_file_name(index, &scene.source_path, ext));
             std::fs::copy(audio_path, &dest)?;
         }
     }
@@ -451,76 +1116,70 @@ pub async fn render_project(
         );
     }
 
+    // Probe music length for `duration: match-music` scenes
+    let music_durations: Vec<Option<f64>> = scenes
+        .iter()
+        .enumerate()
+        .map(|(i, scene)| {
+            explain::scene_music_duration(
+                scene,
+                i,
+                config,
+                project_path,
+                encoder::probe_video_duration,
+            )
+        })
+        .collect();
+
+    // `match-music` scenes without a usable music file behave exactly like `auto`
+    let auto_timed: Vec<bool> = scenes
+        .iter()
+        .zip(music_durations.iter())
+        .map(|(scene, music_dur)| {
+            scene.frontmatter.duration.is_auto()
+                || (scene.frontmatter.duration.is_match_music() && music_dur.is_none())
+        })
+        .collect();
+
     // Duration resolution pass — runs once (format-independent)
     debug!("Resolving durations for {} scenes", scenes.len());
-    let effective_durations: Vec<f64> = scenes
+    let (effective_durations, duration_sources): (Vec<f64>, Vec<explain::DurationSource>) = scenes
         .iter()
         .enumerate()
         .map(|(i, scene)| {
-            // For sequence scenes, sum resolved sub-scene durations
-            if scene.is_sequence() {
-                let sub_scenes = scene.frontmatter.sub_scenes.as_ref().unwrap();
-                match crate::scene::resolve_sub_scene_durations(
-                    sub_scenes,
-                    tts_durations[i],
-                    config.voice.padding_before,
-                    config.voice.padding_after,
-                    config.voice.auto_fallback_duration,
-                ) {
-                    Ok(durs) => return durs.iter().sum(),
-                    Err(e) => {
-                        eprintln!("  Scene {}: sub-scene duration error ({}), using fallback", i + 1, e);
-                    }
-                }
-            }
-            // For video-clip scenes with auto duration, probe the source video
-            if scene.is_video_clip() && scene.frontmatter.duration.is_auto() {
-                if let Some(ref src) = scene.frontmatter.video_source {
-                    let resolved = crate::scene::resolve_asset_path(src, project_path);
-                    match encoder::probe_video_duration(&resolved) {
-                        Ok(dur) => return dur,
-                        Err(e) => {
-                            eprintln!(
-                                "  Scene {}: could not probe video duration ({}), using fallback",
-                                i + 1, e
-                            );
-                        }
-                    }
-                }
-            }
-            scene.frontmatter.duration.resolve(
+            explain::resolve_scene_duration(
+                scene,
+                i,
                 tts_durations[i],
-                config.voice.padding_before,
-                config.voice.padding_after,
-                config.voice.auto_fallback_duration,
+                music_durations[i],
+                config,
+                project_path,
+                encoder::probe_video_duration,
             )
         })
-        .collect();
+        .unzip();
 
-    for (i, (scene, &dur)) in scenes.iter().zip(effective_durations.iter()).enumerate() {
-        if scene.frontmatter.duration.is_auto() {
-            let source = if scene.is_video_clip() {
-                "video probe"
-            } else if tts_durations[i].is_some() {
-                "TTS + padding"
-            } else {
-                "fallback"
-            };
+    for (i, &dur) in effective_durations.iter().enumerate() {
+        if music_durations[i].is_some() {
+            eprintln!(
+                "  Scene {}: duration match-music → {:.1}s (music probe)",
+                i + 1,
+                dur
+            );
+        } else if auto_timed[i] {
             eprintln!(
                 "  Scene {}: duration auto → {:.1}s ({})",
                 i + 1,
                 dur,
-                source
+                duration_sources[i].label()
             );
         }
     }
 
     // Compute per-scene audio delay and content padding (format-independent)
-    let audio_delays: Vec<f64> = scenes
-        .iter()
-        .enumerate()
-        .map(|(i, scene)| {
-            if scene.frontmatter.duration.is_auto() && tts_durations[i].is_some() {
+    let audio_delays: Vec<f64> = (0..scenes.len())
+        .map(|i| {
+            if auto_timed[i] && tts_durations[i].is_some() {
                 config.voice.padding_before
             } else {
                 0.0
@@ -528,11 +1187,9 @@ pub async fn render_project(
         })
         .collect();
 
-    let content_paddings_after: Vec<f64> = scenes
-        .iter()
-        .enumerate()
-        .map(|(i, scene)| {
-            if scene.frontmatter.duration.is_auto() && tts_durations[i].is_some() {
+    let content_paddings_after: Vec<f64> = (0..scenes.len())
+        .map(|i| {
+            if auto_timed[i] && tts_durations[i].is_some() {
                 config.voice.padding_after
             } else {
                 0.0
@@ -540,6 +1197,27 @@ pub async fn render_project(
         })
         .collect();
 
+    // Caption layers drawn into HTML scenes' frames (format-independent)
+    let scene_captions: Vec<Option<subtitle::SceneCaptions>> = scenes
+        .iter()
+        .enumerate()
+        .map(|(i, scene)| {
+            if !config.output.subtitles.html_overlay {
+                return None;
+            }
+            let mut words =
+                narration_words(&scene.script, tts_words[i].as_ref(), tts_durations[i])?;
+            for w in &mut words {
+                w.start_secs += audio_delays[i];
+                w.end_secs += audio_delays[i];
+            }
+            Some(subtitle::SceneCaptions {
+                words,
+                max_words_per_line: config.output.subtitles.max_words_per_line,
+            })
+        })
+        .collect();
+
     // Resolve transitions between adjacent scenes (format-independent)
     let transitions: Vec<Option<SceneTransition>> = if scenes.len() > 1 {
         (0..scenes.len() - 1)
@@ -550,14 +1228,20 @@ pub async fn render_project(
     };
     let has_transitions = transitions.iter().any(|t| t.is_some());
 
-    // Determine project slug for output filenames
-    let project_slug = config
-        .project
-        .name
-        .to_lowercase()
-        .replace(|c: char| !c.is_alphanumeric(), "-")
-        .trim_matches('-')
-        .to_string();
+    // Intro boost + ducking: each scene needs its start offset in the final video
+    // so only scenes overlapping the intro window get the boost
+    let scene_gap = config.video.scene_gap_secs;
+    let music_ducking: Vec<Option<encoder::MusicDucking>> =
+        encoder::scene_start_times(&effective_durations, &transitions, scene_gap)
+            .into_iter()
+            .map(|start| {
+                config
+                    .audio
+                    .intro_boost
+                    .as_ref()
+                    .map(|boost| encoder::MusicDucking::for_scene(boost, start))
+            })
+            .collect();
 
     // Progress tracking across all formats
     let total_formats = formats.len();
@@ -575,7 +1259,8 @@ pub async fn render_project(
             "Rendering format '{}' ({}x{}, platform={:?})",
             fmt_name, width, height, platform_name
         );
-        let platform = resolve_encoding(&quality, platform_name.as_deref());
+        let mut platform = resolve_encoding(&quality, platform_name.as_deref());
+        platform.hw_encoder = hw_encoder;
 
         eprintln!(
             "{} Format \"{}\": {}x{}{}",
@@ -589,7 +1274,101 @@ pub async fn render_project(
                 .unwrap_or_default(),
         );
 
+        // Apply per-format overrides to scenes
+        let fmt_scenes: Vec<Scene> = scenes
+            .iter()
+            .map(|s| apply_format_overrides(s, fmt_name))
+            .collect();
+
+        // Scenes with a `quality` override get their own encoder settings; the
+        // concat step re-encodes, so mixed per-scene parameters join cleanly
+        let scene_platforms: Vec<PlatformPreset> = fmt_scenes
+            .iter()
+            .map(|s| PlatformPreset {
+                hw_encoder,
+                ..scene_encoding(s, &quality, &config.quality, platform_name.as_deref())
+            })
+            .collect();
+
+        // Compute scene content hashes for incremental rendering
+        let scene_hashes: Vec<String> = fmt_scenes
+            .iter()
+            .enumerate()
+            .map(|(i, scene)| {
+                scene_content_hash(
+                    scene,
+                    *width,
+                    *height,
+                    scene.effective_fps(fps),
+                    config.video.output_fps.unwrap_or(scene.effective_fps(fps)),
+                    &config.theme,
+                    &hash_voice,
+                    effective_durations[i],
+                    fmt_name,
+                    music_ducking[i].as_ref(),
+                    scene_captions[i].as_ref(),
+                    registry.emoji_font(),
+                )
+            })
+            .collect();
+
+        // Output filename: slug-format.mp4 (or just slug.mp4 if single format).
+        // Scenes are joined and post-processed as MP4, then converted to
+        // `final_path` when another container is configured.
+        let final_path =
+            format_output_path(config, fmt_name, total_formats, output_dir, project_path);
+        let output_path = final_path.with_extension("mp4");
+
+        // Skip formats whose inputs match the last render (--only-changed-formats)
+        let format_hash = format_content_hash(
+            &scene_hashes,
+            &scene_platforms,
+            &transitions,
+            config,
+            external_srt.as_deref(),
+            registry.emoji_font(),
+            &final_path,
+        );
+        let format_stamp = cache_dir.join(format!("format-{fmt_name}.hash"));
+        if only_changed_formats && !no_cache {
+            let unchanged = std::fs::read_to_string(&format_stamp)
+                .is_ok_and(|stamp| stamp.trim() == format_hash);
+            let loop_path = config.output.loop_count.map(|_| encoder::loop_path(&final_path));
+            let loop_present = loop_path.as_deref().map_or(true, Path::exists);
+            if let Some((subtitle_path, captioned_path)) =
+                reusable_format_output(&final_path, &config.output.subtitles)
+                    .filter(|_| unchanged && loop_present)
+            {
+                eprintln!(
+                    "{} Format \"{}\" unchanged, reusing {}",
+                    "cache:".green().bold(),
+                    fmt_name,
+                    final_path.display()
+                );
+                let sha256 = file_sha256(&final_path)?;
+                results.push(FormatOutput {
+                    format_name: fmt_name.clone(),
+                    output_path: final_path,
+                    effective_durations: effective_durations.clone(),
+                    subtitle_path,
+                    captioned_path,
+                    loop_path,
+                    sha256,
+                    stats: RenderStats {
+                        tts_secs,
+                        ..Default::default()
+                    },
+                });
+                let done = scenes.len() as f64 + ((fmt_idx + 1) * steps_per_format) as f64;
+                progress
+                    .report(done, total_steps, &format!("Format {fmt_name} unchanged"))
+                    .await;
+                continue;
+            }
+        }
+
         // Launch browser for this format's dimensions
+        let capture_start = Instant::now();
         eprintln!("{} Launching browser...", "render:".cyan().bold());
         let (browser, handler_handle) = browser::launch_browser(*width, *height).await?;
 
@@ -597,16 +1376,6 @@ pub async fn render_project(
         let fmt_temp_dir = temp_dir.path().join(fmt_name);
         std::fs::create_dir_all(&fmt_temp_dir)?;
 
-        // Apply per-format overrides to scenes
-        let fmt_scenes: Vec<Scene> = scenes
-            .iter()
-            .map(|s| apply_format_overrides(s, fmt_name))
-            .collect();
-
-        // Resolve project-wide background music (if configured)
-        let project_bg_music = config.audio.background.as_ref().map(|bg| {
-            crate::scene::resolve_asset_path(&bg.file, project_path)
-        });
         let project_bg_volume = config.audio.background.as_ref()
             .map(|bg| {
                 // Convert dB to linear volume (0.0-1.0 range)
@@ -622,14 +1391,7 @@ pub async fn render_project(
             .map(|(i, scene)| {
                 let scene_output = fmt_temp_dir.join(format!("scene-{i:03}.mp4"));
                 let audio = audio_paths[i].clone();
-                // Scene-level music overrides project-level background music
-                let music = scene
-                    .frontmatter
-                    .audio
-                    .as_ref()
-                    .and_then(|a| a.music.as_deref())
-                    .map(|m| crate::scene::resolve_asset_path(m, project_path))
-                    .or_else(|| project_bg_music.clone());
+                let music = scene_music_path(scene, config, project_path);
                 let music_volume = scene
                     .frontmatter
                     .audio
@@ -653,7 +1415,7 @@ pub async fn render_project(
         let browser_ref = &browser;
         let registry_ref = &registry;
         let theme_ref = &config.theme;
-        let platform_ref = &platform;
+        let scene_platforms_ref = &scene_platforms;
         let durations_ref = &effective_durations;
         let prep_ref = &scene_prep;
         let scenes_ref = &fmt_scenes;
@@ -663,24 +1425,12 @@ pub async fn render_project(
         let voice_config_ref = &config.voice;
         let project_path_ref = project_path;
         let cache_dir_ref = &cache_dir;
+        let ducking_ref = &music_ducking;
+        let captions_ref = &scene_captions;
+        let concat_method_ref = config.video.concat_method.as_str();
+        let capture_retry = browser::CaptureRetry::from_config(&config.render);
+        let output_fps = config.video.output_fps;
 
-        // Compute scene content hashes for incremental rendering
-        let scene_hashes: Vec<String> = fmt_scenes
-            .iter()
-            .enumerate()
-            .map(|(i, scene)| {
-                scene_content_hash(
-                    scene,
-                    *width,
-                    *height,
-                    fps,
-                    &config.theme,
-                    &config.voice,
-                    effective_durations[i],
-                    fmt_name,
-                )
-            })
-            .collect();
         let scene_hashes_ref = &scene_hashes;
 
         // Check cache hits before rendering
@@ -723,6 +1473,8 @@ pub async fn render_project(
                 let music = &prep_ref[i].2;
                 let music_volume = prep_ref[i].3;
                 let dur = durations_ref[i];
+                let scene_fps = scene.effective_fps(fps);
+                let scene_output_fps = output_fps.unwrap_or(scene_fps);
 
                 // Check if this scene is cached
                 if let Some(ref cached_path) = cache_paths_ref[i] {
@@ -754,8 +1506,9 @@ pub async fn render_project(
                         theme_ref,
                         *width,
                         *height,
-                        fps,
-                        platform_ref,
+                        scene_fps,
+                        scene_output_fps,
+                        &scene_platforms_ref[i],
                         scene_output,
                         audio.as_deref(),
                         music.as_deref(),
@@ -763,6 +1516,9 @@ pub async fn render_project(
                         &sub_durs,
                         audio_delays_ref[i],
                         project_path_ref,
+                        ducking_ref[i].as_ref(),
+                        concat_method_ref,
+                        capture_retry,
                     )
                     .await?
                 } else if scene.is_video_clip() {
@@ -774,7 +1530,9 @@ pub async fn render_project(
                         i + 1, dur, resolved_src.display()
                     );
                     let trim_dur = match scene.frontmatter.duration {
-                        crate::scene::SceneDuration::Fixed(_) => Some(dur),
+                        crate::scene::SceneDuration::Fixed(_)
+                        | crate::scene::SceneDuration::MatchMusic
+                        | crate::scene::SceneDuration::Percent(_) => Some(dur),
                         crate::scene::SceneDuration::Auto => None, // use full clip duration
                     };
                     let source_vol = scene.frontmatter.source_volume.unwrap_or(0.0);
@@ -783,14 +1541,16 @@ pub async fn render_project(
                         scene_output,
                         *width,
                         *height,
-                        fps,
+                        scene_output_fps,
                         trim_dur,
-                        platform_ref,
+                        &scene_platforms_ref[i],
                         audio.as_deref(),
                         music.as_deref(),
                         music_volume,
+                        &encoder::MusicFade::for_scene(scene, dur),
                         audio_delays_ref[i],
                         source_vol,
+                        ducking_ref[i].as_ref(),
                     )?
                 } else {
                     // Normal HTML-rendered scene
@@ -802,8 +1562,9 @@ pub async fn render_project(
                         theme_ref,
                         *width,
                         *height,
-                        fps,
-                        platform_ref,
+                        scene_fps,
+                        scene_output_fps,
+                        &scene_platforms_ref[i],
                         scene_output,
                         audio.as_deref(),
                         music.as_deref(),
@@ -812,7 +1573,9 @@ pub async fn render_project(
                         audio_delays_ref[i],
                         content_paddings_ref[i],
                         Some(project_path_ref),
-                        use_gpu,
+                        ducking_ref[i].as_ref(),
+                        captions_ref[i].as_ref(),
+                        capture_retry,
                     )
                     .await?
                 };
@@ -851,7 +1614,8 @@ pub async fn render_project(
                     scene_name,
                 );
             } else {
-                let total_frames = Scene::total_frames_for_duration(dur, fps);
+                let total_frames =
+                    Scene::total_frames_for_duration(dur, scenes[i].effective_fps(fps));
                 eprintln!(
                     "  Scene {} ({}): rendered \u{2713} ({:.1}s, {} frames, {:.1}s)",
                     i + 1,
@@ -885,7 +1649,7 @@ pub async fn render_project(
                     *width,
                     *height,
                     actual_dur,
-                    &platform,
+                    &scene_platforms[i],
                 )
                 .await?;
             }
@@ -894,13 +1658,12 @@ pub async fn render_project(
         // Close browser for this format
         drop(browser);
         handler_handle.abort();
+        let capture_secs = capture_start.elapsed().as_secs_f64();
+        let encode_start = Instant::now();
 
-        // Output filename: slug-format.mp4 (or just slug.mp4 if single format)
-        let output_path = if total_formats == 1 && *fmt_name == "default" {
-            output_dir.join(format!("{project_slug}.mp4"))
-        } else {
-            output_dir.join(format!("{project_slug}-{fmt_name}.mp4"))
-        };
+        if let Some(dir) = output_path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
 
         // Debug: save intermediate scene files for inspection
         if std::env::var("VIDGEN_DEBUG").is_ok() {
@@ -954,14 +1717,73 @@ pub async fn render_project(
                 );
             }
         }
+        // Black pause between scenes (video.scene_gap_secs), silent when the scenes have audio
+        let scene_gap = if config.video.scene_gap_secs > 0.0 && scene_files.len() > 1 {
+            let with_audio = scene_files.iter().any(|f| encoder::has_audio_stream(f));
+            Some(encoder::create_scene_gap(
+                &fmt_temp_dir,
+                config.video.scene_gap_secs,
+                *width,
+                *height,
+                config.video.output_fps.unwrap_or(fps),
+                with_audio,
+                &platform,
+            )?)
+        } else {
+            None
+        };
         encoder::concat_scenes_with_transitions(
             &scene_files,
             &actual_durations,
             &transitions,
             &output_path,
             &platform,
+            config.video.output_fps.unwrap_or(fps),
+            &config.video.concat_method,
+            scene_gap.as_ref(),
         )?;
 
+        // A leading transition_in on the first scene fades the video in from black
+        if let Some(intro) =
+            encoder::resolve_intro_transition(&fmt_scenes[0], actual_durations[0], &config.video)
+        {
+            eprintln!(
+                "{} Fading in from black ({:.1}s)...",
+                "render:".cyan().bold(),
+                intro.duration
+            );
+            encoder::apply_fade_from_black(&output_path, intro.duration, &platform)?;
+        }
+
+        // One continuous soundtrack under the joined video; scenes with their own music keep it
+        if let Some(ref music) = config.audio.music {
+            let gap = config.video.scene_gap_secs;
+            let starts = encoder::scene_start_times(&actual_durations, &transitions, gap);
+            let total = encoder::joined_duration(&actual_durations, &transitions, gap);
+            let muted: Vec<(f64, f64)> = fmt_scenes
+                .iter()
+                .enumerate()
+                .filter(|(_, scene)| {
+                    scene.frontmatter.audio.as_ref().is_some_and(|a| a.music.is_some())
+                })
+                .map(|(i, _)| (starts[i], starts[i] + actual_durations[i]))
+                .collect();
+            eprintln!(
+                "{} Mixing project music over {:.1}s...",
+                "render:".cyan().bold(),
+                total
+            );
+            encoder::apply_project_music(
+                &output_path,
+                &crate::scene::resolve_asset_path(music, project_path),
+                total,
+                config.audio.music_volume,
+                config.audio.fade_out,
+                &muted,
+                &platform,
+            )?;
+        }
+
         // Apply audio fades if project-level background music has fade config
         if let Some(ref bg) = config.audio.background {
             let total_video_dur: f64 = effective_durations.iter().sum();
@@ -976,6 +1798,24 @@ pub async fn render_project(
             }
         }
 
+        let loudness = &config.output.audio;
+        if loudness.normalize {
+            eprintln!(
+                "{} Normalizing loudness to {} LUFS...",
+                "render:".cyan().bold(),
+                loudness.target_lufs
+            );
+            if !encoder::normalize_loudness(&output_path, loudness.target_lufs, &platform)? {
+                eprintln!("  No audio stream, skipping loudness normalization");
+            }
+        }
+
+        // Title/author/... tags (output.metadata); later conversions carry them over
+        encoder::apply_metadata(
+            &output_path,
+            &config.output.metadata.tags(&config.project.name),
+        )?;
+
         eprintln!(
             "{} Output: {}",
             "done:".green().bold(),
@@ -983,70 +1823,236 @@ pub async fn render_project(
         );
 
         // Generate subtitles if enabled
-        let subtitle_path = if config.output.subtitles.enabled {
-            let mut all_words = Vec::new();
-            let mut scene_offset = 0.0_f64;
+        let subs = &config.output.subtitles;
+        let subtitle_path = if subs.enabled || subs.karaoke_vtt {
+            let scene_gaps =
+                encoder::scene_gaps(&transitions, scenes.len(), config.video.scene_gap_secs);
 
-            for (i, scene) in scenes.iter().enumerate() {
-                let script = scene.script.trim();
-                if !script.is_empty() && tts_durations[i].is_some() {
-                    // Use TTS duration (voice only) instead of effective duration (which includes padding)
-                    let words = tts::timestamps::estimate_word_timestamps(
-                        script,
-                        tts_durations[i].unwrap(),
-                    );
-                    for mut w in words {
-                        // Shift by scene offset + audio delay (padding_before)
-                        w.start_secs += scene_offset + audio_delays[i];
-                        w.end_secs += scene_offset + audio_delays[i];
-                        all_words.push(w);
-                    }
-                }
-                scene_offset += effective_durations[i];
+            // Scene-local timing: shifted by the audio delay (padding_before) only
+            let scene_words: Vec<Vec<tts::timestamps::WordTimestamp>> = scenes
+                .iter()
+                .enumerate()
+                .map(|(i, scene)| {
+                    narration_words(&scene.script, tts_words[i].as_ref(), tts_durations[i])
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|mut w| {
+                            w.start_secs += audio_delays[i];
+                            w.end_secs += audio_delays[i];
+                            w
+                        })
+                        .collect()
+                })
+                .collect();
+            let mut scene_offsets = Vec::with_capacity(scenes.len());
+            let mut scene_offset = 0.0_f64;
+            for i in 0..scenes.len() {
+                scene_offsets.push(scene_offset);
+                scene_offset += effective_durations[i] + scene_gaps[i];
+            }
+            let all_words = combined_words(&scene_words, &scene_offsets);
+
+            if subs.karaoke_vtt && !all_words.is_empty() {
+                let vtt_path = output_path.with_extension("vtt");
+                std::fs::write(
+                    &vtt_path,
+                    subtitle::to_karaoke_vtt(&all_words, subs.max_words_per_line),
+                )?;
+                eprintln!(
+                    "{} Karaoke subtitles: {}",
+                    "done:".green().bold(),
+                    vtt_path.display()
+                );
             }
 
-            if !all_words.is_empty() {
-                let entries = subtitle::group_into_subtitles(
-                    &all_words,
-                    config.output.subtitles.max_words_per_line,
+            let srt_path =
+                write_srt_sidecar(subs, external_srt.as_deref(), &all_words, &output_path)?;
+            if let Some(ref srt_path) = srt_path {
+                let source = if external_srt.is_some() {
+                    " (from source_file)"
+                } else {
+                    ""
+                };
+                eprintln!(
+                    "{} Subtitles: {}{}",
+                    "done:".green().bold(),
+                    srt_path.display(),
+                    source
                 );
-                let srt_content = subtitle::to_srt(&entries);
-                let srt_path = output_path.with_extension("srt");
-                std::fs::write(&srt_path, &srt_content)?;
+            }
+            if subs.per_scene && subs.enabled {
+                let dir = per_scene_subtitle_dir(&output_path);
+                let written = write_scene_srts(&scene_words, subs.max_words_per_line, &dir)?;
                 eprintln!(
-                    "{} Subtitles: {}",
+                    "{} Per-scene subtitles: {} file(s) in {}",
                     "done:".green().bold(),
-                    srt_path.display()
+                    written.len(),
+                    dir.display()
                 );
-                Some(srt_path)
-            } else {
-                None
             }
+            srt_path
         } else {
             None
         };
 
         // Burn subtitles into video if requested
-        if config.output.subtitles.burn_in {
+        let mut captioned_path = None;
+        if subs.burn_in {
             if let Some(ref srt_path) = subtitle_path {
                 eprintln!(
                     "{} Burning subtitles into video...",
                     "render:".cyan().bold()
                 );
-                encoder::burn_in_subtitles(&output_path, srt_path)?;
+                let (style, fonts_dir) = burn_in_style(subs, project_path);
+                let burned = encoder::burn_in_subtitles(
+                    &output_path,
+                    srt_path,
+                    subs.keep_clean,
+                    &style,
+                    fonts_dir.as_deref(),
+                )?;
                 eprintln!(
                     "{} Subtitles burned in: {}",
                     "done:".green().bold(),
-                    output_path.display()
+                    burned.display()
                 );
+                if subs.keep_clean {
+                    captioned_path = Some(burned);
+                }
+            }
+        }
+
+        // Final pass: add extra narration tracks (other languages) as audio streams
+        if !config.audio.tracks.is_empty() {
+            let tracks: Vec<encoder::AudioTrack> = config
+                .audio
+                .tracks
+                .iter()
+                .map(|t| encoder::AudioTrack {
+                    path: crate::scene::resolve_asset_path(&t.file, project_path),
+                    language: t.language.clone(),
+                    title: t.title.clone(),
+                })
+                .collect();
+            let primary_language = config.voice.language.as_deref();
+            for video in std::iter::once(&output_path).chain(captioned_path.as_ref()) {
+                encoder::mux_audio_tracks(video, primary_language, &tracks)?;
             }
+            eprintln!(
+                "{} Added {} audio track(s): {}",
+                "done:".green().bold(),
+                tracks.len(),
+                tracks.iter().map(|t| t.language.as_str()).collect::<Vec<_>>().join(", ")
+            );
         }
 
+        // Looped copy for social (output.loop), made from the MP4 before conversion
+        let loop_path = match config.output.loop_count {
+            Some(count) => {
+                let duration = encoder::probe_video_duration(&output_path)?;
+                let mode = encoder::LoopMode::resolve(config.output.loop_crossfade, duration);
+                if config.output.loop_crossfade.is_some() && mode == encoder::LoopMode::Copy {
+                    eprintln!(
+                        "{} output.loop_crossfade must be shorter than half the video ({:.2}s), looping without it",
+                        "warning:".yellow().bold(),
+                        duration
+                    );
+                }
+                eprintln!("{} Looping {} times...", "render:".cyan().bold(), count);
+                Some(encoder::loop_video(&output_path, count, mode, &platform)?)
+            }
+            None => None,
+        };
+
+        // Convert to the configured container (GIF, WebM, ProRes MOV)
+        let container = config.output.container;
+        let (output_path, captioned_path, loop_path) = if final_path == output_path {
+            (output_path, captioned_path, loop_path)
+        } else {
+            eprintln!(
+                "{} Converting to {}...",
+                "render:".cyan().bold(),
+                container
+            );
+            encoder::convert_container(&output_path, &final_path, container, &platform)?;
+            std::fs::remove_file(&output_path)?;
+            let captioned_path = match captioned_path {
+                Some(captioned) => {
+                    let converted = captioned.with_extension(container.extension());
+                    encoder::convert_container(&captioned, &converted, container, &platform)?;
+                    std::fs::remove_file(&captioned)?;
+                    Some(converted)
+                }
+                None => None,
+            };
+            let loop_path = match loop_path {
+                Some(looped) => {
+                    let converted = encoder::loop_path(&final_path);
+                    encoder::convert_container(&looped, &converted, container, &platform)?;
+                    std::fs::remove_file(&looped)?;
+                    Some(converted)
+                }
+                None => None,
+            };
+            eprintln!(
+                "{} Output: {}",
+                "done:".green().bold(),
+                final_path.display()
+            );
+            (final_path, captioned_path, loop_path)
+        };
+        if let Some(ref looped) = loop_path {
+            eprintln!("{} Loop: {}", "done:".green().bold(), looped.display());
+        }
+
+        // Soft subtitle track players can toggle (output.subtitles.embed)
+        if subs.embed {
+            if let Some(ref srt_path) = subtitle_path {
+                let language = subs
+                    .language
+                    .as_deref()
+                    .or(config.voice.language.as_deref())
+                    .unwrap_or("und");
+                encoder::embed_subtitles(&output_path, srt_path, language)?;
+                eprintln!(
+                    "{} Subtitle track ({}) embedded",
+                    "done:".green().bold(),
+                    language
+                );
+            }
+        }
+
+        // Cover image for web players (output.poster_frame)
+        if let Some(frame) = config.output.poster_frame {
+            for video in std::iter::once(&output_path).chain(captioned_path.as_ref()) {
+                encoder::embed_poster_frame(video, frame)?;
+            }
+            eprintln!(
+                "{} Poster frame {} embedded",
+                "done:".green().bold(),
+                frame
+            );
+        }
+
+        if !no_cache {
+            let _ = std::fs::write(&format_stamp, &format_hash);
+        }
+
+        // Checksum of the finished file, after the last pass that rewrites it
+        let sha256 = file_sha256(&output_path)?;
         results.push(FormatOutput {
             format_name: fmt_name.clone(),
             output_path,
             effective_durations: effective_durations.clone(),
             subtitle_path,
+            captioned_path,
+            loop_path,
+            sha256,
+            stats: RenderStats {
+                tts_secs,
+                capture_secs,
+                encode_secs: encode_start.elapsed().as_secs_f64(),
+            },
         });
 
         // Progress: format complete
@@ -1101,6 +2107,76 @@ mod tests {
         // Should not panic — no peer/token, so report is a no-op
         progress.report(1.0, 10.0, "test step").await;
         progress.report(10.0, 10.0, "done").await;
+        assert!(progress.json_event(1.0, 10.0, "test step").is_none());
+    }
+
+    #[test]
+    fn test_file_sha256_matches_recomputed_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let video = dir.path().join("out.mp4");
+        // Larger than one copy buffer, so the streamed hash covers several reads
+        let bytes: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&video, &bytes).unwrap();
+
+        let reported = file_sha256(&video).unwrap();
+        let recomputed = format!("{:x}", Sha256::digest(std::fs::read(&video).unwrap()));
+        assert_eq!(reported, recomputed);
+        assert_eq!(reported.len(), 64);
+
+        std::fs::write(&video, "abc").unwrap();
+        assert_eq!(
+            file_sha256(&video).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert!(file_sha256(&dir.path().join("missing.mp4")).is_err());
+    }
+
+    #[test]
+    fn test_format_output_path_uses_container_extension() {
+        let mut config: ProjectConfig = toml::from_str("[project]\nname = \"My Video\"\n").unwrap();
+        let out = Path::new("output");
+        assert_eq!(
+            format_output_path(&config, "default", 1, out, Path::new(".")),
+            Path::new("output/my-video.mp4")
+        );
+        for (container, expected) in [
+            (crate::config::OutputContainer::Mov, "output/my-video-portrait.mov"),
+            (crate::config::OutputContainer::Webm, "output/my-video-portrait.webm"),
+            (crate::config::OutputContainer::Gif, "output/my-video-portrait.gif"),
+        ] {
+            config.output.container = container;
+            assert_eq!(
+                format_output_path(&config, "portrait", 2, out, Path::new(".")),
+                Path::new(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn test_render_progress_json_events() {
+        let progress = RenderProgress::json();
+        let line = progress.json_event(2.0, 10.0, "TTS synthesis complete").unwrap();
+        let event: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(event["progress"], 2.0);
+        assert_eq!(event["total"], 10.0);
+        assert_eq!(event["message"], "TTS synthesis complete");
+        assert!(!line.contains('\n'));
+    }
+
+    #[test]
+    fn test_render_progress_json_monotonic() {
+        let progress = RenderProgress::json();
+        // Parallel scenes can complete out of order (scene 3 before scene 2)
+        let values: Vec<f64> = [2.0, 5.0, 4.0, 6.0, 10.0]
+            .iter()
+            .map(|&p| {
+                let line = progress.json_event(p, 10.0, "step").unwrap();
+                let event: serde_json::Value = serde_json::from_str(&line).unwrap();
+                event["progress"].as_f64().unwrap()
+            })
+            .collect();
+        assert_eq!(values, [2.0, 5.0, 5.0, 6.0, 10.0]);
+        assert!(values.windows(2).all(|w| w[0] <= w[1]));
     }
 
     #[test]
@@ -1115,6 +2191,7 @@ mod tests {
                 height: 1080,
                 label: Some("YouTube".into()),
                 platform: None,
+                output_dir: None,
             },
         );
         formats.insert(
@@ -1124,6 +2201,7 @@ mod tests {
                 height: 1920,
                 label: Some("Reels".into()),
                 platform: Some("instagram-reels".into()),
+                output_dir: None,
             },
         );
         let config = ProjectConfig {
@@ -1139,6 +2217,9 @@ mod tests {
             theme: ThemeConfig::default(),
             output: OutputConfig::default(),
             audio: crate::config::AudioConfig::default(),
+            render: crate::config::RenderConfig::default(),
+            quality: crate::config::QualityConfig::default(),
+            variables: Default::default(),
         };
         let result = resolve_formats(&config, None);
         assert_eq!(result.len(), 2);
@@ -1166,6 +2247,9 @@ mod tests {
             theme: ThemeConfig::default(),
             output: OutputConfig::default(),
             audio: crate::config::AudioConfig::default(),
+            render: crate::config::RenderConfig::default(),
+            quality: crate::config::QualityConfig::default(),
+            variables: Default::default(),
         };
         let result = resolve_formats(&config, None);
         assert_eq!(result.len(), 1);
@@ -1186,6 +2270,7 @@ mod tests {
                 height: 1080,
                 label: None,
                 platform: None,
+                output_dir: None,
             },
         );
         formats.insert(
@@ -1195,6 +2280,7 @@ mod tests {
                 height: 1920,
                 label: None,
                 platform: None,
+                output_dir: None,
             },
         );
         formats.insert(
@@ -1204,6 +2290,7 @@ mod tests {
                 height: 1080,
                 label: None,
                 platform: None,
+                output_dir: None,
             },
         );
         let config = ProjectConfig {
@@ -1219,6 +2306,9 @@ mod tests {
             theme: ThemeConfig::default(),
             output: OutputConfig::default(),
             audio: crate::config::AudioConfig::default(),
+            render: crate::config::RenderConfig::default(),
+            quality: crate::config::QualityConfig::default(),
+            variables: Default::default(),
         };
         let filter = vec!["portrait".into(), "square".into()];
         let result = resolve_formats(&config, Some(&filter));
@@ -1226,4 +2316,592 @@ mod tests {
         assert_eq!(result[0].0, "portrait");
         assert_eq!(result[1].0, "square");
     }
+
+    #[test]
+    fn test_format_output_dir_override() {
+        use crate::config::*;
+        use std::collections::BTreeMap;
+        let mut formats = BTreeMap::new();
+        formats.insert(
+            "landscape".into(),
+            FormatConfig {
+                width: 1920,
+                height: 1080,
+                label: None,
+                platform: None,
+                output_dir: None,
+            },
+        );
+        formats.insert(
+            "portrait".into(),
+            FormatConfig {
+                width: 1080,
+                height: 1920,
+                label: None,
+                platform: None,
+                output_dir: Some("./uploads/reels".into()),
+            },
+        );
+        let config = ProjectConfig {
+            project: ProjectInfo {
+                name: "Test".into(),
+                version: "1.0.0".into(),
+            },
+            video: VideoConfig {
+                formats: Some(formats),
+                ..Default::default()
+            },
+            voice: VoiceConfig::default(),
+            theme: ThemeConfig::default(),
+            output: OutputConfig::default(),
+            audio: crate::config::AudioConfig::default(),
+            render: crate::config::RenderConfig::default(),
+            quality: crate::config::QualityConfig::default(),
+            variables: Default::default(),
+        };
+        let project = Path::new("/projects/demo");
+        let default_dir = project.join("output");
+
+        assert_eq!(
+            format_output_dir(&config, "portrait", &default_dir, project),
+            project.join("uploads/reels")
+        );
+        assert_eq!(
+            format_output_dir(&config, "landscape", &default_dir, project),
+            default_dir
+        );
+        assert_eq!(
+            format_output_dir(&config, "default", &default_dir, project),
+            default_dir
+        );
+    }
+
+    /// Project config with the given formats and `[render]` size limits.
+    fn sized_config(
+        formats: &[(&str, u32, u32)],
+        max_dimension: u32,
+        auto_downscale: bool,
+    ) -> ProjectConfig {
+        use crate::config::*;
+        let formats = formats
+            .iter()
+            .map(|&(name, width, height)| {
+                let fc = FormatConfig {
+                    width,
+                    height,
+                    label: None,
+                    platform: None,
+                    output_dir: None,
+                };
+                (name.to_string(), fc)
+            })
+            .collect();
+        ProjectConfig {
+            project: ProjectInfo {
+                name: "Test".into(),
+                version: "1.0.0".into(),
+            },
+            video: VideoConfig {
+                formats: Some(formats),
+                ..Default::default()
+            },
+            voice: VoiceConfig::default(),
+            theme: ThemeConfig::default(),
+            output: OutputConfig::default(),
+            audio: AudioConfig::default(),
+            render: RenderConfig {
+                max_dimension,
+                auto_downscale,
+                ..RenderConfig::default()
+            },
+            quality: QualityConfig::default(),
+            variables: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_max_dimension_in_range_unchanged() {
+        let config = sized_config(
+            &[("landscape", 1920, 1080), ("portrait", 1080, 1920)],
+            7680,
+            false,
+        );
+        config.validate().unwrap();
+        let sizes: Vec<(u32, u32)> = resolve_formats(&config, None)
+            .into_iter()
+            .map(|(_, w, h, _)| (w, h))
+            .collect();
+        assert_eq!(sizes, [(1920, 1080), (1080, 1920)]);
+    }
+
+    #[test]
+    fn test_max_dimension_over_cap_errors() {
+        let config = sized_config(&[("huge", 7680, 4320)], 3840, false);
+        let err = config.validate().unwrap_err();
+        assert!(matches!(
+            err,
+            VidgenError::DimensionTooLarge {
+                width: 7680,
+                height: 4320,
+                max: 3840,
+                ..
+            }
+        ));
+        assert!(err.hint().unwrap().contains("auto_downscale"));
+    }
+
+    #[test]
+    fn test_max_dimension_auto_downscale_keeps_aspect() {
+        // Over the 7680 default too: the cap is render.max_dimension alone
+        let config = sized_config(&[("tall", 1000, 3001), ("uhd", 8000, 4500)], 1920, true);
+        config.validate().unwrap();
+        let result = resolve_formats(&config, None);
+        // Odd results are rounded down to even for yuv420p
+        assert_eq!((result[0].1, result[0].2), (638, 1920));
+        assert_eq!((result[1].1, result[1].2), (1920, 1080));
+    }
+
+    #[test]
+    fn test_scene_quality_override_sets_encoder_crf() {
+        let path = Path::new("scene.md");
+        let high = crate::scene::parse_scene(
+            "---\ntemplate: title-card\nquality: high\n---\n",
+            path,
+        )
+        .unwrap();
+        let plain = crate::scene::parse_scene("---\ntemplate: title-card\n---\n", path).unwrap();
+        let project = QualityPreset::from_name("standard");
+        let custom = QualityConfig::default();
+
+        let enc = scene_encoding(&high, &project, &custom, None);
+        assert_eq!((enc.crf, enc.preset), (18, "slow"));
+        assert_eq!(scene_encoding(&plain, &project, &custom, None).crf, 23);
+
+        // The override keeps the platform's CRF offset logic
+        let enc = scene_encoding(&high, &project, &custom, Some("youtube-hd"));
+        let base = resolve_encoding(&project, Some("youtube-hd"));
+        assert_eq!(enc.crf, base.crf - 5);
+    }
+
+    #[test]
+    fn test_scene_quality_override_uses_custom_presets() {
+        let scene = crate::scene::parse_scene(
+            "---\ntemplate: title-card\nquality: crisp\n---\n",
+            Path::new("scene.md"),
+        )
+        .unwrap();
+        let mut custom = QualityConfig::default();
+        custom.presets.insert(
+            "crisp".into(),
+            crate::config::CustomQuality {
+                crf: 12,
+                preset: "slower".into(),
+            },
+        );
+        let enc = scene_encoding(&scene, &QualityPreset::from_name("draft"), &custom, None);
+        assert_eq!((enc.crf, enc.preset), (12, "slower"));
+    }
+    #[test]
+    fn test_unknown_format_override_keys_reported() {
+        use crate::config::FormatConfig;
+        let scene = crate::scene::parse_scene(
+            "---\ntemplate: title-card\nformat_overrides:\n  potrait:\n    props:\n      title: Hi\n  landscape:\n    props:\n      title: Hello\n---\n",
+            Path::new("scene.md"),
+        )
+        .unwrap();
+        let mut config = ProjectConfig {
+            project: crate::config::ProjectInfo {
+                name: "Test".into(),
+                version: "1.0.0".into(),
+            },
+            video: crate::config::VideoConfig::default(),
+            voice: crate::config::VoiceConfig::default(),
+            theme: crate::config::ThemeConfig::default(),
+            output: crate::config::OutputConfig::default(),
+            audio: crate::config::AudioConfig::default(),
+            render: crate::config::RenderConfig::default(),
+            quality: crate::config::QualityConfig::default(),
+            variables: Default::default(),
+        };
+        assert_eq!(
+            unknown_format_override_keys(&scene, &config),
+            vec!["landscape", "potrait"]
+        );
+
+        let mut formats = std::collections::BTreeMap::new();
+        for (name, width, height) in [("landscape", 1920, 1080), ("portrait", 1080, 1920)] {
+            formats.insert(
+                name.to_string(),
+                FormatConfig {
+                    width,
+                    height,
+                    label: None,
+                    platform: None,
+                    output_dir: None,
+                },
+            );
+        }
+        config.video.formats = Some(formats);
+        assert_eq!(
+            unknown_format_override_keys(&scene, &config),
+            vec!["potrait"]
+        );
+    }
+
+    #[test]
+    fn test_scene_voice_speed_is_forwarded_to_synthesize() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config: ProjectConfig = toml::from_str("[project]\nname = \"Speed\"\n").unwrap();
+        config.voice.speed = 1.5;
+        // 30 words: 12s at speed 1.0 with the dry-TTS estimate
+        let script = ["word"; 30].join(" ");
+        let scenes: Vec<Scene> = ["", "voice:\n  speed: 0.75\n"]
+            .iter()
+            .map(|voice| {
+                let content = format!("---\ntemplate: title-card\n{voice}---\n{script}");
+                crate::scene::parse_scene(&content, Path::new("scene.md")).unwrap()
+            })
+            .collect();
+
+        let narration = synthesize_narration(
+            &scenes,
+            &config,
+            dir.path(),
+            dir.path(),
+            true,
+            false,
+            "test",
+        );
+        // The scene without an override uses the global voice.speed
+        assert!((narration.durations[0].unwrap() - 8.0).abs() < 1e-9);
+        assert!((narration.durations[1].unwrap() - 16.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_recorded_narration_replaces_tts() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("assets/audio")).unwrap();
+        let wav = dir.path().join("assets/audio/scene-01.wav");
+        tts::silent::write_silent_wav(&wav, 3.5).unwrap();
+        // 16-bit mono 44.1kHz PCM after a 44-byte header
+        let probe = |p: &Path| Ok((std::fs::metadata(p)?.len() - 44) as f64 / 88_200.0);
+
+        let recorded = crate::scene::parse_scene(
+            "---\ntemplate: title-card\nduration: auto\naudio:\n  voice: \"@assets/audio/scene-01.wav\"\n---\nThis script is not synthesized.",
+            Path::new("scene.md"),
+        )
+        .unwrap();
+        let result = recorded_narration(&recorded, dir.path(), probe)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.audio_path, wav);
+        assert!((result.duration_secs - 3.5).abs() < 1e-3);
+        assert!(!result.cached);
+
+        let tts_scene = crate::scene::parse_scene(
+            "---\ntemplate: title-card\n---\nNarrated by TTS.",
+            Path::new("b.md"),
+        )
+        .unwrap();
+        assert!(
+            recorded_narration(&tts_scene, dir.path(), |_| -> VidgenResult<f64> {
+                panic!("no recording to probe")
+            })
+            .is_none()
+        );
+
+        let trimmed = crate::scene::parse_scene(
+            "---\ntemplate: title-card\nduration: auto\naudio:\n  voice: \"@assets/audio/scene-01.wav\"\n  start_at: 1.0\n  end_at: 2.5\n---\n",
+            Path::new("t.md"),
+        )
+        .unwrap();
+        let result = recorded_narration(&trimmed, dir.path(), probe)
+            .unwrap()
+            .unwrap();
+        assert!((result.duration_secs - 1.5).abs() < 1e-3);
+        let config = crate::config::VoiceConfig::default();
+        let resolved = trimmed.frontmatter.duration.resolve(
+            Some(result.duration_secs),
+            config.padding_before,
+            config.padding_after,
+            5.0,
+            None,
+        );
+        assert!((resolved - (1.5 + config.padding_before + config.padding_after)).abs() < 1e-3);
+
+        // end_at past the end of the file is clamped; an empty window is an error
+        let past_end = crate::scene::parse_scene(
+            "---\ntemplate: title-card\naudio:\n  voice: \"@assets/audio/scene-01.wav\"\n  start_at: 3.0\n  end_at: 60\n---\n",
+            Path::new("p.md"),
+        )
+        .unwrap();
+        let result = recorded_narration(&past_end, dir.path(), probe)
+            .unwrap()
+            .unwrap();
+        assert!((result.duration_secs - 0.5).abs() < 1e-3);
+        let empty = crate::scene::parse_scene(
+            "---\ntemplate: title-card\naudio:\n  voice: \"@assets/audio/scene-01.wav\"\n  start_at: 4.0\n---\n",
+            Path::new("e.md"),
+        )
+        .unwrap();
+        assert!(recorded_narration(&empty, dir.path(), probe)
+            .unwrap()
+            .is_err());
+
+        let missing = crate::scene::parse_scene(
+            "---\ntemplate: title-card\naudio:\n  voice: \"@assets/audio/missing.wav\"\n---\n",
+            Path::new("c.md"),
+        )
+        .unwrap();
+        let err = recorded_narration(&missing, dir.path(), probe)
+            .unwrap()
+            .unwrap_err();
+        assert!(err.to_string().contains("missing.wav"));
+    }
+
+    #[test]
+    fn test_format_hash_only_changes_for_overridden_format() {
+        use crate::config::*;
+        let mut formats = std::collections::BTreeMap::new();
+        for (name, width, height) in [("landscape", 1920, 1080), ("portrait", 1080, 1920)] {
+            formats.insert(
+                name.to_string(),
+                FormatConfig {
+                    width,
+                    height,
+                    label: None,
+                    platform: None,
+                    output_dir: None,
+                },
+            );
+        }
+        let config = ProjectConfig {
+            project: ProjectInfo {
+                name: "Test".into(),
+                version: "1.0.0".into(),
+            },
+            video: VideoConfig {
+                formats: Some(formats),
+                ..Default::default()
+            },
+            voice: VoiceConfig::default(),
+            theme: ThemeConfig::default(),
+            output: OutputConfig::default(),
+            audio: AudioConfig::default(),
+            render: RenderConfig::default(),
+            quality: QualityConfig::default(),
+            variables: Default::default(),
+        };
+        let quality = QualityPreset::from_name("standard");
+        let format_hashes = |portrait_title: &str| -> Vec<(String, String)> {
+            let scenes = [
+                crate::scene::parse_scene(
+                    "---\ntemplate: title-card\nduration: 3\n---\nIntro.",
+                    Path::new("01-intro.md"),
+                )
+                .unwrap(),
+                crate::scene::parse_scene(
+                    &format!(
+                        "---\ntemplate: title-card\nduration: 4\nformat_overrides:\n  portrait:\n    props:\n      title: {portrait_title}\n---\nOutro."
+                    ),
+                    Path::new("02-outro.md"),
+                )
+                .unwrap(),
+            ];
+            resolve_formats(&config, None)
+                .into_iter()
+                .map(|(name, width, height, _)| {
+                    let fmt_scenes: Vec<Scene> = scenes
+                        .iter()
+                        .map(|s| apply_format_overrides(s, &name))
+                        .collect();
+                    let hashes: Vec<String> = fmt_scenes
+                        .iter()
+                        .map(|s| {
+                            let duration = s.frontmatter.duration.resolve(None, 0.0, 0.0, 5.0, None);
+                            scene_content_hash(
+                                s,
+                                width,
+                                height,
+                                30,
+                                30,
+                                &config.theme,
+                                &config.voice,
+                                duration,
+                                &name,
+                                None,
+                                None,
+                                None,
+                            )
+                        })
+                        .collect();
+                    let platforms: Vec<PlatformPreset> = fmt_scenes
+                        .iter()
+                        .map(|s| scene_encoding(s, &quality, &config.quality, None))
+                        .collect();
+                    let output =
+                        format_output_path(&config, &name, 2, Path::new("output"), Path::new("."));
+                    let hash = format_content_hash(
+                        &hashes, &platforms, &[None], &config, None, None, &output,
+                    );
+                    (name, hash)
+                })
+                .collect()
+        };
+
+        let before = format_hashes("Short");
+        let after = format_hashes("Tall");
+        assert_eq!(before[0].0, "landscape");
+        assert_eq!(
+            before[0].1, after[0].1,
+            "landscape output should stay cached"
+        );
+        assert_eq!(before[1].0, "portrait");
+        assert_ne!(before[1].1, after[1].1, "portrait output should re-render");
+    }
+
+    #[test]
+    fn test_scene_hash_changes_with_emoji_font() {
+        let scene = crate::scene::parse_scene(
+            "---\ntemplate: title-card\nduration: 3\n---\nHi \u{1F44B}",
+            Path::new("01-intro.md"),
+        )
+        .unwrap();
+        let hash = |font: Option<&Path>| {
+            scene_content_hash(
+                &scene,
+                1920,
+                1080,
+                30,
+                30,
+                &ThemeConfig::default(),
+                &VoiceConfig::default(),
+                3.0,
+                "landscape",
+                None,
+                None,
+                font,
+            )
+        };
+        let noto = Path::new("assets/fonts/NotoColorEmoji.ttf");
+        assert_ne!(hash(None), hash(Some(noto)));
+        assert_ne!(
+            hash(Some(noto)),
+            hash(Some(Path::new("assets/fonts/NotoColorEmoji-Regular.ttf")))
+        );
+    }
+
+    #[test]
+    fn test_reusable_format_output_needs_every_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let video = dir.path().join("demo.mp4");
+        let mut subs = crate::config::SubtitleConfig::default();
+        assert!(reusable_format_output(&video, &subs).is_none());
+
+        std::fs::write(&video, b"mp4").unwrap();
+        assert_eq!(reusable_format_output(&video, &subs), Some((None, None)));
+
+        subs.enabled = true;
+        assert!(reusable_format_output(&video, &subs).is_none());
+        std::fs::write(dir.path().join("demo.srt"), b"1").unwrap();
+        assert_eq!(
+            reusable_format_output(&video, &subs),
+            Some((Some(dir.path().join("demo.srt")), None))
+        );
+    }
+
+    #[test]
+    fn test_source_file_replaces_generated_subtitles() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        std::fs::create_dir_all(project.join("assets")).unwrap();
+        let edited = "1\n00:00:00,000 --> 00:00:02,000\nProfessionally edited line\n";
+        std::fs::write(project.join("assets/final.srt"), edited).unwrap();
+
+        let mut subs = SubtitleConfig {
+            enabled: true,
+            burn_in: true,
+            ..Default::default()
+        };
+        assert_eq!(external_subtitles(&subs, project).unwrap(), None);
+        subs.source_file = Some("@assets/final.srt".into());
+        let external = external_subtitles(&subs, project).unwrap();
+        assert_eq!(external.as_deref(), Some(edited));
+
+        // The sidecar (and so burn-in) gets the provided file, not the narration words
+        let words = crate::tts::timestamps::estimate_word_timestamps("Generated from TTS", 1.0);
+        let video = project.join("demo.mp4");
+        let srt = write_srt_sidecar(&subs, external.as_deref(), &words, &video)
+            .unwrap()
+            .unwrap();
+        assert_eq!(srt, project.join("demo.srt"));
+        assert_eq!(std::fs::read_to_string(&srt).unwrap(), edited);
+
+        // Without a source file the words are grouped into generated entries
+        write_srt_sidecar(&subs, None, &words, &video).unwrap();
+        let generated = std::fs::read_to_string(&srt).unwrap();
+        assert!(generated.contains("Generated from TTS"));
+
+        subs.source_file = Some("@assets/missing.srt".into());
+        let err = external_subtitles(&subs, project).unwrap_err().to_string();
+        assert!(err.contains("missing.srt"), "{err}");
+    }
+
+    #[test]
+    fn test_per_scene_srts_start_at_zero_and_combined_uses_offsets() {
+        use crate::tts::timestamps::estimate_word_timestamps;
+        let dir = tempfile::tempdir().unwrap();
+        let scene_words = vec![
+            estimate_word_timestamps("First scene words", 3.0),
+            Vec::new(),
+            estimate_word_timestamps("Third scene words", 3.0),
+        ];
+
+        let video = dir.path().join("demo.mp4");
+        let srt_dir = per_scene_subtitle_dir(&video);
+        assert_eq!(srt_dir, dir.path().join("subtitles"));
+        let written = write_scene_srts(&scene_words, 6, &srt_dir).unwrap();
+        // Scenes without narration get no file; numbering follows the scene order
+        assert_eq!(written, [srt_dir.join("01.srt"), srt_dir.join("03.srt")]);
+        for path in &written {
+            let srt = std::fs::read_to_string(path).unwrap();
+            assert!(srt.starts_with("1\n00:00:00,000 --> "), "{srt}");
+        }
+
+        let combined = combined_words(&scene_words, &[0.0, 4.0, 6.5]);
+        assert_eq!(combined.len(), 6);
+        assert_eq!(combined[0].start_secs, 0.0);
+        assert_eq!(combined[3].start_secs, 6.5);
+        let srt = subtitle::to_srt(&subtitle::group_into_subtitles(&combined, 3));
+        assert!(srt.contains("2\n00:00:06,500 --> "), "{srt}");
+    }
+
+    #[test]
+    fn test_burn_in_style_uses_project_font_file() {
+        let project = Path::new("/projects/demo");
+        let mut subs = SubtitleConfig {
+            burn_in: true,
+            font_file: Some("@assets/fonts/Inter-Bold.ttf".into()),
+            ..Default::default()
+        };
+        let (style, fonts_dir) = burn_in_style(&subs, project);
+        assert!(style.contains("FontName=Inter-Bold"));
+        assert_eq!(fonts_dir, Some(project.join("assets/fonts")));
+
+        let filter =
+            encoder::subtitle_filter(Path::new("/out/demo.srt"), &style, fonts_dir.as_deref());
+        assert!(filter.contains(":fontsdir='/projects/demo/assets/fonts':"));
+        assert!(filter.contains("FontName=Inter-Bold"));
+
+        // An explicit FontName in `style` overrides the file stem
+        subs.style = Some("FontName=Inter".into());
+        let (style, _) = burn_in_style(&subs, project);
+        assert!(style.contains("FontName=Inter,") || style.ends_with("FontName=Inter"));
+        assert!(!style.contains("Inter-Bold"));
+
+        // Without a font file, no fontsdir is passed
+        let (style, fonts_dir) = burn_in_style(&SubtitleConfig::default(), project);
+        assert!(fonts_dir.is_none());
+        assert!(!encoder::subtitle_filter(Path::new("a.srt"), &style, None).contains("fontsdir"));
+    }
 }
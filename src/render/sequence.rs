@@ -22,6 +22,7 @@ pub async fn render_sequence_scene(
     width: u32,
     height: u32,
     fps: u32,
+    output_fps: u32,
     platform: &PlatformPreset,
     output_path: &Path,
     audio_path: Option<&Path>,
@@ -30,6 +31,9 @@ pub async fn render_sequence_scene(
     sub_durations: &[f64],
     audio_delay_secs: f64,
     project_path: &Path,
+    ducking: Option<&encoder::MusicDucking>,
+    concat_method: &str,
+    retry: browser::CaptureRetry,
 ) -> VidgenResult<PathBuf> {
     let sub_scenes = scene.frontmatter.sub_scenes.as_ref().unwrap();
     let temp_dir = tempfile::tempdir()
@@ -74,14 +78,16 @@ pub async fn render_sequence_scene(
                 &sub_output,
                 width,
                 height,
-                fps,
+                output_fps,
                 Some(dur),
                 platform,
                 None,  // no voice on individual sub-scenes
                 None,  // no music on individual sub-scenes
                 0.0,
+                &encoder::MusicFade::default(),
                 0.0,
                 source_vol,
+                None,
             )?;
         } else {
             // HTML template sub-scene: render via Chromium
@@ -110,6 +116,14 @@ pub async fn render_sequence_scene(
                     voice: None,
                     audio: None,
                     format_overrides: None,
+                    wait_for: None,
+                    fps: None,
+                    tags: Vec::new(),
+                    markdown: scene.frontmatter.markdown,
+                    quality: None,
+                    motion_blur: scene.frontmatter.motion_blur,
+                    min_duration: None,
+                    seed: scene.frontmatter.seed,
                 },
                 script: String::new(), // no per-sub-scene voiceover
                 source_path: scene.source_path.clone(),
@@ -124,6 +138,7 @@ pub async fn render_sequence_scene(
                 width,
                 height,
                 fps,
+                output_fps,
                 platform,
                 &sub_output,
                 None,  // no audio per sub-scene
@@ -133,7 +148,9 @@ pub async fn render_sequence_scene(
                 0.0,
                 0.0,
                 Some(project_path),
-                false, // no GPU for sub-scenes
+                None,
+                None, // captions cover HTML scenes only
+                retry,
             )
             .await?;
         }
@@ -159,7 +176,7 @@ pub async fn render_sequence_scene(
 
     // Step 2: Concatenate sub-scene videos with hard cuts (no transitions)
     let concat_path = temp_dir.path().join("sequence-concat.mp4");
-    encoder::concat_scenes(&sub_files, &concat_path)?;
+    encoder::concat_scenes(&sub_files, &concat_path, platform, output_fps, concat_method)?;
 
     // Step 3: Mix voiceover + music onto the concatenated video
     // Copy concat to final output, then mix audio in-place
@@ -171,8 +188,10 @@ pub async fn render_sequence_scene(
             audio_path,
             music_path,
             music_volume,
+            &encoder::MusicFade::for_scene(scene, sub_durations.iter().sum()),
             audio_delay_secs,
             platform,
+            ducking,
         )?;
     }
 
@@ -2,7 +2,7 @@ use crate::error::{VidgenError, VidgenResult};
 use serde::de::{self, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
@@ -16,6 +16,12 @@ pub enum SceneDuration {
     Auto,
     /// Explicit duration in seconds.
     Fixed(f64),
+    /// Duration matches the length of the scene's background music file.
+    /// Falls back to `Auto` behaviour when no music is configured or it can't be probed.
+    MatchMusic,
+    /// Share of `output.target_duration` in percent (`"20%"`), or of the
+    /// sequence's duration for sub-scenes.
+    Percent(f64),
 }
 
 impl SceneDuration {
@@ -24,19 +30,52 @@ impl SceneDuration {
     /// - `Auto` with TTS: `tts_duration + padding_before + padding_after`
     /// - `Auto` without TTS: `fallback`
     /// - `Fixed(d)`: `d`
+    /// - `MatchMusic`: same as `Auto` (no music duration known)
+    ///
+    /// `min_duration` raises an `Auto` result to at least that many seconds.
     pub fn resolve(
         &self,
         tts_duration: Option<f64>,
         padding_before: f64,
         padding_after: f64,
         fallback: f64,
+        min_duration: Option<f64>,
     ) -> f64 {
-        match self {
-            SceneDuration::Auto => match tts_duration {
-                Some(d) => d + padding_before + padding_after,
-                None => fallback,
-            },
-            SceneDuration::Fixed(d) => *d,
+        self.resolve_with_music(
+            tts_duration,
+            None,
+            padding_before,
+            padding_after,
+            fallback,
+            min_duration,
+        )
+    }
+
+    /// Resolve the effective duration, taking the probed music length into account.
+    ///
+    /// - `MatchMusic` with music: `music_duration`
+    /// - `MatchMusic` without music: same as `Auto`
+    /// - `Auto` / `Fixed(d)`: as in [`SceneDuration::resolve`]
+    /// - `Percent`: same as `Auto` (no target known; see [`SceneDuration::percent_of`])
+    pub fn resolve_with_music(
+        &self,
+        tts_duration: Option<f64>,
+        music_duration: Option<f64>,
+        padding_before: f64,
+        padding_after: f64,
+        fallback: f64,
+        min_duration: Option<f64>,
+    ) -> f64 {
+        match (self, music_duration) {
+            (SceneDuration::MatchMusic, Some(m)) if m > 0.0 => m,
+            (SceneDuration::Auto | SceneDuration::MatchMusic | SceneDuration::Percent(_), _) => {
+                let secs = match tts_duration {
+                    Some(d) => d + padding_before + padding_after,
+                    None => fallback,
+                };
+                secs.max(min_duration.unwrap_or(0.0))
+            }
+            (SceneDuration::Fixed(d), _) => *d,
         }
     }
 
@@ -44,13 +83,51 @@ impl SceneDuration {
         matches!(self, SceneDuration::Auto)
     }
 
+    pub fn is_match_music(&self) -> bool {
+        matches!(self, SceneDuration::MatchMusic)
+    }
+
     #[allow(dead_code)]
     pub fn as_fixed(&self) -> Option<f64> {
         match self {
             SceneDuration::Fixed(d) => Some(*d),
-            SceneDuration::Auto => None,
+            SceneDuration::Auto | SceneDuration::MatchMusic | SceneDuration::Percent(_) => None,
         }
     }
+
+    /// Seconds of a `Percent` duration out of `total` seconds.
+    pub fn percent_of(&self, total: f64) -> Option<f64> {
+        match self {
+            SceneDuration::Percent(p) => Some(total * p / 100.0),
+            _ => None,
+        }
+    }
+}
+
+/// Check `duration: "N%"` scenes against `output.target_duration`: a target
+/// must be set and the percentages may add up to at most 100%.
+pub fn validate_percent_durations(scenes: &[Scene], target: Option<f64>) -> VidgenResult<()> {
+    let total: f64 = scenes
+        .iter()
+        .filter_map(|s| match s.frontmatter.duration {
+            SceneDuration::Percent(p) => Some(p),
+            _ => None,
+        })
+        .sum();
+    if total == 0.0 {
+        return Ok(());
+    }
+    if target.is_none() {
+        return Err(VidgenError::ConfigParse(
+            "Scenes use percentage durations but output.target_duration is not set.".into(),
+        ));
+    }
+    if total > 100.0 + 1e-9 {
+        return Err(VidgenError::ConfigParse(format!(
+            "Percentage scene durations add up to {total}%. Must be at most 100%."
+        )));
+    }
+    Ok(())
 }
 
 impl Serialize for SceneDuration {
@@ -58,6 +135,8 @@ impl Serialize for SceneDuration {
         match self {
             SceneDuration::Auto => serializer.serialize_str("auto"),
             SceneDuration::Fixed(d) => serializer.serialize_f64(*d),
+            SceneDuration::MatchMusic => serializer.serialize_str("match-music"),
+            SceneDuration::Percent(p) => serializer.serialize_str(&format!("{p}%")),
         }
     }
 }
@@ -70,12 +149,25 @@ impl<'de> Deserialize<'de> for SceneDuration {
             type Value = SceneDuration;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("\"auto\" or a number (integer or float)")
+                formatter.write_str(
+                    "\"auto\", \"match-music\", a percentage such as \"20%\" or a number (integer or float)",
+                )
             }
 
             fn visit_str<E: de::Error>(self, value: &str) -> Result<SceneDuration, E> {
                 if value.eq_ignore_ascii_case("auto") {
                     Ok(SceneDuration::Auto)
+                } else if value.eq_ignore_ascii_case("match-music") {
+                    Ok(SceneDuration::MatchMusic)
+                } else if let Some(num_str) = value.strip_suffix('%') {
+                    // "20%" of output.target_duration
+                    num_str
+                        .trim()
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|p| *p > 0.0 && *p <= 100.0)
+                        .map(SceneDuration::Percent)
+                        .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(value), &self))
                 } else if let Some(num_str) = value.strip_suffix('s') {
                     // Support "5s" or "2.5s" suffix notation
                     num_str
@@ -117,10 +209,11 @@ impl schemars::JsonSchema for SceneDuration {
     fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
         serde_json::from_value(serde_json::json!({
             "oneOf": [
-                { "type": "string", "enum": ["auto"] },
+                { "type": "string", "enum": ["auto", "match-music"] },
+                { "type": "string", "pattern": "^[0-9.]+%$" },
                 { "type": "number" }
             ],
-            "description": "Scene duration: \"auto\" (derive from TTS audio + padding) or a number in seconds"
+            "description": "Scene duration: \"auto\" (derive from TTS audio + padding), \"match-music\" (length of the background music), a share of output.target_duration such as \"20%\" or a number in seconds"
         }))
         .unwrap()
     }
@@ -207,6 +300,35 @@ pub struct SceneAudioConfig {
     /// Music volume from 0.0 to 1.0 (default 0.3)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub music_volume: Option<f64>,
+    /// Recorded voiceover used instead of TTS (supports @assets/ prefix)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voice: Option<String>,
+    /// Start of the portion of `voice` to use, in seconds into the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_at: Option<f64>,
+    /// End of the portion of `voice` to use, in seconds into the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_at: Option<f64>,
+    /// Seconds to fade `music` in at the start of the scene (default 0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub music_fade_in: Option<f64>,
+    /// Seconds to fade `music` out at the end of the scene (default 0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub music_fade_out: Option<f64>,
+}
+
+impl SceneAudioConfig {
+    /// Whether `start_at`/`end_at` select a portion of the recording.
+    pub fn is_trimmed(&self) -> bool {
+        self.start_at.is_some() || self.end_at.is_some()
+    }
+
+    /// Length of the `start_at`..`end_at` window within a recording of `full` seconds.
+    pub fn trimmed_length(&self, full: f64) -> f64 {
+        let start = self.start_at.unwrap_or(0.0).clamp(0.0, full);
+        let end = self.end_at.unwrap_or(full).min(full);
+        (end - start).max(0.0)
+    }
 }
 
 /// Overlay/lower-third info banner that appears on top of a scene.
@@ -285,7 +407,7 @@ pub fn resolve_sub_scene_durations(
 
     for (i, sub) in sub_scenes.iter().enumerate() {
         match &sub.duration {
-            SceneDuration::Auto => {
+            SceneDuration::Auto | SceneDuration::MatchMusic => {
                 if auto_idx.is_some() {
                     return Err(VidgenError::Other(
                         "Only one sub-scene in a sequence may have duration: auto".into(),
@@ -296,6 +418,9 @@ pub fn resolve_sub_scene_durations(
             SceneDuration::Fixed(d) => {
                 fixed_sum += d;
             }
+            SceneDuration::Percent(p) => {
+                fixed_sum += total_available * p / 100.0;
+            }
         }
     }
 
@@ -306,6 +431,8 @@ pub fn resolve_sub_scene_durations(
             durations.push(remaining);
         } else if let SceneDuration::Fixed(d) = &sub.duration {
             durations.push(*d);
+        } else if let Some(d) = sub.duration.percent_of(total_available) {
+            durations.push(d);
         } else {
             unreachable!();
         }
@@ -348,6 +475,36 @@ pub struct SceneFrontmatter {
     pub audio: Option<SceneAudioConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub format_overrides: Option<HashMap<String, FormatOverride>>,
+    /// CSS selector that must exist in the DOM before each frame is captured.
+    /// For custom templates that build their content from JavaScript.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_for: Option<String>,
+    /// Capture/encode this scene at a different frame rate (e.g. 60 for smooth
+    /// kinetic text). The final video is normalized back to the project fps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fps: Option<u32>,
+    /// Free-form labels (e.g. "draft", "section-2") for `render --tag` filtering.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Render `**bold**` / `*italic*` in text props of built-in templates that support it.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub markdown: bool,
+    /// Encode this scene with a different quality preset (e.g. "high" for fine
+    /// gradients). The joined video still uses the project quality.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+    /// Blend adjacent frames while encoding this scene for a smoother look at
+    /// low frame rates. Slows the scene's encode, so it is off by default.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub motion_blur: bool,
+    /// Shortest an `auto` scene may be, in seconds, so short narration doesn't
+    /// flash on-screen text by too quickly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_duration: Option<f64>,
+    /// Seed for the template's `rand` values. Pin it to keep a randomized
+    /// layout while other scenes change; defaults to one derived from the file name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
 }
 
 /// Per-format overrides that can be applied to a scene when rendering a specific format.
@@ -363,8 +520,31 @@ pub struct FormatOverride {
 pub struct BackgroundConfig {
     pub color: Option<String>,
     pub image: Option<String>,
+    /// Ken Burns motion for `image`: zoom-in, zoom-out, pan-left, pan-right.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub motion: Option<String>,
+    /// Color or gradient painted over `image` (e.g. "rgba(0,0,0,0.4)") to keep
+    /// text readable on busy photos.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overlay: Option<String>,
+    /// Blur radius for `image`, in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blur: Option<f64>,
+}
+
+/// SplitMix64 — a tiny, well-distributed PRNG; stable across platforms and
+/// releases, so a seed always maps to the same sequence.
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
 }
 
+/// Valid values for `background.motion`.
+pub const BACKGROUND_MOTIONS: &[&str] = &["zoom-in", "zoom-out", "pan-left", "pan-right"];
+
 #[derive(Debug)]
 pub struct Scene {
     pub frontmatter: SceneFrontmatter,
@@ -383,6 +563,46 @@ impl Scene {
         self.frontmatter.sub_scenes.as_ref().is_some_and(|s| !s.is_empty())
     }
 
+    /// Seed for this scene's `rand` template values: the frontmatter `seed`,
+    /// else an FNV-1a hash of the path under `scenes/` (e.g. `intro/01-title.md`)
+    /// so each scene, including same-named ones in other sections, differs but
+    /// stays stable.
+    pub fn rand_seed(&self) -> u64 {
+        self.frontmatter.seed.unwrap_or_else(|| {
+            let components: Vec<_> = self.source_path.components().collect();
+            let start = components
+                .iter()
+                .rposition(|c| c.as_os_str() == "scenes")
+                .map_or(components.len().saturating_sub(1), |i| i + 1);
+            let name = components[start..]
+                .iter()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            name.bytes().fold(0xCBF2_9CE4_8422_2325, |h, b| {
+                (h ^ b as u64).wrapping_mul(0x0100_0000_01B3)
+            })
+        })
+    }
+
+    /// `count` deterministic values in `[0, 1)` from [`Scene::rand_seed`].
+    pub fn rand_values(&self, count: usize) -> Vec<f64> {
+        let mut state = self.rand_seed();
+        (0..count)
+            .map(|_| (splitmix64(&mut state) >> 11) as f64 / (1u64 << 53) as f64)
+            .collect()
+    }
+
+    /// Returns true if the scene is labeled with `tag`.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.frontmatter.tags.iter().any(|t| t == tag)
+    }
+
+    /// Frame rate for capturing this scene: the scene `fps` override, else the project fps.
+    pub fn effective_fps(&self, project_fps: u32) -> u32 {
+        self.frontmatter.fps.unwrap_or(project_fps)
+    }
+
     /// Compute total frames for a given effective duration (in seconds).
     pub fn total_frames_for_duration(effective_duration: f64, fps: u32) -> u32 {
         (effective_duration * fps as f64).ceil() as u32
@@ -393,7 +613,7 @@ impl Scene {
     pub fn total_frames(&self, fps: u32) -> u32 {
         let effective = match &self.frontmatter.duration {
             SceneDuration::Fixed(d) => *d,
-            SceneDuration::Auto => 3.0, // preview fallback
+            SceneDuration::Auto | SceneDuration::MatchMusic | SceneDuration::Percent(_) => 3.0, // preview fallback
         };
         Self::total_frames_for_duration(effective, fps)
     }
@@ -402,6 +622,8 @@ impl Scene {
 /// Split a markdown file into YAML frontmatter and body text.
 /// Expects `---` delimiters.
 fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    // Some Windows editors save UTF-8 with a byte order mark, which is not whitespace
+    let content = content.strip_prefix('\u{FEFF}').unwrap_or(content);
     let trimmed = content.trim_start();
     if !trimmed.starts_with("---") {
         return None;
@@ -440,7 +662,10 @@ pub fn parse_scene(content: &str, path: &Path) -> VidgenResult<Scene> {
 
     // Validate sub_scenes
     if let Some(ref subs) = frontmatter.sub_scenes {
-        let auto_count = subs.iter().filter(|s| s.duration.is_auto()).count();
+        let auto_count = subs
+            .iter()
+            .filter(|s| s.duration.is_auto() || s.duration.is_match_music())
+            .count();
         if auto_count > 1 {
             return Err(VidgenError::SceneParse {
                 path: path.to_path_buf(),
@@ -465,6 +690,41 @@ pub fn parse_scene(content: &str, path: &Path) -> VidgenResult<Scene> {
         }
     }
 
+    // Validate music fades
+    if let Some(audio) = &frontmatter.audio {
+        for (key, fade) in [("music_fade_in", audio.music_fade_in), ("music_fade_out", audio.music_fade_out)] {
+            if let Some(secs) = fade.filter(|s| !(s.is_finite() && *s >= 0.0)) {
+                return Err(VidgenError::SceneParse {
+                    path: path.to_path_buf(),
+                    message: format!("audio.{key} {secs} must be a non-negative number of seconds"),
+                });
+            }
+        }
+    }
+
+    // Validate background blur
+    if let Some(blur) = frontmatter.background.as_ref().and_then(|bg| bg.blur) {
+        if !(blur.is_finite() && blur >= 0.0) {
+            return Err(VidgenError::SceneParse {
+                path: path.to_path_buf(),
+                message: format!("background.blur {blur} must be a non-negative number of pixels"),
+            });
+        }
+    }
+
+    // Validate background motion
+    if let Some(motion) = frontmatter.background.as_ref().and_then(|bg| bg.motion.as_deref()) {
+        if !BACKGROUND_MOTIONS.contains(&motion) {
+            return Err(VidgenError::SceneParse {
+                path: path.to_path_buf(),
+                message: format!(
+                    "Invalid background.motion: \"{motion}\". Must be one of: {}",
+                    BACKGROUND_MOTIONS.join(", ")
+                ),
+            });
+        }
+    }
+
     // Validate overlay config
     if let Some(ref ov) = frontmatter.overlay {
         let valid_styles = ["modern", "minimal", "news", "gradient"];
@@ -483,6 +743,27 @@ pub fn parse_scene(content: &str, path: &Path) -> VidgenResult<Scene> {
         }
     }
 
+    // Validate fps override
+    if let Some(fps) = frontmatter.fps {
+        if !(1..=240).contains(&fps) {
+            return Err(VidgenError::SceneParse {
+                path: path.to_path_buf(),
+                message: format!("Invalid fps: {fps}. Must be between 1 and 240."),
+            });
+        }
+    }
+
+    if let Some(min) = frontmatter.min_duration {
+        if !(min > 0.0 && min.is_finite()) {
+            return Err(VidgenError::SceneParse {
+                path: path.to_path_buf(),
+                message: format!(
+                    "Invalid min_duration: {min}. Must be a positive number of seconds."
+                ),
+            });
+        }
+    }
+
     // Validate source_volume range
     if let Some(sv) = &frontmatter.source_volume {
         if !(*sv >= 0.0 && *sv <= 1.0) {
@@ -507,40 +788,166 @@ pub fn parse_scene(content: &str, path: &Path) -> VidgenResult<Scene> {
     })
 }
 
-/// Write a scene back to a markdown file (frontmatter + script body).
-pub fn write_scene(scene: &Scene, path: &Path) -> VidgenResult<()> {
+/// Render a scene as markdown: frontmatter in canonical field order + script body.
+pub fn format_scene(scene: &Scene, path: &Path) -> VidgenResult<String> {
     let yaml = serde_yml::to_string(&scene.frontmatter).map_err(|e| VidgenError::SceneParse {
         path: path.to_path_buf(),
         message: format!("Failed to serialize frontmatter: {e}"),
     })?;
-    let content = format!("---\n{}---\n\n{}\n", yaml, scene.script);
-    std::fs::write(path, content)?;
+    Ok(format!("---\n{}---\n\n{}\n", yaml, scene.script))
+}
+
+/// Write a scene back to a markdown file (frontmatter + script body).
+pub fn write_scene(scene: &Scene, path: &Path) -> VidgenResult<()> {
+    std::fs::write(path, format_scene(scene, path)?)?;
     Ok(())
 }
 
-/// Load all scenes from a project's scenes/ directory, sorted by filename.
-pub fn load_scenes(project_path: &Path) -> VidgenResult<Vec<Scene>> {
+/// Name of the project file listing scene files to skip.
+pub const IGNORE_FILE: &str = ".vidgenignore";
+
+/// Read glob patterns from the project's `.vidgenignore` (one per line; blank
+/// lines and `#` comments are skipped). A missing file means no patterns.
+pub fn load_ignore_patterns(project_path: &Path) -> Vec<String> {
+    std::fs::read_to_string(project_path.join(IGNORE_FILE))
+        .map(|content| {
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                .map(|l| {
+                    l.trim_start_matches("scenes/")
+                        .trim_end_matches('/')
+                        .to_string()
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Match `name` against a glob `pattern` supporting `*` (any run of characters)
+/// and `?` (any single character).
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let (p, n): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+    let (mut pi, mut ni) = (0, 0);
+    // Position of the last `*` and the name index it was tried at, for backtracking
+    let mut star: Option<(usize, usize)> = None;
+    while ni < n.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == n[ni]) {
+            pi += 1;
+            ni += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ni));
+            pi += 1;
+        } else if let Some((sp, sn)) = star {
+            pi = sp + 1;
+            ni = sn + 1;
+            star = Some((sp, sn + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+/// True when a scene file or section folder (given by its path) matches any
+/// `.vidgenignore` pattern. Patterns are matched against the file or folder name.
+pub fn is_ignored(path: &Path, patterns: &[String]) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    patterns.iter().any(|p| glob_match(p, name))
+}
+
+/// Extract the template slug from a scene filename: `01-title-card.md` → `title-card`.
+/// Only a numeric prefix is stripped, so `intro-card.md` keeps its full stem.
+pub fn extract_scene_slug(path: &Path) -> String {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("scene");
+    // Strip the leading numeric prefix (e.g., "01-")
+    if let Some((prefix, after)) = stem.split_once('-') {
+        if !prefix.is_empty() && prefix.chars().all(|c| c.is_ascii_digit()) && !after.is_empty() {
+            return after.to_string();
+        }
+    }
+    stem.to_string()
+}
+
+/// File name for scene `index` (0-based): `NN-<slug>.<ext>`, numbered by the
+/// scene's position so scenes in different section folders never collide.
+pub fn audio_file_name(index: usize, scene_path: &Path, ext: &str) -> String {
+    format!("{:02}-{}.{ext}", index + 1, extract_scene_slug(scene_path))
+}
+
+/// Sorted `.md` scene files under a project's `scenes/` directory, including
+/// section subfolders (`scenes/02-body/01-chart.md`). Files and folders matching
+/// `.vidgenignore`, and hidden folders, are skipped. Paths sort component by
+/// component, so a section's scenes play together in the section's place.
+pub fn scene_file_paths(project_path: &Path) -> VidgenResult<Vec<PathBuf>> {
+    sorted_scene_files(project_path, &load_ignore_patterns(project_path))
+}
+
+/// Like [`scene_file_paths`], but including files and folders matched by
+/// `.vidgenignore` (drafts still reference assets).
+pub fn all_scene_file_paths(project_path: &Path) -> VidgenResult<Vec<PathBuf>> {
+    sorted_scene_files(project_path, &[])
+}
+
+fn sorted_scene_files(project_path: &Path, ignore: &[String]) -> VidgenResult<Vec<PathBuf>> {
     let scenes_dir = project_path.join("scenes");
     if !scenes_dir.exists() {
-        return Err(VidgenError::NoScenes(scenes_dir));
+        return Ok(vec![]);
     }
+    let mut entries = Vec::new();
+    collect_scene_files(&scenes_dir, ignore, &mut entries)?;
+    entries.sort();
+    Ok(entries)
+}
 
-    let mut entries: Vec<PathBuf> = Vec::new();
-    for entry in std::fs::read_dir(&scenes_dir)? {
-        match entry {
-            Ok(e) => {
-                let path = e.path();
-                if path.extension().is_some_and(|ext| ext == "md") {
-                    entries.push(path);
-                }
-            }
+fn collect_scene_files(dir: &Path, ignore: &[String], out: &mut Vec<PathBuf>) -> VidgenResult<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = match entry {
+            Ok(e) => e.path(),
             Err(e) => {
-                warn!("Could not read entry in {}: {}", scenes_dir.display(), e);
+                warn!("Could not read entry in {}: {}", dir.display(), e);
+                continue;
+            }
+        };
+        if is_ignored(&path, ignore) {
+            debug!("Skipping {} (matches {})", path.display(), IGNORE_FILE);
+            continue;
+        }
+        if path.is_dir() {
+            let hidden = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with('.'));
+            if !hidden {
+                collect_scene_files(&path, ignore, out)?;
             }
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            out.push(path);
         }
     }
+    Ok(())
+}
 
-    entries.sort();
+/// Load all scenes from a project's scenes/ directory and its section
+/// subfolders, sorted by path. Files matching `.vidgenignore` are skipped.
+/// Scripts are returned as written; see [`load_scenes_with_variables`].
+pub fn load_scenes(project_path: &Path) -> VidgenResult<Vec<Scene>> {
+    load_scenes_with_variables(project_path, &BTreeMap::new())
+}
+
+/// [`load_scenes`] with `{{name}}` placeholders in each script replaced from the
+/// project's `[variables]` (see [`substitute_variables`]).
+pub fn load_scenes_with_variables(
+    project_path: &Path,
+    variables: &BTreeMap<String, String>,
+) -> VidgenResult<Vec<Scene>> {
+    let scenes_dir = project_path.join("scenes");
+    if !scenes_dir.exists() {
+        return Err(VidgenError::NoScenes(scenes_dir));
+    }
+
+    let entries = scene_file_paths(project_path)?;
 
     if entries.is_empty() {
         return Err(VidgenError::NoScenes(scenes_dir));
@@ -551,11 +958,54 @@ pub fn load_scenes(project_path: &Path) -> VidgenResult<Vec<Scene>> {
     let mut scenes = Vec::new();
     for path in entries {
         let content = std::fs::read_to_string(&path)?;
-        scenes.push(parse_scene(&content, &path)?);
+        let mut scene = parse_scene(&content, &path)?;
+        if !variables.is_empty() {
+            scene.script = substitute_variables(&scene.script, variables, &path);
+        }
+        scenes.push(scene);
     }
     Ok(scenes)
 }
 
+/// Replace `{{name}}` placeholders in a script with values from `[variables]`.
+///
+/// Only bare names (letters, digits, `_`, `-`) are substituted, so Handlebars-style
+/// expressions such as `{{#if x}}` or `{{{html}}}` are left alone. Unknown names are
+/// kept verbatim with a warning.
+pub fn substitute_variables(
+    script: &str,
+    variables: &BTreeMap<String, String>,
+    source: &Path,
+) -> String {
+    let mut out = String::with_capacity(script.len());
+    let mut rest = script;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = after[..end].trim();
+        let is_name = !name.is_empty()
+            && !after.starts_with('{')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        out.push_str(&rest[..start]);
+        match variables.get(name).filter(|_| is_name) {
+            Some(value) => out.push_str(value),
+            None => {
+                if is_name {
+                    warn!("{}: unknown script variable '{name}'", source.display());
+                }
+                out.push_str(&rest[start..start + 2 + end + 2]);
+            }
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
 /// Check if a string looks like an HTTP/HTTPS URL.
 pub fn is_url(raw: &str) -> bool {
     raw.starts_with("http://") || raw.starts_with("https://")
@@ -662,6 +1112,36 @@ mod tests {
         assert!(body.contains("More text."));
     }
 
+    #[test]
+    fn test_split_frontmatter_strips_bom() {
+        let content = "\u{FEFF}---\ntemplate: title-card\n---\nHello world";
+        let (yaml, body) = split_frontmatter(content).unwrap();
+        assert_eq!(yaml, "template: title-card");
+        assert_eq!(body, "Hello world");
+    }
+
+    #[test]
+    fn test_parse_scene_with_bom() {
+        let content = "\u{FEFF}---\r\ntemplate: quote-card\r\nprops:\r\n  quote: \"Hi\"\r\n---\r\nScript.";
+        let scene = parse_scene(content, Path::new("bom.md")).unwrap();
+        assert_eq!(scene.frontmatter.template, "quote-card");
+        assert_eq!(scene.script, "Script.");
+    }
+
+    #[test]
+    fn test_background_motion_validation() {
+        let ok = "---\ntemplate: title-card\nbackground:\n  image: bg.jpg\n  motion: pan-left\n---\n";
+        let scene = parse_scene(ok, Path::new("ok.md")).unwrap();
+        assert_eq!(
+            scene.frontmatter.background.unwrap().motion.as_deref(),
+            Some("pan-left")
+        );
+
+        let bad = "---\ntemplate: title-card\nbackground:\n  image: bg.jpg\n  motion: spin\n---\n";
+        let err = parse_scene(bad, Path::new("bad.md")).unwrap_err();
+        assert!(err.to_string().contains("background.motion"));
+    }
+
     #[test]
     fn test_parse_scene_basic() {
         let content = "---\ntemplate: title-card\nduration: 5\nprops:\n  title: \"Welcome\"\n---\nScript text here.";
@@ -681,6 +1161,32 @@ mod tests {
         let scene = parse_scene(content, Path::new("test.md")).unwrap();
         assert_eq!(scene.frontmatter.duration, SceneDuration::Auto); // new default
         assert!(scene.frontmatter.props.is_empty());
+        assert!(scene.frontmatter.wait_for.is_none());
+    }
+
+    #[test]
+    fn test_parse_scene_wait_for() {
+        let content = "---\ntemplate: my-chart\nwait_for: \".chart.ready\"\n---\n";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        assert_eq!(scene.frontmatter.wait_for.as_deref(), Some(".chart.ready"));
+    }
+
+    #[test]
+    fn test_scene_fps_override() {
+        let content = "---\ntemplate: kinetic-text\nduration: 2\nfps: 60\n---\n";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        assert_eq!(scene.effective_fps(30), 60);
+        assert_eq!(Scene::total_frames_for_duration(2.0, scene.effective_fps(30)), 120);
+
+        let content = "---\ntemplate: kinetic-text\nduration: 2\n---\n";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        assert_eq!(scene.effective_fps(30), 30);
+    }
+
+    #[test]
+    fn test_scene_fps_override_out_of_range() {
+        let content = "---\ntemplate: kinetic-text\nfps: 0\n---\n";
+        assert!(parse_scene(content, Path::new("test.md")).is_err());
     }
 
     #[test]
@@ -708,24 +1214,151 @@ mod tests {
     #[test]
     fn test_scene_duration_resolve_auto_with_tts() {
         let d = SceneDuration::Auto;
-        let effective = d.resolve(Some(5.0), 0.5, 0.5, 3.0);
+        let effective = d.resolve(Some(5.0), 0.5, 0.5, 3.0, None);
         assert!((effective - 6.0).abs() < f64::EPSILON);
     }
 
     #[test]
     fn test_scene_duration_resolve_auto_without_tts() {
         let d = SceneDuration::Auto;
-        let effective = d.resolve(None, 0.5, 0.5, 3.0);
+        let effective = d.resolve(None, 0.5, 0.5, 3.0, None);
         assert!((effective - 3.0).abs() < f64::EPSILON);
     }
 
     #[test]
     fn test_scene_duration_resolve_fixed() {
         let d = SceneDuration::Fixed(7.0);
-        let effective = d.resolve(Some(5.0), 0.5, 0.5, 3.0);
+        let effective = d.resolve(Some(5.0), 0.5, 0.5, 3.0, None);
         assert!((effective - 7.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_scene_duration_resolve_min_duration() {
+        let content = "---\ntemplate: title-card\nmin_duration: 4\n---\nHi.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let min = scene.frontmatter.min_duration;
+        assert_eq!(min, Some(4.0));
+
+        // 1s of narration + 1s padding is raised to the minimum
+        let short = SceneDuration::Auto.resolve(Some(1.0), 0.5, 0.5, 3.0, min);
+        assert!((short - 4.0).abs() < f64::EPSILON);
+        // Longer narration is unaffected
+        let long = SceneDuration::Auto.resolve(Some(5.0), 0.5, 0.5, 3.0, min);
+        assert!((long - 6.0).abs() < f64::EPSILON);
+        // So are fixed durations and probed music lengths
+        assert!(
+            (SceneDuration::Fixed(2.0).resolve(Some(1.0), 0.5, 0.5, 3.0, min) - 2.0).abs()
+                < f64::EPSILON
+        );
+        let music =
+            SceneDuration::MatchMusic.resolve_with_music(None, Some(2.5), 0.5, 0.5, 3.0, min);
+        assert!((music - 2.5).abs() < f64::EPSILON);
+
+        let err = parse_scene(
+            "---\ntemplate: title-card\nmin_duration: 0\n---\n",
+            Path::new("bad.md"),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("Invalid min_duration"), "{err}");
+    }
+
+    #[test]
+    fn test_parse_scene_duration_match_music() {
+        let content = "---\ntemplate: title-card\nduration: match-music\n---\nScript.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        assert_eq!(scene.frontmatter.duration, SceneDuration::MatchMusic);
+        assert!(scene.frontmatter.duration.is_match_music());
+        assert!(!scene.frontmatter.duration.is_auto());
+    }
+
+    #[test]
+    fn test_parse_scene_duration_percent() {
+        let content = "---\ntemplate: title-card\nduration: \"20%\"\n---\nScript.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        assert_eq!(scene.frontmatter.duration, SceneDuration::Percent(20.0));
+        assert!(!scene.frontmatter.duration.is_auto());
+        assert_eq!(scene.frontmatter.duration.as_fixed(), None);
+
+        let yaml = serde_yml::to_string(&SceneDuration::Percent(12.5)).unwrap();
+        let parsed: SceneDuration = serde_yml::from_str(&yaml).unwrap();
+        assert_eq!(parsed, SceneDuration::Percent(12.5));
+
+        for bad in ["0%", "150%", "abc%"] {
+            let content = format!("---\ntemplate: title-card\nduration: \"{bad}\"\n---\n");
+            assert!(
+                parse_scene(&content, Path::new("test.md")).is_err(),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_scene_duration_percent_resolves_against_target() {
+        let d = SceneDuration::Percent(20.0);
+        assert_eq!(d.percent_of(60.0), Some(12.0));
+        assert_eq!(SceneDuration::Fixed(3.0).percent_of(60.0), None);
+
+        let scenes = |percents: &[&str]| -> Vec<Scene> {
+            percents
+                .iter()
+                .map(|p| {
+                    let content = format!("---\ntemplate: title-card\nduration: \"{p}\"\n---\n");
+                    parse_scene(&content, Path::new("test.md")).unwrap()
+                })
+                .collect()
+        };
+        let fits = scenes(&["20%", "30%", "50%"]);
+        assert!(validate_percent_durations(&fits, Some(60.0)).is_ok());
+
+        let err = validate_percent_durations(&fits, None).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("output.target_duration is not set"));
+
+        let over = scenes(&["40%", "30%", "50%"]);
+        let err = validate_percent_durations(&over, Some(60.0)).unwrap_err();
+        assert!(err.to_string().contains("add up to 120%"));
+    }
+
+    #[test]
+    fn test_scene_duration_match_music_serde_roundtrip() {
+        let yaml = serde_yml::to_string(&SceneDuration::MatchMusic).unwrap();
+        assert_eq!(yaml.trim(), "match-music");
+        let parsed: SceneDuration = serde_yml::from_str(&yaml).unwrap();
+        assert_eq!(parsed, SceneDuration::MatchMusic);
+
+        let json = serde_json::to_value(SceneDuration::MatchMusic).unwrap();
+        assert_eq!(json, serde_json::json!("match-music"));
+        let parsed: SceneDuration = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, SceneDuration::MatchMusic);
+    }
+
+    #[test]
+    fn test_scene_duration_resolve_match_music_with_probe() {
+        let d = SceneDuration::MatchMusic;
+        let effective = d.resolve_with_music(Some(5.0), Some(12.5), 0.5, 0.5, 3.0, None);
+        assert!((effective - 12.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_scene_duration_resolve_match_music_without_music() {
+        let d = SceneDuration::MatchMusic;
+        // Falls back to auto: TTS + padding, then the fallback duration
+        let effective = d.resolve_with_music(Some(5.0), None, 0.5, 0.5, 3.0, None);
+        assert!((effective - 6.0).abs() < f64::EPSILON);
+        let effective = d.resolve_with_music(None, None, 0.5, 0.5, 3.0, None);
+        assert!((effective - 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_scene_duration_resolve_with_music_ignored_for_other_variants() {
+        let fixed = SceneDuration::Fixed(7.0);
+        assert!((fixed.resolve_with_music(None, Some(20.0), 0.5, 0.5, 3.0, None) - 7.0).abs() < f64::EPSILON);
+        let auto = SceneDuration::Auto;
+        assert!((auto.resolve_with_music(Some(4.0), Some(20.0), 0.5, 0.5, 3.0, None) - 5.0).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_scene_roundtrip() {
         let content = "---\ntemplate: title-card\nduration: 5\nprops:\n  title: \"Hello\"\n---\n\nScript text.";
@@ -839,6 +1472,26 @@ Optional voiceover text."#;
         assert!(scene.frontmatter.source_volume.is_none());
     }
 
+    #[test]
+    fn test_music_fades_must_be_non_negative() {
+        let content = "---\ntemplate: title-card\naudio:\n  music: song.mp3\n  music_fade_out: -1\n---\nText.";
+        let err = parse_scene(content, Path::new("test.md")).unwrap_err();
+        assert!(err.to_string().contains("audio.music_fade_out"));
+        let content = "---\ntemplate: title-card\naudio:\n  music: song.mp3\n  music_fade_in: 1.5\n---\nText.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        assert_eq!(scene.frontmatter.audio.unwrap().music_fade_in, Some(1.5));
+    }
+
+    #[test]
+    fn test_background_blur_must_be_non_negative() {
+        let content = "---\ntemplate: title-card\nbackground:\n  image: bg.jpg\n  blur: -2\n---\nText.";
+        let err = parse_scene(content, Path::new("test.md")).unwrap_err();
+        assert!(err.to_string().contains("background.blur"));
+        let content = "---\ntemplate: title-card\nbackground:\n  image: bg.jpg\n  blur: 4\n---\nText.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        assert_eq!(scene.frontmatter.background.unwrap().blur, Some(4.0));
+    }
+
     #[test]
     fn test_parse_overlay_full() {
         let content = r#"---
@@ -861,6 +1514,37 @@ Text."#;
         assert_eq!(ov.position, "bottom-right");
     }
 
+    #[test]
+    fn test_rand_values_in_unit_range() {
+        let content = "---\ntemplate: title-card\nseed: 3\n---\nText.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        assert_eq!(scene.rand_seed(), 3);
+        let values = scene.rand_values(100);
+        assert_eq!(values.len(), 100);
+        assert!(values.iter().all(|v| (0.0..1.0).contains(v)));
+        assert_eq!(values, scene.rand_values(100));
+    }
+
+    #[test]
+    fn test_rand_seed_uses_path_under_scenes() {
+        let seed = |path: &str| {
+            parse_scene("---\ntemplate: title-card\n---\n", Path::new(path))
+                .unwrap()
+                .rand_seed()
+        };
+        // Same file name in different sections gets different values
+        assert_ne!(
+            seed("/p/scenes/intro/01-title.md"),
+            seed("/p/scenes/outro/01-title.md")
+        );
+        // Top-level scenes hash just the file name, wherever the project lives
+        assert_eq!(seed("/p/scenes/01-title.md"), seed("01-title.md"));
+        assert_eq!(
+            seed("/p/scenes/intro/01-title.md"),
+            seed("/other/scenes/intro/01-title.md")
+        );
+    }
+
     #[test]
     fn test_parse_overlay_minimal() {
         let content = "---\ntemplate: title-card\noverlay:\n  text: \"example.com\"\n---\nText.";
@@ -1111,6 +1795,130 @@ Script."#;
         assert!(!is_url("relative/path.png"));
     }
 
+    #[test]
+    fn test_substitute_variables() {
+        let vars = BTreeMap::from([("product".to_string(), "Acme".to_string())]);
+        let src = Path::new("test.md");
+        assert_eq!(
+            substitute_variables("Meet {{product}} and {{ product }}.", &vars, src),
+            "Meet Acme and Acme."
+        );
+        // Unknown names and Handlebars expressions stay as written
+        assert_eq!(
+            substitute_variables("{{missing}} {{#if product}} {{{product}}} {{product", &vars, src),
+            "{{missing}} {{#if product}} {{{product}}} {{product"
+        );
+    }
+
+    #[test]
+    fn test_load_scenes_applies_script_variables() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        std::fs::create_dir_all(project.join("scenes")).unwrap();
+        std::fs::write(
+            project.join("project.toml"),
+            "[project]\nname = \"Vars\"\n\n[variables]\nproduct = \"Acme Cloud\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            project.join("scenes/01-intro.md"),
+            "---\ntemplate: kinetic-text\n---\nTry {{product}} today.",
+        )
+        .unwrap();
+
+        assert_eq!(load_scenes(project).unwrap()[0].script, "Try {{product}} today.");
+        let config = crate::config::load_config(project).unwrap();
+        let scenes = load_scenes_with_variables(project, &config.variables).unwrap();
+        // The script is the TTS input...
+        assert_eq!(scenes[0].script, "Try Acme Cloud today.");
+        // ...and the on-screen text
+        let registry = crate::template::TemplateRegistry::new().unwrap();
+        let html = registry
+            .render_scene_html(
+                &scenes[0],
+                &crate::config::ThemeConfig::default(),
+                1920,
+                1080,
+                0,
+                150,
+                None,
+            )
+            .unwrap();
+        assert!(html.contains("Acme"));
+        assert!(!html.contains("{{product}}"));
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("_draft*.md", "_draft.md"));
+        assert!(glob_match("_draft*.md", "_draft-outro.md"));
+        assert!(glob_match("0?-*.md", "03-title-card.md"));
+        assert!(glob_match("*", "anything.md"));
+        assert!(!glob_match("_draft*.md", "01-draft.md"));
+        assert!(!glob_match("0?-*.md", "10-title.md"));
+    }
+
+    #[test]
+    fn test_load_scenes_skips_ignored_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        std::fs::create_dir_all(project.join("scenes")).unwrap();
+        let scene = "---\ntemplate: title-card\n---\nHello.";
+        std::fs::write(project.join("scenes/01-intro.md"), scene).unwrap();
+        std::fs::write(project.join("scenes/02-outro.md"), scene).unwrap();
+        std::fs::write(project.join("scenes/_draft.md"), scene).unwrap();
+        std::fs::write(
+            project.join(IGNORE_FILE),
+            "# drafts\n\nscenes/_draft*.md\n02-*\n",
+        )
+        .unwrap();
+
+        let scenes = load_scenes(project).unwrap();
+        assert_eq!(scenes.len(), 1);
+        assert!(scenes[0].source_path.ends_with("01-intro.md"));
+
+        // Without the ignore file every scene loads again
+        std::fs::remove_file(project.join(IGNORE_FILE)).unwrap();
+        assert_eq!(load_scenes(project).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_load_scenes_nested_sections_in_path_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path();
+        let files = [
+            "03-outro.md",
+            "02-body/10-summary.md",
+            "01-intro.md",
+            "02-body/02-chart.md",
+            "02-body/01-problem.md",
+            "02-body/extra/01-aside.md",
+            "_drafts/01-idea.md",
+            ".trash/01-old.md",
+        ];
+        for file in files {
+            let path = project.join("scenes").join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, format!("---\ntemplate: title-card\n---\n{file}")).unwrap();
+        }
+        std::fs::write(project.join(IGNORE_FILE), "_drafts/\n").unwrap();
+
+        let scenes = load_scenes(project).unwrap();
+        let order: Vec<&str> = scenes.iter().map(|s| s.script.as_str()).collect();
+        assert_eq!(
+            order,
+            [
+                "01-intro.md",
+                "02-body/01-problem.md",
+                "02-body/02-chart.md",
+                "02-body/10-summary.md",
+                "02-body/extra/01-aside.md",
+                "03-outro.md",
+            ]
+        );
+        assert_eq!(scene_file_paths(project).unwrap().len(), 6);
+    }
+
     #[test]
     fn test_download_cache_key() {
         let key1 = url_cache_key("https://example.com/image.png");
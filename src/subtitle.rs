@@ -1,3 +1,4 @@
+use crate::config::ThemeConfig;
 use crate::tts::timestamps::WordTimestamp;
 
 /// A single subtitle entry (one or more words shown together).
@@ -9,6 +10,58 @@ pub struct SubtitleEntry {
     pub text: String,
 }
 
+/// Burn-in style used when no `output.subtitles.preset` is set.
+pub const DEFAULT_SUBTITLE_STYLE: &str = "FontSize=24,PrimaryColour=&H00FFFFFF,Alignment=2";
+
+/// Named burn-in styles (`output.subtitles.preset`), as ASS `force_style` strings.
+pub const SUBTITLE_PRESETS: &[(&str, &str)] = &[
+    (
+        "youtube",
+        "FontName=Roboto,FontSize=22,PrimaryColour=&H00FFFFFF,OutlineColour=&H00000000,\
+         BorderStyle=1,Outline=2,Shadow=1,Alignment=2,MarginV=30",
+    ),
+    (
+        "tiktok-bold",
+        "FontName=Montserrat,FontSize=28,Bold=1,PrimaryColour=&H00FFFFFF,\
+         OutlineColour=&H00000000,BorderStyle=1,Outline=4,Shadow=0,Alignment=2,MarginV=120",
+    ),
+    (
+        "minimal",
+        "FontName=Helvetica,FontSize=18,PrimaryColour=&H00FFFFFF,BorderStyle=1,Outline=0,\
+         Shadow=1,Alignment=2,MarginV=20",
+    ),
+    (
+        "clean",
+        "FontName=Inter,FontSize=20,PrimaryColour=&H00FFFFFF,BackColour=&H80000000,\
+         BorderStyle=3,Outline=1,Shadow=0,Alignment=2,MarginV=40",
+    ),
+];
+
+/// Build the ASS `force_style` for burn-in: the preset (or the default style),
+/// with each `Key=Value` from the custom `style` replacing or extending it.
+pub fn force_style(preset: Option<&str>, style: Option<&str>) -> String {
+    let base = preset
+        .and_then(|name| SUBTITLE_PRESETS.iter().find(|(n, _)| *n == name))
+        .map_or(DEFAULT_SUBTITLE_STYLE, |(_, s)| s);
+
+    let mut entries: Vec<(String, String)> = Vec::new();
+    for pair in base.split(',').chain(style.unwrap_or("").split(',')) {
+        let Some((key, value)) = pair.split_once('=') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        match entries.iter_mut().find(|(k, _)| k.eq_ignore_ascii_case(key)) {
+            Some(entry) => entry.1 = value.to_string(),
+            None => entries.push((key.to_string(), value.to_string())),
+        }
+    }
+    entries
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 /// Group word timestamps into subtitle entries.
 /// Groups up to `max_words_per_line` words per subtitle entry for readability.
 pub fn group_into_subtitles(
@@ -60,6 +113,125 @@ pub fn to_srt(entries: &[SubtitleEntry]) -> String {
     out
 }
 
+/// Write word timestamps as a karaoke-style WebVTT string: words are grouped
+/// into cues of up to `max_words_per_line`, and every word after the first in a
+/// cue is preceded by an inline `<HH:MM:SS.mmm>` tag marking when it is spoken,
+/// so players can highlight word by word.
+pub fn to_karaoke_vtt(words: &[WordTimestamp], max_words_per_line: usize) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for chunk in words.chunks(max_words_per_line.max(1)) {
+        let start = chunk[0].start_secs;
+        let end = chunk.last().unwrap().end_secs;
+        out.push_str(&format!(
+            "{} --> {}\n",
+            format_vtt_time(start),
+            format_vtt_time(end)
+        ));
+        for (i, word) in chunk.iter().enumerate() {
+            if i > 0 {
+                out.push(' ');
+                // Timestamp tags must fall strictly inside the cue
+                if word.start_secs > start && word.start_secs < end {
+                    out.push_str(&format!("<{}>", format_vtt_time(word.start_secs)));
+                }
+            }
+            out.push_str(&escape_vtt_text(&word.word));
+        }
+        out.push_str("\n\n");
+    }
+    out
+}
+
+/// Escape the characters WebVTT cue text treats as markup.
+fn escape_vtt_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Element id of the in-page caption layer (`output.subtitles.html_overlay`).
+pub const CAPTION_LAYER_ID: &str = "vidgen-caption";
+
+/// Word timings of one scene, relative to the scene start, rendered by the
+/// browser as a caption layer instead of being burned in by FFmpeg.
+#[derive(Debug, Clone)]
+pub struct SceneCaptions {
+    pub words: Vec<WordTimestamp>,
+    pub max_words_per_line: usize,
+}
+
+impl SceneCaptions {
+    /// Caption markup at `secs` into the scene: the current line (grouped like
+    /// the SRT) with the word being spoken marked `class="active"`. Empty between
+    /// lines and outside the narration.
+    pub fn markup_at(&self, secs: f64) -> String {
+        let max = self.max_words_per_line.max(1);
+        let Some(line) = self
+            .words
+            .chunks(max)
+            .find(|line| secs >= line[0].start_secs && secs < line[line.len() - 1].end_secs)
+        else {
+            return String::new();
+        };
+        let active = line.iter().rposition(|w| w.start_secs <= secs);
+        line.iter()
+            .enumerate()
+            .map(|(i, w)| {
+                let class = if Some(i) == active {
+                    " class=\"active\""
+                } else {
+                    ""
+                };
+                format!("<span{class}>{}</span>", escape_vtt_text(&w.word))
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Scene HTML with the caption layer added and filled for `frame`. The layer
+    /// uses the theme's body font, text color, and primary color for the active
+    /// word; templates can restyle it via `#vidgen-caption`.
+    pub fn frame_html(
+        &self,
+        scene_html: &str,
+        theme: &ThemeConfig,
+        frame: u32,
+        fps: u32,
+    ) -> String {
+        let layer = format!(
+            "<style>\
+             #{id}{{position:fixed;left:5%;right:5%;bottom:8%;z-index:2147483647;\
+             text-align:center;pointer-events:none;font-family:'{font}',system-ui,sans-serif;\
+             font-size:calc(min(100vw,100vh)*0.05);font-weight:700;line-height:1.3;\
+             color:{text};text-shadow:0 0 0.3em rgba(0,0,0,0.8)}}\
+             #{id} span.active{{color:{primary}}}\
+             </style><div id=\"{id}\">{markup}</div>",
+            id = CAPTION_LAYER_ID,
+            font = theme.font_body,
+            text = theme.text,
+            primary = theme.primary,
+            markup = self.markup_at(frame as f64 / fps.max(1) as f64),
+        );
+        match scene_html.rfind("</body>") {
+            Some(pos) => format!("{}{layer}{}", &scene_html[..pos], &scene_html[pos..]),
+            None => format!("{scene_html}{layer}"),
+        }
+    }
+
+    /// JavaScript that replaces the caption layer's content with `markup`.
+    pub fn update_js(markup: &str) -> String {
+        format!(
+            "document.getElementById('{CAPTION_LAYER_ID}').innerHTML = {};",
+            serde_json::Value::from(markup)
+        )
+    }
+}
+
+/// Format seconds as WebVTT timestamp: "HH:MM:SS.mmm"
+fn format_vtt_time(secs: f64) -> String {
+    format_srt_time(secs).replacen(',', ".", 1)
+}
+
 /// Format seconds as SRT timestamp: "HH:MM:SS,mmm"
 fn format_srt_time(secs: f64) -> String {
     let total_ms = (secs * 1000.0).round() as u64;
@@ -76,6 +248,43 @@ fn format_srt_time(secs: f64) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_force_style_presets() {
+        assert_eq!(force_style(None, None), DEFAULT_SUBTITLE_STYLE);
+        assert_eq!(
+            force_style(Some("youtube"), None),
+            "FontName=Roboto,FontSize=22,PrimaryColour=&H00FFFFFF,OutlineColour=&H00000000,\
+             BorderStyle=1,Outline=2,Shadow=1,Alignment=2,MarginV=30"
+        );
+        assert_eq!(
+            force_style(Some("tiktok-bold"), None),
+            "FontName=Montserrat,FontSize=28,Bold=1,PrimaryColour=&H00FFFFFF,\
+             OutlineColour=&H00000000,BorderStyle=1,Outline=4,Shadow=0,Alignment=2,MarginV=120"
+        );
+        assert_eq!(
+            force_style(Some("minimal"), None),
+            "FontName=Helvetica,FontSize=18,PrimaryColour=&H00FFFFFF,BorderStyle=1,Outline=0,\
+             Shadow=1,Alignment=2,MarginV=20"
+        );
+        assert!(force_style(Some("clean"), None).contains("BorderStyle=3"));
+    }
+
+    #[test]
+    fn test_force_style_custom_overrides_win() {
+        let style = force_style(Some("youtube"), Some("FontSize=30, marginv=80,Italic=1"));
+        assert!(style.contains("FontSize=30"));
+        assert!(!style.contains("FontSize=22"));
+        // Keys match case-insensitively and keep the preset's position
+        assert!(style.contains("MarginV=80"));
+        assert!(style.ends_with("MarginV=80,Italic=1"));
+
+        // Overrides also apply on top of the default style
+        assert_eq!(
+            force_style(None, Some("Alignment=8")),
+            "FontSize=24,PrimaryColour=&H00FFFFFF,Alignment=8"
+        );
+    }
+
     #[test]
     fn test_format_srt_time() {
         assert_eq!(format_srt_time(0.0), "00:00:00,000");
@@ -121,6 +330,101 @@ mod tests {
         assert!(srt.contains("2\n00:00:02,500 --> 00:00:05,000\nGoodbye world\n"));
     }
 
+    #[test]
+    fn test_karaoke_vtt_inline_word_tags() {
+        let words: Vec<WordTimestamp> = ["Hello", "big", "world", "again"]
+            .iter()
+            .enumerate()
+            .map(|(i, w)| WordTimestamp {
+                word: w.to_string(),
+                start_secs: 1.0 + i as f64 * 0.5,
+                end_secs: 1.5 + i as f64 * 0.5,
+            })
+            .collect();
+        let vtt = to_karaoke_vtt(&words, 3);
+        assert!(vtt.starts_with("WEBVTT\n\n"));
+        assert!(vtt.contains(
+            "00:00:01.000 --> 00:00:02.500\nHello <00:00:01.500>big <00:00:02.000>world\n\n"
+        ));
+        // The next cue starts fresh: its first word needs no tag
+        assert!(vtt.contains("00:00:02.500 --> 00:00:03.000\nagain\n\n"));
+    }
+
+    #[test]
+    fn test_karaoke_vtt_escapes_and_skips_out_of_range_tags() {
+        let words = vec![
+            WordTimestamp {
+                word: "R&D".into(),
+                start_secs: 0.0,
+                end_secs: 0.4,
+            },
+            WordTimestamp {
+                word: "<now>".into(),
+                start_secs: 0.0,
+                end_secs: 0.8,
+            },
+        ];
+        let vtt = to_karaoke_vtt(&words, 6);
+        assert!(vtt.contains("00:00:00.000 --> 00:00:00.800\nR&amp;D &lt;now&gt;\n"));
+        assert_eq!(to_karaoke_vtt(&[], 6), "WEBVTT\n\n");
+    }
+
+    #[test]
+    fn test_caption_layer_shows_active_word_at_frame() {
+        // Narration starts 1.0s into the scene (padding_before already applied)
+        let words: Vec<WordTimestamp> = ["Hello", "big", "world", "again"]
+            .iter()
+            .enumerate()
+            .map(|(i, w)| WordTimestamp {
+                word: w.to_string(),
+                start_secs: 1.0 + i as f64 * 0.5,
+                end_secs: 1.5 + i as f64 * 0.5,
+            })
+            .collect();
+        let captions = SceneCaptions {
+            words,
+            max_words_per_line: 3,
+        };
+        let scene = crate::scene::parse_scene(
+            "---\ntemplate: title-card\nprops:\n  title: \"Captions\"\n---\nHello big world again",
+            std::path::Path::new("01-intro.md"),
+        )
+        .unwrap();
+        let theme = ThemeConfig::default();
+        let registry = crate::template::TemplateRegistry::new().unwrap();
+        let html = registry
+            .render_scene_html(&scene, &theme, 1920, 1080, 0, 120, None)
+            .unwrap();
+
+        // Frame 48 at 30fps = 1.6s: "big" is being spoken within the first line
+        let frame = captions.frame_html(&html, &theme, 48, 30);
+        assert!(frame.contains(
+            "<div id=\"vidgen-caption\"><span>Hello</span> <span class=\"active\">big</span> \
+             <span>world</span></div>"
+        ));
+        assert!(frame.find("vidgen-caption\">").unwrap() < frame.rfind("</body>").unwrap());
+        assert!(frame.contains(&format!("span.active{{color:{}}}", theme.primary)));
+
+        // Before the narration the layer is empty; frame 80 (2.67s) is on the next line
+        assert!(captions
+            .frame_html(&html, &theme, 0, 30)
+            .contains("<div id=\"vidgen-caption\"></div>"));
+        assert_eq!(
+            captions.markup_at(80.0 / 30.0),
+            "<span class=\"active\">again</span>"
+        );
+        assert_eq!(captions.markup_at(3.0), "");
+    }
+
+    #[test]
+    fn test_caption_update_js_quotes_markup() {
+        let js = SceneCaptions::update_js("<span>it's \"on\"</span>");
+        assert_eq!(
+            js,
+            "document.getElementById('vidgen-caption').innerHTML = \"<span>it's \\\"on\\\"</span>\";"
+        );
+    }
+
     #[test]
     fn test_group_empty() {
         let entries = group_into_subtitles(&[], 6);
@@ -1,9 +1,10 @@
-use crate::config::ThemeConfig;
+use crate::config::{RenderConfig, ThemeConfig};
 use crate::error::{VidgenError, VidgenResult};
-use crate::scene::Scene;
+use crate::scene::{BackgroundConfig, Scene};
 use handlebars::Handlebars;
 use serde_json::json;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use tracing::{debug, trace, warn};
 
 /// Check if a string contains emoji characters (Unicode ranges for common emoji).
@@ -57,6 +58,50 @@ fn inject_emoji_support(html: &str) -> String {
     }
 }
 
+/// Font family embedded by `render.emoji_fallback`. The built-in templates list
+/// it last in their font stacks, so it only supplies glyphs the theme fonts lack.
+pub const EMOJI_FALLBACK_FAMILY: &str = "Noto Color Emoji";
+
+/// A Noto Color Emoji font in `<project>/assets/fonts/` (e.g. `NotoColorEmoji.ttf`).
+pub fn find_emoji_font(project_path: &Path) -> Option<PathBuf> {
+    let mut fonts: Vec<PathBuf> = std::fs::read_dir(project_path.join("assets").join("fonts"))
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            let name = p
+                .file_name()
+                .map(|n| n.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            name.starts_with("notocoloremoji")
+                && [".ttf", ".otf", ".woff2"].iter().any(|ext| name.ends_with(ext))
+        })
+        .collect();
+    fonts.sort();
+    fonts.into_iter().next()
+}
+
+/// `@font-face` for the emoji font, plus a page-wide fallback stack for custom
+/// templates that don't set a font. Inserted at the start of `<head>` so the
+/// template's own `font-family` rules still win.
+fn inject_emoji_font(html: &str, font: &Path) -> String {
+    let url = format!("file://{}", font.display()).replace('\'', "%27");
+    let style = format!(
+        "<style>@font-face {{ font-family: '{EMOJI_FALLBACK_FAMILY}'; src: url('{url}'); }}\n\
+         body {{ font-family: system-ui, sans-serif, '{EMOJI_FALLBACK_FAMILY}'; }}</style>"
+    );
+    match html.find("<head") {
+        Some(pos) => match html[pos..].find('>') {
+            Some(close) => {
+                let insert_pos = pos + close + 1;
+                format!("{}{}{}", &html[..insert_pos], style, &html[insert_pos..])
+            }
+            None => html.to_string(),
+        },
+        None => format!("{style}{html}"),
+    }
+}
+
 /// Resolve `@assets/...` prefixes in a JSON value to absolute `file://` URLs.
 /// Only transforms string values; recurses into arrays and objects.
 fn resolve_asset_values(value: &mut serde_json::Value, project_path: &Path) {
@@ -104,6 +149,273 @@ fn inject_base_tag(html: &str, project_path: &Path) -> String {
     }
 }
 
+/// Motion progress for background images. Uses the same voiceover window as the
+/// text animations (`--content-progress`, set per frame by the capture loop), so
+/// the background settles together with the content during the hold.
+const BG_MOTION_PROGRESS: &str = "var(--content-progress, var(--progress, 0))";
+
+/// CSS transform for a `background.motion` value.
+fn background_motion_transform(motion: &str) -> Option<String> {
+    let p = BG_MOTION_PROGRESS;
+    match motion {
+        "zoom-in" => Some(format!("scale(calc(1 + 0.12 * {p}))")),
+        "zoom-out" => Some(format!("scale(calc(1.12 - 0.12 * {p}))")),
+        "pan-left" => Some(format!("scale(1.12) translateX(calc(5% - 10% * {p}))")),
+        "pan-right" => Some(format!("scale(1.12) translateX(calc(-5% + 10% * {p}))")),
+        _ => None,
+    }
+}
+
+/// CSS `background` layers for a scene background, top to bottom: `overlay`,
+/// then `image`, then `color` showing through transparent parts of the image.
+fn background_layers(bg: &BackgroundConfig, project_path: Option<&Path>) -> Vec<String> {
+    let mut layers = Vec::new();
+    if let Some(overlay) = bg.overlay.as_deref() {
+        if overlay.contains("gradient(") {
+            layers.push(overlay.to_string());
+        } else {
+            // A plain color only works as the bottom layer; wrap it so it can sit on top
+            layers.push(format!("linear-gradient({overlay}, {overlay})"));
+        }
+    }
+    if let Some(image) = bg.image.as_deref() {
+        let url = match (image.strip_prefix("@assets/"), project_path) {
+            (Some(suffix), Some(pp)) => {
+                format!("file://{}", pp.join("assets").join(suffix).display())
+            }
+            _ => image.to_string(),
+        };
+        layers.push(format!(
+            "url('{}') center/cover no-repeat",
+            url.replace('\'', "%27").replace('"', "%22")
+        ));
+    }
+    if let Some(color) = bg.color.as_deref() {
+        layers.push(color.to_string());
+    }
+    layers
+}
+
+/// Full-bleed layer for `background.image` and `background.overlay`, painted
+/// behind the template content over the scene's `color`.
+fn background_layer(bg: &BackgroundConfig, project_path: Option<&Path>) -> Option<String> {
+    if bg.image.is_none() && bg.overlay.is_none() {
+        return None;
+    }
+    let transform = bg
+        .motion
+        .as_deref()
+        .and_then(background_motion_transform)
+        .map(|t| format!("transform:{t};"))
+        .unwrap_or_default();
+    // Blur softens the layer's edges too, so bleed it past the viewport
+    let (inset, filter) = match bg.blur.filter(|b| *b > 0.0) {
+        Some(blur) => (format!("-{}px", blur * 2.0), format!("filter:blur({blur}px);")),
+        None => ("0".to_string(), String::new()),
+    };
+    Some(format!(
+        "<div class=\"vidgen-bg-image\" style=\"position:fixed;inset:{inset};z-index:-1;\
+         background:{};transform-origin:center;{transform}{filter}\"></div>",
+        background_layers(bg, project_path).join(", ").replace('"', "&quot;")
+    ))
+}
+
+/// Insert `layer` right after the opening `<body>` tag.
+fn inject_after_body_open(html: &str, layer: &str) -> String {
+    match html.find("<body") {
+        Some(pos) => match html[pos..].find('>') {
+            Some(close) => {
+                let insert_pos = pos + close + 1;
+                format!("{}\n{}{}", &html[..insert_pos], layer, &html[insert_pos..])
+            }
+            None => html.to_string(),
+        },
+        None => html.to_string(),
+    }
+}
+
+/// Props that accept inline markdown (`markdown: true`), per built-in template.
+/// These templates render the props with triple-stache when `markdown` is set.
+const MARKDOWN_PROPS: &[(&str, &[&str])] = &[
+    ("quote-card", &["quote"]),
+    ("cta-card", &["heading", "items"]),
+];
+
+/// Replace the template's markdown-enabled props with rendered HTML and set the
+/// `markdown` flag the templates use to switch to triple-stache or `{{md}}` output.
+fn apply_prop_markdown(data: &mut serde_json::Value, template_name: &str) {
+    let Some(obj) = data.as_object_mut() else {
+        return;
+    };
+    obj.insert("markdown".into(), json!(true));
+    let Some((_, props)) = MARKDOWN_PROPS.iter().find(|(t, _)| *t == template_name) else {
+        return;
+    };
+    for key in props.iter() {
+        match obj.get_mut(*key) {
+            Some(serde_json::Value::String(s)) => *s = inline_markdown_to_html(s),
+            Some(serde_json::Value::Array(items)) => {
+                for item in items {
+                    if let serde_json::Value::String(s) = item {
+                        *s = inline_markdown_to_html(s);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Convert block markdown (paragraphs, lists, headings, code) to HTML.
+/// Raw HTML in the source is escaped rather than passed through.
+fn markdown_to_html(text: &str) -> String {
+    use pulldown_cmark::{Event, Options, Parser};
+
+    let parser = Parser::new_ext(text, Options::ENABLE_STRIKETHROUGH).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        other => other,
+    });
+    let mut html = String::with_capacity(text.len() * 3 / 2);
+    pulldown_cmark::html::push_html(&mut html, parser);
+    html
+}
+
+/// Render a single-line prop with [`markdown_to_html`], dropping the `<p>`
+/// wrapper so the HTML fits inside the template's own element. Text with
+/// several blocks keeps its full markup.
+fn inline_markdown_to_html(text: &str) -> String {
+    let html = markdown_to_html(text);
+    let html = html.trim_end();
+    match html.strip_prefix("<p>").and_then(|s| s.strip_suffix("</p>")) {
+        Some(inner) if !inner.contains("<p>") => inner.to_string(),
+        _ => html.to_string(),
+    }
+}
+
+/// `{{md field}}`: render a string as markdown. Helper output is written
+/// unescaped, so the HTML isn't escaped a second time by Handlebars.
+fn md_helper(
+    h: &handlebars::Helper,
+    _: &Handlebars,
+    _: &handlebars::Context,
+    _: &mut handlebars::RenderContext,
+    out: &mut dyn handlebars::Output,
+) -> handlebars::HelperResult {
+    let text = match h.param(0).map(|p| p.value()) {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(serde_json::Value::Null) | None => return Ok(()),
+        Some(other) => other.to_string(),
+    };
+    out.write(&markdown_to_html(&text))?;
+    Ok(())
+}
+
+// Formatting helpers for templates, e.g. `{{upper title}}` or `{{number_format views 2}}`
+handlebars::handlebars_helper!(upper_helper: |s: str| s.to_uppercase());
+handlebars::handlebars_helper!(lower_helper: |s: str| s.to_lowercase());
+handlebars::handlebars_helper!(truncate_helper: |s: str, n: u64| truncate_text(s, n as usize));
+handlebars::handlebars_helper!(repeat_helper: |s: str, n: u64| s.repeat(n as usize));
+handlebars::handlebars_helper!(number_format_helper: |value: Json, *args| {
+    let decimals = args.get(1).and_then(|d| d.as_u64()).map(|d| d.min(20) as usize);
+    numeric_value(Some(value)).map(|n| format_number(n, decimals)).unwrap_or_default()
+});
+
+/// Keep the first `max` characters of `text`, marking the cut with an ellipsis.
+fn truncate_text(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut out: String = text
+        .chars()
+        .take(max)
+        .collect::<String>()
+        .trim_end()
+        .to_string();
+    out.push('…');
+    out
+}
+
+/// Format `value` with thousands separators (`1234567.5` → `1,234,567.5`).
+/// `decimals` fixes the number of decimal places; otherwise the value's own are kept.
+fn format_number(value: f64, decimals: Option<usize>) -> String {
+    let formatted = match decimals {
+        Some(d) => format!("{:.*}", d, value.abs()),
+        None => value.abs().to_string(),
+    };
+    let (int_part, frac_part) = match formatted.split_once('.') {
+        Some((i, f)) => (i, Some(f)),
+        None => (formatted.as_str(), None),
+    };
+    let mut out = String::with_capacity(formatted.len() + int_part.len() / 3 + 1);
+    if value < 0.0 && formatted.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        out.push('-');
+    }
+    for (i, ch) in int_part.chars().enumerate() {
+        if i > 0 && (int_part.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(ch);
+    }
+    if let Some(frac) = frac_part {
+        out.push('.');
+        out.push_str(frac);
+    }
+    out
+}
+
+/// Numeric value of a prop, accepting numeric strings such as `"42.5"`.
+fn numeric_value(value: Option<&serde_json::Value>) -> Option<f64> {
+    match value? {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .filter(|v: &f64| v.is_finite())
+}
+
+/// Give each of the `bars` its `index`, a `percent` of the chart width
+/// (relative to `max`, or to the largest value when `max` is unset) and a
+/// `color` (defaulting to `default_color`), and set `total_bars`.
+fn prepare_bar_chart(data: &mut serde_json::Value, default_color: &str) {
+    let Some(obj) = data.as_object_mut() else {
+        return;
+    };
+    let bars = obj
+        .get("bars")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let largest = bars
+        .iter()
+        .filter_map(|bar| numeric_value(bar.get("value")))
+        .fold(0.0_f64, f64::max);
+    let max = numeric_value(obj.get("max"))
+        .filter(|m| *m > 0.0)
+        .unwrap_or(if largest > 0.0 { largest } else { 1.0 });
+
+    let total_bars = bars.len();
+    let bars: Vec<serde_json::Value> = bars
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut bar)| {
+            let value = numeric_value(bar.get("value")).unwrap_or(0.0);
+            let percent = (value / max * 100.0).clamp(0.0, 100.0);
+            if let Some(bar) = bar.as_object_mut() {
+                bar.insert("index".into(), json!(i));
+                // Display form of f64 keeps CSS values short ("50", "33.33")
+                let percent = (percent * 100.0).round() / 100.0;
+                bar.insert("percent".into(), json!(percent.to_string()));
+                if !bar.get("color").is_some_and(|c| c.is_string()) {
+                    bar.insert("color".into(), json!(default_color));
+                }
+            }
+            bar
+        })
+        .collect();
+    obj.insert("bars".into(), json!(bars));
+    obj.insert("total_bars".into(), json!(total_bars.max(1)));
+}
+
 const TITLE_CARD_TEMPLATE: &str = include_str!("templates/title-card.html");
 const CONTENT_TEXT_TEMPLATE: &str = include_str!("templates/content-text.html");
 const QUOTE_CARD_TEMPLATE: &str = include_str!("templates/quote-card.html");
@@ -113,15 +425,435 @@ const SPLIT_SCREEN_TEMPLATE: &str = include_str!("templates/split-screen.html");
 const KINETIC_TEXT_TEMPLATE: &str = include_str!("templates/kinetic-text.html");
 const SLIDESHOW_TEMPLATE: &str = include_str!("templates/slideshow.html");
 const CAPTION_OVERLAY_TEMPLATE: &str = include_str!("templates/caption-overlay.html");
+const BAR_CHART_TEMPLATE: &str = include_str!("templates/bar-chart.html");
+const CODE_BLOCK_TEMPLATE: &str = include_str!("templates/code-block.html");
+const RICH_TEXT_TEMPLATE: &str = include_str!("templates/rich-text.html");
+const THEME_PREVIEW_TEMPLATE: &str = include_str!("templates/theme-preview.html");
+
+/// Swatch sheet behind `vidgen theme-preview`. Registered like the other
+/// built-ins but left out of [`TemplateRegistry::template_names`].
+pub const THEME_PREVIEW: &str = "theme-preview";
+
+/// Expected JSON type of a template prop, used for coercion and validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropType {
+    String,
+    Number,
+    Boolean,
+    Array,
+}
+
+impl PropType {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "string" => Some(Self::String),
+            "number" => Some(Self::Number),
+            "boolean" | "bool" => Some(Self::Boolean),
+            "array" => Some(Self::Array),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+            Self::Array => "array",
+        }
+    }
+
+    fn matches(self, value: &serde_json::Value) -> bool {
+        match self {
+            Self::String => value.is_string(),
+            Self::Number => value.is_number(),
+            Self::Boolean => value.is_boolean(),
+            Self::Array => value.is_array(),
+        }
+    }
+}
+
+/// A built-in template prop: its type plus what the JSON Schema export needs.
+struct PropSpec {
+    name: &'static str,
+    ty: PropType,
+    required: bool,
+    /// Value the renderer injects when the prop is omitted
+    default: Option<&'static str>,
+    /// Allowed values for string props that select a variant
+    choices: &'static [&'static str],
+    /// JSON type of array elements
+    items: Option<&'static str>,
+    description: &'static str,
+}
+
+const fn required(name: &'static str, ty: PropType, description: &'static str) -> PropSpec {
+    PropSpec {
+        name,
+        ty,
+        required: true,
+        default: None,
+        choices: &[],
+        items: None,
+        description,
+    }
+}
+
+const fn optional(name: &'static str, ty: PropType, description: &'static str) -> PropSpec {
+    PropSpec {
+        required: false,
+        ..required(name, ty, description)
+    }
+}
+
+const fn list(
+    name: &'static str,
+    is_required: bool,
+    items: &'static str,
+    description: &'static str,
+) -> PropSpec {
+    PropSpec {
+        required: is_required,
+        items: Some(items),
+        ..required(name, PropType::Array, description)
+    }
+}
+
+const fn choice(
+    name: &'static str,
+    default: Option<&'static str>,
+    choices: &'static [&'static str],
+    description: &'static str,
+) -> PropSpec {
+    PropSpec {
+        default,
+        choices,
+        ..optional(name, PropType::String, description)
+    }
+}
+
+/// Props of the built-in templates.
+const BUILTIN_PROPS: &[(&str, &[PropSpec])] = &[
+    (
+        "title-card",
+        &[
+            required("title", PropType::String, "Main heading text"),
+            optional(
+                "subtitle",
+                PropType::String,
+                "Secondary text below the title",
+            ),
+        ],
+    ),
+    (
+        "content-text",
+        &[
+            required("heading", PropType::String, "Section heading"),
+            required("body", PropType::String, "Body text content"),
+        ],
+    ),
+    (
+        "rich-text",
+        &[
+            required(
+                "content",
+                PropType::String,
+                "Markdown content: paragraphs, lists, headings, emphasis, code",
+            ),
+            optional("heading", PropType::String, "Heading above the content"),
+        ],
+    ),
+    (
+        "quote-card",
+        &[
+            required("quote", PropType::String, "Quote text"),
+            optional("author", PropType::String, "Author name"),
+            optional(
+                "source",
+                PropType::String,
+                "Source attribution, e.g. book or speech name",
+            ),
+            optional(
+                "background_style",
+                PropType::String,
+                "CSS background for the quote card",
+            ),
+        ],
+    ),
+    (
+        "split-screen",
+        &[
+            list("panels", true, "object", "Panels of {label, content}"),
+            choice(
+                "layout",
+                None,
+                &["33-67", "67-33", "grid"],
+                "Column layout (equal columns when omitted)",
+            ),
+        ],
+    ),
+    (
+        "lower-third",
+        &[
+            required("name", PropType::String, "Person or speaker name"),
+            optional("title", PropType::String, "Title or role"),
+            choice(
+                "position",
+                Some("left"),
+                &["left", "center", "right"],
+                "Bar position",
+            ),
+            optional(
+                "accent_color",
+                PropType::String,
+                "Accent bar color (defaults to the theme primary)",
+            ),
+        ],
+    ),
+    (
+        "cta-card",
+        &[
+            required("heading", PropType::String, "Main CTA heading"),
+            optional("subheading", PropType::String, "Secondary text"),
+            list("items", false, "string", "Bulleted list items"),
+            optional("qr_code_url", PropType::String, "URL rendered as a QR code"),
+        ],
+    ),
+    (
+        "kinetic-text",
+        &[
+            optional(
+                "text",
+                PropType::String,
+                "Text revealed word by word (falls back to the script body)",
+            ),
+            choice(
+                "style",
+                Some("fade"),
+                &["fade", "bounce", "slide"],
+                "Word reveal animation",
+            ),
+            optional(
+                "highlight_color",
+                PropType::String,
+                "Color of highlighted words",
+            ),
+        ],
+    ),
+    (
+        "slideshow",
+        &[list(
+            "slides",
+            true,
+            "object",
+            "Slides of {heading, body, image}",
+        )],
+    ),
+    (
+        "bar-chart",
+        &[
+            list("bars", true, "object", "Bars of {label, value, color}"),
+            optional("heading", PropType::String, "Chart heading"),
+            optional(
+                "max",
+                PropType::Number,
+                "Value of a full-width bar (defaults to the largest value)",
+            ),
+        ],
+    ),
+    (
+        "code-block",
+        &[
+            required("code", PropType::String, "Source code, one line per line"),
+            optional(
+                "language",
+                PropType::String,
+                "Language for highlighting, e.g. rust, python, js, bash",
+            ),
+            list(
+                "highlight_lines",
+                false,
+                "number",
+                "1-based line numbers to emphasize",
+            ),
+        ],
+    ),
+    (
+        "caption-overlay",
+        &[
+            optional(
+                "text",
+                PropType::String,
+                "Caption text (falls back to the script body)",
+            ),
+            choice(
+                "style",
+                Some("outline"),
+                &["outline", "background-box", "drop-shadow"],
+                "Text style",
+            ),
+            choice(
+                "position",
+                Some("bottom"),
+                &["bottom", "top", "center"],
+                "Caption position",
+            ),
+        ],
+    ),
+];
+
+fn builtin_props(template: &str) -> Option<&'static [PropSpec]> {
+    BUILTIN_PROPS
+        .iter()
+        .find(|(name, _)| *name == template)
+        .map(|(_, props)| *props)
+}
+
+/// JSON Schema (draft 2020-12) for a built-in template's props: types,
+/// required props, defaults and allowed values. `None` for unknown templates.
+pub fn prop_schema(template: &str) -> Option<serde_json::Value> {
+    let props = builtin_props(template)?;
+    let properties: serde_json::Map<String, serde_json::Value> = props
+        .iter()
+        .map(|prop| {
+            let mut schema = json!({ "type": prop.ty.name(), "description": prop.description });
+            if let Some(items) = prop.items {
+                schema["items"] = json!({ "type": items });
+            }
+            if !prop.choices.is_empty() {
+                schema["enum"] = json!(prop.choices);
+            }
+            if let Some(default) = prop.default {
+                schema["default"] = json!(default);
+            }
+            (prop.name.to_string(), schema)
+        })
+        .collect();
+    let required: Vec<&str> = props
+        .iter()
+        .filter(|p| p.required)
+        .map(|p| p.name)
+        .collect();
+    Some(json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": template,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    }))
+}
+
+/// Number of seeded `rand` values (each in `[0, 1)`) given to every template,
+/// e.g. `{{lookup rand 0}}` or `--jitter: {{lookup rand 3}}`.
+const RAND_VALUE_COUNT: usize = 16;
+
+/// Defaults that suit a built-in template.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TemplateDefaults {
+    /// Transition used at a boundary next to one of the template's scenes when
+    /// neither the scenes nor `[video] default_transition` set one
+    pub transition: Option<&'static str>,
+    /// Shortest scene length (seconds) at which the template's progress-driven
+    /// animation is still readable; shorter scenes rush through it
+    pub min_duration: Option<f64>,
+}
+
+const fn defaults(transition: Option<&'static str>, min_duration: Option<f64>) -> TemplateDefaults {
+    TemplateDefaults {
+        transition,
+        min_duration,
+    }
+}
+
+const TEMPLATE_DEFAULTS: &[(&str, TemplateDefaults)] = &[
+    ("slideshow", defaults(Some("slide-left"), Some(4.0))),
+    ("kinetic-text", defaults(None, Some(3.0))),
+    ("caption-overlay", defaults(None, Some(2.5))),
+    ("lower-third", defaults(None, Some(3.0))),
+    ("cta-card", defaults(None, Some(3.0))),
+    ("content-text", defaults(None, Some(2.0))),
+    ("bar-chart", defaults(None, Some(3.0))),
+    ("code-block", defaults(None, Some(4.0))),
+    ("rich-text", defaults(None, Some(3.0))),
+];
+
+/// The defaults declared for a built-in template, if any.
+pub fn template_defaults(template: &str) -> Option<&'static TemplateDefaults> {
+    TEMPLATE_DEFAULTS
+        .iter()
+        .find(|(name, _)| *name == template)
+        .map(|(_, defaults)| defaults)
+}
+
+/// The default transition declared for a template, if any.
+pub fn template_default_transition(template: &str) -> Option<&'static str> {
+    template_defaults(template)?.transition
+}
+
+/// Read a custom template's prop types from a `<!-- vidgen:props name=type ... -->`
+/// comment, e.g. `<!-- vidgen:props count=number show_logo=boolean -->`.
+fn parse_prop_declaration(content: &str) -> Option<HashMap<String, PropType>> {
+    let start = content.find("<!-- vidgen:props")? + "<!-- vidgen:props".len();
+    let end = start + content[start..].find("-->")?;
+    Some(
+        content[start..end]
+            .split_whitespace()
+            .filter_map(|pair| {
+                let (name, ty) = pair.split_once('=')?;
+                Some((name.to_string(), PropType::parse(ty)?))
+            })
+            .collect(),
+    )
+}
+
+/// Convert a prop to its expected type when the conversion is unambiguous
+/// (`"5"` → 5, `"true"` → true, 5 → `"5"`). Other values are returned unchanged.
+fn coerce_prop(value: &serde_json::Value, expected: PropType) -> serde_json::Value {
+    use serde_json::Value;
+    match (expected, value) {
+        (PropType::Number, Value::String(s)) => {
+            let s = s.trim();
+            if let Ok(i) = s.parse::<i64>() {
+                json!(i)
+            } else {
+                s.parse::<f64>()
+                    .ok()
+                    .and_then(serde_json::Number::from_f64)
+                    .map(Value::Number)
+                    .unwrap_or_else(|| value.clone())
+            }
+        }
+        (PropType::Boolean, Value::String(s)) => match s.trim().to_ascii_lowercase().as_str() {
+            "true" | "yes" => Value::Bool(true),
+            "false" | "no" => Value::Bool(false),
+            _ => value.clone(),
+        },
+        (PropType::String, Value::Number(n)) => Value::String(n.to_string()),
+        (PropType::String, Value::Bool(b)) => Value::String(b.to_string()),
+        _ => value.clone(),
+    }
+}
 
 pub struct TemplateRegistry<'a> {
     hbs: Handlebars<'a>,
+    /// Known prop types per template (built-ins plus declared custom templates).
+    prop_types: HashMap<String, HashMap<String, PropType>>,
+    /// Emoji font embedded in every page (`render.emoji_fallback`).
+    emoji_font: Option<PathBuf>,
+    /// Templates registered from the project, including overrides of built-ins.
+    project_templates: HashSet<String>,
 }
 
 impl<'a> TemplateRegistry<'a> {
     pub fn new() -> VidgenResult<Self> {
         let mut hbs = Handlebars::new();
         hbs.set_strict_mode(false); // Allow missing optional variables
+        hbs.register_helper("md", Box::new(md_helper));
+        hbs.register_helper("upper", Box::new(upper_helper));
+        hbs.register_helper("lower", Box::new(lower_helper));
+        hbs.register_helper("truncate", Box::new(truncate_helper));
+        hbs.register_helper("number_format", Box::new(number_format_helper));
+        hbs.register_helper("repeat", Box::new(repeat_helper));
 
         hbs.register_template_string("title-card", TITLE_CARD_TEMPLATE)
             .map_err(|e| VidgenError::TemplateRender(e.to_string()))?;
@@ -141,14 +873,62 @@ impl<'a> TemplateRegistry<'a> {
             .map_err(|e| VidgenError::TemplateRender(e.to_string()))?;
         hbs.register_template_string("caption-overlay", CAPTION_OVERLAY_TEMPLATE)
             .map_err(|e| VidgenError::TemplateRender(e.to_string()))?;
+        hbs.register_template_string("bar-chart", BAR_CHART_TEMPLATE)
+            .map_err(|e| VidgenError::TemplateRender(e.to_string()))?;
+        hbs.register_template_string("code-block", CODE_BLOCK_TEMPLATE)
+            .map_err(|e| VidgenError::TemplateRender(e.to_string()))?;
+        hbs.register_template_string("rich-text", RICH_TEXT_TEMPLATE)
+            .map_err(|e| VidgenError::TemplateRender(e.to_string()))?;
+        // Swatch sheet behind `vidgen theme-preview`; not meant for project scenes
+        hbs.register_template_string(THEME_PREVIEW, THEME_PREVIEW_TEMPLATE)
+            .map_err(|e| VidgenError::TemplateRender(e.to_string()))?;
+
+        debug!("Template registry initialized with 13 built-in templates");
+        let prop_types = BUILTIN_PROPS
+            .iter()
+            .map(|(template, props)| {
+                let types = props.iter().map(|p| (p.name.to_string(), p.ty)).collect();
+                (template.to_string(), types)
+            })
+            .collect();
+
+        Ok(Self {
+            hbs,
+            prop_types,
+            emoji_font: None,
+            project_templates: HashSet::new(),
+        })
+    }
 
-        debug!("Template registry initialized with 9 built-in templates");
-        Ok(Self { hbs })
+    /// Embed the project's Noto Color Emoji font when `render.emoji_fallback` is on.
+    /// Without the font file, pages keep using the Twemoji CDN.
+    pub fn apply_emoji_fallback(&mut self, render: &RenderConfig, project_path: &Path) {
+        if !render.emoji_fallback {
+            return;
+        }
+        self.emoji_font = find_emoji_font(project_path);
+        if self.emoji_font.is_none() {
+            warn!(
+                "render.emoji_fallback is on but no NotoColorEmoji font was found in {}",
+                project_path.join("assets").join("fonts").display()
+            );
+        }
     }
 
-    /// Return sorted list of all registered template names.
+    /// Emoji font embedded by [`Self::apply_emoji_fallback`], if any.
+    pub fn emoji_font(&self) -> Option<&Path> {
+        self.emoji_font.as_deref()
+    }
+
+    /// Return sorted list of all registered template names usable in scenes.
     pub fn template_names(&self) -> Vec<String> {
-        let mut names: Vec<String> = self.hbs.get_templates().keys().cloned().collect();
+        let mut names: Vec<String> = self
+            .hbs
+            .get_templates()
+            .keys()
+            .filter(|n| *n != THEME_PREVIEW)
+            .cloned()
+            .collect();
         names.sort();
         names
     }
@@ -158,9 +938,53 @@ impl<'a> TemplateRegistry<'a> {
         self.hbs.has_template(name)
     }
 
+    /// Scene props with the template's declared types applied (see `coerce_prop`).
+    fn coerced_props(&self, scene: &Scene) -> HashMap<String, serde_json::Value> {
+        let types = self.prop_types.get(&scene.frontmatter.template);
+        scene
+            .frontmatter
+            .props
+            .iter()
+            .map(|(key, value)| {
+                let value = match types.and_then(|t| t.get(key)) {
+                    Some(expected) => coerce_prop(value, *expected),
+                    None => value.clone(),
+                };
+                (key.clone(), value)
+            })
+            .collect()
+    }
+
+    /// Describe props whose type doesn't match what the scene's template expects,
+    /// even after coercion (e.g. a string where `cta-card` expects an `items` array).
+    pub fn prop_type_warnings(&self, scene: &Scene) -> Vec<String> {
+        let template = &scene.frontmatter.template;
+        let Some(types) = self.prop_types.get(template) else {
+            return Vec::new();
+        };
+        let mut warnings: Vec<String> = self
+            .coerced_props(scene)
+            .iter()
+            .filter_map(|(key, value)| {
+                let expected = types.get(key)?;
+                (!value.is_null() && !expected.matches(value)).then(|| {
+                    format!(
+                        "prop '{key}' should be of type {} for template '{template}' (got {value})",
+                        expected.name()
+                    )
+                })
+            })
+            .collect();
+        warnings.sort();
+        warnings
+    }
+
     /// Register project-local templates from `<project_path>/templates/components/*.html`.
-    /// Project templates can override built-in templates.
+    /// Project templates can override built-in templates. Partials from
+    /// `templates/partials/*.html` (or `.hbs`) are registered first, so components
+    /// can include them by file stem (`{{> base}}`).
     pub fn register_project_templates(&mut self, project_path: &Path) -> VidgenResult<()> {
+        self.register_project_partials(project_path)?;
         let components_dir = project_path.join("templates").join("components");
         if !components_dir.exists() {
             return Ok(());
@@ -171,7 +995,17 @@ impl<'a> TemplateRegistry<'a> {
             if path.extension().is_some_and(|ext| ext == "html") {
                 if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
                     debug!("Registering project template: {}", stem);
+                    self.project_templates.insert(stem.to_string());
                     let content = std::fs::read_to_string(&path)?;
+                    // An override replaces the built-in's prop types with its own (if declared)
+                    match parse_prop_declaration(&content) {
+                        Some(types) => {
+                            self.prop_types.insert(stem.to_string(), types);
+                        }
+                        None => {
+                            self.prop_types.remove(stem);
+                        }
+                    }
                     self.hbs
                         .register_template_string(stem, &content)
                         .map_err(|e| {
@@ -186,12 +1020,76 @@ impl<'a> TemplateRegistry<'a> {
         Ok(())
     }
 
+    /// Register `<project_path>/templates/partials/*.{html,hbs}` as Handlebars
+    /// partials named by file stem.
+    fn register_project_partials(&mut self, project_path: &Path) -> VidgenResult<()> {
+        let partials_dir = project_path.join("templates").join("partials");
+        if !partials_dir.exists() {
+            return Ok(());
+        }
+        for entry in std::fs::read_dir(&partials_dir)?.flatten() {
+            let path = entry.path();
+            if !path
+                .extension()
+                .is_some_and(|ext| ext == "html" || ext == "hbs")
+            {
+                continue;
+            }
+            if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                debug!("Registering project partial: {}", stem);
+                let content = std::fs::read_to_string(&path)?;
+                self.hbs.register_partial(stem, &content).map_err(|e| {
+                    VidgenError::TemplateRender(format!(
+                        "Failed to register project partial '{}': {}",
+                        stem, e
+                    ))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that a scene sets every prop its built-in template requires, so a
+    /// missing `quote` fails with a clear error instead of rendering blank.
+    /// Empty strings and lists count as missing. Custom templates, including
+    /// project overrides of built-ins, are not checked.
+    pub fn validate_scene_props(&self, scene: &Scene) -> VidgenResult<()> {
+        let template_name = &scene.frontmatter.template;
+        if self.project_templates.contains(template_name) {
+            return Ok(());
+        }
+        let Some(specs) = builtin_props(template_name) else {
+            return Ok(());
+        };
+        let missing: Vec<&str> = specs
+            .iter()
+            .filter(|spec| spec.required)
+            .filter(|spec| match scene.frontmatter.props.get(spec.name) {
+                None | Some(serde_json::Value::Null) => true,
+                Some(serde_json::Value::String(s)) => s.trim().is_empty(),
+                Some(serde_json::Value::Array(items)) => items.is_empty(),
+                Some(_) => false,
+            })
+            .map(|spec| spec.name)
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        Err(VidgenError::TemplateRender(format!(
+            "{}: template '{}' is missing required prop(s): {}",
+            scene.source_path.display(),
+            template_name,
+            missing.join(", ")
+        )))
+    }
+
     /// Render a scene to a full HTML document string.
     ///
     /// `frame` and `total_frames` are injected for CSS custom property animation.
     /// If `project_path` is provided, asset references (`@assets/...`) in props are
     /// resolved to absolute `file://` URLs and a `<base>` tag is injected so that
     /// relative paths in templates work correctly in headless Chromium.
+    #[allow(clippy::too_many_arguments)]
     pub fn render_scene_html(
         &self,
         scene: &Scene,
@@ -211,6 +1109,7 @@ impl<'a> TemplateRegistry<'a> {
         if !self.hbs.has_template(template_name) {
             return Err(VidgenError::TemplateNotFound(template_name.clone()));
         }
+        self.validate_scene_props(scene)?;
 
         // Compute effective background: scene-level override or theme default
         let effective_bg = scene
@@ -233,13 +1132,18 @@ impl<'a> TemplateRegistry<'a> {
             "theme_text": &theme.text,
             "theme_font_heading": &theme.font_heading,
             "theme_font_body": &theme.font_body,
-            "script": &scene.script,
+            "script": crate::tts::pauses::strip_pause_markers(&scene.script),
+            "seed": scene.rand_seed(),
+            "rand": scene.rand_values(RAND_VALUE_COUNT),
         });
 
-        // Merge scene props into the top-level data
+        // Merge scene props (coerced to the template's prop types) into the top-level data
+        for warning in self.prop_type_warnings(scene) {
+            warn!("{}: {}", scene.source_path.display(), warning);
+        }
         if let Some(obj) = data.as_object_mut() {
-            for (key, value) in &scene.frontmatter.props {
-                obj.insert(key.clone(), value.clone());
+            for (key, value) in self.coerced_props(scene) {
+                obj.insert(key, value);
             }
         }
 
@@ -248,6 +1152,10 @@ impl<'a> TemplateRegistry<'a> {
             resolve_asset_values(&mut data, pp);
         }
 
+        if scene.frontmatter.markdown {
+            apply_prop_markdown(&mut data, template_name);
+        }
+
         // Inject defaults for lower-third template
         if template_name == "lower-third" {
             if let Some(obj) = data.as_object_mut() {
@@ -348,12 +1256,59 @@ impl<'a> TemplateRegistry<'a> {
             }
         }
 
+        // Code-block preprocessing: split code into highlighted line objects
+        if template_name == "code-block" {
+            let code = data.get("code").and_then(|v| v.as_str()).unwrap_or("");
+            let language = data.get("language").and_then(|v| v.as_str()).unwrap_or("");
+            let highlight: Vec<u64> = data
+                .get("highlight_lines")
+                .and_then(|v| v.as_array())
+                .map(|lines| lines.iter().filter_map(|l| l.as_u64()).collect())
+                .unwrap_or_default();
+            let lines: Vec<serde_json::Value> = crate::highlight::highlight_lines(code, language)
+                .into_iter()
+                .enumerate()
+                .map(|(i, html)| {
+                    json!({
+                        "index": i,
+                        "number": i + 1,
+                        "html": html,
+                        "highlighted": highlight.contains(&(i as u64 + 1)),
+                    })
+                })
+                .collect();
+            let total_lines = lines.len().max(1);
+            if let Some(obj) = data.as_object_mut() {
+                obj.insert("lines".into(), json!(lines));
+                obj.insert("total_lines".into(), json!(total_lines));
+            }
+        }
+
+        // Bar-chart preprocessing: scale values to bar widths, default colors
+        if template_name == "bar-chart" {
+            prepare_bar_chart(&mut data, &theme.primary);
+        }
+
         let html = self.hbs
             .render(template_name, &data)
             .map_err(|e| VidgenError::TemplateRender(e.to_string()))?;
 
-        // Inject Twemoji CDN script if emoji characters are detected
-        let html = inject_emoji_support(&html);
+        // Background image/overlay layer (with optional Ken Burns motion)
+        let html = match scene
+            .frontmatter
+            .background
+            .as_ref()
+            .and_then(|bg| background_layer(bg, project_path))
+        {
+            Some(layer) => inject_after_body_open(&html, &layer),
+            None => html,
+        };
+
+        // Embedded emoji font, else the Twemoji CDN script if emoji characters are detected
+        let html = match self.emoji_font {
+            Some(ref font) => inject_emoji_font(&html, font),
+            None => inject_emoji_support(&html),
+        };
 
         // Inject <base> tag for file:// asset resolution in headless Chromium
         if let Some(pp) = project_path {
@@ -396,6 +1351,54 @@ mod tests {
         assert!(html.contains("#0F172A")); // background color
     }
 
+    #[test]
+    fn test_title_card_without_subtitle_has_no_subtitle_element() {
+        let registry = TemplateRegistry::new().unwrap();
+        let content = "---\ntemplate: title-card\nprops:\n  title: \"Hello World\"\n---\nScript.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 0, 150, None)
+            .unwrap();
+        assert!(html.contains("<h1 class=\"title\">Hello World</h1>"));
+        assert!(!html.contains("class=\"subtitle\""));
+    }
+
+    #[test]
+    fn test_quote_card_without_attribution_has_no_divider() {
+        let registry = TemplateRegistry::new().unwrap();
+        let content = "---\ntemplate: quote-card\nprops:\n  quote: \"Less is more.\"\n---\nVoiceover.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 75, 150, None)
+            .unwrap();
+        assert!(!html.contains("class=\"divider\""));
+        assert!(!html.contains("class=\"attribution\""));
+        assert!(!html.contains("class=\"source\""));
+
+        // A source alone still gets the divider above it
+        let content = "---\ntemplate: quote-card\nprops:\n  quote: \"Less is more.\"\n  source: \"Design notes\"\n---\nVoiceover.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 75, 150, None)
+            .unwrap();
+        assert!(html.contains("class=\"divider\""));
+        assert!(html.contains("<p class=\"source\">Design notes</p>"));
+        assert!(!html.contains("class=\"attribution\""));
+    }
+
+    #[test]
+    fn test_split_screen_panel_without_label() {
+        let registry = TemplateRegistry::new().unwrap();
+        let content = "---\ntemplate: split-screen\nprops:\n  panels:\n    - content: \"Only content\"\n---\nVoiceover.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 50, 150, None)
+            .unwrap();
+        assert!(html.contains("Only content"));
+        assert!(!html.contains("class=\"panel-label\""));
+        assert!(!html.contains("class=\"panel-divider\""));
+    }
+
     #[test]
     fn test_render_content_text() {
         let registry = TemplateRegistry::new().unwrap();
@@ -429,6 +1432,76 @@ Voiceover."#;
         assert!(html.contains("&ldquo;")); // decorative quote mark
     }
 
+    #[test]
+    fn test_render_bar_chart() {
+        let registry = TemplateRegistry::new().unwrap();
+        let content = r##"---
+template: bar-chart
+props:
+  heading: "Monthly signups"
+  bars:
+    - label: "January"
+      value: 120
+    - label: "February"
+      value: "60"
+      color: "#22c55e"
+    - label: "March"
+      value: 240
+---
+Voiceover."##;
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 75, 150, None)
+            .unwrap();
+        assert!(html.contains("Monthly signups"));
+        for text in ["January", "February", "March", ">120<", ">60<", ">240<"] {
+            assert!(html.contains(text), "missing {text}");
+        }
+        // Widths are relative to the largest bar and grow with --content-progress
+        assert!(html.contains("--total-bars: 3;"));
+        assert!(html.contains("--bar-index: 0; --bar-percent: 50; --bar-color: #2563EB;"));
+        assert!(html.contains("--bar-index: 1; --bar-percent: 25; --bar-color: #22c55e;"));
+        assert!(html.contains("--bar-index: 2; --bar-percent: 100;"));
+        assert!(html.contains("var(--content-progress)"));
+
+        // An explicit max rescales the bars
+        let scaled = content.replace("  bars:", "  max: 480\n  bars:");
+        let scene = parse_scene(&scaled, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 75, 150, None)
+            .unwrap();
+        assert!(html.contains("--bar-index: 2; --bar-percent: 50;"));
+    }
+
+    #[test]
+    fn test_render_code_block() {
+        let registry = TemplateRegistry::new().unwrap();
+        let content = r#"---
+template: code-block
+props:
+  language: rust
+  highlight_lines: [2]
+  code: |
+    fn main() {
+        println!("a < b");
+    }
+---
+Voiceover."#;
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 75, 150, None)
+            .unwrap();
+        assert!(html.contains("class=\"language-rust\""));
+        assert!(html.contains("<span class=\"tok-keyword\">fn</span> main() {"));
+        assert!(html.contains("println!(<span class=\"tok-string\">&quot;a &lt; b&quot;</span>);"));
+        // One staggered line object per source line; line 2 is emphasized
+        assert!(html.contains("--total-lines: 3;"));
+        assert!(html.contains("<div class=\"line\" style=\"--line-index: 0;\">"));
+        assert!(html.contains("<div class=\"line highlighted\" style=\"--line-index: 1;\">"));
+        assert!(html.contains("<div class=\"line\" style=\"--line-index: 2;\">"));
+        assert!(html.contains("var(--content-progress)"));
+    }
+
     #[test]
     fn test_render_lower_third() {
         let registry = TemplateRegistry::new().unwrap();
@@ -467,6 +1540,294 @@ Voiceover."#;
         assert!(html.contains("Share with the world"));
     }
 
+    #[test]
+    fn test_quote_markdown_only_with_flag() {
+        let registry = TemplateRegistry::new().unwrap();
+        let plain = "---\ntemplate: quote-card\nprops:\n  quote: \"Ship **small** changes\"\n---\nVoiceover.";
+        let scene = parse_scene(plain, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 75, 150, None)
+            .unwrap();
+        assert!(html.contains("Ship **small** changes"));
+        assert!(!html.contains("<strong>"));
+
+        let flagged = "---\ntemplate: quote-card\nmarkdown: true\nprops:\n  quote: \"Ship **small** <changes>\"\n---\nVoiceover.";
+        let scene = parse_scene(flagged, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 75, 150, None)
+            .unwrap();
+        assert!(html.contains("Ship <strong>small</strong> &lt;changes&gt;"));
+    }
+
+    #[test]
+    fn test_cta_markdown_heading_and_items() {
+        let registry = TemplateRegistry::new().unwrap();
+        let content = r#"---
+template: cta-card
+markdown: true
+props:
+  heading: "Start *today*"
+  items:
+    - "**Free** forever"
+    - "No card needed"
+---
+Voiceover."#;
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 100, 150, None)
+            .unwrap();
+        assert!(html.contains("Start <em>today</em>"));
+        assert!(!html.contains("<p>Start"));
+        assert!(html.contains("<strong>Free</strong> forever"));
+        assert!(html.contains("No card needed"));
+    }
+
+    #[test]
+    fn test_content_text_body_markdown_only_with_flag() {
+        let registry = TemplateRegistry::new().unwrap();
+        let plain = "---\ntemplate: content-text\nprops:\n  heading: \"Notes\"\n  body: \"**x**\"\n---\nSay **this** literally.";
+        let scene = parse_scene(plain, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 75, 150, None)
+            .unwrap();
+        assert!(html.contains("<p class=\"body-text\">**x**</p>"));
+        assert!(!html.contains("<strong>"));
+
+        let flagged = "---\ntemplate: content-text\nmarkdown: true\nprops:\n  heading: \"Notes\"\n  body: |\n    **x** <b>y</b>\n\n    - one\n    - two\n---\nSay **this** literally.";
+        let scene = parse_scene(flagged, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 75, 150, None)
+            .unwrap();
+        assert!(html.contains("<p><strong>x</strong> &lt;b&gt;y&lt;/b&gt;</p>"));
+        assert!(html.contains("<ul>\n<li>one</li>\n<li>two</li>\n</ul>"));
+    }
+
+    #[test]
+    fn test_md_helper_in_custom_template() {
+        let mut registry = TemplateRegistry::new().unwrap();
+        registry
+            .hbs
+            .register_template_string("custom", "<body>{{md text}}|{{script}}</body>")
+            .unwrap();
+        let content = "---\ntemplate: custom\nprops:\n  text: \"**x**\"\n---\nKeep **this**.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 0, 150, None)
+            .unwrap();
+        // Rendered once, not double-escaped; the script stays literal
+        assert!(html.contains("<p><strong>x</strong></p>\n|Keep **this**."), "{html}");
+    }
+
+    #[test]
+    fn test_render_rich_text() {
+        let registry = TemplateRegistry::new().unwrap();
+        let content = "---\ntemplate: rich-text\nprops:\n  heading: \"Agenda\"\n  content: |\n    ## Today\n\n    1. Plan\n    2. `ship`\n---\nVoiceover.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 75, 150, None)
+            .unwrap();
+        assert!(html.contains("<h2 class=\"heading\">Agenda</h2>"));
+        assert!(html.contains("<h2>Today</h2>"));
+        assert!(html.contains("<li><code>ship</code></li>"));
+    }
+
+    /// Render `source` as a one-off custom template with `props`.
+    fn render_inline(source: &str, props: &str) -> String {
+        let mut registry = TemplateRegistry::new().unwrap();
+        registry
+            .hbs
+            .register_template_string("inline", source)
+            .unwrap();
+        let content = format!("---\ntemplate: inline\nprops:\n{props}\n---\nScript.");
+        let scene = parse_scene(&content, Path::new("test.md")).unwrap();
+        registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 0, 150, None)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_case_helpers() {
+        let html = render_inline(
+            "<body>{{upper title}}|{{lower title}}</body>",
+            "  title: \"Hello <World>\"",
+        );
+        assert!(html.contains("HELLO &lt;WORLD&gt;|hello &lt;world&gt;"), "{html}");
+    }
+
+    #[test]
+    fn test_truncate_helper() {
+        let html = render_inline(
+            "<body>{{truncate text 9}}|{{truncate text 50}}</body>",
+            "  text: \"Ship small changes\"",
+        );
+        assert!(html.contains("Ship smal…|Ship small changes"), "{html}");
+        assert_eq!(truncate_text("Ship small", 5), "Ship…");
+    }
+
+    #[test]
+    fn test_number_format_helper() {
+        let html = render_inline(
+            "<body>{{number_format views}}|{{number_format price 2}}|{{number_format raw}}</body>",
+            "  views: 1234567\n  price: 1999.5\n  raw: \"-48000\"",
+        );
+        assert!(html.contains("1,234,567|1,999.50|-48,000"), "{html}");
+        assert_eq!(format_number(999.0, None), "999");
+        assert_eq!(format_number(-0.001, Some(2)), "0.00");
+    }
+
+    #[test]
+    fn test_repeat_helper() {
+        let html = render_inline(
+            "<body>{{repeat star rating}}</body>",
+            "  star: \"★\"\n  rating: 3",
+        );
+        assert!(html.contains("<body>★★★</body>"), "{html}");
+    }
+
+    #[test]
+    fn test_markdown_to_html() {
+        assert_eq!(markdown_to_html("**x**"), "<p><strong>x</strong></p>\n");
+        assert_eq!(
+            markdown_to_html("<script>alert(1)</script>"),
+            "&lt;script&gt;alert(1)&lt;/script&gt;"
+        );
+    }
+
+    #[test]
+    fn test_background_image_motion_uses_content_progress() {
+        let registry = TemplateRegistry::new().unwrap();
+        let content = "---\ntemplate: title-card\nbackground:\n  image: \"@assets/bg.jpg\"\n  motion: zoom-in\nprops:\n  title: \"Hi\"\n---\nVoiceover.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 30, 150, Some(Path::new("/proj")))
+            .unwrap();
+        let layer_start = html.find("vidgen-bg-image").unwrap();
+        let layer = &html[layer_start..html[layer_start..].find("</div>").unwrap() + layer_start];
+        assert!(layer.contains("url('file:///proj/assets/bg.jpg')"));
+        assert!(layer.contains("transform:scale(calc(1 + 0.12 * var(--content-progress"));
+        // The layer sits inside <body>, before the template content
+        assert!(html.find("<body").unwrap() < layer_start);
+        assert!(layer_start < html.find("Hi</h1>").unwrap());
+    }
+
+    #[test]
+    fn test_background_image_without_motion_is_static() {
+        let registry = TemplateRegistry::new().unwrap();
+        let content = "---\ntemplate: title-card\nbackground:\n  image: \"bg.jpg\"\nprops:\n  title: \"Hi\"\n---\nVoiceover.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 0, 150, None)
+            .unwrap();
+        assert!(html.contains("url('bg.jpg')"));
+        assert!(!html.contains("transform:scale"));
+    }
+
+    #[test]
+    fn test_background_layers_color_image_and_overlay() {
+        let registry = TemplateRegistry::new().unwrap();
+        let content = "---\ntemplate: title-card\nbackground:\n  color: \"#112233\"\n  image: \"logo.png\"\n  overlay: \"rgba(0,0,0,0.4)\"\n  blur: 6\nprops:\n  title: \"Hi\"\n---\nVoiceover.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 0, 150, None)
+            .unwrap();
+        let layer_start = html.find("vidgen-bg-image").unwrap();
+        let layer = &html[layer_start..html[layer_start..].find("</div>").unwrap() + layer_start];
+        // Overlay on top, then the image, then the color behind transparent pixels
+        assert!(layer.contains(
+            "background:linear-gradient(rgba(0,0,0,0.4), rgba(0,0,0,0.4)), \
+             url('logo.png') center/cover no-repeat, #112233;"
+        ));
+        assert!(layer.contains("filter:blur(6px);"));
+        assert!(layer.contains("inset:-12px;"));
+        // The color is still the body background
+        assert!(html.contains("background: #112233"));
+    }
+
+    #[test]
+    fn test_background_color_only_has_no_layer() {
+        let mut bg = BackgroundConfig {
+            color: Some("#FF0000".into()),
+            image: None,
+            motion: None,
+            overlay: None,
+            blur: None,
+        };
+        assert!(background_layer(&bg, None).is_none());
+        bg.overlay = Some("linear-gradient(transparent, black)".into());
+        assert_eq!(
+            background_layers(&bg, None),
+            ["linear-gradient(transparent, black)", "#FF0000"]
+        );
+        assert!(background_layer(&bg, None).unwrap().contains("inset:0;"));
+    }
+
+    #[test]
+    fn test_scene_seed_sets_rand_values() {
+        let mut registry = TemplateRegistry::new().unwrap();
+        registry
+            .hbs
+            .register_template_string(
+                "rand-test",
+                "<body>{{seed}}|{{lookup rand 0}}|{{lookup rand 1}}</body>",
+            )
+            .unwrap();
+        let render = |frontmatter: &str, file: &str| {
+            let content = format!("---\ntemplate: rand-test\n{frontmatter}---\nScript.");
+            let scene = parse_scene(&content, Path::new(file)).unwrap();
+            registry
+                .render_scene_html(&scene, &test_theme(), 1920, 1080, 0, 150, None)
+                .unwrap()
+        };
+
+        let seeded = render("seed: 42\n", "01-a.md");
+        assert!(seeded.contains("<body>42|"), "{seeded}");
+        // The same seed reproduces the values, even in another scene file
+        assert_eq!(seeded, render("seed: 42\n", "02-b.md"));
+        assert_ne!(seeded, render("seed: 7\n", "01-a.md"));
+
+        // Without a seed, each file gets its own stable values
+        assert_eq!(render("", "01-a.md"), render("", "01-a.md"));
+        assert_ne!(render("", "01-a.md"), render("", "02-b.md"));
+    }
+
+    #[test]
+    fn test_emoji_fallback_embeds_noto_font() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = "---\ntemplate: title-card\nprops:\n  title: \"Hello \u{1F1E9}\u{1F1EA}\"\n---\nScript.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let render = |emoji_fallback: bool| {
+            let mut registry = TemplateRegistry::new().unwrap();
+            let settings = RenderConfig {
+                emoji_fallback,
+                ..Default::default()
+            };
+            registry.apply_emoji_fallback(&settings, dir.path());
+            registry
+                .render_scene_html(&scene, &test_theme(), 1920, 1080, 0, 150, Some(dir.path()))
+                .unwrap()
+        };
+
+        // Enabled without a font file: the Twemoji CDN is still used
+        let html = render(true);
+        assert!(!html.contains("@font-face"));
+        assert!(html.contains("twemoji"));
+
+        let fonts = dir.path().join("assets").join("fonts");
+        std::fs::create_dir_all(&fonts).unwrap();
+        std::fs::write(fonts.join("NotoColorEmoji.ttf"), b"font").unwrap();
+        assert_eq!(find_emoji_font(dir.path()), Some(fonts.join("NotoColorEmoji.ttf")));
+
+        let html = render(true);
+        assert!(html.contains("@font-face { font-family: 'Noto Color Emoji'"), "{html}");
+        assert!(html.contains(&format!("url('file://{}')", fonts.join("NotoColorEmoji.ttf").display())));
+        assert!(html.contains("sans-serif, 'Noto Color Emoji'"));
+        assert!(!html.contains("twemoji"));
+        // The @font-face comes before the template's styles so they keep precedence
+        assert!(html.find("@font-face").unwrap() < html.find(".title").unwrap());
+
+        assert!(!render(false).contains("@font-face"));
+    }
+
     #[test]
     fn test_render_kinetic_text() {
         let registry = TemplateRegistry::new().unwrap();
@@ -579,6 +1940,111 @@ Voiceover."#;
         assert!(html.contains("It works!"));
     }
 
+    #[test]
+    fn test_project_partials_are_included_by_components() {
+        let dir = tempfile::tempdir().unwrap();
+        let templates_dir = dir.path().join("templates");
+        std::fs::create_dir_all(templates_dir.join("partials")).unwrap();
+        std::fs::create_dir_all(templates_dir.join("components")).unwrap();
+
+        let base = r#"<head><meta charset="utf-8"><style>
+  body { width: {{width}}px; height: {{height}}px; background: {{effective_background}}; }
+</style></head>"#;
+        std::fs::write(templates_dir.join("partials/base.html"), base).unwrap();
+        std::fs::write(
+            templates_dir.join("partials/badge.hbs"),
+            "<span class=\"badge\">{{upper label}}</span>",
+        )
+        .unwrap();
+        let component = r#"<!DOCTYPE html>
+<html>{{> base}}
+<body><h1>{{custom_field}}</h1>{{> badge label=custom_field}}</body></html>"#;
+        std::fs::write(templates_dir.join("components/with-base.html"), component).unwrap();
+
+        let mut registry = TemplateRegistry::new().unwrap();
+        registry.register_project_templates(dir.path()).unwrap();
+
+        let content =
+            "---\ntemplate: with-base\nprops:\n  custom_field: \"Shared\"\n---\nScript.";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let html = registry
+            .render_scene_html(&scene, &test_theme(), 1920, 1080, 0, 150, None)
+            .unwrap();
+        assert!(html.contains("width: 1920px; height: 1080px; background: #0F172A;"));
+        assert!(html.contains("<h1>Shared</h1>"));
+        assert!(html.contains("<span class=\"badge\">SHARED</span>"));
+    }
+
+    #[test]
+    fn test_validate_scene_props_reports_missing_required_props() {
+        let registry = TemplateRegistry::new().unwrap();
+        let cases = [
+            ("title-card", "title"),
+            ("content-text", "heading, body"),
+            ("rich-text", "content"),
+            ("quote-card", "quote"),
+            ("split-screen", "panels"),
+            ("lower-third", "name"),
+            ("cta-card", "heading"),
+            ("slideshow", "slides"),
+            ("bar-chart", "bars"),
+            ("code-block", "code"),
+        ];
+        for (template, missing) in cases {
+            let content = format!("---\ntemplate: {template}\n---\nVoiceover.");
+            let scene = parse_scene(&content, Path::new("01-scene.md")).unwrap();
+            let err = registry.validate_scene_props(&scene).unwrap_err();
+            assert!(matches!(err, VidgenError::TemplateRender(_)));
+            let expected = format!(
+                "01-scene.md: template '{template}' is missing required prop(s): {missing}"
+            );
+            assert!(err.to_string().contains(&expected), "{err}");
+            // Rendering fails the same way instead of producing a blank frame
+            assert!(registry
+                .render_scene_html(&scene, &test_theme(), 1920, 1080, 0, 150, None)
+                .is_err());
+        }
+
+        // Templates without required props fall back to the script
+        for template in ["kinetic-text", "caption-overlay"] {
+            let content = format!("---\ntemplate: {template}\n---\nVoiceover.");
+            let scene = parse_scene(&content, Path::new("test.md")).unwrap();
+            registry.validate_scene_props(&scene).unwrap();
+        }
+
+        // Blank strings and empty lists count as missing
+        let content =
+            "---\ntemplate: content-text\nprops:\n  heading: \"  \"\n  body: \"Text\"\n---\n";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let err = registry.validate_scene_props(&scene).unwrap_err();
+        assert!(
+            err.to_string()
+                .ends_with("missing required prop(s): heading"),
+            "{err}"
+        );
+        let content = "---\ntemplate: slideshow\nprops:\n  slides: []\n---\n";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        assert!(registry.validate_scene_props(&scene).is_err());
+    }
+
+    #[test]
+    fn test_validate_scene_props_skips_project_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        let components_dir = dir.path().join("templates").join("components");
+        std::fs::create_dir_all(&components_dir).unwrap();
+        std::fs::write(
+            components_dir.join("title-card.html"),
+            "<html><body>{{headline}}</body></html>",
+        )
+        .unwrap();
+
+        let mut registry = TemplateRegistry::new().unwrap();
+        registry.register_project_templates(dir.path()).unwrap();
+        let content = "---\ntemplate: title-card\nprops:\n  headline: \"Custom\"\n---\n";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        registry.validate_scene_props(&scene).unwrap();
+    }
+
     #[test]
     fn test_project_template_overrides_builtin() {
         let dir = tempfile::tempdir().unwrap();
@@ -615,6 +2081,66 @@ Voiceover."#;
         registry.register_project_templates(dir.path()).unwrap();
     }
 
+    #[test]
+    fn test_declared_number_prop_is_coerced() {
+        let dir = tempfile::tempdir().unwrap();
+        let components_dir = dir.path().join("templates").join("components");
+        std::fs::create_dir_all(&components_dir).unwrap();
+        let custom_html = r#"<!-- vidgen:props count=number live=boolean -->
+<html><body><span>{{count}}</span></body></html>"#;
+        std::fs::write(components_dir.join("counter.html"), custom_html).unwrap();
+
+        let mut registry = TemplateRegistry::new().unwrap();
+        registry.register_project_templates(dir.path()).unwrap();
+
+        let content = "---\ntemplate: counter\nprops:\n  count: \"5\"\n  live: \"yes\"\n---\n";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+        let props = registry.coerced_props(&scene);
+        assert_eq!(props["count"], json!(5));
+        assert_eq!(props["live"], json!(true));
+        assert!(registry.prop_type_warnings(&scene).is_empty());
+    }
+
+    #[test]
+    fn test_prop_type_mismatch_warns() {
+        let registry = TemplateRegistry::new().unwrap();
+        let content = "---\ntemplate: cta-card\nprops:\n  heading: 2024\n  items: \"Subscribe\"\n---\n";
+        let scene = parse_scene(content, Path::new("test.md")).unwrap();
+
+        // The numeric heading coerces cleanly to a string; the items string can't become an array
+        assert_eq!(registry.coerced_props(&scene)["heading"], json!("2024"));
+        let warnings = registry.prop_type_warnings(&scene);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("'items' should be of type array"));
+    }
+
+    #[test]
+    fn test_prop_schema() {
+        let schema = prop_schema("cta-card").unwrap();
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], json!(["heading"]));
+        assert_eq!(schema["properties"]["heading"]["type"], "string");
+        assert_eq!(schema["properties"]["items"]["type"], "array");
+        assert_eq!(schema["properties"]["items"]["items"]["type"], "string");
+
+        let captions = prop_schema("caption-overlay").unwrap();
+        assert_eq!(captions["required"], json!([]));
+        assert_eq!(captions["properties"]["position"]["default"], "bottom");
+        assert_eq!(
+            captions["properties"]["style"]["enum"],
+            json!(["outline", "background-box", "drop-shadow"])
+        );
+
+        // Every listed built-in has a schema; the theme-preview sheet isn't listed
+        let registry = TemplateRegistry::new().unwrap();
+        let names = registry.template_names();
+        assert!(!names.iter().any(|n| n == THEME_PREVIEW));
+        assert!(registry.has_template(THEME_PREVIEW));
+        for name in names {
+            assert!(prop_schema(&name).is_some(), "{name}");
+        }
+    }
+
     #[test]
     fn test_render_slideshow() {
         let registry = TemplateRegistry::new().unwrap();
@@ -688,6 +2214,10 @@ Voiceover."#;
             ("content-text", CONTENT_TEXT_TEMPLATE),
             ("slideshow", SLIDESHOW_TEMPLATE),
             ("caption-overlay", CAPTION_OVERLAY_TEMPLATE),
+            ("bar-chart", BAR_CHART_TEMPLATE),
+            ("code-block", CODE_BLOCK_TEMPLATE),
+            ("rich-text", RICH_TEXT_TEMPLATE),
+            ("theme-preview", THEME_PREVIEW_TEMPLATE),
         ];
         for (name, src) in templates {
             assert!(
@@ -1,4 +1,5 @@
 use crate::error::VidgenResult;
+use crate::tts::timestamps::WordTimestamp;
 use crate::tts::{SynthesisResult, TtsEngine};
 use sha2::{Digest, Sha256};
 use std::path::Path;
@@ -7,8 +8,10 @@ use std::path::Path;
 ///
 /// Cache key is SHA-256 of `"{engine}\0{voice}\0{speed}\0{text}"`.
 /// Cached audio is stored in `<project>/assets/voiceover/<hash>.wav`
-/// with a `<hash>.json` sidecar containing duration metadata.
-/// Synthesize TTS with caching support and optional force flag.
+/// with a `<hash>.json` sidecar containing duration metadata and, when the
+/// engine reported them, word timings.
+///
+/// With `force`, the cached entry is ignored and the fresh audio overwrites it.
 pub fn synthesize_cached_with_options(
     engine: &dyn TtsEngine,
     text: &str,
@@ -31,13 +34,13 @@ pub fn synthesize_cached_with_options(
                 audio_path: output_path.to_path_buf(),
                 duration_secs,
                 cached: true,
-                word_timestamps: None,
+                word_timestamps: read_sidecar_words(&cached_json),
             });
         }
     }
 
     // Cache miss: synthesize, then populate cache
-    let result = engine.synthesize(text, voice, speed, output_path)?;
+    let result = crate::tts::pauses::synthesize_script(engine, text, voice, speed, output_path)?;
 
     std::fs::create_dir_all(&cache_dir)?;
     std::fs::copy(output_path, &cached_wav)?;
@@ -47,6 +50,7 @@ pub fn synthesize_cached_with_options(
         engine.engine_name(),
         voice,
         text,
+        result.word_timestamps.as_deref(),
     );
 
     Ok(result)
@@ -76,15 +80,33 @@ pub fn read_sidecar(path: &Path) -> Option<f64> {
     v.get("duration_secs")?.as_f64()
 }
 
+/// Read engine-reported word timings from a JSON sidecar, if it has any.
+pub fn read_sidecar_words(path: &Path) -> Option<Vec<WordTimestamp>> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    let mut v: serde_json::Value = serde_json::from_str(&contents).ok()?;
+    serde_json::from_value(v.get_mut("words")?.take()).ok()
+}
+
 /// Write a JSON sidecar with duration and metadata for human inspection.
-fn write_sidecar(path: &Path, duration_secs: f64, engine: &str, voice: Option<&str>, text: &str) {
+/// Engine word timings are kept so cache hits can still use them.
+fn write_sidecar(
+    path: &Path,
+    duration_secs: f64,
+    engine: &str,
+    voice: Option<&str>,
+    text: &str,
+    words: Option<&[WordTimestamp]>,
+) {
     let text_preview: String = text.chars().take(80).collect();
-    let sidecar = serde_json::json!({
+    let mut sidecar = serde_json::json!({
         "duration_secs": duration_secs,
         "engine": engine,
         "voice": voice.unwrap_or(""),
         "text_preview": text_preview,
     });
+    if let Some(words) = words {
+        sidecar["words"] = serde_json::json!(words);
+    }
     let _ = std::fs::write(
         path,
         serde_json::to_string_pretty(&sidecar).unwrap_or_default(),
@@ -131,6 +153,80 @@ mod tests {
         assert_ne!(a, b);
     }
 
+    /// Silent engine reporting a configurable name, for cache hit/miss tests.
+    struct NamedEngine(&'static str);
+
+    impl TtsEngine for NamedEngine {
+        fn synthesize(
+            &self,
+            text: &str,
+            voice: Option<&str>,
+            speed: f32,
+            output_path: &Path,
+        ) -> VidgenResult<SynthesisResult> {
+            crate::tts::silent::SilentTtsEngine.synthesize(text, voice, speed, output_path)
+        }
+
+        fn list_voices(&self) -> VidgenResult<Vec<crate::tts::VoiceInfo>> {
+            Ok(Vec::new())
+        }
+
+        fn engine_name(&self) -> &str {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_cache_miss_on_engine_or_speed_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out.wav");
+        let text = "Hello from the cache";
+        let synth = |engine: &dyn TtsEngine, speed: f32| {
+            synthesize_cached_with_options(engine, text, None, speed, &out, dir.path(), false)
+                .unwrap()
+                .cached
+        };
+
+        assert!(!synth(&NamedEngine("native"), 1.0));
+        assert!(synth(&NamedEngine("native"), 1.0));
+        assert!(!synth(&NamedEngine("edge"), 1.0));
+        assert!(!synth(&NamedEngine("native"), 1.5));
+    }
+
+    #[test]
+    fn test_force_bypasses_cache_and_rewrites_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out.wav");
+        let text = "Hello again";
+        let sidecar = dir.path().join(format!(
+            "assets/voiceover/{}.json",
+            cache_key("native", None, 1.0, text)
+        ));
+        let synth = |force: bool| {
+            synthesize_cached_with_options(
+                &NamedEngine("native"),
+                text,
+                None,
+                1.0,
+                &out,
+                dir.path(),
+                force,
+            )
+            .unwrap()
+        };
+
+        synth(false);
+        write_sidecar(&sidecar, 99.0, "native", None, text, None);
+
+        let forced = synth(true);
+        assert!(!forced.cached);
+        assert_ne!(read_sidecar(&sidecar), Some(99.0));
+
+        let again = synth(false);
+        assert!(again.cached);
+        assert_eq!(again.duration_secs, forced.duration_secs);
+    }
+
     #[test]
     fn test_sidecar_roundtrip() {
         let dir = tempfile::tempdir().unwrap();
@@ -142,10 +238,26 @@ mod tests {
             "elevenlabs",
             Some("Rachel"),
             "Getting started with vidgen is easy.",
+            None,
         );
 
         let duration = read_sidecar(&path);
         assert_eq!(duration, Some(4.2));
+        assert!(read_sidecar_words(&path).is_none());
+    }
+
+    #[test]
+    fn test_sidecar_keeps_word_timings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("words.json");
+        let words = crate::tts::timestamps::estimate_word_timestamps("Hello there", 1.0);
+
+        write_sidecar(&path, 1.0, "elevenlabs", None, "Hello there", Some(&words));
+
+        let read = read_sidecar_words(&path).unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[1].word, "there");
+        assert_eq!(read[1].end_secs, 1.0);
     }
 
     #[test]
@@ -169,7 +281,7 @@ mod tests {
         let path = dir.path().join("long.json");
         let long_text = "a".repeat(200);
 
-        write_sidecar(&path, 1.0, "native", None, &long_text);
+        write_sidecar(&path, 1.0, "native", None, &long_text, None);
 
         let contents = std::fs::read_to_string(&path).unwrap();
         let v: serde_json::Value = serde_json::from_str(&contents).unwrap();
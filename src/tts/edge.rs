@@ -40,7 +40,7 @@ fn speed_to_rate(speed: f32) -> String {
 }
 
 /// Default voice when none is specified.
-const DEFAULT_VOICE: &str = "en-US-AriaNeural";
+pub(crate) const DEFAULT_VOICE: &str = "en-US-AriaNeural";
 
 impl TtsEngine for EdgeTtsEngine {
     fn synthesize(
@@ -123,6 +123,10 @@ impl TtsEngine for EdgeTtsEngine {
     fn engine_name(&self) -> &str {
         "edge"
     }
+
+    fn default_voice(&self) -> Option<&str> {
+        Some(DEFAULT_VOICE)
+    }
 }
 
 /// Parse the block format output of `edge-tts --list-voices`.
@@ -201,6 +205,12 @@ fn extract_language(voice_id: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_default_voice() {
+        let engine = EdgeTtsEngine;
+        assert_eq!(engine.default_voice(), Some(DEFAULT_VOICE));
+    }
+
     #[test]
     fn test_speed_to_rate() {
         assert_eq!(speed_to_rate(1.0), "+0%");
@@ -1,16 +1,19 @@
 use crate::error::{VidgenError, VidgenResult};
+use crate::tts::timestamps::WordTimestamp;
 use crate::tts::{ffprobe_duration, SynthesisResult, TtsEngine, VoiceInfo};
+use base64::Engine;
 use std::path::Path;
 use std::process::Command;
 
 const API_BASE: &str = "https://api.elevenlabs.io/v1";
-const DEFAULT_VOICE_ID: &str = "21m00Tcm4TlvDq8ikWAM"; // Rachel
+pub(crate) const DEFAULT_VOICE_ID: &str = "21m00Tcm4TlvDq8ikWAM"; // Rachel
 const DEFAULT_MODEL_ID: &str = "eleven_multilingual_v2";
 
 /// TTS engine using ElevenLabs cloud API.
 ///
 /// Requires `ELEVEN_API_KEY` environment variable.
-/// Returns premium neural voices via REST API (`POST /v1/text-to-speech/{voice_id}`).
+/// Returns premium neural voices via REST API (`POST /v1/text-to-speech/{voice_id}/with-timestamps`),
+/// along with word timings from the response's character alignment.
 #[derive(Debug)]
 pub struct ElevenLabsTtsEngine {
     api_key: String,
@@ -42,7 +45,9 @@ impl TtsEngine for ElevenLabsTtsEngine {
         output_path: &Path,
     ) -> VidgenResult<SynthesisResult> {
         let voice_id = voice.unwrap_or(DEFAULT_VOICE_ID);
-        let url = format!("{API_BASE}/text-to-speech/{voice_id}?output_format=mp3_44100_128");
+        let url = format!(
+            "{API_BASE}/text-to-speech/{voice_id}/with-timestamps?output_format=mp3_44100_128"
+        );
 
         let body = serde_json::json!({
             "text": text,
@@ -55,10 +60,11 @@ impl TtsEngine for ElevenLabsTtsEngine {
             .send(body.to_string().as_bytes())
             .map_err(|e| VidgenError::Tts(format!("ElevenLabs API request failed: {e}")))?;
 
-        let bytes = response
+        let json = response
             .into_body()
-            .read_to_vec()
+            .read_to_string()
             .map_err(|e| VidgenError::Tts(format!("Failed to read ElevenLabs response: {e}")))?;
+        let (bytes, words) = parse_timestamps_response(&json)?;
 
         // Write MP3 to temp file, then convert to WAV
         let mp3_path = output_path.with_extension("mp3");
@@ -101,11 +107,28 @@ impl TtsEngine for ElevenLabsTtsEngine {
 
         let duration_secs = ffprobe_duration(output_path)?;
 
+        // atempo shortens the audio, so the timings shrink with it
+        let tempo = if (speed - 1.0).abs() > 0.01 {
+            speed.clamp(0.5, 100.0) as f64
+        } else {
+            1.0
+        };
+        let word_timestamps = words.map(|words| {
+            words
+                .into_iter()
+                .map(|w| WordTimestamp {
+                    start_secs: w.start_secs / tempo,
+                    end_secs: (w.end_secs / tempo).min(duration_secs),
+                    ..w
+                })
+                .collect()
+        });
+
         Ok(SynthesisResult {
             audio_path: output_path.to_path_buf(),
             duration_secs,
             cached: false,
-            word_timestamps: None,
+            word_timestamps,
         })
     }
 
@@ -128,6 +151,69 @@ impl TtsEngine for ElevenLabsTtsEngine {
     fn engine_name(&self) -> &str {
         "elevenlabs"
     }
+
+    fn default_voice(&self) -> Option<&str> {
+        Some(DEFAULT_VOICE_ID)
+    }
+}
+
+/// Character-level timing from a `with-timestamps` response.
+#[derive(serde::Deserialize)]
+struct Alignment {
+    characters: Vec<String>,
+    character_start_times_seconds: Vec<f64>,
+    character_end_times_seconds: Vec<f64>,
+}
+
+/// Response from `POST /v1/text-to-speech/{voice_id}/with-timestamps`.
+#[derive(serde::Deserialize)]
+struct TimestampsResponse {
+    audio_base64: String,
+    alignment: Option<Alignment>,
+}
+
+/// Decode a `with-timestamps` response into MP3 bytes and word timings
+/// (`None` when the response carries no alignment).
+fn parse_timestamps_response(json: &str) -> VidgenResult<(Vec<u8>, Option<Vec<WordTimestamp>>)> {
+    let response: TimestampsResponse = serde_json::from_str(json)
+        .map_err(|e| VidgenError::Tts(format!("Invalid ElevenLabs response: {e}")))?;
+    let audio = base64::engine::general_purpose::STANDARD
+        .decode(response.audio_base64)
+        .map_err(|e| VidgenError::Tts(format!("Invalid ElevenLabs audio data: {e}")))?;
+    Ok((audio, response.alignment.map(|a| alignment_words(&a))))
+}
+
+/// Group character timings into words: each whitespace-separated run becomes one
+/// word, from its first character's start to its last character's end.
+fn alignment_words(alignment: &Alignment) -> Vec<WordTimestamp> {
+    let mut words: Vec<WordTimestamp> = Vec::new();
+    let mut current: Option<WordTimestamp> = None;
+    let timed = alignment
+        .characters
+        .iter()
+        .zip(&alignment.character_start_times_seconds)
+        .zip(&alignment.character_end_times_seconds);
+    for ((ch, &start), &end) in timed {
+        if ch.trim().is_empty() {
+            words.extend(current.take());
+            continue;
+        }
+        match current.as_mut() {
+            Some(word) => {
+                word.word.push_str(ch);
+                word.end_secs = end.max(word.end_secs);
+            }
+            None => {
+                current = Some(WordTimestamp {
+                    word: ch.clone(),
+                    start_secs: start,
+                    end_secs: end,
+                })
+            }
+        }
+    }
+    words.extend(current);
+    words
 }
 
 /// Parsed labels from an ElevenLabs voice entry.
@@ -180,6 +266,14 @@ fn parse_voices_response(json: &str) -> Vec<VoiceInfo> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_default_voice() {
+        let engine = ElevenLabsTtsEngine {
+            api_key: "test".into(),
+        };
+        assert_eq!(engine.default_voice(), Some(DEFAULT_VOICE_ID));
+    }
+
     #[test]
     fn test_parse_voices_response() {
         let json = r#"{
@@ -230,6 +324,49 @@ mod tests {
         assert_eq!(voices[0].gender, ""); // default
     }
 
+    #[test]
+    fn test_parse_timestamps_response() {
+        let text = "Hello big world";
+        let chars: Vec<String> = text.chars().map(String::from).collect();
+        let starts: Vec<f64> = (0..chars.len()).map(|i| i as f64 * 0.1).collect();
+        let ends: Vec<f64> = starts.iter().map(|s| s + 0.1).collect();
+        let json = serde_json::json!({
+            "audio_base64": base64::engine::general_purpose::STANDARD.encode(b"ID3mp3"),
+            "alignment": {
+                "characters": chars,
+                "character_start_times_seconds": starts,
+                "character_end_times_seconds": ends,
+            },
+            "normalized_alignment": null,
+        })
+        .to_string();
+
+        let (audio, words) = parse_timestamps_response(&json).unwrap();
+        assert_eq!(audio, b"ID3mp3");
+        let words = words.unwrap();
+        let names: Vec<_> = words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(names, ["Hello", "big", "world"]);
+
+        // Monotonic, non-overlapping, and spanning the whole clip
+        for w in &words {
+            assert!(w.start_secs < w.end_secs);
+        }
+        for pair in words.windows(2) {
+            assert!(pair[0].end_secs <= pair[1].start_secs);
+        }
+        assert_eq!(words[0].start_secs, 0.0);
+        assert!((words[2].end_secs - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_timestamps_response_without_alignment() {
+        let json = r#"{"audio_base64": "SUQz", "alignment": null}"#;
+        let (audio, words) = parse_timestamps_response(json).unwrap();
+        assert_eq!(audio, b"ID3");
+        assert!(words.is_none());
+        assert!(parse_timestamps_response("not json").is_err());
+    }
+
     #[test]
     fn test_parse_voices_response_empty() {
         let json = r#"{"voices": []}"#;
@@ -2,7 +2,9 @@ pub mod cache;
 pub mod edge;
 pub mod elevenlabs;
 pub mod native;
+pub mod pauses;
 pub mod piper;
+pub mod silent;
 pub mod timestamps;
 
 use crate::config::VoiceConfig;
@@ -52,8 +54,17 @@ pub trait TtsEngine: Send + Sync {
     fn list_voices(&self) -> VidgenResult<Vec<VoiceInfo>>;
 
     fn engine_name(&self) -> &str;
+
+    /// Voice used when neither the scene nor `voice.default_voice` picks one,
+    /// passed explicitly so every engine's choice is predictable.
+    fn default_voice(&self) -> Option<&str> {
+        None
+    }
 }
 
+/// Names accepted by `create_engine`.
+pub const ENGINE_NAMES: &[&str] = &["native", "edge", "elevenlabs", "piper"];
+
 /// Factory: create a TTS engine from project voice config.
 pub fn create_engine(config: &VoiceConfig) -> VidgenResult<Box<dyn TtsEngine>> {
     debug!("Creating TTS engine: {}", config.engine);
@@ -71,7 +82,7 @@ pub fn create_engine(config: &VoiceConfig) -> VidgenResult<Box<dyn TtsEngine>> {
             Ok(Box::new(engine))
         }
         "piper" => {
-            let engine = piper::PiperTtsEngine::new()?;
+            let engine = piper::PiperTtsEngine::new(config)?;
             Ok(Box::new(engine))
         }
         other => Err(VidgenError::Tts(format!(
@@ -80,6 +91,79 @@ pub fn create_engine(config: &VoiceConfig) -> VidgenResult<Box<dyn TtsEngine>> {
     }
 }
 
+/// Default voice of the engine named `engine` (see [`TtsEngine::default_voice`]),
+/// without creating it. Lets cache keys be predicted from the config alone.
+pub fn default_voice_for(engine: &str) -> Option<&'static str> {
+    match engine {
+        "native" => Some(native::default_voice()),
+        "edge" => Some(edge::DEFAULT_VOICE),
+        "elevenlabs" => Some(elevenlabs::DEFAULT_VOICE_ID),
+        "piper" => Some(piper::DEFAULT_MODEL),
+        _ => None,
+    }
+}
+
+/// Engine names to try in order: the primary `engine`, then `fallback` entries
+/// (duplicates removed).
+pub fn engine_chain(config: &VoiceConfig) -> Vec<String> {
+    let mut chain = vec![config.engine.clone()];
+    for name in &config.fallback {
+        if !chain.contains(name) {
+            chain.push(name.clone());
+        }
+    }
+    chain
+}
+
+/// Create every engine in the fallback chain that is available. Engines that
+/// cannot be created (missing binary, missing API key) are skipped with a log line.
+pub fn create_engine_chain(config: &VoiceConfig) -> Vec<Box<dyn TtsEngine>> {
+    engine_chain(config)
+        .into_iter()
+        .filter_map(|name| {
+            let mut cfg = config.clone();
+            cfg.engine = name;
+            match create_engine(&cfg) {
+                Ok(engine) => Some(engine),
+                Err(e) => {
+                    debug!("TTS engine '{}' unavailable: {}", cfg.engine, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Try `synth` with each engine in order until one succeeds.
+///
+/// Each candidate carries the voice to use with it — voice IDs are engine-specific,
+/// so fallback engines are usually given `None`, which becomes the engine's
+/// [`TtsEngine::default_voice`]. Returns the
+/// result together with the name of the engine that served it, or the last error.
+pub fn synthesize_with_fallback(
+    candidates: &[(&dyn TtsEngine, Option<&str>)],
+    mut synth: impl FnMut(&dyn TtsEngine, Option<&str>) -> VidgenResult<SynthesisResult>,
+) -> VidgenResult<(SynthesisResult, String)> {
+    let mut last_err = VidgenError::Tts("No TTS engine available".into());
+    for (i, (engine, voice)) in candidates.iter().enumerate() {
+        match synth(*engine, voice.or(engine.default_voice())) {
+            Ok(result) => return Ok((result, engine.engine_name().to_string())),
+            Err(e) => {
+                if let Some((next, _)) = candidates.get(i + 1) {
+                    eprintln!(
+                        "    TTS engine '{}' failed ({}), trying '{}'",
+                        engine.engine_name(),
+                        e,
+                        next.engine_name()
+                    );
+                }
+                last_err = e;
+            }
+        }
+    }
+    Err(last_err)
+}
+
 /// Query audio duration via ffprobe. Returns seconds.
 pub fn ffprobe_duration(path: &Path) -> VidgenResult<f64> {
     let output = Command::new("ffprobe")
@@ -111,6 +195,124 @@ pub fn ffprobe_duration(path: &Path) -> VidgenResult<f64> {
 mod tests {
     use super::*;
 
+    /// Test engine that either fails or returns a fixed result.
+    struct FakeEngine {
+        name: &'static str,
+        fails: bool,
+        default_voice: Option<&'static str>,
+    }
+
+    impl TtsEngine for FakeEngine {
+        fn synthesize(
+            &self,
+            _text: &str,
+            _voice: Option<&str>,
+            _speed: f32,
+            output_path: &Path,
+        ) -> VidgenResult<SynthesisResult> {
+            if self.fails {
+                return Err(VidgenError::Tts(format!("{} is down", self.name)));
+            }
+            Ok(SynthesisResult {
+                audio_path: output_path.to_path_buf(),
+                duration_secs: 1.5,
+                cached: false,
+                word_timestamps: None,
+            })
+        }
+
+        fn list_voices(&self) -> VidgenResult<Vec<VoiceInfo>> {
+            Ok(Vec::new())
+        }
+
+        fn engine_name(&self) -> &str {
+            self.name
+        }
+
+        fn default_voice(&self) -> Option<&str> {
+            self.default_voice
+        }
+    }
+
+    #[test]
+    fn test_engine_chain_order_and_dedup() {
+        let config = VoiceConfig {
+            engine: "elevenlabs".into(),
+            fallback: vec!["elevenlabs".into(), "edge".into(), "native".into()],
+            ..Default::default()
+        };
+        assert_eq!(engine_chain(&config), ["elevenlabs", "edge", "native"]);
+        assert_eq!(engine_chain(&VoiceConfig::default()), ["native"]);
+    }
+
+    #[test]
+    fn test_fallback_skips_failing_primary() {
+        let primary = FakeEngine { name: "elevenlabs", fails: true, default_voice: None };
+        let backup = FakeEngine { name: "edge", fails: false, default_voice: None };
+        let last = FakeEngine { name: "native", fails: false, default_voice: None };
+        let candidates: [(&dyn TtsEngine, Option<&str>); 3] =
+            [(&primary, Some("Rachel")), (&backup, None), (&last, None)];
+
+        let mut voices_used = Vec::new();
+        let (result, served_by) = synthesize_with_fallback(&candidates, |engine, voice| {
+            voices_used.push(voice.map(str::to_string));
+            engine.synthesize("Hello", voice, 1.0, Path::new("/tmp/out.wav"))
+        })
+        .unwrap();
+
+        assert_eq!(served_by, "edge");
+        assert_eq!(result.duration_secs, 1.5);
+        // The primary got its voice; the fallback ran with its default voice, and
+        // the chain stopped at the first success.
+        assert_eq!(voices_used, [Some("Rachel".to_string()), None]);
+    }
+
+    #[test]
+    fn test_fallback_uses_engine_default_voice() {
+        let engine = FakeEngine {
+            name: "edge",
+            fails: false,
+            default_voice: Some("en-US-AriaNeural"),
+        };
+        let mut voices_used = Vec::new();
+        for voice in [None, Some("en-US-GuyNeural")] {
+            synthesize_with_fallback(&[(&engine, voice)], |engine, voice| {
+                voices_used.push(voice.map(str::to_string));
+                engine.synthesize("Hello", voice, 1.0, Path::new("/tmp/out.wav"))
+            })
+            .unwrap();
+        }
+        // No configured voice: the engine's default is passed explicitly
+        assert_eq!(
+            voices_used,
+            [Some("en-US-AriaNeural".to_string()), Some("en-US-GuyNeural".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_fallback_all_failing_returns_last_error() {
+        let a = FakeEngine { name: "elevenlabs", fails: true, default_voice: None };
+        let b = FakeEngine { name: "edge", fails: true, default_voice: None };
+        let candidates: [(&dyn TtsEngine, Option<&str>); 2] = [(&a, None), (&b, None)];
+        let err = synthesize_with_fallback(&candidates, |engine, voice| {
+            engine.synthesize("Hello", voice, 1.0, Path::new("/tmp/out.wav"))
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("edge is down"));
+
+        assert!(synthesize_with_fallback(&[], |e, v| e.synthesize("x", v, 1.0, Path::new("x")))
+            .is_err());
+    }
+
+    #[test]
+    fn test_every_engine_has_default_voice() {
+        for name in ENGINE_NAMES {
+            assert!(default_voice_for(name).is_some(), "{name} has no default voice");
+        }
+        assert_eq!(default_voice_for("edge"), Some("en-US-AriaNeural"));
+        assert_eq!(default_voice_for(silent::ENGINE_NAME), None);
+    }
+
     #[test]
     fn test_create_engine_native() {
         let config = VoiceConfig {
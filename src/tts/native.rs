@@ -16,14 +16,34 @@ enum Platform {
     Linux,
 }
 
+impl Platform {
+    fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            Self::MacOS
+        } else {
+            Self::Linux
+        }
+    }
+
+    /// Voice when none is specified: `say`'s stock US English voice, or
+    /// espeak-ng's English voice.
+    fn default_voice(self) -> &'static str {
+        match self {
+            Self::MacOS => "Samantha",
+            Self::Linux => "en",
+        }
+    }
+}
+
+/// Default voice of the native engine on this platform.
+pub(crate) fn default_voice() -> &'static str {
+    Platform::current().default_voice()
+}
+
 impl NativeTtsEngine {
     /// Create a new NativeTtsEngine, verifying the platform command is available.
     pub fn new() -> VidgenResult<Self> {
-        let platform = if cfg!(target_os = "macos") {
-            Platform::MacOS
-        } else {
-            Platform::Linux
-        };
+        let platform = Platform::current();
 
         // Verify the command exists
         let cmd = match platform {
@@ -82,6 +102,10 @@ impl TtsEngine for NativeTtsEngine {
     fn engine_name(&self) -> &str {
         "native"
     }
+
+    fn default_voice(&self) -> Option<&str> {
+        Some(self.platform.default_voice())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -278,3 +302,20 @@ fn list_voices_linux() -> VidgenResult<Vec<VoiceInfo>> {
 
     Ok(voices)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_voice_per_platform() {
+        let mac = NativeTtsEngine {
+            platform: Platform::MacOS,
+        };
+        let linux = NativeTtsEngine {
+            platform: Platform::Linux,
+        };
+        assert_eq!(mac.default_voice(), Some("Samantha"));
+        assert_eq!(linux.default_voice(), Some("en"));
+    }
+}
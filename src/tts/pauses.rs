@@ -0,0 +1,282 @@
+use crate::error::{VidgenError, VidgenResult};
+use crate::tts::timestamps::{estimate_word_timestamps, WordTimestamp};
+use crate::tts::{SynthesisResult, TtsEngine};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Sample rate the voiced segments and silences are joined at.
+const SAMPLE_RATE: u32 = 44_100;
+
+/// Part of a narration script split on `[pause:SECS]` markers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    /// Text to voice
+    Text(String),
+    /// Seconds of silence
+    Pause(f64),
+}
+
+/// Split a script on inline `[pause:1.5]` markers. Text around a marker is
+/// trimmed and empty text is dropped. Malformed markers (`[pause:]`,
+/// `[pause:abc]`, negative lengths) are kept as literal text.
+pub fn split_script_with_pauses(script: &str) -> Vec<Segment> {
+    const OPEN: &str = "[pause:";
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut rest = script;
+
+    while let Some(start) = rest.find(OPEN) {
+        let after = &rest[start + OPEN.len()..];
+        let secs = after
+            .find(']')
+            .and_then(|end| Some((end, after[..end].trim().parse::<f64>().ok()?)))
+            .filter(|(_, secs)| secs.is_finite() && *secs >= 0.0);
+        match secs {
+            Some((end, secs)) => {
+                text.push_str(&rest[..start]);
+                flush(&mut text, &mut segments);
+                segments.push(Segment::Pause(secs));
+                rest = &after[end + 1..];
+            }
+            None => {
+                // Not a marker: keep "[pause:" as text and look further on
+                text.push_str(&rest[..start + OPEN.len()]);
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+    flush(&mut text, &mut segments);
+    segments
+}
+
+/// `script` without its pause markers, for showing on screen.
+pub fn strip_pause_markers(script: &str) -> String {
+    split_script_with_pauses(script)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Text(text) => Some(text),
+            Segment::Pause(_) => None,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Estimated narration length of `script` (see [`crate::tts::silent::estimate_duration`]),
+/// counting `[pause:SECS]` markers as their silence rather than as words.
+pub fn estimate_script_duration(script: &str, speed: f32) -> f64 {
+    split_script_with_pauses(script)
+        .iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => crate::tts::silent::estimate_duration(text, speed),
+            Segment::Pause(secs) => *secs,
+        })
+        .sum()
+}
+
+/// Move the trimmed, non-empty `text` into `segments`.
+fn flush(text: &mut String, segments: &mut Vec<Segment>) {
+    let trimmed = text.trim();
+    if !trimmed.is_empty() {
+        segments.push(Segment::Text(trimmed.to_string()));
+    }
+    text.clear();
+}
+
+/// One input to [`concat_with_silence_args`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AudioPart {
+    File(PathBuf),
+    Silence(f64),
+}
+
+/// FFmpeg args that join `parts` into one mono WAV at `output`; silences are
+/// generated with `anullsrc`.
+pub fn concat_with_silence_args(parts: &[AudioPart], output: &Path) -> Vec<String> {
+    let mut args: Vec<String> = vec!["-y".into()];
+    let mut filter = String::new();
+    for (i, part) in parts.iter().enumerate() {
+        match part {
+            AudioPart::File(path) => {
+                args.extend(["-i".into(), path.display().to_string()]);
+            }
+            AudioPart::Silence(secs) => args.extend([
+                "-f".into(),
+                "lavfi".into(),
+                "-t".into(),
+                format!("{secs:.3}"),
+                "-i".into(),
+                format!("anullsrc=r={SAMPLE_RATE}:cl=mono"),
+            ]),
+        }
+        filter.push_str(&format!(
+            "[{i}:a]aresample={SAMPLE_RATE},aformat=channel_layouts=mono[a{i}];"
+        ));
+    }
+    for i in 0..parts.len() {
+        filter.push_str(&format!("[a{i}]"));
+    }
+    filter.push_str(&format!("concat=n={}:v=0:a=1[out]", parts.len()));
+    args.extend([
+        "-filter_complex".into(),
+        filter,
+        "-map".into(),
+        "[out]".into(),
+        "-c:a".into(),
+        "pcm_s16le".into(),
+        output.display().to_string(),
+    ]);
+    args
+}
+
+/// Voice `script` with `engine`, honouring `[pause:SECS]` markers: each text
+/// segment is synthesized on its own and the pieces are joined with silence.
+/// Without markers this is a plain `engine.synthesize`. Word timings cover
+/// the joined audio (estimated per segment when the engine reports none).
+pub fn synthesize_script(
+    engine: &dyn TtsEngine,
+    script: &str,
+    voice: Option<&str>,
+    speed: f32,
+    output_path: &Path,
+) -> VidgenResult<SynthesisResult> {
+    let segments = split_script_with_pauses(script);
+    if !segments.iter().any(|s| matches!(s, Segment::Pause(_))) {
+        return engine.synthesize(script, voice, speed, output_path);
+    }
+
+    let work_dir = tempfile::tempdir()?;
+    let mut parts = Vec::with_capacity(segments.len());
+    let mut words: Vec<WordTimestamp> = Vec::new();
+    let mut offset = 0.0_f64;
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Pause(secs) => {
+                parts.push(AudioPart::Silence(*secs));
+                offset += secs;
+            }
+            Segment::Text(text) => {
+                let path = work_dir.path().join(format!("segment-{i:03}.wav"));
+                let result = engine.synthesize(text, voice, speed, &path)?;
+                let segment_words = result
+                    .word_timestamps
+                    .unwrap_or_else(|| estimate_word_timestamps(text, result.duration_secs));
+                words.extend(segment_words.into_iter().map(|mut w| {
+                    w.start_secs += offset;
+                    w.end_secs += offset;
+                    w
+                }));
+                parts.push(AudioPart::File(result.audio_path));
+                offset += result.duration_secs;
+            }
+        }
+    }
+
+    let output = Command::new("ffmpeg")
+        .args(concat_with_silence_args(&parts, output_path))
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| VidgenError::Tts(format!("Failed to spawn ffmpeg for pauses: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(VidgenError::Tts(format!(
+            "Joining narration around pauses failed: {}",
+            stderr.lines().last().unwrap_or("unknown error")
+        )));
+    }
+
+    Ok(SynthesisResult {
+        audio_path: output_path.to_path_buf(),
+        duration_secs: offset,
+        cached: false,
+        word_timestamps: Some(words),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> Segment {
+        Segment::Text(s.into())
+    }
+
+    #[test]
+    fn test_split_without_markers() {
+        assert_eq!(
+            split_script_with_pauses("Just one line."),
+            [text("Just one line.")]
+        );
+        assert!(split_script_with_pauses("  ").is_empty());
+    }
+
+    #[test]
+    fn test_split_multiple_markers() {
+        assert_eq!(
+            split_script_with_pauses("First. [pause:1.5] Second. [pause: 2 ] Third."),
+            [
+                text("First."),
+                Segment::Pause(1.5),
+                text("Second."),
+                Segment::Pause(2.0),
+                text("Third."),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_markers_at_start_and_end() {
+        assert_eq!(
+            split_script_with_pauses("[pause:0.5]Hello there.[pause:1]"),
+            [
+                Segment::Pause(0.5),
+                text("Hello there."),
+                Segment::Pause(1.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_keeps_malformed_markers_as_text() {
+        assert_eq!(
+            split_script_with_pauses("Wait [pause:abc] for [pause:] it [pause:-1] [pause:2"),
+            [text("Wait [pause:abc] for [pause:] it [pause:-1] [pause:2")]
+        );
+        // A malformed marker doesn't hide a valid one after it
+        assert_eq!(
+            split_script_with_pauses("A [pause:x] B [pause:1] C"),
+            [text("A [pause:x] B"), Segment::Pause(1.0), text("C")]
+        );
+    }
+
+    #[test]
+    fn test_strip_pause_markers() {
+        assert_eq!(
+            strip_pause_markers("Hello. [pause:1] World [pause:x]"),
+            "Hello. World [pause:x]"
+        );
+    }
+
+    #[test]
+    fn test_estimate_script_duration_counts_pauses() {
+        // 5 words at 150 wpm = 2s, plus 1.5s of pause
+        let secs = estimate_script_duration("One two [pause:1.5] three four five", 1.0);
+        assert!((secs - 3.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_concat_with_silence_args() {
+        let parts = [
+            AudioPart::File(PathBuf::from("/tmp/a.wav")),
+            AudioPart::Silence(1.5),
+            AudioPart::File(PathBuf::from("/tmp/b.wav")),
+        ];
+        let args = concat_with_silence_args(&parts, Path::new("/tmp/out.wav"));
+        let joined = args.join(" ");
+        assert!(joined
+            .contains("-i /tmp/a.wav -f lavfi -t 1.500 -i anullsrc=r=44100:cl=mono -i /tmp/b.wav"));
+        assert!(joined.contains("[a0][a1][a2]concat=n=3:v=0:a=1[out]"));
+        assert_eq!(args.last().unwrap(), "/tmp/out.wav");
+    }
+}
@@ -1,7 +1,8 @@
+use crate::config::VoiceConfig;
 use crate::error::{VidgenError, VidgenResult};
 use crate::tts::{ffprobe_duration, SynthesisResult, TtsEngine, VoiceInfo};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
 /// TTS engine using Piper, a fast local neural TTS.
@@ -9,14 +10,21 @@ use std::process::{Command, Stdio};
 /// Requires the `piper` binary on PATH and a downloaded ONNX voice model.
 /// See <https://github.com/rhasspy/piper> for installation.
 /// Piper outputs WAV directly — no ffmpeg conversion step needed.
-pub struct PiperTtsEngine;
+pub struct PiperTtsEngine {
+    /// Where `<voice>.onnx` models live (`[voice] piper_voices_dir` or `PIPER_VOICES_DIR`)
+    voices_dir: Option<PathBuf>,
+}
 
 /// Default Piper voice model when none is specified.
-const DEFAULT_MODEL: &str = "en_US-amy-medium";
+pub(crate) const DEFAULT_MODEL: &str = "en_US-amy-medium";
+
+/// Env var naming the voices directory when `piper_voices_dir` isn't set.
+const VOICES_DIR_ENV: &str = "PIPER_VOICES_DIR";
 
 impl PiperTtsEngine {
-    /// Create a new PiperTtsEngine, verifying `piper` is on PATH.
-    pub fn new() -> VidgenResult<Self> {
+    /// Create a new PiperTtsEngine, verifying `piper` is on PATH and that the
+    /// default voice's model file exists.
+    pub fn new(config: &VoiceConfig) -> VidgenResult<Self> {
         let check = Command::new("which")
             .arg("piper")
             .output()
@@ -28,10 +36,67 @@ impl PiperTtsEngine {
             ));
         }
 
-        Ok(Self)
+        let engine = Self {
+            voices_dir: config
+                .piper_voices_dir
+                .clone()
+                .or_else(|| std::env::var(VOICES_DIR_ENV).ok())
+                .map(PathBuf::from),
+        };
+        engine.check_model(config.default_voice.as_deref())?;
+        Ok(engine)
+    }
+
+    /// Model file for `voice`: an `.onnx` path as given, else `<voices_dir>/<voice>.onnx`.
+    /// Without a voices directory the bare name is passed to piper unchanged.
+    fn model_path(&self, voice: Option<&str>) -> PathBuf {
+        let voice = voice.unwrap_or(DEFAULT_MODEL);
+        match &self.voices_dir {
+            Some(dir) if !voice.ends_with(".onnx") => dir.join(format!("{voice}.onnx")),
+            _ => PathBuf::from(voice),
+        }
+    }
+
+    /// Fail early when `voice` resolves to a model file that isn't there.
+    fn check_model(&self, voice: Option<&str>) -> VidgenResult<PathBuf> {
+        let model = self.model_path(voice);
+        let is_file_path = self.voices_dir.is_some() || model.extension().is_some_and(|e| e == "onnx");
+        if is_file_path && !model.is_file() {
+            return Err(VidgenError::Tts(format!(
+                "Piper model not found: {}. Download the .onnx and .onnx.json files from \
+                 https://github.com/rhasspy/piper/blob/master/VOICES.md into [voice] piper_voices_dir",
+                model.display()
+            )));
+        }
+        Ok(model)
     }
 }
 
+/// Voices in `dir`: one per `.onnx` model, named by file stem and sorted.
+fn voices_in_dir(dir: &Path) -> VidgenResult<Vec<VoiceInfo>> {
+    let mut stems: Vec<String> = std::fs::read_dir(dir)
+        .map_err(|e| VidgenError::Tts(format!("Failed to read Piper voices in {}: {e}", dir.display())))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "onnx"))
+        .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .collect();
+    stems.sort();
+    Ok(stems
+        .into_iter()
+        .map(|stem| VoiceInfo {
+            // Piper model names start with the locale, e.g. "de_DE-thorsten-high"
+            language: stem.split('-').next().unwrap_or_default().replace('_', "-"),
+            name: stem.clone(),
+            id: stem,
+            gender: String::new(), // not part of the model name
+            engine: "piper".into(),
+            available: true,
+            note: None,
+        })
+        .collect())
+}
+
 /// Convert a vidgen speed multiplier to piper's `--length-scale`.
 ///
 /// Piper's length_scale is inverse: higher values = slower speech.
@@ -49,12 +114,13 @@ impl TtsEngine for PiperTtsEngine {
         speed: f32,
         output_path: &Path,
     ) -> VidgenResult<SynthesisResult> {
-        let model = voice.unwrap_or(DEFAULT_MODEL);
+        let model = self.check_model(voice)?;
         let length_scale = speed_to_length_scale(speed);
 
         // Piper reads text from stdin and writes WAV to --output_file
         let mut child = Command::new("piper")
-            .args(["--model", model])
+            .arg("--model")
+            .arg(&model)
             .args(["--length-scale", &format!("{length_scale:.2}")])
             .arg("--output_file")
             .arg(output_path)
@@ -92,6 +158,9 @@ impl TtsEngine for PiperTtsEngine {
     }
 
     fn list_voices(&self) -> VidgenResult<Vec<VoiceInfo>> {
+        if let Some(ref dir) = self.voices_dir {
+            return voices_in_dir(dir);
+        }
         // Piper doesn't have a --list-voices command.
         // Models are downloaded separately from https://github.com/rhasspy/piper/blob/master/VOICES.md
         Ok(vec![VoiceInfo {
@@ -111,12 +180,22 @@ impl TtsEngine for PiperTtsEngine {
     fn engine_name(&self) -> &str {
         "piper"
     }
+
+    fn default_voice(&self) -> Option<&str> {
+        Some(DEFAULT_MODEL)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_default_voice() {
+        let engine = PiperTtsEngine { voices_dir: None };
+        assert_eq!(engine.default_voice(), Some(DEFAULT_MODEL));
+    }
+
     #[test]
     fn test_speed_to_length_scale_normal() {
         let scale = speed_to_length_scale(1.0);
@@ -154,7 +233,7 @@ mod tests {
     #[test]
     fn test_list_voices() {
         // list_voices doesn't require piper to be installed
-        let engine = PiperTtsEngine;
+        let engine = PiperTtsEngine { voices_dir: None };
         let voices = engine.list_voices().unwrap();
         assert_eq!(voices.len(), 1);
         assert_eq!(voices[0].id, "en_US-amy-medium");
@@ -164,7 +243,42 @@ mod tests {
 
     #[test]
     fn test_engine_name() {
-        let engine = PiperTtsEngine;
+        let engine = PiperTtsEngine { voices_dir: None };
         assert_eq!(engine.engine_name(), "piper");
     }
+
+    #[test]
+    fn test_voices_dir_models() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["en_US-amy-medium.onnx", "en_US-amy-medium.onnx.json", "de_DE-thorsten-high.onnx"] {
+            std::fs::write(dir.path().join(file), b"").unwrap();
+        }
+        let engine = PiperTtsEngine {
+            voices_dir: Some(dir.path().to_path_buf()),
+        };
+
+        let voices = engine.list_voices().unwrap();
+        let ids: Vec<_> = voices.iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(ids, ["de_DE-thorsten-high", "en_US-amy-medium"]);
+        assert_eq!(voices[0].language, "de-DE");
+        assert!(voices.iter().all(|v| v.engine == "piper"));
+
+        // Voice names resolve inside the directory; the default is amy
+        assert_eq!(
+            engine.check_model(Some("de_DE-thorsten-high")).unwrap(),
+            dir.path().join("de_DE-thorsten-high.onnx")
+        );
+        assert_eq!(engine.check_model(None).unwrap(), dir.path().join("en_US-amy-medium.onnx"));
+        let err = engine.check_model(Some("fr_FR-siwis-low")).unwrap_err();
+        assert!(matches!(err, VidgenError::Tts(_)));
+        assert!(err.to_string().contains("fr_FR-siwis-low.onnx"));
+    }
+
+    #[test]
+    fn test_model_path_without_voices_dir() {
+        let engine = PiperTtsEngine { voices_dir: None };
+        // Bare names go to piper unchanged; explicit .onnx paths must exist
+        assert_eq!(engine.check_model(None).unwrap(), PathBuf::from(DEFAULT_MODEL));
+        assert!(engine.check_model(Some("/missing/voice.onnx")).is_err());
+    }
 }
@@ -0,0 +1,109 @@
+use crate::error::VidgenResult;
+use crate::tts::{timestamps, SynthesisResult, TtsEngine, VoiceInfo};
+use std::io::Write;
+use std::path::Path;
+
+/// Placeholder engine behind `--dry-tts`: writes silent WAVs whose length is
+/// estimated from the script's word count, so auto durations and subtitles
+/// behave like a real render without running a TTS engine.
+///
+/// The WAV is written directly, so neither FFmpeg nor an engine binary is needed.
+pub struct SilentTtsEngine;
+
+/// Engine name reported for dry-TTS audio (also keys the scene render cache).
+pub const ENGINE_NAME: &str = "silent";
+
+/// Speaking rate assumed for the estimate, at `speed = 1.0`.
+pub const WORDS_PER_MINUTE: f64 = 150.0;
+
+const SAMPLE_RATE: u32 = 44_100;
+
+/// Estimated narration length for `text` at the given speed multiplier.
+pub fn estimate_duration(text: &str, speed: f32) -> f64 {
+    let words = text.split_whitespace().count() as f64;
+    let speed = if speed > 0.0 { speed as f64 } else { 1.0 };
+    words / (WORDS_PER_MINUTE * speed) * 60.0
+}
+
+/// Write a 16-bit mono PCM WAV of silence lasting `duration_secs`.
+pub fn write_silent_wav(path: &Path, duration_secs: f64) -> VidgenResult<()> {
+    let samples = (duration_secs.max(0.0) * SAMPLE_RATE as f64).round() as u32;
+    let data_len = samples * 2;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes()); // byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes()); // block align
+    wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.resize(44 + data_len as usize, 0);
+
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(&wav)?;
+    Ok(())
+}
+
+impl TtsEngine for SilentTtsEngine {
+    fn synthesize(
+        &self,
+        text: &str,
+        _voice: Option<&str>,
+        speed: f32,
+        output_path: &Path,
+    ) -> VidgenResult<SynthesisResult> {
+        let duration_secs = estimate_duration(text, speed);
+        write_silent_wav(output_path, duration_secs)?;
+        Ok(SynthesisResult {
+            audio_path: output_path.to_path_buf(),
+            duration_secs,
+            cached: false,
+            word_timestamps: Some(timestamps::estimate_word_timestamps(text, duration_secs)),
+        })
+    }
+
+    fn list_voices(&self) -> VidgenResult<Vec<VoiceInfo>> {
+        Ok(Vec::new())
+    }
+
+    fn engine_name(&self) -> &str {
+        ENGINE_NAME
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_duration_uses_wpm() {
+        let thirty_words = ["word"; 30].join(" ");
+        assert!((estimate_duration(&thirty_words, 1.0) - 12.0).abs() < 1e-9);
+        assert!((estimate_duration(&thirty_words, 1.5) - 8.0).abs() < 1e-9);
+        assert_eq!(estimate_duration("", 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_silent_wav_has_expected_length() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("scene.wav");
+        let text = ["hello"; 5].join(" ");
+        let result = SilentTtsEngine.synthesize(&text, None, 1.0, &path).unwrap();
+        assert!((result.duration_secs - 2.0).abs() < 1e-9);
+        assert_eq!(result.word_timestamps.unwrap().len(), 5);
+
+        let wav = std::fs::read(&path).unwrap();
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..12], b"WAVE");
+        let byte_rate = u32::from_le_bytes(wav[28..32].try_into().unwrap());
+        let data_len = u32::from_le_bytes(wav[40..44].try_into().unwrap());
+        assert_eq!(data_len as usize, wav.len() - 44);
+        assert!((data_len as f64 / byte_rate as f64 - 2.0).abs() < 1e-4);
+        assert!(wav[44..].iter().all(|&b| b == 0));
+    }
+}
@@ -1,5 +1,5 @@
-/// Estimated word-level timestamp from TTS duration.
-#[derive(Debug, Clone)]
+/// Word-level timestamp, reported by the TTS engine or estimated from its duration.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct WordTimestamp {
     pub word: String,
     pub start_secs: f64,